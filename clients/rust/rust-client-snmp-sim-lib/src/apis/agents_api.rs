@@ -23,6 +23,7 @@ pub enum AgentsGetError {
     Status404(crate::models::AgentsGet400Response),
     Status409(crate::models::AgentsGet400Response),
     Status500(crate::models::AgentsGet400Response),
+    Status502(crate::models::AgentsGet400Response),
     UnknownValue(serde_json::Value),
 }
 
@@ -34,6 +35,7 @@ pub enum AgentsIdDeleteError {
     Status404(crate::models::AgentsGet400Response),
     Status409(crate::models::AgentsGet400Response),
     Status500(crate::models::AgentsGet400Response),
+    Status502(crate::models::AgentsGet400Response),
     UnknownValue(serde_json::Value),
 }
 
@@ -45,6 +47,7 @@ pub enum AgentsIdGetError {
     Status404(crate::models::AgentsGet400Response),
     Status409(crate::models::AgentsGet400Response),
     Status500(crate::models::AgentsGet400Response),
+    Status502(crate::models::AgentsGet400Response),
     UnknownValue(serde_json::Value),
 }
 
@@ -56,6 +59,7 @@ pub enum AgentsIdPutError {
     Status404(crate::models::AgentsGet400Response),
     Status409(crate::models::AgentsGet400Response),
     Status500(crate::models::AgentsGet400Response),
+    Status502(crate::models::AgentsGet400Response),
     UnknownValue(serde_json::Value),
 }
 
@@ -67,9 +71,21 @@ pub enum AgentsPostError {
     Status404(crate::models::AgentsGet400Response),
     Status409(crate::models::AgentsGet400Response),
     Status500(crate::models::AgentsGet400Response),
+    Status502(crate::models::AgentsGet400Response),
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`agents_record_post`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AgentsRecordPostError {
+    Status400(crate::models::AgentsGet400Response),
+    Status404(crate::models::AgentsGet400Response),
+    Status409(crate::models::AgentsGet400Response),
+    Status500(crate::models::AgentsGet400Response),
+    Status502(crate::models::AgentsGet400Response),
+    UnknownValue(serde_json::Value),
+}
 
 pub async fn agents_get(configuration: &configuration::Configuration, page: Option<i64>, page_size: Option<i64>) -> Result<crate::models::ResponseAgents, Error<AgentsGetError>> {
     let local_var_configuration = configuration;
@@ -214,3 +230,31 @@ pub async fn agents_post(configuration: &configuration::Configuration, body: cra
     }
 }
 
+pub async fn agents_record_post(configuration: &configuration::Configuration, body: crate::models::RequestRecordAgent) -> Result<crate::models::ResponseAgent, Error<AgentsRecordPostError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/agents/record", local_var_configuration.base_path);
+    let mut local_var_req_builder = local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder = local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    local_var_req_builder = local_var_req_builder.json(&body);
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<AgentsRecordPostError> = serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent { status: local_var_status, content: local_var_content, entity: local_var_entity };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
@@ -6,12 +6,18 @@ pub mod request_device;
 pub use self::request_device::RequestDevice;
 pub mod request_device_agent;
 pub use self::request_device_agent::RequestDeviceAgent;
+pub mod request_device_agentx;
+pub use self::request_device_agentx::RequestDeviceAgentx;
+pub mod request_device_proxy;
+pub use self::request_device_proxy::RequestDeviceProxy;
 pub mod request_device_snmp_protocol_attributes;
 pub use self::request_device_snmp_protocol_attributes::RequestDeviceSnmpProtocolAttributes;
 pub mod request_device_snmp_protocol_attributes_snmp_v1;
 pub use self::request_device_snmp_protocol_attributes_snmp_v1::RequestDeviceSnmpProtocolAttributesSnmpV1;
 pub mod request_device_snmp_protocol_attributes_snmp_v3;
 pub use self::request_device_snmp_protocol_attributes_snmp_v3::RequestDeviceSnmpProtocolAttributesSnmpV3;
+pub mod request_record_agent;
+pub use self::request_record_agent::RequestRecordAgent;
 pub mod response_agent;
 pub use self::response_agent::ResponseAgent;
 pub mod response_agents;
@@ -14,6 +14,9 @@
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct RequestAgent {
+    /// The format of the SNMP data file, \"snmpwalk\" or \"snmprec\", picked by the extension of the file by default, the \"*.snmprec\" files are the recordings of snmpsim
+    #[serde(rename = "data_format", skip_serializing_if = "Option::is_none")]
+    pub data_format: Option<DataFormat>,
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// The name of this agent.
@@ -28,6 +31,7 @@ impl RequestAgent {
     /// An agent as a response body.
     pub fn new(name: String, snmp_data_url: String) -> RequestAgent {
         RequestAgent {
+            data_format: None,
             description: None,
             name,
             snmp_data_url,
@@ -35,4 +39,18 @@ impl RequestAgent {
     }
 }
 
+/// The format of the SNMP data file, \"snmpwalk\" or \"snmprec\", picked by the extension of the file by default, the \"*.snmprec\" files are the recordings of snmpsim
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum DataFormat {
+    #[serde(rename = "snmpwalk")]
+    Snmpwalk,
+    #[serde(rename = "snmprec")]
+    Snmprec,
+}
+
+impl Default for DataFormat {
+    fn default() -> DataFormat {
+        Self::Snmpwalk
+    }
+}
 
@@ -16,17 +16,33 @@
 pub struct RequestDevice {
     #[serde(rename = "agent")]
     pub agent: Box<crate::models::RequestDeviceAgent>,
+    /// The AgentX master agent dispatching the requests for the subtrees registered by the subagents
+    #[serde(rename = "agentx", skip_serializing_if = "Option::is_none")]
+    pub agentx: Option<Box<crate::models::RequestDeviceAgentx>>,
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// The name of this agent.
     #[serde(rename = "name")]
     pub name: String,
+    /// The upstream agent serving the variables not simulated by the device
+    #[serde(rename = "proxy", skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<Box<crate::models::RequestDeviceProxy>>,
+    /// The IPv4 address, the IPv6 address or the host name the device is bound to, the device bound to "::" is reachable on both the IPv4 and IPv6 addresses of the host
     #[serde(rename = "snmp_host")]
     pub snmp_host: String,
     #[serde(rename = "snmp_port")]
     pub snmp_port: i32,
     #[serde(rename = "snmp_protocol_attributes")]
     pub snmp_protocol_attributes: Box<crate::models::RequestDeviceSnmpProtocolAttributes>,
+    /// The sysLocation.0 value served by the device instead of the one of the SNMP data
+    #[serde(rename = "sys_location", skip_serializing_if = "Option::is_none")]
+    pub sys_location: Option<String>,
+    /// The sysName.0 value served by the device instead of the one of the SNMP data
+    #[serde(rename = "sys_name", skip_serializing_if = "Option::is_none")]
+    pub sys_name: Option<String>,
+    /// The transport the device receives the SNMP requests on, \"udp\" by default or \"tcp\" (RFC 3430)
+    #[serde(rename = "transport", skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
 }
 
 impl RequestDevice {
@@ -34,13 +50,32 @@ impl RequestDevice {
     pub fn new(agent: crate::models::RequestDeviceAgent, name: String, snmp_host: String, snmp_port: i32, snmp_protocol_attributes: crate::models::RequestDeviceSnmpProtocolAttributes) -> RequestDevice {
         RequestDevice {
             agent: Box::new(agent),
+            agentx: None,
             description: None,
             name,
+            proxy: None,
             snmp_host,
             snmp_port,
             snmp_protocol_attributes: Box::new(snmp_protocol_attributes),
+            sys_location: None,
+            sys_name: None,
+            transport: None,
         }
     }
 }
 
+/// The transport the device receives the SNMP requests on, \"udp\" by default or \"tcp\" (RFC 3430)
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Transport {
+    #[serde(rename = "udp")]
+    Udp,
+    #[serde(rename = "tcp")]
+    Tcp,
+}
+
+impl Default for Transport {
+    fn default() -> Transport {
+        Self::Udp
+    }
+}
 
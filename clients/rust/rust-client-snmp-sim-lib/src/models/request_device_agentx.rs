@@ -0,0 +1,35 @@
+/*
+ * 
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+/// RequestDeviceAgentx : The AgentX master agent dispatching the requests for the subtrees registered by the subagents
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct RequestDeviceAgentx {
+    /// The address the master agent listens on for the subagents, either a TCP socket address, f.e. \"127.0.0.1:705\", or the path of a Unix domain socket, f.e. \"unix:/var/agentx/master\"
+    #[serde(rename = "address")]
+    pub address: String,
+    /// The timeout of each request dispatched to a subagent in milliseconds, 1000 by default. The timeout of the subagent session or registration takes precedence if specified by the subagent
+    #[serde(rename = "timeout", skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i32>,
+}
+
+impl RequestDeviceAgentx {
+    /// The AgentX master agent dispatching the requests for the subtrees registered by the subagents
+    pub fn new(address: String) -> RequestDeviceAgentx {
+        RequestDeviceAgentx {
+            address,
+            timeout: None,
+        }
+    }
+}
+
+
@@ -0,0 +1,50 @@
+/*
+ * 
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+/// RequestDeviceProxy : The upstream agent serving the variables not simulated by the device
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct RequestDeviceProxy {
+    /// The number of seconds the upstream values are cached for, they are not cached by default
+    #[serde(rename = "cache_ttl", skip_serializing_if = "Option::is_none")]
+    pub cache_ttl: Option<i32>,
+    /// The number of retransmissions of each forwarded request, 1 by default
+    #[serde(rename = "retries", skip_serializing_if = "Option::is_none")]
+    pub retries: Option<i32>,
+    #[serde(rename = "snmp_protocol_attributes")]
+    pub snmp_protocol_attributes: Box<crate::models::RequestDeviceSnmpProtocolAttributes>,
+    /// The forwarded subtrees, f.e. [\".1.3.6.1.2.1.2\"]. The variables absent from the SNMP data are forwarded if empty
+    #[serde(rename = "subtrees", skip_serializing_if = "Option::is_none")]
+    pub subtrees: Option<Vec<String>>,
+    /// The socket address of the upstream agent, f.e. \"192.168.1.1:161\"
+    #[serde(rename = "target")]
+    pub target: String,
+    /// The timeout of each forwarded request in milliseconds, 1000 by default
+    #[serde(rename = "timeout", skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i32>,
+}
+
+impl RequestDeviceProxy {
+    /// The upstream agent serving the variables not simulated by the device
+    pub fn new(snmp_protocol_attributes: crate::models::RequestDeviceSnmpProtocolAttributes, target: String) -> RequestDeviceProxy {
+        RequestDeviceProxy {
+            cache_ttl: None,
+            retries: None,
+            snmp_protocol_attributes: Box::new(snmp_protocol_attributes),
+            subtrees: None,
+            target,
+            timeout: None,
+        }
+    }
+}
+
+
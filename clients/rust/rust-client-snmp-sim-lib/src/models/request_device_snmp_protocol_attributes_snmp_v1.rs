@@ -15,12 +15,21 @@
 pub struct RequestDeviceSnmpProtocolAttributesSnmpV1 {
     #[serde(rename = "community", skip_serializing_if = "Option::is_none")]
     pub community: Option<String>,
+    #[serde(rename = "read_write_community", skip_serializing_if = "Option::is_none")]
+    pub read_write_community: Option<String>,
+    #[serde(rename = "authentication_failure_trap", skip_serializing_if = "Option::is_none")]
+    pub authentication_failure_trap: Option<String>,
+    #[serde(rename = "max_message_size", skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<i32>,
 }
 
 impl RequestDeviceSnmpProtocolAttributesSnmpV1 {
     pub fn new() -> RequestDeviceSnmpProtocolAttributesSnmpV1 {
         RequestDeviceSnmpProtocolAttributesSnmpV1 {
             community: None,
+            read_write_community: None,
+            authentication_failure_trap: None,
+            max_message_size: None,
         }
     }
 }
@@ -44,6 +44,14 @@ pub enum Authentication {
     MD5,
     #[serde(rename = "SHA")]
     SHA,
+    #[serde(rename = "SHA224")]
+    SHA224,
+    #[serde(rename = "SHA256")]
+    SHA256,
+    #[serde(rename = "SHA384")]
+    SHA384,
+    #[serde(rename = "SHA512")]
+    SHA512,
 }
 
 impl Default for Authentication {
@@ -58,6 +66,10 @@ pub enum Encryption {
     DES,
     #[serde(rename = "AES")]
     AES,
+    #[serde(rename = "AES192")]
+    AES192,
+    #[serde(rename = "AES256")]
+    AES256,
 }
 
 impl Default for Encryption {
@@ -0,0 +1,79 @@
+/*
+ * 
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+/// RequestRecordAgent : An agent recorded by walking a remote SNMP agent.
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct RequestRecordAgent {
+    #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The max-repetitions of the GETBULK requests, 10 by default
+    #[serde(rename = "max_repetitions", skip_serializing_if = "Option::is_none")]
+    pub max_repetitions: Option<i32>,
+    /// The requests used to walk the agent, GETBULK by default, the SNMPv1 agent is walked by GETNEXT
+    #[serde(rename = "method", skip_serializing_if = "Option::is_none")]
+    pub method: Option<Method>,
+    /// The name of this agent.
+    #[serde(rename = "name")]
+    pub name: String,
+    /// The number of retransmissions of each request, 5 by default
+    #[serde(rename = "retries", skip_serializing_if = "Option::is_none")]
+    pub retries: Option<i32>,
+    /// The root of the walked subtree, \".1.3.6.1\" by default
+    #[serde(rename = "root_oid", skip_serializing_if = "Option::is_none")]
+    pub root_oid: Option<String>,
+    /// The path of the SNMP data file the walked variables are written to, f.e. \"./os/linux.dat\"
+    #[serde(rename = "snmp_data_url")]
+    pub snmp_data_url: String,
+    #[serde(rename = "snmp_protocol_attributes")]
+    pub snmp_protocol_attributes: Box<crate::models::RequestDeviceSnmpProtocolAttributes>,
+    /// The socket address of the walked agent, f.e. \"192.168.1.1:161\"
+    #[serde(rename = "target")]
+    pub target: String,
+    /// The timeout of each request in milliseconds, 1000 by default
+    #[serde(rename = "timeout", skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i32>,
+}
+
+impl RequestRecordAgent {
+    /// An agent recorded by walking a remote SNMP agent.
+    pub fn new(name: String, snmp_data_url: String, snmp_protocol_attributes: crate::models::RequestDeviceSnmpProtocolAttributes, target: String) -> RequestRecordAgent {
+        RequestRecordAgent {
+            description: None,
+            max_repetitions: None,
+            method: None,
+            name,
+            retries: None,
+            root_oid: None,
+            snmp_data_url,
+            snmp_protocol_attributes: Box::new(snmp_protocol_attributes),
+            target,
+            timeout: None,
+        }
+    }
+}
+
+/// The requests used to walk the agent, GETBULK by default, the SNMPv1 agent is walked by GETNEXT
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Method {
+    #[serde(rename = "GETNEXT")]
+    Getnext,
+    #[serde(rename = "GETBULK")]
+    Getbulk,
+}
+
+impl Default for Method {
+    fn default() -> Method {
+        Self::Getnext
+    }
+}
+
@@ -14,6 +14,9 @@
 
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub struct ResponseAgent {
+    /// The format of the SNMP data file, picked by the extension of the file if not present
+    #[serde(rename = "data_format", skip_serializing_if = "Option::is_none")]
+    pub data_format: Option<DataFormat>,
     /// Agent's optional description.
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -32,6 +35,7 @@ impl ResponseAgent {
     /// An agent as a response body.
     pub fn new(id: String, name: String, snmp_data_url: String) -> ResponseAgent {
         ResponseAgent {
+            data_format: None,
             description: None,
             id,
             name,
@@ -40,4 +44,18 @@ impl ResponseAgent {
     }
 }
 
+/// The format of the SNMP data file, picked by the extension of the file if not present
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum DataFormat {
+    #[serde(rename = "snmpwalk")]
+    Snmpwalk,
+    #[serde(rename = "snmprec")]
+    Snmprec,
+}
+
+impl Default for DataFormat {
+    fn default() -> DataFormat {
+        Self::Snmpwalk
+    }
+}
 
@@ -16,6 +16,9 @@
 pub struct ResponseDevice {
     #[serde(rename = "agent")]
     pub agent: Box<crate::models::ResponseAgentsItemsInner>,
+    /// The AgentX master agent dispatching the requests for the subtrees registered by the subagents
+    #[serde(rename = "agentx", skip_serializing_if = "Option::is_none")]
+    pub agentx: Option<Box<crate::models::RequestDeviceAgentx>>,
     /// Device's optional description.
     #[serde(rename = "description", skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
@@ -25,27 +28,58 @@ pub struct ResponseDevice {
     /// Device's name.
     #[serde(rename = "name")]
     pub name: String,
+    /// The upstream agent serving the variables not simulated by the device
+    #[serde(rename = "proxy", skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<Box<crate::models::RequestDeviceProxy>>,
     #[serde(rename = "snmp_host")]
     pub snmp_host: String,
     #[serde(rename = "snmp_port")]
     pub snmp_port: i32,
     #[serde(rename = "snmp_protocol_attributes")]
     pub snmp_protocol_attributes: Box<crate::models::RequestDeviceSnmpProtocolAttributes>,
+    /// The sysLocation.0 value served by the device instead of the one of the SNMP data
+    #[serde(rename = "sys_location", skip_serializing_if = "Option::is_none")]
+    pub sys_location: Option<String>,
+    /// The sysName.0 value served by the device instead of the one of the SNMP data
+    #[serde(rename = "sys_name", skip_serializing_if = "Option::is_none")]
+    pub sys_name: Option<String>,
+    /// The transport the device receives the SNMP requests on
+    #[serde(rename = "transport")]
+    pub transport: Transport,
 }
 
 impl ResponseDevice {
     /// An managed device as a response body.
-    pub fn new(agent: crate::models::ResponseAgentsItemsInner, id: String, name: String, snmp_host: String, snmp_port: i32, snmp_protocol_attributes: crate::models::RequestDeviceSnmpProtocolAttributes) -> ResponseDevice {
+    pub fn new(agent: crate::models::ResponseAgentsItemsInner, id: String, name: String, snmp_host: String, snmp_port: i32, snmp_protocol_attributes: crate::models::RequestDeviceSnmpProtocolAttributes, transport: Transport) -> ResponseDevice {
         ResponseDevice {
             agent: Box::new(agent),
+            agentx: None,
             description: None,
             id,
             name,
+            proxy: None,
             snmp_host,
             snmp_port,
             snmp_protocol_attributes: Box::new(snmp_protocol_attributes),
+            sys_location: None,
+            sys_name: None,
+            transport,
         }
     }
 }
 
+/// The transport the device receives the SNMP requests on
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Transport {
+    #[serde(rename = "udp")]
+    Udp,
+    #[serde(rename = "tcp")]
+    Tcp,
+}
+
+impl Default for Transport {
+    fn default() -> Transport {
+        Self::Udp
+    }
+}
 
@@ -322,6 +322,18 @@ mod test {
         assert!(now.elapsed() > Duration::from_secs(3));
     }
 
+    #[actix_async::test]
+    async fn do_send_capacity() {
+        let addr = TestActor::default().start();
+
+        addr.do_send(TestMsg);
+        assert_eq!(996, addr.send(TestMsg).await.unwrap());
+
+        // the messages of do_send must not exhaust the capacity when they are dequeued
+        let res = addr.send(TestMsg).timeout(Duration::from_secs(1)).await;
+        assert_eq!(res, Ok(996));
+    }
+
     #[actix_async::test]
     async fn panic_recovery() {
         let supervisor = Supervisor::builder().workers(1).build();
@@ -82,6 +82,16 @@ pub struct Sender<T> {
 
 impl<T> Sender<T> {
     pub(crate) fn do_send(&self, msg: T) -> Result<(), T> {
+        // The message bypasses the capacity, but it still has to be counted. Otherwise the counter
+        // would underflow when the message is dequeued and block all the following send operations.
+        self.channel.in_queue.fetch_add(1, Ordering::Relaxed);
+        self.push(msg).map_err(|msg| {
+            self.channel.in_queue.fetch_sub(1, Ordering::Relaxed);
+            msg
+        })
+    }
+
+    fn push(&self, msg: T) -> Result<(), T> {
         self.channel.queue.push(msg).map(|()| {
             // Notify all blocked streams.
             self.channel.stream_ops.notify(usize::MAX);
@@ -170,7 +180,7 @@ impl<T> Future for SendFuture<'_, T> {
                     Ordering::Relaxed,
                 ) {
                     Ok(cur) => {
-                        return match this.sender.do_send(msg) {
+                        return match this.sender.push(msg) {
                             Ok(_) => {
                                 // If the capacity is larger than 1, notify another blocked send operation.
                                 match cap - cur {
@@ -83,6 +83,13 @@ impl<T: Encode> ToOpaque for T {
     }
 }
 
+impl Opaque {
+    /// Wraps the value already encoded in Basic Encoding Rules.
+    pub fn new(value: alloc::vec::Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
 impl AsRef<[u8]> for Opaque {
     fn as_ref(&self) -> &[u8] {
         self.0.as_ref()
@@ -3,6 +3,7 @@ const VALUE_TYPE_DELIMITER: char = ':';
 
 pub mod parser;
 pub use crate::parser::snmp_data::SnmpDataParser;
+pub use crate::parser::snmp_data::SnmprecParser;
 
 pub mod property;
 pub use crate::property::PropertyParser;
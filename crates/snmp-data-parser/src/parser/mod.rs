@@ -1,6 +1,6 @@
 pub mod snmp_data;
 
-use crate::parser::snmp_data::ModifierExtractor;
+use crate::parser::snmp_data::{DataType, ModifierError, ModifierExtractor};
 use std::cell::RefCell;
 use std::io::BufRead;
 
@@ -11,33 +11,84 @@ pub enum ParserError {
     #[error("property error: {0}")]
     PropertyError(#[from] PropertyError),
 
-    #[error("Unrecognozed data type: {0} {1}")]
-    UnrecognozedDataType(String, strum::ParseError),
+    #[error("Line {line}: Unrecognozed data type: {data_type} {error}")]
+    UnrecognozedDataType {
+        line: usize,
+        data_type: String,
+        error: strum::ParseError,
+    },
+
+    #[error("Line {line}: Invalid OID {name}")]
+    InvalidOid { line: usize, name: String },
+
+    #[error("Line {line}: Invalid OID template {name}: {error}")]
+    InvalidOidTemplate {
+        line: usize,
+        name: String,
+        error: ModifierError,
+    },
+
+    #[error("Line {line}: Invalid {data_type} value '{value}' of {name}: {reason}")]
+    InvalidValue {
+        line: usize,
+        name: String,
+        data_type: DataType,
+        value: String,
+        reason: String,
+    },
+
+    #[error("Line {line}: Invalid record '{record}': {reason}")]
+    InvalidRecord {
+        line: usize,
+        record: String,
+        reason: String,
+    },
+
+    #[error("Line {line}: The line can't be read: {error}")]
+    ReadError { line: usize, error: std::io::Error },
+
+    #[error("{} invalid lines in the SNMP data: {}", .0.len(), display_errors(.0))]
+    InvalidData(Vec<ParserError>),
+}
+
+fn display_errors(errors: &[ParserError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("; ")
 }
 
 pub trait SnmpDataItems {
-    /// Add the given property.
+    /// Add the given property read from the `line`.
     fn add_data<Extractor: ModifierExtractor>(
         &mut self,
         extractor: &Extractor,
         property: Property,
+        line: usize,
     ) -> Result<(), ParserError>;
 
-    /// Parse the content from `line_parser` and add the data.
+    /// Parse the content from `line_parser` and add the data, all the lines which cannot be added are reported by the
+    /// `ParserError::InvalidData` once the content is parsed.
     fn parse<B: BufRead, Extractor: ModifierExtractor>(
         &mut self,
         extractor: &Extractor,
         line_parser: &RefCell<PropertyParser<B>>,
     ) -> Result<(), ParserError> {
+        let mut errors = vec![];
         loop {
-            let line = match line_parser.borrow_mut().next() {
+            let property = match line_parser.borrow_mut().next() {
                 Some(val) if val.is_ok() => val.unwrap(),
-                // errors are ignored for now
+                // errors are ignored for now, f.e. the continuation lines of the multi-line strings of snmpwalk
                 Some(_) => continue,
-                None => return Ok(()),
+                None if errors.is_empty() => return Ok(()),
+                None => return Err(ParserError::InvalidData(errors)),
             };
 
-            self.add_data(extractor, line)?
+            let line = line_parser.borrow().line_number();
+            if let Err(error) = self.add_data(extractor, property, line) {
+                errors.push(error);
+            }
         }
     }
 }
@@ -1,17 +1,18 @@
-use crate::parser::snmp_data::modifier_extractor::ModifierExtractor;
+use crate::parser::snmp_data::modifier_extractor::{ModifierContext, ModifierExtractor, PreLoadedModifier};
 use crate::parser::ParserError;
 use crate::{parser::SnmpDataItems, property::Property, VALUE_TYPE_DELIMITER};
 
 use rasn::prelude::ObjectIdentifier;
 use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
 use std::str::FromStr;
-use strum_macros::EnumString;
+use strum_macros::{Display, EnumString};
 
 #[derive(Debug, Clone)]
 pub struct SnmpDataItem {
     pub data_type: DataType,
     pub data_value: String,
-    pub pre_loaded_mod: Vec<String>,
+    pub pre_loaded_mod: Vec<PreLoadedModifier>,
     pub post_loaded_mod: Option<String>,
 }
 
@@ -27,22 +28,56 @@ impl SnmpDataItem {
     }
 
     fn process_data_value_modifiers<Extractor: ModifierExtractor>(&mut self, extractor: &Extractor) -> Self {
-        if let Some((property_value, pre_loaded_modifier)) = extractor.process_pre_loaded_modifier(&self.data_value) {
-            // TODO implement value pre-loaded modifiers support #35
+        // the offsets of the modifiers extracted from left to right are the positions in the final data value
+        while let Some((property_value, pre_loaded_modifier)) = extractor.process_pre_loaded_modifier(&self.data_value)
+        {
             self.pre_loaded_mod.push(pre_loaded_modifier);
             self.data_value = property_value;
         }
 
         if let Some((property_value, post_loaded_modifier)) = extractor.process_post_loaded_modifier(&self.data_value) {
-            // TODO implement value post-loaded modifiers support #36
+            // the post-loaded modifier is evaluated each time the value is served
             self.post_loaded_mod = Some(post_loaded_modifier);
             self.data_value = property_value;
         }
 
-        // TODO validate data_value against the expected data type #38
-
         self.clone()
     }
+
+    /// Inserts the results of the pre-loaded modifiers into the data value, the modifier which cannot be evaluated is
+    /// omitted from the value. Returns the reason if the resulting value is not valid for the data type.
+    pub fn apply_pre_loaded_modifiers<Extractor: ModifierExtractor>(
+        &mut self,
+        extractor: &Extractor,
+        context: &ModifierContext,
+    ) -> Result<(), String> {
+        let mut data_value = String::with_capacity(self.data_value.len());
+        let mut position = 0;
+        for modifier in self.pre_loaded_mod.drain(..) {
+            data_value.push_str(&self.data_value[position..modifier.offset]);
+            position = modifier.offset;
+            match extractor.evaluate_pre_loaded_modifier(&modifier.expression, context) {
+                Ok(result) => data_value.push_str(&result),
+                Err(error) => tracing::warn!(
+                    "The pre-loaded modifier {} is not applied: {error}",
+                    modifier.expression
+                ),
+            }
+        }
+        data_value.push_str(&self.data_value[position..]);
+        self.data_value = data_value;
+
+        self.data_type.validate_value(&self.data_value)
+    }
+
+    /// Validates the data value against the data type, the value of the pre-loaded modifiers is known only once the
+    /// modifiers are evaluated.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.pre_loaded_mod.is_empty() {
+            true => self.data_type.validate_value(&self.data_value),
+            false => Ok(()),
+        }
+    }
 }
 
 /// An SNMP file data
@@ -64,13 +99,14 @@ impl std::ops::DerefMut for SnmpData {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, EnumString)]
+// the data type is displayed as in the snmpwalk output
+#[derive(Clone, Debug, PartialEq, EnumString, Display)]
 pub enum DataType {
-    #[strum(serialize = "String", serialize = "STRING")]
+    #[strum(to_string = "STRING", serialize = "String")]
     String,
     #[strum(serialize = "OID")]
     Oid,
-    #[strum(serialize = "INTEGER", serialize = "Integer32")]
+    #[strum(to_string = "INTEGER", serialize = "Integer32")]
     Integer,
     #[strum(serialize = "Timeticks")]
     Timeticks,
@@ -86,7 +122,7 @@ pub enum DataType {
     HexString,
     #[strum(serialize = "Network Address")]
     NetworkAddress,
-    #[strum(serialize = "Bits", serialize = "BITS")]
+    #[strum(to_string = "BITS", serialize = "Bits")]
     Bits,
     #[strum(serialize = "Null")]
     Null,
@@ -98,10 +134,110 @@ pub enum DataType {
     OctetString,
 }
 
+impl DataType {
+    /// Validates the lexical form and the range of the data value as it is displayed in the snmpwalk output and
+    /// encoded when the value is served.
+    pub fn validate_value(&self, data_value: &str) -> Result<(), String> {
+        match self {
+            // f.e. "up(1)", the enumerated value is displayed with its label
+            DataType::Integer => match data_value
+                .strip_suffix(')')
+                .and_then(|value| value.rsplit_once('('))
+            {
+                Some((_, number)) => validate_number::<i32>(number, "a 32-bit signed integer"),
+                None => validate_number::<i32>(data_value, "a 32-bit signed integer"),
+            },
+            DataType::Counter32 | DataType::Gauge32 | DataType::UInteger32 => {
+                validate_number::<u32>(data_value, "a 32-bit unsigned integer")
+            }
+            DataType::Counter64 => validate_number::<u64>(data_value, "a 64-bit unsigned integer"),
+            // f.e. "(328918409) 38 days, 1:39:44.09" or just the hundredths of a second
+            DataType::Timeticks => match data_value.strip_prefix('(') {
+                Some(value) => match value.split_once(')') {
+                    Some((ticks, _)) => validate_number::<u32>(ticks, "a 32-bit unsigned integer"),
+                    None => Err("the closing parenthesis is missing".to_string()),
+                },
+                None => validate_number::<u32>(data_value, "a 32-bit unsigned integer"),
+            },
+            DataType::IpAddress => data_value
+                .parse::<Ipv4Addr>()
+                .map(|_| ())
+                .map_err(|error| error.to_string()),
+            // f.e. "C0:A8:68:01"
+            DataType::NetworkAddress => {
+                let octets = data_value.split(':').collect::<Vec<&str>>();
+                match data_value.parse::<Ipv4Addr>().is_ok()
+                    || (octets.len() == 4 && octets.iter().all(|octet| is_hex_octet(octet)))
+                {
+                    true => Ok(()),
+                    false => Err("the network address must be 4 hexadecimal octets separated by ':'".to_string()),
+                }
+            }
+            DataType::Oid => parse_oid(data_value)
+                .map(|_| ())
+                .ok_or_else(|| "the object identifier must be at least 2 dot separated sub-identifiers".to_string()),
+            // f.e. "00 1B 53 B8 09 D9"
+            DataType::HexString => match data_value.split_whitespace().all(is_hex_octet) {
+                true => Ok(()),
+                false => Err("the octets must be hexadecimal numbers separated by spaces".to_string()),
+            },
+            // f.e. "80 0" or "80 linkDown(0)", the hexadecimal octets followed by the numbers or the names of the bits
+            DataType::Bits => match data_value
+                .split_whitespace()
+                .all(|token| is_hex_octet(token) || token.ends_with(')'))
+            {
+                true => Ok(()),
+                false => Err("the bits must be hexadecimal octets followed by the set bits".to_string()),
+            },
+            DataType::String | DataType::OctetString | DataType::Null | DataType::Opaque => Ok(()),
+        }
+    }
+}
+
+fn validate_number<T: FromStr>(data_value: &str, expected: &str) -> Result<(), String>
+where
+    T::Err: std::fmt::Display,
+{
+    data_value
+        .parse::<T>()
+        .map(|_| ())
+        .map_err(|error| format!("{error}, expected {expected}"))
+}
+
+fn is_hex_octet(octet: &str) -> bool {
+    (1..=2).contains(&octet.len()) && octet.chars().all(|digit| digit.is_ascii_hexdigit())
+}
+
 impl SnmpData {
     pub fn new() -> Self {
         SnmpData { data: BTreeMap::new() }
     }
+
+    /// Evaluates the pre-loaded modifiers of all the items, once when the data are loaded by a device.
+    /// The item whose value of the pre-loaded modifiers is not valid for its data type is dropped from the data.
+    pub fn apply_pre_loaded_modifiers<Extractor: ModifierExtractor>(
+        &mut self,
+        extractor: &Extractor,
+        context: &ModifierContext,
+    ) {
+        self.data.retain(|name, item| {
+            if item.pre_loaded_mod.is_empty() {
+                return true;
+            }
+            match item.apply_pre_loaded_modifiers(extractor, context) {
+                Ok(()) => true,
+                Err(reason) => {
+                    tracing::warn!(
+                        "The {} item {} is dropped, its value '{}' of the pre-loaded modifiers is invalid: {reason}",
+                        item.data_type,
+                        oid_to_string(name.clone()),
+                        item.data_value
+                    );
+                    false
+                }
+            }
+        });
+    }
 }
 
 impl SnmpDataItems for SnmpData {
@@ -109,6 +245,7 @@ impl SnmpDataItems for SnmpData {
         &mut self,
         extractor: &Extractor,
         property: Property,
+        line: usize,
     ) -> Result<(), ParserError> {
         let value_type_index = property
             .value
@@ -119,17 +256,60 @@ impl SnmpDataItems for SnmpData {
         let data_value: &str;
         if value_type_index != usize::max_value() && value_type_index != 0 {
             let (data_type_raw, value) = property.value.split_at(value_type_index);
-            data_type = DataType::from_str(data_type_raw)
-                .map_err(|err| ParserError::UnrecognozedDataType(data_type_raw.to_string(), err))?;
+            data_type = DataType::from_str(data_type_raw).map_err(|error| ParserError::UnrecognozedDataType {
+                line,
+                data_type: data_type_raw.to_string(),
+                error,
+            })?;
             data_value = value.trim().trim_start_matches(VALUE_TYPE_DELIMITER).trim();
         } else {
             data_value = property.value.trim();
         }
 
-        let snmp_data_item = SnmpDataItem::new(extractor, data_type, data_value);
+        // the OID template expands into a row of a table for each of its instances
+        let invalid_template = |error| ParserError::InvalidOidTemplate {
+            line,
+            name: property.name.clone(),
+            error,
+        };
+        let instances = match extractor
+            .expand_oid_template(&property.name)
+            .map_err(invalid_template)?
+        {
+            Some(instances) => instances
+                .into_iter()
+                .map(|instance| {
+                    let data_value = extractor
+                        .apply_oid_indices(data_value, &instance.indices)
+                        .map_err(invalid_template)?;
+                    Ok((
+                        instance.oid,
+                        SnmpDataItem::new(extractor, data_type.clone(), &data_value),
+                    ))
+                })
+                .collect::<Result<Vec<_>, ParserError>>()?,
+            None => vec![(
+                property.name.clone(),
+                SnmpDataItem::new(extractor, data_type, data_value),
+            )],
+        };
 
-        // TODO implement OID pre-loaded modifiers support #37
-        self.insert(string_to_oid(&property.name), snmp_data_item);
+        for (name, snmp_data_item) in instances {
+            let oid = parse_oid(&name).ok_or_else(|| ParserError::InvalidOid {
+                line,
+                name: name.clone(),
+            })?;
+            snmp_data_item
+                .validate()
+                .map_err(|reason| ParserError::InvalidValue {
+                    line,
+                    name,
+                    data_type: snmp_data_item.data_type.clone(),
+                    value: snmp_data_item.data_value.clone(),
+                    reason,
+                })?;
+            self.insert(oid, snmp_data_item);
+        }
 
         Ok(())
     }
@@ -140,12 +320,15 @@ pub fn oid_to_string(oid: ObjectIdentifier) -> String {
 }
 
 pub fn string_to_oid(oid: &str) -> ObjectIdentifier {
-    rasn::types::ObjectIdentifier::new(
-        oid.trim_matches('.')
-            .split('.')
-            .map(|val| val.parse::<u32>().unwrap())
-            .collect::<Vec<u32>>()
-            .to_vec(),
-    )
-    .unwrap()
+    parse_oid(oid).unwrap()
+}
+
+/// Parses the dot separated sub-identifiers of the OID, None if the OID is invalid.
+pub fn parse_oid(oid: &str) -> Option<ObjectIdentifier> {
+    let arcs = oid
+        .trim_matches('.')
+        .split('.')
+        .map(|val| val.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    rasn::types::ObjectIdentifier::new(arcs)
 }
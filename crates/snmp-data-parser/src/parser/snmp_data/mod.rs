@@ -1,9 +1,11 @@
 pub mod component;
 pub mod modifier_extractor;
 pub mod snmp_data_parser;
+pub mod snmprec_parser;
 pub mod verax_modifier_extractor;
 
 pub use component::*;
 pub use modifier_extractor::*;
 pub use snmp_data_parser::*;
+pub use snmprec_parser::*;
 pub use verax_modifier_extractor::*;
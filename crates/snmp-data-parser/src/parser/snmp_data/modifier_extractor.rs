@@ -1,4 +1,125 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
 pub trait ModifierExtractor {
     fn process_post_loaded_modifier(&self, data_value: &str) -> Option<(String, String)>;
-    fn process_pre_loaded_modifier(&self, data_value: &str) -> Option<(String, String)>;
+
+    /// Evaluates the post-loaded modifier each time the value is served, `elapsed` is the time in hundredths of a
+    /// second since the data are loaded and the `state` is kept for the data item between the evaluations.
+    fn evaluate_post_loaded_modifier(
+        &self,
+        data_value: &str,
+        expression: &str,
+        state: &mut ModifierState,
+        elapsed: u64,
+        context: &ModifierContext,
+    ) -> Result<String, ModifierError>;
+
+    /// Removes the first pre-loaded modifier from the data value, the offset of the returned modifier is the position
+    /// of its result in the returned data value.
+    fn process_pre_loaded_modifier(&self, data_value: &str) -> Option<(String, PreLoadedModifier)>;
+
+    /// Expands the OID template into its instances when the data are parsed, None if the OID is not a template.
+    fn expand_oid_template(&self, oid: &str) -> Result<Option<Vec<OidInstance>>, ModifierError>;
+
+    /// Replaces the references to the indices of the expanded OID template in the data value of the instance.
+    fn apply_oid_indices(&self, data_value: &str, indices: &[u32]) -> Result<String, ModifierError>;
+
+    /// Evaluates the pre-loaded modifier once, when the data are loaded by a device.
+    fn evaluate_pre_loaded_modifier(
+        &self,
+        expression: &str,
+        context: &ModifierContext,
+    ) -> Result<String, ModifierError>;
+}
+
+/// The pre-loaded modifier extracted from the data value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreLoadedModifier {
+    /// The position in the data value the result of the modifier is inserted at
+    pub offset: usize,
+    pub expression: String,
+}
+
+/// The instance of an OID template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidInstance {
+    pub oid: String,
+    /// The sub-identifiers substituted for the templates of the OID, in the order of the templates
+    pub indices: Vec<u32>,
+}
+
+/// The state of the post-loaded modifier of a data item kept between its evaluations
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModifierState {
+    /// The value served by the last evaluation, None before the first one
+    pub value: Option<String>,
+    /// The time of the next change of the value, in hundredths of a second since the data are loaded
+    pub next_change: u64,
+}
+
+/// The context the modifiers of a device are evaluated in, shared by the requests served by the device
+#[derive(Debug)]
+pub struct ModifierContext {
+    /// The integer unique for each device loading the data
+    pub unique_id: u64,
+    random_state: AtomicU64,
+}
+
+impl ModifierContext {
+    pub fn new(unique_id: u64) -> Self {
+        // the pseudo random sequence is seeded by the current time, the state of xorshift must not be zero
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default();
+        ModifierContext {
+            unique_id,
+            random_state: AtomicU64::new((seed ^ unique_id.rotate_left(32)) | 1),
+        }
+    }
+
+    /// The next pseudo random integer of the xorshift64* generator, good enough to simulate the values but never to be
+    /// used for any security purposes.
+    pub fn next_random(&self) -> u64 {
+        let xorshift = |mut state: u64| {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state
+        };
+        let state = self
+            .random_state
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| Some(xorshift(state)))
+            .map_or_else(xorshift, xorshift);
+        state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// The pseudo random integer from the inclusive range.
+    pub fn random_range(&self, min: i64, max: i64) -> i64 {
+        let span = max.wrapping_sub(min) as u64;
+        match span.checked_add(1) {
+            Some(span) => min.wrapping_add((self.next_random() % span) as i64),
+            // the range covers all the 64-bit integers
+            None => self.next_random() as i64,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ModifierError {
+    #[error("Invalid modifier expression: {0}")]
+    InvalidExpression(String),
+
+    #[error("Unsupported modifier function: {0}")]
+    UnsupportedFunction(String),
+
+    #[error("Invalid arguments of the modifier function {0}: {1}")]
+    InvalidArguments(String, String),
+
+    #[error("The value '{0}' cannot be modified")]
+    InvalidValue(String),
+
+    #[error("The OID template {0} expands into more than {1} instances")]
+    TooManyInstances(String, usize),
 }
@@ -0,0 +1,223 @@
+use crate::parser::snmp_data::{DataType, SnmpData};
+use crate::parser::{ModifierExtractor, ParserError, SnmpDataItems};
+use crate::property::Property;
+use std::io::BufRead;
+
+const SNMPREC_DELIMITER: char = '|';
+// the tag suffix of the hexadecimal encoded value
+const SNMPREC_HEX_SUFFIX: char = 'x';
+// the tag is followed by the variation module generating the value, f.e. "65:numeric"
+const SNMPREC_VARIATION_DELIMITER: char = ':';
+
+/// Reader returning `SnmpData` object from a `BufRead` of the snmprec recording of snmpsim, a record per line in the
+/// form "oid|tag|value", f.e. "1.3.6.1.2.1.1.3.0|67|328918409".
+pub struct SnmprecParser<B, Extractor> {
+    reader: B,
+    line_number: usize,
+    extractor: Extractor,
+}
+
+impl<B: BufRead, Extractor: ModifierExtractor> SnmprecParser<B, Extractor> {
+    /// Return a new `SnmprecParser` from a `Reader`.
+    pub fn new(reader: B, extractor: Extractor) -> Self {
+        SnmprecParser {
+            reader,
+            line_number: 0,
+            extractor,
+        }
+    }
+}
+
+impl<B: BufRead, Extractor: ModifierExtractor> Iterator for SnmprecParser<B, Extractor> {
+    type Item = Result<SnmpData, ParserError>;
+
+    fn next(&mut self) -> std::option::Option<<Self as std::iter::Iterator>::Item> {
+        let mut snmp_data = SnmpData::new();
+        let mut errors = vec![];
+        let mut line = vec![];
+        loop {
+            line.clear();
+            // the octets of the record which are not UTF-8 are replaced, so the rest of the record is still served
+            match self.reader.read_until(b'\n', &mut line) {
+                Ok(0) => break,
+                Ok(_) => self.line_number += 1,
+                Err(error) => {
+                    errors.push(ParserError::ReadError {
+                        line: self.line_number + 1,
+                        error,
+                    });
+                    break;
+                }
+            }
+
+            let record = String::from_utf8_lossy(&line);
+            let record = record.trim_end_matches(['\r', '\n']);
+            if record.trim().is_empty() || record.starts_with('#') {
+                continue;
+            }
+
+            // the record is added as the property of the snmpwalk output, so it is validated and the OID templates
+            // and the value modifiers are processed the same way
+            let result = record_to_property(record, self.line_number).and_then(|property| match property {
+                Some(property) => snmp_data.add_data(&self.extractor, property, self.line_number),
+                None => Ok(()),
+            });
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+
+        match errors.is_empty() {
+            true if snmp_data.is_empty() => None,
+            true => Some(Ok(snmp_data)),
+            false => Some(Err(ParserError::InvalidData(errors))),
+        }
+    }
+}
+
+/// Maps the numeric tag of the snmprec record to the data type, the tags are the ASN.1 tags of the SNMP types.
+pub fn snmprec_data_type(tag: u8) -> Option<DataType> {
+    match tag {
+        2 => Some(DataType::Integer),
+        4 => Some(DataType::String),
+        5 => Some(DataType::Null),
+        6 => Some(DataType::Oid),
+        64 => Some(DataType::IpAddress),
+        65 => Some(DataType::Counter32),
+        66 => Some(DataType::Gauge32),
+        67 => Some(DataType::Timeticks),
+        68 => Some(DataType::Opaque),
+        70 => Some(DataType::Counter64),
+        71 => Some(DataType::UInteger32),
+        _ => None,
+    }
+}
+
+// Converts the record to the property of the snmpwalk output, f.e. "1.3.6.1.2.1.1.3.0|67|328918409" to
+// ".1.3.6.1.2.1.1.3.0 = Timeticks: 328918409", None if the record is skipped.
+fn record_to_property(record: &str, line: usize) -> Result<Option<Property>, ParserError> {
+    let invalid_record = |reason: &str| ParserError::InvalidRecord {
+        line,
+        record: record.to_string(),
+        reason: reason.to_string(),
+    };
+    let mut fields = record.splitn(3, SNMPREC_DELIMITER);
+    let (oid, tag, value) = match (fields.next(), fields.next(), fields.next()) {
+        (Some(oid), Some(tag), Some(value)) if !oid.trim().is_empty() => (oid.trim(), tag.trim(), value),
+        _ => return Err(invalid_record("the record must be in the form 'oid|tag|value'")),
+    };
+    let name = format!(".{}", oid.trim_start_matches('.'));
+
+    let (tag, variation) = match tag.split_once(SNMPREC_VARIATION_DELIMITER) {
+        Some((tag, variation)) => (tag, Some(variation)),
+        None => (tag, None),
+    };
+    let (tag, hex_encoded) = match tag.strip_suffix(SNMPREC_HEX_SUFFIX) {
+        Some(tag) => (tag, true),
+        None => (tag, false),
+    };
+    let data_type = tag
+        .parse::<u8>()
+        .ok()
+        .and_then(snmprec_data_type)
+        .ok_or_else(|| ParserError::UnrecognozedDataType {
+            line,
+            data_type: tag.to_string(),
+            error: strum::ParseError::VariantNotFound,
+        })?;
+
+    // the variation modules of snmpsim are not simulated, the value is the initial one of their options
+    let (value, hex_encoded) = match variation {
+        Some(variation) => match variation_value(value) {
+            Some((value, hex_value)) => (value, hex_encoded || hex_value),
+            None => {
+                tracing::warn!("Line {line}: The value of the variation module {variation} of {name} is skipped");
+                return Ok(None);
+            }
+        },
+        None => (value.to_string(), hex_encoded),
+    };
+
+    let value = match hex_encoded {
+        true => {
+            let octets = hex_to_octets(&value).ok_or_else(|| invalid_record("the value is not hexadecimal"))?;
+            return Ok(Some(octets_to_property(name, data_type, &octets)));
+        }
+        false => value,
+    };
+
+    let property = match data_type {
+        DataType::String => Property {
+            name,
+            value: format!("{}: {value}", DataType::String),
+        },
+        DataType::Opaque => octets_to_property(name, data_type, value.as_bytes()),
+        DataType::Oid => Property {
+            name,
+            value: format!("{}: .{}", DataType::Oid, value.trim().trim_start_matches('.')),
+        },
+        data_type => Property {
+            name,
+            value: format!("{data_type}: {}", value.trim()),
+        },
+    };
+    Ok(Some(property))
+}
+
+// The value of the variation module from its options, f.e. "min=0,max=100,initial=50", the value is either hexadecimal
+// encoded or not.
+fn variation_value(options: &str) -> Option<(String, bool)> {
+    let options = options
+        .split(',')
+        .filter_map(|option| option.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect::<Vec<(&str, &str)>>();
+    let option = |key: &str| {
+        options
+            .iter()
+            .find(|(option, _)| *option == key)
+            .map(|(_, value)| value.to_string())
+    };
+
+    option("hexvalue")
+        .map(|value| (value, true))
+        .or_else(|| option("value").map(|value| (value, false)))
+        .or_else(|| option("initial").map(|value| (value, false)))
+}
+
+fn hex_to_octets(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    if value.len() % 2 != 0 || !value.chars().all(|digit| digit.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect()
+}
+
+// The octets of the hexadecimal encoded value displayed as in the snmpwalk output
+fn octets_to_property(name: String, data_type: DataType, octets: &[u8]) -> Property {
+    let hex_string = || {
+        octets
+            .iter()
+            .map(|octet| format!("{octet:02X}"))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+    let value = match data_type {
+        DataType::String => format!("{}: {}", DataType::HexString, hex_string()),
+        DataType::Opaque => format!("{}: {}", DataType::Opaque, hex_string()),
+        DataType::IpAddress => format!(
+            "{}: {}",
+            DataType::IpAddress,
+            octets
+                .iter()
+                .map(|octet| octet.to_string())
+                .collect::<Vec<String>>()
+                .join(".")
+        ),
+        data_type => format!("{data_type}: {}", String::from_utf8_lossy(octets)),
+    };
+    Property { name, value }
+}
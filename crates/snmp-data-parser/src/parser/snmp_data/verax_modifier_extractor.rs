@@ -1,4 +1,14 @@
-use crate::parser::snmp_data::modifier_extractor::ModifierExtractor;
+use crate::parser::snmp_data::modifier_extractor::{
+    ModifierContext, ModifierError, ModifierExtractor, ModifierState, OidInstance, PreLoadedModifier,
+};
+
+// the longest random string generated by the str.rnd() modifier
+const MAX_RANDOM_STRING_LENGTH: usize = 255;
+
+// the most instances a single OID template expands into
+const MAX_OID_TEMPLATE_INSTANCES: usize = 65536;
+
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
 #[derive(Clone)]
 pub struct VeraxModifierExtractor;
@@ -7,8 +17,254 @@ impl ModifierExtractor for VeraxModifierExtractor {
         self.process_modifier(data_value, r"//\$([\W\w]*)")
     }
 
-    fn process_pre_loaded_modifier(&self, data_value: &str) -> Option<(String, String)> {
-        self.process_modifier(data_value, r"//\^([\W\w]*)\^//")
+    /// Supports the functions `type.rnd(direction,min_step,max_step,min_interval,max_interval,min,max)` and
+    /// `type.tme(...)` of the same arguments, the type is `int`, `g32`, `c32` or `c64`
+    /// - `rnd` changes the value by a random step of the step range once a random interval of the interval range
+    ///   elapses, the value is increased if the direction is 1, increased or decreased at random if 0
+    /// - `tme` changes the value by the average step for each average interval elapsed since the data are loaded, the
+    ///   value is increased if the direction is 1, decreased if 0
+    ///
+    /// The intervals are in hundredths of a second. The value is kept within the min and max, the counters `c32` and
+    /// `c64` wrap around to the min, the `int` and `g32` values stay at the bound.
+    fn evaluate_post_loaded_modifier(
+        &self,
+        data_value: &str,
+        expression: &str,
+        state: &mut ModifierState,
+        elapsed: u64,
+        context: &ModifierContext,
+    ) -> Result<String, ModifierError> {
+        let (function, arguments) = parse_expression(expression)?;
+        let wraps = match function {
+            "c32.rnd" | "c32.tme" | "c64.rnd" | "c64.tme" => true,
+            "int.rnd" | "int.tme" | "g32.rnd" | "g32.tme" => false,
+            _ => return Err(ModifierError::UnsupportedFunction(function.to_string())),
+        };
+        let arguments = arguments
+            .iter()
+            .map(|argument| parse_argument::<i128>(function, argument))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (direction, min_step, max_step, min_interval, max_interval, min, max) = match arguments.as_slice() {
+            [direction, min_step, max_step, min_interval, max_interval, min, max] => (
+                *direction,
+                *min_step,
+                *max_step,
+                *min_interval,
+                *max_interval,
+                *min,
+                *max,
+            ),
+            arguments => {
+                return Err(ModifierError::InvalidArguments(
+                    function.to_string(),
+                    format!("unexpected number of arguments {}", arguments.len()),
+                ))
+            }
+        };
+        let invalid_arguments =
+            |reason: &str| ModifierError::InvalidArguments(function.to_string(), reason.to_string());
+        if direction != 0 && direction != 1 {
+            return Err(invalid_arguments("the direction is neither 0 nor 1"));
+        }
+        if min_step < 0 || min_step > max_step || max_step > i64::MAX as i128 {
+            return Err(invalid_arguments("invalid step range"));
+        }
+        if min_interval < 0 || min_interval > max_interval || max_interval > i64::MAX as i128 {
+            return Err(invalid_arguments("invalid interval range"));
+        }
+        if min > max {
+            return Err(invalid_arguments("the minimum is greater than the maximum"));
+        }
+
+        let initial = data_value
+            .trim()
+            .parse::<i128>()
+            .map_err(|_| ModifierError::InvalidValue(data_value.to_string()))?;
+        let bounded = |value: i128| {
+            if wraps {
+                min + (value - min).rem_euclid(max - min + 1)
+            } else {
+                value.clamp(min, max)
+            }
+        };
+
+        let value = if function.ends_with(".tme") {
+            let changes = elapsed as i128 / ((min_interval + max_interval) / 2).max(1);
+            let delta = changes * ((min_step + max_step) / 2);
+            bounded(if direction == 1 {
+                initial + delta
+            } else {
+                initial - delta
+            })
+        } else {
+            // the first evaluation serves the loaded value and schedules the first change
+            let value = match &state.value {
+                None => initial,
+                Some(value) if elapsed < state.next_change => return Ok(value.clone()),
+                Some(value) => {
+                    let value = value.parse::<i128>().unwrap_or(initial);
+                    let step = context.random_range(min_step as i64, max_step as i64) as i128;
+                    if direction == 1 || context.next_random() % 2 == 0 {
+                        bounded(value + step)
+                    } else {
+                        bounded(value - step)
+                    }
+                }
+            };
+            state.next_change = elapsed + context.random_range(min_interval as i64, max_interval as i64) as u64;
+            value
+        };
+
+        state.value = Some(value.to_string());
+        Ok(value.to_string())
+    }
+
+    fn process_pre_loaded_modifier(&self, data_value: &str) -> Option<(String, PreLoadedModifier)> {
+        if !data_value.contains("//") {
+            return None;
+        }
+        // the value can contain several pre-loaded modifiers, each of them is processed separately
+        let pre_loaded_re = regex::Regex::new(r"//\^([\W\w]*?)\^//").unwrap();
+        let caps = pre_loaded_re.captures(data_value)?;
+        let modifier = caps.get(0).unwrap();
+        let property_value = format!("{}{}", &data_value[..modifier.start()], &data_value[modifier.end()..]);
+
+        Some((
+            property_value,
+            PreLoadedModifier {
+                offset: modifier.start(),
+                expression: caps.get(1).unwrap().as_str().to_string(),
+            },
+        ))
+    }
+
+    /// The sub-identifier of the OID template is a list of the indices and the inclusive ranges of the indices in
+    /// brackets, f.e. `.1.3.6.1.2.1.2.2.1.1.[1-48]` or `.1.3.6.1.2.1.4.20.1.1.10.0.[0,1].[1-3,10]`. The template of
+    /// several sub-identifiers expands into all their combinations.
+    fn expand_oid_template(&self, oid: &str) -> Result<Option<Vec<OidInstance>>, ModifierError> {
+        if !oid.contains('[') {
+            return Ok(None);
+        }
+
+        let mut instances = vec![OidInstance {
+            oid: String::new(),
+            indices: vec![],
+        }];
+        for sub_identifier in oid.trim_start_matches('.').split('.') {
+            let template = match sub_identifier
+                .strip_prefix('[')
+                .and_then(|list| list.strip_suffix(']'))
+            {
+                Some(list) => parse_index_list(list)?,
+                None => {
+                    for instance in instances.iter_mut() {
+                        instance.oid.push('.');
+                        instance.oid.push_str(sub_identifier);
+                    }
+                    continue;
+                }
+            };
+            if instances.len().saturating_mul(template.len()) > MAX_OID_TEMPLATE_INSTANCES {
+                return Err(ModifierError::TooManyInstances(
+                    oid.to_string(),
+                    MAX_OID_TEMPLATE_INSTANCES,
+                ));
+            }
+            instances = instances
+                .iter()
+                .flat_map(|instance| {
+                    template.iter().map(move |index| {
+                        let mut indices = instance.indices.clone();
+                        indices.push(*index);
+                        OidInstance {
+                            oid: format!("{}.{index}", instance.oid),
+                            indices,
+                        }
+                    })
+                })
+                .collect();
+        }
+
+        Ok(Some(instances))
+    }
+
+    /// The `//^oid.idx()^//` modifier of the data value is replaced by the index of the first template of the OID,
+    /// `//^oid.idx(n)^//` by the index of the n-th one.
+    fn apply_oid_indices(&self, data_value: &str, indices: &[u32]) -> Result<String, ModifierError> {
+        if !data_value.contains("//") {
+            return Ok(data_value.to_string());
+        }
+
+        let index_re = regex::Regex::new(r"//\^\s*oid\.idx\(\s*(\d*)\s*\)\s*\^//").unwrap();
+        let mut result = String::with_capacity(data_value.len());
+        let mut position = 0;
+        for caps in index_re.captures_iter(data_value) {
+            let modifier = caps.get(0).unwrap();
+            let template = match caps.get(1).unwrap().as_str() {
+                "" => 1,
+                template => parse_argument::<usize>("oid.idx", template)?,
+            };
+            let index = template
+                .checked_sub(1)
+                .and_then(|template| indices.get(template))
+                .ok_or_else(|| {
+                    ModifierError::InvalidArguments(
+                        "oid.idx".to_string(),
+                        format!("the OID has no template {template}"),
+                    )
+                })?;
+            result.push_str(&data_value[position..modifier.start()]);
+            result.push_str(&index.to_string());
+            position = modifier.end();
+        }
+        result.push_str(&data_value[position..]);
+
+        Ok(result)
+    }
+
+    /// Supports the functions
+    /// - `int.unq()` the integer unique for each device
+    /// - `int.rnd(min,max)` the random integer from the inclusive range
+    /// - `str.rnd(length)` the random alphanumeric string of the length
+    fn evaluate_pre_loaded_modifier(
+        &self,
+        expression: &str,
+        context: &ModifierContext,
+    ) -> Result<String, ModifierError> {
+        let (function, arguments) = parse_expression(expression)?;
+        match (function, arguments.as_slice()) {
+            ("int.unq", []) => Ok(context.unique_id.to_string()),
+            ("int.rnd", [min, max]) => {
+                let (min, max) = (
+                    parse_argument::<i64>(function, min)?,
+                    parse_argument::<i64>(function, max)?,
+                );
+                if min > max {
+                    return Err(ModifierError::InvalidArguments(
+                        function.to_string(),
+                        format!("the minimum {min} is greater than the maximum {max}"),
+                    ));
+                }
+                Ok(context.random_range(min, max).to_string())
+            }
+            ("str.rnd", [length]) => {
+                let length = parse_argument::<usize>(function, length)?;
+                if length > MAX_RANDOM_STRING_LENGTH {
+                    return Err(ModifierError::InvalidArguments(
+                        function.to_string(),
+                        format!("the length {length} exceeds {MAX_RANDOM_STRING_LENGTH}"),
+                    ));
+                }
+                Ok((0..length)
+                    .map(|_| ALPHANUMERIC[context.random_range(0, ALPHANUMERIC.len() as i64 - 1) as usize] as char)
+                    .collect())
+            }
+            ("int.unq" | "int.rnd" | "str.rnd", arguments) => Err(ModifierError::InvalidArguments(
+                function.to_string(),
+                format!("unexpected number of arguments {}", arguments.len()),
+            )),
+            _ => Err(ModifierError::UnsupportedFunction(function.to_string())),
+        }
     }
 }
 
@@ -30,3 +286,56 @@ impl VeraxModifierExtractor {
         None
     }
 }
+
+// the Verax modifier is a function call of the form `type.function(argument, ...)`, f.e. `int.rnd(1,10)`
+fn parse_expression(expression: &str) -> Result<(&str, Vec<&str>), ModifierError> {
+    let invalid_expression = || ModifierError::InvalidExpression(expression.to_string());
+    let (function, arguments) = expression
+        .trim()
+        .strip_suffix(')')
+        .and_then(|call| call.split_once('('))
+        .ok_or_else(invalid_expression)?;
+    if function.split('.').count() != 2 || function.split('.').any(|name| name.is_empty()) {
+        return Err(invalid_expression());
+    }
+
+    let arguments = match arguments.trim() {
+        "" => vec![],
+        arguments => arguments.split(',').map(str::trim).collect(),
+    };
+    Ok((function.trim(), arguments))
+}
+
+fn parse_argument<T: std::str::FromStr>(function: &str, argument: &str) -> Result<T, ModifierError> {
+    argument.parse::<T>().map_err(|_| {
+        ModifierError::InvalidArguments(function.to_string(), format!("'{argument}' is not a valid number"))
+    })
+}
+
+// the comma separated list of the indices and the ranges of the indices, f.e. `1,3,5-7`
+fn parse_index_list(list: &str) -> Result<Vec<u32>, ModifierError> {
+    let invalid_list = || ModifierError::InvalidExpression(format!("[{list}]"));
+    let mut indices = vec![];
+    for item in list.split(',').map(str::trim) {
+        let (first, last) = match item.split_once('-') {
+            Some((first, last)) => (first.trim(), last.trim()),
+            None => (item, item),
+        };
+        let (first, last) = (
+            first.parse::<u32>().map_err(|_| invalid_list())?,
+            last.parse::<u32>().map_err(|_| invalid_list())?,
+        );
+        if first > last {
+            return Err(invalid_list());
+        }
+        if indices.len() + (last - first) as usize >= MAX_OID_TEMPLATE_INSTANCES {
+            return Err(ModifierError::TooManyInstances(
+                format!("[{list}]"),
+                MAX_OID_TEMPLATE_INSTANCES,
+            ));
+        }
+        indices.extend(first..=last);
+    }
+
+    Ok(indices)
+}
@@ -44,19 +44,28 @@ impl fmt::Display for Property {
 #[derive(Debug, Clone)]
 pub struct PropertyParser<B> {
     line_reader: LineReader<B>,
+    line_number: usize,
 }
 
 impl<B: BufRead> PropertyParser<B> {
     /// Return a new `PropertyParser` from a `LineReader`.
     pub fn new(line_reader: LineReader<B>) -> PropertyParser<B> {
-        PropertyParser { line_reader }
+        PropertyParser {
+            line_reader,
+            line_number: 0,
+        }
     }
 
     /// Return a new `PropertyParser` from a `Reader`.
     pub fn from_reader(reader: B) -> PropertyParser<B> {
         let line_reader = LineReader::new(reader);
 
-        PropertyParser { line_reader }
+        PropertyParser::new(line_reader)
+    }
+
+    /// Return the number of the line the last property is parsed from.
+    pub fn line_number(&self) -> usize {
+        self.line_number
     }
 
     fn parse(&self, line: Line) -> Result<Property, PropertyError> {
@@ -93,6 +102,9 @@ impl<B: BufRead> Iterator for PropertyParser<B> {
     type Item = Result<Property, PropertyError>;
 
     fn next(&mut self) -> Option<Result<Property, PropertyError>> {
-        self.line_reader.next().map(|line| self.parse(line))
+        self.line_reader.next().map(|line| {
+            self.line_number = line.number();
+            self.parse(line)
+        })
     }
 }
@@ -60,3 +60,518 @@ pub mod parser {
         assert!(valids.next().is_none());
     }
 }
+
+pub mod modifiers {
+    use snmp_data_parser::parser::snmp_data::component::{string_to_oid, DataType, SnmpData, SnmpDataItem};
+    use snmp_data_parser::parser::snmp_data::{
+        ModifierContext, ModifierError, ModifierExtractor, ModifierState, PreLoadedModifier, VeraxModifierExtractor,
+    };
+
+    #[test]
+    fn extracts_pre_loaded_modifiers() {
+        let item = SnmpDataItem::new(
+            &VeraxModifierExtractor {},
+            DataType::String,
+            "host-//^int.unq()^//-//^str.rnd(4)^//.local",
+        );
+        assert_eq!(item.data_value, "host--.local");
+        assert_eq!(
+            item.pre_loaded_mod,
+            vec![
+                PreLoadedModifier {
+                    offset: 5,
+                    expression: "int.unq()".to_string()
+                },
+                PreLoadedModifier {
+                    offset: 6,
+                    expression: "str.rnd(4)".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn applies_pre_loaded_modifiers() {
+        let mut item = SnmpDataItem::new(
+            &VeraxModifierExtractor {},
+            DataType::String,
+            "host-//^int.unq()^//-//^str.rnd(4)^//.local",
+        );
+        assert!(item
+            .apply_pre_loaded_modifiers(&VeraxModifierExtractor {}, &ModifierContext::new(7))
+            .is_ok());
+        assert!(item.pre_loaded_mod.is_empty());
+        assert!(item.data_value.starts_with("host-7-"));
+        assert!(item.data_value.ends_with(".local"));
+        assert_eq!(item.data_value.len(), "host-7-xxxx.local".len());
+    }
+
+    #[test]
+    fn omits_invalid_pre_loaded_modifier() {
+        let mut item = SnmpDataItem::new(&VeraxModifierExtractor {}, DataType::Integer, "1//^int.foo()^//");
+        assert!(item
+            .apply_pre_loaded_modifiers(&VeraxModifierExtractor {}, &ModifierContext::new(1))
+            .is_ok());
+        assert_eq!(item.data_value, "1");
+    }
+
+    #[test]
+    fn drops_item_of_invalid_pre_loaded_value() {
+        let extractor = VeraxModifierExtractor {};
+        let mut snmp_data = SnmpData::default();
+        snmp_data.insert(
+            string_to_oid(".1.3.6.1.2.1.1.1.0"),
+            SnmpDataItem::new(&extractor, DataType::Integer, "1//^str.rnd(4)^//"),
+        );
+        snmp_data.insert(
+            string_to_oid(".1.3.6.1.2.1.1.2.0"),
+            SnmpDataItem::new(&extractor, DataType::Integer, "1//^int.unq()^//"),
+        );
+        snmp_data.apply_pre_loaded_modifiers(&extractor, &ModifierContext::new(7));
+        assert!(!snmp_data.contains_key(&string_to_oid(".1.3.6.1.2.1.1.1.0")));
+        assert_eq!(
+            snmp_data
+                .get(&string_to_oid(".1.3.6.1.2.1.1.2.0"))
+                .map(|item| item.data_value.as_str()),
+            Some("17")
+        );
+    }
+
+    #[test]
+    fn evaluates_random_integer_in_range() {
+        let context = ModifierContext::new(1);
+        for _ in 0..100 {
+            let value: i64 = VeraxModifierExtractor {}
+                .evaluate_pre_loaded_modifier("int.rnd(-5, 5)", &context)
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!((-5..=5).contains(&value));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        let context = ModifierContext::new(1);
+        let extractor = VeraxModifierExtractor {};
+        assert_eq!(
+            extractor.evaluate_pre_loaded_modifier("int.unq", &context),
+            Err(ModifierError::InvalidExpression("int.unq".to_string()))
+        );
+        assert_eq!(
+            extractor.evaluate_pre_loaded_modifier("ip.unq()", &context),
+            Err(ModifierError::UnsupportedFunction("ip.unq".to_string()))
+        );
+        assert!(matches!(
+            extractor.evaluate_pre_loaded_modifier("int.rnd(10,1)", &context),
+            Err(ModifierError::InvalidArguments(_, _))
+        ));
+        assert!(matches!(
+            extractor.evaluate_pre_loaded_modifier("int.unq(1)", &context),
+            Err(ModifierError::InvalidArguments(_, _))
+        ));
+    }
+
+    #[test]
+    fn extracts_post_loaded_modifier() {
+        let item = SnmpDataItem::new(
+            &VeraxModifierExtractor {},
+            DataType::Counter32,
+            "914518245//$c32.rnd(1,0,500,600,100000,0,4294967295)",
+        );
+        assert_eq!(item.data_value, "914518245");
+        assert_eq!(
+            item.post_loaded_mod,
+            Some("c32.rnd(1,0,500,600,100000,0,4294967295)".to_string())
+        );
+    }
+
+    #[test]
+    fn increments_counter_after_interval() {
+        let context = ModifierContext::new(1);
+        let extractor = VeraxModifierExtractor {};
+        let mut state = ModifierState::default();
+        let expression = "c32.rnd(1,1,10,100,100,0,4294967295)";
+
+        // the loaded value is served until the first interval elapses
+        assert_eq!(
+            extractor.evaluate_post_loaded_modifier("4294967290", expression, &mut state, 0, &context),
+            Ok("4294967290".to_string())
+        );
+        assert_eq!(
+            extractor.evaluate_post_loaded_modifier("4294967290", expression, &mut state, 99, &context),
+            Ok("4294967290".to_string())
+        );
+
+        // the counter wraps around to the minimum
+        let value: u64 = extractor
+            .evaluate_post_loaded_modifier("4294967290", expression, &mut state, 100, &context)
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(value > 4294967290 || value < 5);
+        assert_eq!(state.next_change, 200);
+    }
+
+    #[test]
+    fn keeps_integer_within_range() {
+        let context = ModifierContext::new(1);
+        let extractor = VeraxModifierExtractor {};
+        let mut state = ModifierState::default();
+        for elapsed in 0..100 {
+            let value: i64 = extractor
+                .evaluate_post_loaded_modifier("12", "int.rnd(0,0,10,0,0,1,100)", &mut state, elapsed, &context)
+                .unwrap()
+                .parse()
+                .unwrap();
+            assert!((1..=100).contains(&value));
+        }
+    }
+
+    #[test]
+    fn changes_value_by_elapsed_time() {
+        let context = ModifierContext::new(1);
+        let extractor = VeraxModifierExtractor {};
+        let mut state = ModifierState::default();
+        let expression = "g32.tme(0,10,30,100,300,0,1000)";
+
+        // the value decreases by 20 each 2 seconds and stays at the minimum
+        for (elapsed, expected) in [(0, "500"), (199, "500"), (200, "480"), (1000, "400"), (60000, "0")] {
+            assert_eq!(
+                extractor.evaluate_post_loaded_modifier("500", expression, &mut state, elapsed, &context),
+                Ok(expected.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_post_loaded_modifiers() {
+        let context = ModifierContext::new(1);
+        let extractor = VeraxModifierExtractor {};
+        let mut state = ModifierState::default();
+        assert_eq!(
+            extractor.evaluate_post_loaded_modifier("1", "str.rnd(1,0,1,1,1,0,1)", &mut state, 0, &context),
+            Err(ModifierError::UnsupportedFunction("str.rnd".to_string()))
+        );
+        assert!(matches!(
+            extractor.evaluate_post_loaded_modifier("1", "int.rnd(0,0,10)", &mut state, 0, &context),
+            Err(ModifierError::InvalidArguments(_, _))
+        ));
+        assert!(matches!(
+            extractor.evaluate_post_loaded_modifier("1", "int.rnd(2,0,10,0,10,1,100)", &mut state, 0, &context),
+            Err(ModifierError::InvalidArguments(_, _))
+        ));
+        assert_eq!(
+            extractor.evaluate_post_loaded_modifier(
+                "(23) 0:00:00.23",
+                "int.rnd(0,0,10,0,10,1,100)",
+                &mut state,
+                0,
+                &context
+            ),
+            Err(ModifierError::InvalidValue("(23) 0:00:00.23".to_string()))
+        );
+    }
+}
+
+pub mod templates {
+    use snmp_data_parser::parser::snmp_data::component::{string_to_oid, DataType};
+    use snmp_data_parser::parser::snmp_data::{ModifierError, VeraxModifierExtractor};
+    use snmp_data_parser::parser::ParserError;
+    use snmp_data_parser::SnmpDataParser;
+
+    const SWITCH: &str = "\
+.1.3.6.1.2.1.2.1.0 = INTEGER: 48
+.1.3.6.1.2.1.2.2.1.1.[1-48] = INTEGER: //^oid.idx()^//
+.1.3.6.1.2.1.2.2.1.2.[1-48] = STRING: GigabitEthernet1/0///^oid.idx()^//
+.1.3.6.1.2.1.2.2.1.10.[1-48] = Counter32: 0//$c32.rnd(1,0,500,100,1000,0,4294967295)
+.1.3.6.1.2.1.2.2.1.2.48 = STRING: uplink
+.1.3.6.1.4.1.9.9.[1,3].[5-6] = STRING: row //^oid.idx(2)^// of //^oid.idx(1)^//
+";
+
+    #[test]
+    fn expands_templates_into_rows() {
+        let snmp_data = SnmpDataParser::new(SWITCH.as_bytes(), VeraxModifierExtractor {})
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(snmp_data.len(), 1 + 3 * 48 + 4);
+
+        let if_index = snmp_data
+            .get(&string_to_oid(".1.3.6.1.2.1.2.2.1.1.17"))
+            .unwrap();
+        assert_eq!(if_index.data_type, DataType::Integer);
+        assert_eq!(if_index.data_value, "17");
+
+        let if_descr = snmp_data
+            .get(&string_to_oid(".1.3.6.1.2.1.2.2.1.2.1"))
+            .unwrap();
+        assert_eq!(if_descr.data_value, "GigabitEthernet1/0/1");
+
+        // the row of the template is replaced by the line which follows it
+        let if_descr = snmp_data
+            .get(&string_to_oid(".1.3.6.1.2.1.2.2.1.2.48"))
+            .unwrap();
+        assert_eq!(if_descr.data_value, "uplink");
+
+        let if_in_octets = snmp_data
+            .get(&string_to_oid(".1.3.6.1.2.1.2.2.1.10.48"))
+            .unwrap();
+        assert_eq!(if_in_octets.data_value, "0");
+        assert_eq!(
+            if_in_octets.post_loaded_mod,
+            Some("c32.rnd(1,0,500,100,1000,0,4294967295)".to_string())
+        );
+
+        let row = snmp_data
+            .get(&string_to_oid(".1.3.6.1.4.1.9.9.3.5"))
+            .unwrap();
+        assert_eq!(row.data_value, "row 5 of 3");
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        for (template, expected) in [
+            (
+                ".1.3.6.1.2.1.2.2.1.1.[5-1] = INTEGER: 1",
+                ModifierError::InvalidExpression("[5-1]".to_string()),
+            ),
+            (
+                ".1.3.6.1.2.1.2.2.1.1.[a] = INTEGER: 1",
+                ModifierError::InvalidExpression("[a]".to_string()),
+            ),
+            (
+                ".1.3.6.1.2.1.2.2.1.1.[1-1000].[1-1000] = INTEGER: 1",
+                ModifierError::TooManyInstances(".1.3.6.1.2.1.2.2.1.1.[1-1000].[1-1000]".to_string(), 65536),
+            ),
+            (
+                ".1.3.6.1.2.1.2.2.1.1.[1-2] = INTEGER: //^oid.idx(2)^//",
+                ModifierError::InvalidArguments("oid.idx".to_string(), "the OID has no template 2".to_string()),
+            ),
+        ] {
+            match SnmpDataParser::new(template.as_bytes(), VeraxModifierExtractor {}).next() {
+                Some(Err(ParserError::InvalidData(errors))) => match errors.as_slice() {
+                    [ParserError::InvalidOidTemplate { line: 1, error, .. }] => assert_eq!(error, &expected),
+                    errors => panic!("unexpected errors {errors:?}"),
+                },
+                result => panic!("unexpected result {result:?}"),
+            }
+        }
+    }
+}
+
+pub mod validation {
+    use snmp_data_parser::parser::snmp_data::component::DataType;
+    use snmp_data_parser::parser::snmp_data::VeraxModifierExtractor;
+    use snmp_data_parser::parser::ParserError;
+    use snmp_data_parser::SnmpDataParser;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    #[test]
+    fn accepts_valid_values() {
+        let input = BufReader::new(File::open("./tests/resources/os-linux-std.txt").unwrap());
+        let snmp_data = SnmpDataParser::new(input, VeraxModifierExtractor {})
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(!snmp_data.is_empty());
+    }
+
+    #[test]
+    fn validates_value_of_data_type() {
+        for (data_type, value) in [
+            (DataType::Integer, "-2147483648"),
+            (DataType::Integer, "up(1)"),
+            (DataType::Counter32, "4294967295"),
+            (DataType::Counter64, "18446744073709551615"),
+            (DataType::Timeticks, "(328918409) 38 days, 1:39:44.09"),
+            (DataType::Timeticks, "328918409"),
+            (DataType::IpAddress, "192.168.104.1"),
+            (DataType::NetworkAddress, "C0:A8:68:01"),
+            (DataType::Oid, ".1.3.6.1.4.1.8072.3.2.10"),
+            (DataType::HexString, "00 1B 53 B8 09 D9"),
+            (DataType::HexString, ""),
+            (DataType::Bits, "80 0"),
+            (DataType::String, "any value"),
+        ] {
+            assert_eq!(data_type.validate_value(value), Ok(()), "{data_type} {value}");
+        }
+
+        for (data_type, value) in [
+            (DataType::Integer, "2147483648"),
+            (DataType::Integer, "up(one)"),
+            (DataType::Gauge32, "-1"),
+            (DataType::Counter64, "18446744073709551616"),
+            (DataType::Timeticks, "(328918409 38 days"),
+            (DataType::IpAddress, "192.168.104"),
+            (DataType::NetworkAddress, "C0:A8:68"),
+            (DataType::Oid, ".1"),
+            (DataType::Oid, ".1.3.six"),
+            (DataType::HexString, "00 1B 5G"),
+            (DataType::Bits, "up"),
+        ] {
+            assert!(data_type.validate_value(value).is_err(), "{data_type} {value}");
+        }
+    }
+
+    #[test]
+    fn reports_all_invalid_lines() {
+        let input = r#".1.3.6.1.2.1.1.1.0 = STRING: "Linux"
+.1.3.6.1.2.1.1.3.0 = Timeticks: (abc) 38 days
+.1.3.6.1.2.1.2.1.0 = INTEGER: 2
+.1.3.6.1.2.1.2.2.1.5.1 = Gauge32: 10000000000
+.1.3.6.1.2.1.2.2.1.7.1 = Unknown: 1
+.1.3.6.1.2.1.2.2.1.8.x = INTEGER: 1
+.1.3.6.1.2.1.2.2.1.10.1 = Counter32: 0//^c32.rnd(1,0,500,100,1000,0,4294967295)^//
+.1.3.6.1.2.1.2.2.1.2.1 = INTEGER: //^int.unq()^//
+.1.3.6.1.2.1.2.2.1.8.1 = INTEGER: up(1)
+"#;
+        match SnmpDataParser::new(input.as_bytes(), VeraxModifierExtractor {}).next() {
+            Some(Err(ParserError::InvalidData(errors))) => {
+                assert_eq!(errors.len(), 4);
+                assert!(matches!(
+                    &errors[0],
+                    ParserError::InvalidValue { line: 2, name, data_type: DataType::Timeticks, value, .. }
+                        if name == ".1.3.6.1.2.1.1.3.0" && value == "(abc) 38 days"
+                ));
+                assert!(matches!(
+                    &errors[1],
+                    ParserError::InvalidValue {
+                        line: 4,
+                        data_type: DataType::Gauge32,
+                        ..
+                    }
+                ));
+                assert!(matches!(
+                    &errors[2],
+                    ParserError::UnrecognozedDataType { line: 5, data_type, .. } if data_type == "Unknown"
+                ));
+                assert!(matches!(&errors[3], ParserError::InvalidOid { line: 6, .. }));
+            }
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+}
+
+pub mod snmprec {
+    use snmp_data_parser::parser::snmp_data::component::{string_to_oid, DataType};
+    use snmp_data_parser::parser::snmp_data::VeraxModifierExtractor;
+    use snmp_data_parser::parser::ParserError;
+    use snmp_data_parser::SnmprecParser;
+
+    #[test]
+    fn parses_records() {
+        let input = "# a recording of snmpsim
+1.3.6.1.2.1.1.1.0|4|Linux zeus 4.8.6.5-smp #2 SMP Sun Nov 13 14:58:11 CDT 2016 i686
+1.3.6.1.2.1.1.2.0|6|1.3.6.1.4.1.8072.3.2.10
+1.3.6.1.2.1.1.3.0|67|328918409
+1.3.6.1.2.1.2.2.1.6.2|4x|00127962f940
+1.3.6.1.2.1.2.2.1.10.2|65:numeric|scale=10,deviation=1,function=cos,cumulative=1,initial=914518245
+1.3.6.1.2.1.2.2.1.16.2|65:writecache|value=42
+1.3.6.1.2.1.4.20.1.1.10.0.0.1|64x|0a000001
+1.3.6.1.2.1.31.1.1.1.6.2|70|18446744073709551615
+1.3.6.1.4.1.2021.10.1.6.1|68x|9f780441c80000
+1.3.6.1.2.1.2.2.1.21.2|66:delay|wait=100
+";
+        let snmp_data = SnmprecParser::new(input.as_bytes(), VeraxModifierExtractor {})
+            .next()
+            .unwrap()
+            .unwrap();
+        let item = |oid: &str| snmp_data.get(&string_to_oid(oid)).unwrap();
+
+        assert_eq!(snmp_data.len(), 9);
+        assert_eq!(item(".1.3.6.1.2.1.1.1.0").data_type, DataType::String);
+        assert_eq!(
+            item(".1.3.6.1.2.1.1.1.0").data_value,
+            "Linux zeus 4.8.6.5-smp #2 SMP Sun Nov 13 14:58:11 CDT 2016 i686"
+        );
+        assert_eq!(item(".1.3.6.1.2.1.1.2.0").data_type, DataType::Oid);
+        assert_eq!(item(".1.3.6.1.2.1.1.2.0").data_value, ".1.3.6.1.4.1.8072.3.2.10");
+        assert_eq!(item(".1.3.6.1.2.1.1.3.0").data_type, DataType::Timeticks);
+        assert_eq!(item(".1.3.6.1.2.1.1.3.0").data_value, "328918409");
+        assert_eq!(item(".1.3.6.1.2.1.2.2.1.6.2").data_type, DataType::HexString);
+        assert_eq!(item(".1.3.6.1.2.1.2.2.1.6.2").data_value, "00 12 79 62 F9 40");
+        assert_eq!(item(".1.3.6.1.2.1.2.2.1.10.2").data_type, DataType::Counter32);
+        assert_eq!(item(".1.3.6.1.2.1.2.2.1.10.2").data_value, "914518245");
+        assert_eq!(item(".1.3.6.1.2.1.2.2.1.16.2").data_value, "42");
+        assert_eq!(item(".1.3.6.1.2.1.4.20.1.1.10.0.0.1").data_type, DataType::IpAddress);
+        assert_eq!(item(".1.3.6.1.2.1.4.20.1.1.10.0.0.1").data_value, "10.0.0.1");
+        assert_eq!(item(".1.3.6.1.2.1.31.1.1.1.6.2").data_type, DataType::Counter64);
+        assert_eq!(item(".1.3.6.1.4.1.2021.10.1.6.1").data_type, DataType::Opaque);
+        assert_eq!(item(".1.3.6.1.4.1.2021.10.1.6.1").data_value, "9F 78 04 41 C8 00 00");
+        // the variation module without the value is skipped
+        assert!(snmp_data
+            .get(&string_to_oid(".1.3.6.1.2.1.2.2.1.21.2"))
+            .is_none());
+    }
+
+    #[test]
+    fn reports_all_invalid_records() {
+        let input = "1.3.6.1.2.1.1.1.0|4|Linux
+1.3.6.1.2.1.1.3.0|67
+1.3.6.1.2.1.1.7.0|2|seventy-two
+1.3.6.1.2.1.1.8.0|99|0
+1.3.6.1.2.1.2.2.1.6.2|4x|00127
+";
+        match SnmprecParser::new(input.as_bytes(), VeraxModifierExtractor {}).next() {
+            Some(Err(ParserError::InvalidData(errors))) => {
+                assert_eq!(errors.len(), 4);
+                assert!(matches!(&errors[0], ParserError::InvalidRecord { line: 2, .. }));
+                assert!(matches!(
+                    &errors[1],
+                    ParserError::InvalidValue {
+                        line: 3,
+                        data_type: DataType::Integer,
+                        ..
+                    }
+                ));
+                assert!(matches!(
+                    &errors[2],
+                    ParserError::UnrecognozedDataType { line: 4, data_type, .. } if data_type == "99"
+                ));
+                assert!(matches!(&errors[3], ParserError::InvalidRecord { line: 5, .. }));
+            }
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+
+    #[test]
+    fn replaces_octets_not_in_utf8() {
+        let input = b"1.3.6.1.2.1.1.1.0|4|caf\xe9\n1.3.6.1.2.1.1.3.0|67|42\n";
+        let snmp_data = SnmprecParser::new(&input[..], VeraxModifierExtractor {})
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(snmp_data.len(), 2);
+        assert_eq!(
+            snmp_data
+                .get(&string_to_oid(".1.3.6.1.2.1.1.1.0"))
+                .unwrap()
+                .data_value,
+            "caf\u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn reports_unreadable_line() {
+        struct FailingReader;
+        impl std::io::Read for FailingReader {
+            fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk failure"))
+            }
+        }
+
+        let input = std::io::Read::chain(
+            &b"1.3.6.1.2.1.1.3.0|67|42\n"[..],
+            std::io::BufReader::new(FailingReader),
+        );
+        match SnmprecParser::new(input, VeraxModifierExtractor {}).next() {
+            Some(Err(ParserError::InvalidData(errors))) => {
+                assert_eq!(errors.len(), 1);
+                assert!(matches!(&errors[0], ParserError::ReadError { line: 2, .. }));
+            }
+            result => panic!("unexpected result {result:?}"),
+        }
+    }
+}
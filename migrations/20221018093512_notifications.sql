@@ -0,0 +1,16 @@
+-- Add migration script here
+-- Create the log of the notifications received by the trap receiver
+CREATE TABLE notifications (
+    id TEXT PRIMARY KEY NOT NULL,
+    received_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    source_address TEXT NOT NULL,
+    source_port INTEGER NOT NULL,
+    snmp_version TEXT NOT NULL,
+    pdu_type TEXT NOT NULL,
+    security_name TEXT NOT NULL,
+    request_id INTEGER NOT NULL,
+    trap_oid TEXT NOT NULL,
+    variables TEXT NOT NULL
+);
+
+CREATE INDEX idx_notifications_on_received_at ON notifications(received_at);
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- The upstream agent of the device forwarding the requests for the variables not simulated by the device
+ALTER TABLE managed_devices
+ADD COLUMN proxy TEXT NULL;
@@ -0,0 +1,4 @@
+-- Add migration script here
+-- The sysName and sysLocation values of the device overriding the ones of the SNMP data
+ALTER TABLE managed_devices ADD COLUMN sys_name TEXT NULL;
+ALTER TABLE managed_devices ADD COLUMN sys_location TEXT NULL;
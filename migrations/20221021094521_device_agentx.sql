@@ -0,0 +1,4 @@
+-- Add migration script here
+-- The AgentX master agent of the device dispatching the requests for the registered subtrees to the subagents
+ALTER TABLE managed_devices
+ADD COLUMN agentx TEXT NULL;
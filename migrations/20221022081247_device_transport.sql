@@ -0,0 +1,4 @@
+-- Add migration script here
+-- The transport the device receives the SNMP requests on, either UDP or TCP (RFC 3430)
+ALTER TABLE managed_devices
+ADD COLUMN transport TEXT NOT NULL DEFAULT 'udp';
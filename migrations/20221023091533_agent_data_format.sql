@@ -0,0 +1,3 @@
+-- Add migration script here
+-- The format of the SNMP data file, picked by the extension of the file if NULL
+ALTER TABLE agents ADD COLUMN data_format TEXT NULL;
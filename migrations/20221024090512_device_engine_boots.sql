@@ -0,0 +1,4 @@
+-- Add migration script here
+-- snmpEngineBoots of the device, it's non-volatile and incremented each time the device is started (RFC 3414, 2.2.2)
+ALTER TABLE managed_devices
+ADD COLUMN engine_boots INTEGER NOT NULL DEFAULT 0;
@@ -1,8 +1,10 @@
 use crate::cli::CliContext;
-use crate::subcommands::agent::{Agent, CreateAgent, UpdateAgent};
+use crate::subcommands::agent::{Agent, CreateAgent, RecordAgent, UpdateAgent};
 use rust_client_snmp_sim_lib::apis::agents_api::*;
 use rust_client_snmp_sim_lib::apis::configuration::Configuration;
-use rust_client_snmp_sim_lib::models::RequestAgent;
+use rust_client_snmp_sim_lib::models::request_agent::DataFormat;
+use rust_client_snmp_sim_lib::models::request_record_agent::Method;
+use rust_client_snmp_sim_lib::models::{RequestAgent, RequestRecordAgent};
 use tracing::{self, trace};
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
@@ -34,6 +36,7 @@ pub(crate) async fn create_agent(ctx: &CliContext<'_>, create_agent: CreateAgent
             .unwrap(),
     );
     agent.description = create_agent.description;
+    agent.data_format = data_format(create_agent.data_format)?;
 
     let created_agent = agents_post(&configuration, agent).await?;
     println!("{:#?}", created_agent);
@@ -41,6 +44,44 @@ pub(crate) async fn create_agent(ctx: &CliContext<'_>, create_agent: CreateAgent
     Ok(())
 }
 
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn record_agent(ctx: &CliContext<'_>, record_agent: RecordAgent) -> Result<(), anyhow::Error> {
+    trace!("Record a new instance of agent={:#?}", record_agent);
+    let mut configuration = Configuration::new();
+    configuration.base_path = ctx.url();
+
+    let mut agent = RequestRecordAgent::new(
+        record_agent.name,
+        record_agent
+            .snmp_data_file
+            .into_os_string()
+            .into_string()
+            .unwrap(),
+        serde_json::from_str(&record_agent.protocol).unwrap(),
+        record_agent.target,
+    );
+    agent.description = record_agent.description;
+    agent.method = match record_agent
+        .method
+        .map(|method| method.to_uppercase())
+        .as_deref()
+    {
+        None => None,
+        Some("GETNEXT") => Some(Method::Getnext),
+        Some("GETBULK") => Some(Method::Getbulk),
+        Some(method) => anyhow::bail!("Unsupported walk method {}, expected GETNEXT or GETBULK", method),
+    };
+    agent.root_oid = record_agent.root_oid;
+    agent.max_repetitions = record_agent.max_repetitions;
+    agent.timeout = record_agent.timeout;
+    agent.retries = record_agent.retries;
+
+    let recorded_agent = agents_record_post(&configuration, agent).await?;
+    println!("{:#?}", recorded_agent);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) async fn update_agent(ctx: &CliContext<'_>, update_agent: UpdateAgent) -> Result<(), anyhow::Error> {
     trace!("Update an existing agent={:#?}", update_agent);
@@ -56,6 +97,7 @@ pub(crate) async fn update_agent(ctx: &CliContext<'_>, update_agent: UpdateAgent
             .unwrap(),
     );
     agent.description = update_agent.description;
+    agent.data_format = data_format(update_agent.data_format)?;
 
     let updated_agent = agents_id_put(&configuration, &update_agent.id, agent).await?;
     println!("{:#?}", updated_agent);
@@ -86,3 +128,15 @@ pub(crate) async fn get_agent(ctx: &CliContext<'_>, agent: Agent) -> Result<(),
 
     Ok(())
 }
+
+fn data_format(data_format: Option<String>) -> Result<Option<DataFormat>, anyhow::Error> {
+    match data_format
+        .map(|data_format| data_format.to_lowercase())
+        .as_deref()
+    {
+        None => Ok(None),
+        Some("snmpwalk") => Ok(Some(DataFormat::Snmpwalk)),
+        Some("snmprec") => Ok(Some(DataFormat::Snmprec)),
+        Some(data_format) => anyhow::bail!("Unsupported data format {}, expected snmpwalk or snmprec", data_format),
+    }
+}
@@ -2,6 +2,7 @@ use crate::cli::CliContext;
 use crate::subcommands::device::{CreateDevice, Device, UpdateDevice};
 use rust_client_snmp_sim_lib::apis::configuration::Configuration;
 use rust_client_snmp_sim_lib::apis::devices_api::*;
+use rust_client_snmp_sim_lib::models::request_device::Transport;
 use rust_client_snmp_sim_lib::models::{RequestDevice, RequestDeviceAgent};
 use tracing::{self, trace};
 
@@ -35,6 +36,15 @@ pub(crate) async fn create_device(ctx: &CliContext<'_>, create_device: CreateDev
         serde_json::from_str(&create_device.protocol).unwrap(),
     );
     device.description = create_device.description;
+    device.proxy = create_device
+        .proxy
+        .map(|proxy| serde_json::from_str(&proxy).unwrap());
+    device.agentx = create_device
+        .agentx
+        .map(|agentx| serde_json::from_str(&agentx).unwrap());
+    device.sys_name = create_device.sys_name;
+    device.sys_location = create_device.sys_location;
+    device.transport = transport(create_device.transport)?;
 
     let created_device = devices_post(&configuration, device).await?;
     println!("{:#?}", created_device);
@@ -58,6 +68,15 @@ pub(crate) async fn update_device(ctx: &CliContext<'_>, update_device: UpdateDev
         serde_json::from_str(&update_device.protocol).unwrap(),
     );
     device.description = update_device.description;
+    device.proxy = update_device
+        .proxy
+        .map(|proxy| serde_json::from_str(&proxy).unwrap());
+    device.agentx = update_device
+        .agentx
+        .map(|agentx| serde_json::from_str(&agentx).unwrap());
+    device.sys_name = update_device.sys_name;
+    device.sys_location = update_device.sys_location;
+    device.transport = transport(update_device.transport)?;
 
     let updated_device = devices_id_put(&configuration, &update_device.id, device).await?;
     println!("{:#?}", updated_device);
@@ -112,3 +131,18 @@ pub(crate) async fn stop_device(ctx: &CliContext<'_>, device: Device) -> Result<
 
     Ok(())
 }
+
+// the transport is omitted from the request unless specified, the device listens on UDP by default
+fn transport(transport: Option<String>) -> Result<Option<Transport>, anyhow::Error> {
+    Ok(
+        match transport
+            .map(|transport| transport.to_lowercase())
+            .as_deref()
+        {
+            None => None,
+            Some("udp") => Some(Transport::Udp),
+            Some("tcp") => Some(Transport::Tcp),
+            Some(transport) => anyhow::bail!("Unsupported transport {}, expected udp or tcp", transport),
+        },
+    )
+}
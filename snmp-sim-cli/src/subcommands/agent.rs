@@ -1,5 +1,5 @@
 use crate::cli::{CliContext, CommandHandler};
-use crate::operations::agent::{create_agent, delete_agent, get_agent, list_agents, update_agent};
+use crate::operations::agent::{create_agent, delete_agent, get_agent, list_agents, record_agent, update_agent};
 use async_trait::async_trait;
 use clap::{Args, Subcommand};
 
@@ -15,6 +15,9 @@ pub(crate) enum AgentCommands {
     /// Create a new instance of SNMP Agent
     Add(CreateAgent),
 
+    /// Record a new instance of SNMP Agent by walking a remote SNMP agent
+    Record(RecordAgent),
+
     /// Update an existing instance of SNMP Agent
     Update(UpdateAgent),
 
@@ -33,9 +36,57 @@ pub(crate) struct CreateAgent {
     #[clap(long)]
     pub description: Option<String>,
 
-    // path to the SNMPWalk output file
+    // path to the SNMPWalk output file or the snmprec recording
     #[clap(long, parse(from_os_str), value_hint = clap::ValueHint::FilePath)]
     pub snmp_data_file: std::path::PathBuf,
+
+    // the format of the SNMP data file, snmpwalk or snmprec, picked by the extension of the file by default
+    #[clap(long)]
+    pub data_format: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct RecordAgent {
+    // agent name
+    #[clap(long)]
+    pub name: String,
+
+    // agent description
+    #[clap(long)]
+    pub description: Option<String>,
+
+    // path to the SNMPWalk output file the walked variables are written to
+    #[clap(long, parse(from_os_str), value_hint = clap::ValueHint::FilePath)]
+    pub snmp_data_file: std::path::PathBuf,
+
+    // socket address of the walked SNMP agent, f.e. 192.168.1.1:161
+    #[clap(long)]
+    pub target: String,
+
+    // SNMP Protocol parameters as JSON string
+    #[clap(long)]
+    pub protocol: String,
+
+    // the requests used to walk the agent, GETNEXT or GETBULK
+    #[clap(long)]
+    pub method: Option<String>,
+
+    // the root of the walked subtree
+    #[clap(long)]
+    pub root_oid: Option<String>,
+
+    // max-repetitions of the GETBULK requests
+    #[clap(long)]
+    pub max_repetitions: Option<i32>,
+
+    // timeout of each request in milliseconds
+    #[clap(long)]
+    pub timeout: Option<i32>,
+
+    // number of retransmissions of each request
+    #[clap(long)]
+    pub retries: Option<i32>,
 }
 
 #[derive(Debug, Args)]
@@ -53,9 +104,13 @@ pub(crate) struct UpdateAgent {
     #[clap(long)]
     pub description: Option<String>,
 
-    // path to the SNMPWalk output file
+    // path to the SNMPWalk output file or the snmprec recording
     #[clap(long, parse(from_os_str), value_hint = clap::ValueHint::FilePath)]
     pub snmp_data_file: std::path::PathBuf,
+
+    // the format of the SNMP data file, snmpwalk or snmprec, picked by the extension of the file by default
+    #[clap(long)]
+    pub data_format: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -72,6 +127,7 @@ impl CommandHandler for AgentCommands {
         match self {
             AgentCommands::Ls => list_agents(ctx).await,
             AgentCommands::Add(args) => create_agent(ctx, args).await,
+            AgentCommands::Record(args) => record_agent(ctx, args).await,
             AgentCommands::Update(args) => update_agent(ctx, args).await,
             AgentCommands::Rm(args) => delete_agent(ctx, args).await,
             AgentCommands::Get(args) => get_agent(ctx, args).await,
@@ -43,7 +43,7 @@ pub(crate) struct CreateDevice {
     #[clap(long)]
     pub agent_id: String,
 
-    // SNMP Device Listener Host Name
+    // SNMP Device Listener Host Name, IPv4 or IPv6 Address ("::" listens on both IPv4 and IPv6)
     #[clap(long)]
     pub host: String,
 
@@ -54,6 +54,26 @@ pub(crate) struct CreateDevice {
     // SNMP Protocol parameters as JSON string
     #[clap(long)]
     pub protocol: String,
+
+    // the upstream agent serving the variables not simulated by the device as JSON string
+    #[clap(long)]
+    pub proxy: Option<String>,
+
+    // the AgentX master agent dispatching the requests to the subagents as JSON string
+    #[clap(long)]
+    pub agentx: Option<String>,
+
+    // sysName.0 served by the device instead of the one of the SNMP data
+    #[clap(long)]
+    pub sys_name: Option<String>,
+
+    // sysLocation.0 served by the device instead of the one of the SNMP data
+    #[clap(long)]
+    pub sys_location: Option<String>,
+
+    // the transport the device receives the SNMP requests on, udp or tcp
+    #[clap(long)]
+    pub transport: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -75,7 +95,7 @@ pub(crate) struct UpdateDevice {
     #[clap(long)]
     pub agent_id: String,
 
-    // SNMP Device Listener Host Name
+    // SNMP Device Listener Host Name, IPv4 or IPv6 Address ("::" listens on both IPv4 and IPv6)
     #[clap(long)]
     pub host: String,
 
@@ -86,6 +106,26 @@ pub(crate) struct UpdateDevice {
     // SNMP Protocol parameters as JSON string
     #[clap(long)]
     pub protocol: String,
+
+    // the upstream agent serving the variables not simulated by the device as JSON string
+    #[clap(long)]
+    pub proxy: Option<String>,
+
+    // the AgentX master agent dispatching the requests to the subagents as JSON string
+    #[clap(long)]
+    pub agentx: Option<String>,
+
+    // sysName.0 served by the device instead of the one of the SNMP data
+    #[clap(long)]
+    pub sys_name: Option<String>,
+
+    // sysLocation.0 served by the device instead of the one of the SNMP data
+    #[clap(long)]
+    pub sys_location: Option<String>,
+
+    // the transport the device receives the SNMP requests on, udp or tcp
+    #[clap(long)]
+    pub transport: Option<String>,
 }
 
 #[derive(Debug, Args)]
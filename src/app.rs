@@ -1,6 +1,7 @@
 use crate::configuration::Settings;
-use crate::routes::{agents_config, devices_config};
+use crate::routes::{agents_config, devices_config, notifications_config};
 use crate::settings::DatabaseSettings;
+use crate::udp_server::udp_trap_receiver::start_trap_receiver;
 use crate::udp_server::{udp_server_delegate::UdpServerDelegate, udp_server_provider::UdpServerProvider};
 use actix_web::{
     dev::ServiceFactory,
@@ -32,7 +33,8 @@ where
     app.service(
         scope(uri_prefix)
             .configure(agents_config)
-            .configure(devices_config),
+            .configure(devices_config)
+            .configure(notifications_config),
     )
 }
 
@@ -82,6 +84,13 @@ impl Service {
             .context("Failed to initialize sea-orm, SQLite database failure!")?
             .expect("Failed to initiate a database connection");
 
+        // start the built-in trap receiver, if configured
+        if let Some(trap_receiver) = &configuration.trap_receiver {
+            start_trap_receiver(trap_receiver, db_conn.clone())
+                .await
+                .context("Failed to start the trap receiver")?;
+        }
+
         let binding_address = format!("{}:{}", configuration.application.host, configuration.application.port);
         tracing::debug!("HttpServer binding address: {}", binding_address);
 
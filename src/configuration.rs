@@ -9,6 +9,9 @@ use std::path::PathBuf;
 pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
+    /// The built-in trap receiver is started only if it is configured
+    #[serde(default)]
+    pub trap_receiver: Option<TrapReceiverSettings>,
 }
 
 /// Returns the SNMP Simulator configuration
@@ -12,6 +12,7 @@ pub struct Model {
     pub name: String,
     pub snmp_data_url: String,
     pub description: Option<String>,
+    pub data_format: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -15,6 +15,12 @@ pub struct Model {
     pub snmp_protocol_attributes: String,
     pub snmp_host: String,
     pub snmp_port: i32,
+    pub proxy: Option<String>,
+    pub sys_name: Option<String>,
+    pub sys_location: Option<String>,
+    pub agentx: Option<String>,
+    pub transport: String,
+    pub engine_boots: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -4,3 +4,4 @@ pub mod prelude;
 
 pub mod agents;
 pub mod managed_devices;
+pub mod notifications;
@@ -2,3 +2,4 @@
 
 pub use super::agents::Entity as Agents;
 pub use super::managed_devices::Entity as ManagedDevices;
+pub use super::notifications::Entity as Notifications;
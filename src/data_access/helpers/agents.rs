@@ -13,6 +13,7 @@ pub(crate) async fn create_agent<'db>(
     agent_name: &str,
     description: &Option<String>,
     snmp_data_url: &str,
+    data_format: &Option<String>,
 ) -> Result<CreateResult<AgentsModel>, DbErr> {
     let result = Agents::find()
         .filter(AgentsColumn::Id.eq(id.to_string()))
@@ -28,6 +29,7 @@ pub(crate) async fn create_agent<'db>(
         name: ActiveValue::set(agent_name.to_string()),
         description: ActiveValue::set(description.clone()),
         snmp_data_url: ActiveValue::set(snmp_data_url.to_string()),
+        data_format: ActiveValue::set(data_format.clone()),
         created_at: ActiveValue::set(chrono::Utc::now()),
         modified_at: ActiveValue::set(chrono::Utc::now()),
     };
@@ -78,6 +80,7 @@ pub(crate) async fn update_agent<'db>(
     agent_name: &String,
     agent_description: &Option<String>,
     snmp_data_url: &str,
+    data_format: &Option<String>,
 ) -> Result<AgentsModel, DbErr> {
     let am: Option<AgentsModel> = Agents::find_by_id(id.to_string()).one(conn).await?;
     let mut agent: AgentsActiveModel = am.unwrap().into();
@@ -85,6 +88,7 @@ pub(crate) async fn update_agent<'db>(
     agent.name = ActiveValue::set(agent_name.to_string());
     agent.description = ActiveValue::set(agent_description.clone());
     agent.snmp_data_url = ActiveValue::set(snmp_data_url.to_string());
+    agent.data_format = ActiveValue::set(data_format.clone());
 
     agent.update(conn).await
 }
@@ -1,10 +1,13 @@
 use crate::data_access::entity::agents::{Entity as Agents, Model as AgentsModel};
 use crate::data_access::entity::managed_devices::{
-    ActiveModel as DevicesActiveModel, Entity as ManagedDevices, Model as DevicesModel,
+    ActiveModel as DevicesActiveModel, Column, Entity as ManagedDevices, Model as DevicesModel,
 };
 use crate::data_access::helpers::get_agent;
 use crate::domain::CreateResult;
-use sea_orm::{entity::prelude::*, query::*, ActiveValue, ConnectionTrait, DbErr, Delete, DeleteResult, EntityTrait};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    entity::prelude::*, query::*, ActiveValue, ConnectionTrait, DbErr, Delete, DeleteResult, EntityTrait, UpdateResult,
+};
 use uuid_dev::Uuid;
 
 #[allow(clippy::too_many_arguments)]
@@ -19,6 +22,11 @@ pub(crate) async fn create_managed_device<'db>(
     snmp_protocol_attributes: &str,
     snmp_host: &str,
     snmp_port: u16,
+    proxy: &Option<String>,
+    sys_name: &Option<String>,
+    sys_location: &Option<String>,
+    agentx: &Option<String>,
+    transport: &str,
 ) -> Result<CreateResult<(DevicesModel, Option<AgentsModel>)>, DbErr> {
     let result = ManagedDevices::find_by_id(id.to_string())
         .find_with_related(Agents)
@@ -39,6 +47,12 @@ pub(crate) async fn create_managed_device<'db>(
         snmp_protocol_attributes: ActiveValue::set(snmp_protocol_attributes.to_string()),
         snmp_host: ActiveValue::set(snmp_host.to_string()),
         snmp_port: ActiveValue::set(snmp_port.into()),
+        proxy: ActiveValue::set(proxy.clone()),
+        sys_name: ActiveValue::set(sys_name.clone()),
+        sys_location: ActiveValue::set(sys_location.clone()),
+        agentx: ActiveValue::set(agentx.clone()),
+        transport: ActiveValue::set(transport.to_string()),
+        engine_boots: ActiveValue::set(0),
     };
 
     let insert_result = ManagedDevices::insert(managed_device.clone())
@@ -107,6 +121,11 @@ pub(crate) async fn update_managed_device<'db>(
     snmp_protocol_attributes: &str,
     snmp_host: &str,
     snmp_port: u16,
+    proxy: &Option<String>,
+    sys_name: &Option<String>,
+    sys_location: &Option<String>,
+    agentx: &Option<String>,
+    transport: &str,
 ) -> Result<(DevicesModel, Option<AgentsModel>), DbErr> {
     let am: Option<DevicesModel> = ManagedDevices::find_by_id(id.to_string()).one(conn).await?;
     let mut managed_device: DevicesActiveModel = am.unwrap().into();
@@ -117,6 +136,11 @@ pub(crate) async fn update_managed_device<'db>(
     managed_device.snmp_protocol_attributes = ActiveValue::set(snmp_protocol_attributes.to_string());
     managed_device.snmp_host = ActiveValue::set(snmp_host.to_string());
     managed_device.snmp_port = ActiveValue::set(snmp_port.into());
+    managed_device.proxy = ActiveValue::set(proxy.clone());
+    managed_device.sys_name = ActiveValue::set(sys_name.clone());
+    managed_device.sys_location = ActiveValue::set(sys_location.clone());
+    managed_device.agentx = ActiveValue::set(agentx.clone());
+    managed_device.transport = ActiveValue::set(transport.to_string());
 
     let device: DevicesModel = managed_device.clone().into();
 
@@ -125,3 +149,23 @@ pub(crate) async fn update_managed_device<'db>(
 
     Ok((device, agent))
 }
+
+#[tracing::instrument(
+    level = "debug",
+    name = "[DA] Incrementing snmpEngineBoots of managed device",
+    skip(conn)
+)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn increment_engine_boots<'db>(
+    conn: &'db impl ConnectionTrait,
+    id: &Uuid,
+    max_engine_boots: u32,
+) -> Result<UpdateResult, DbErr> {
+    // the value is incremented by the database, so the concurrent starts of the device are counted both
+    ManagedDevices::update_many()
+        .col_expr(Column::EngineBoots, Expr::col(Column::EngineBoots).add(1))
+        .filter(Column::Id.eq(id.to_string()))
+        .filter(Column::EngineBoots.lt(max_engine_boots))
+        .exec(conn)
+        .await
+}
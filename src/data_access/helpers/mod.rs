@@ -1,5 +1,6 @@
 pub mod agents;
 pub mod managed_devices;
+pub mod notifications;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use agents::*;
@@ -7,6 +8,9 @@ pub(crate) use agents::*;
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use managed_devices::*;
 
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use notifications::*;
+
 impl From<crate::data_access::entity::agents::ActiveModel> for crate::data_access::entity::agents::Model {
     fn from(am: crate::data_access::entity::agents::ActiveModel) -> Self {
         Self {
@@ -16,6 +20,7 @@ impl From<crate::data_access::entity::agents::ActiveModel> for crate::data_acces
             name: am.name.unwrap(),
             description: am.description.unwrap(),
             snmp_data_url: am.snmp_data_url.unwrap(),
+            data_format: am.data_format.unwrap(),
         }
     }
 }
@@ -34,6 +39,12 @@ impl From<crate::data_access::entity::managed_devices::ActiveModel>
             snmp_protocol_attributes: am.snmp_protocol_attributes.unwrap(),
             snmp_host: am.snmp_host.unwrap(),
             snmp_port: am.snmp_port.unwrap(),
+            proxy: am.proxy.unwrap(),
+            sys_name: am.sys_name.unwrap(),
+            sys_location: am.sys_location.unwrap(),
+            agentx: am.agentx.unwrap(),
+            transport: am.transport.unwrap(),
+            engine_boots: am.engine_boots.unwrap(),
         }
     }
 }
@@ -0,0 +1,55 @@
+use crate::data_access::entity::notifications::{
+    ActiveModel as NotificationsActiveModel, Column as NotificationsColumn, Entity as Notifications,
+    Model as NotificationsModel,
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::prelude::*, ConnectionTrait, DbErr, EntityTrait, QueryOrder};
+
+#[tracing::instrument(level = "debug", name = "[DA] Create a new instance of notification", skip(conn))]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn create_notification<'db>(
+    conn: &'db impl ConnectionTrait,
+    notification: NotificationsModel,
+) -> Result<NotificationsModel, DbErr> {
+    let active_model: NotificationsActiveModel = notification.clone().into();
+    Notifications::insert(active_model).exec(conn).await?;
+    Ok(notification)
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(level = "debug", name = "[DA] Listing notifications", skip(conn))]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn list_notifications<'db>(
+    conn: &'db impl ConnectionTrait,
+    source_address: &Option<String>,
+    source_port: &Option<u16>,
+    trap_oid: &Option<String>,
+    received_from: &Option<DateTime<Utc>>,
+    received_to: &Option<DateTime<Utc>>,
+    page: usize,
+    page_size: usize,
+) -> Result<(usize, Vec<NotificationsModel>), DbErr> {
+    let mut select = Notifications::find();
+    if let Some(source_address) = source_address {
+        select = select.filter(NotificationsColumn::SourceAddress.eq(source_address.clone()));
+    }
+    if let Some(source_port) = source_port {
+        select = select.filter(NotificationsColumn::SourcePort.eq(*source_port as i32));
+    }
+    if let Some(trap_oid) = trap_oid {
+        select = select.filter(NotificationsColumn::TrapOid.eq(trap_oid.clone()));
+    }
+    if let Some(received_from) = received_from {
+        select = select.filter(NotificationsColumn::ReceivedAt.gte(*received_from));
+    }
+    if let Some(received_to) = received_to {
+        select = select.filter(NotificationsColumn::ReceivedAt.lte(*received_to));
+    }
+
+    // the latest notifications come first
+    let paginator = select
+        .order_by_desc(NotificationsColumn::ReceivedAt)
+        .paginate(conn, page_size);
+
+    Ok((paginator.num_items().await?, paginator.fetch_page(page - 1).await?))
+}
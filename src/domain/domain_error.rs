@@ -1,3 +1,4 @@
+use crate::udp_client::ClientError;
 use crate::udp_server::udp_server_error::UdpServerError;
 use shared_common::error_chain_fmt;
 use std::convert::Infallible;
@@ -17,6 +18,9 @@ pub(crate) enum DomainError {
     #[error(transparent)]
     UdpServerError(#[from] UdpServerError),
 
+    #[error(transparent)]
+    ClientError(#[from] ClientError),
+
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
 }
@@ -1,6 +1,10 @@
 use crate::data_access::entity::agents::{ActiveModel, Model};
 use chrono::{DateTime, Utc};
 use sea_orm::ActiveValue;
+use snmp_data_parser::parser::snmp_data::{SnmpData, VeraxModifierExtractor};
+use snmp_data_parser::parser::ParserError;
+use snmp_data_parser::{SnmpDataParser, SnmprecParser};
+use std::io::BufRead;
 use std::str::FromStr;
 use uuid_dev::Uuid;
 
@@ -13,6 +17,49 @@ pub(crate) struct Agent {
     pub name: String,
     pub snmp_data_url: String,
     pub description: Option<String>,
+    /// The format of the SNMP data file, picked by the extension of the file if None
+    pub data_format: Option<SnmpDataFormat>,
+}
+
+/// The format of the SNMP data file
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(strum_macros::Display, strum_macros::EnumString, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum SnmpDataFormat {
+    /// The output of `snmpwalk -On`
+    Snmpwalk,
+    /// The recording of snmpsim, "oid|tag|value" per line
+    Snmprec,
+}
+
+impl SnmpDataFormat {
+    /// The format of the SNMP data file picked by its extension, the recordings of snmpsim are "*.snmprec" files
+    pub fn from_file_name(file_name: &str) -> Self {
+        match std::path::Path::new(file_name)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some(extension) if extension.eq_ignore_ascii_case("snmprec") => SnmpDataFormat::Snmprec,
+            _ => SnmpDataFormat::Snmpwalk,
+        }
+    }
+
+    /// Parses the SNMP data in this format, the empty input is parsed into the empty data.
+    pub fn parse<B: BufRead>(&self, input: B) -> Result<SnmpData, ParserError> {
+        let snmp_data = match self {
+            SnmpDataFormat::Snmpwalk => SnmpDataParser::new(input, VeraxModifierExtractor {}).next(),
+            SnmpDataFormat::Snmprec => SnmprecParser::new(input, VeraxModifierExtractor {}).next(),
+        };
+        snmp_data.unwrap_or_else(|| Ok(SnmpData::new()))
+    }
+}
+
+impl Agent {
+    /// The format of the SNMP data file, either the one of the agent or picked by the extension of the file
+    pub fn snmp_data_format(&self) -> SnmpDataFormat {
+        self.data_format
+            .unwrap_or_else(|| SnmpDataFormat::from_file_name(&self.snmp_data_url))
+    }
 }
 
 impl From<Model> for Agent {
@@ -24,6 +71,10 @@ impl From<Model> for Agent {
             created_at: model.created_at,
             modified_at: model.modified_at,
             snmp_data_url: model.snmp_data_url,
+            // the unknown data format falls back to the one of the file name
+            data_format: model
+                .data_format
+                .and_then(|data_format| SnmpDataFormat::from_str(&data_format).ok()),
         }
     }
 }
@@ -37,6 +88,10 @@ impl From<ActiveModel> for Agent {
             created_at: am.created_at.unwrap(),
             modified_at: am.modified_at.unwrap(),
             snmp_data_url: am.snmp_data_url.unwrap(),
+            data_format: am
+                .data_format
+                .unwrap()
+                .and_then(|data_format| SnmpDataFormat::from_str(&data_format).ok()),
         }
     }
 }
@@ -50,6 +105,7 @@ impl From<Agent> for Model {
             name: agent.name,
             description: agent.description,
             snmp_data_url: agent.snmp_data_url,
+            data_format: agent.data_format.map(|data_format| data_format.to_string()),
         }
     }
 }
@@ -63,6 +119,7 @@ impl From<Agent> for ActiveModel {
             name: ActiveValue::set(agent.name),
             description: ActiveValue::set(agent.description),
             snmp_data_url: ActiveValue::set(agent.snmp_data_url),
+            data_format: ActiveValue::set(agent.data_format.map(|data_format| data_format.to_string())),
         }
     }
 }
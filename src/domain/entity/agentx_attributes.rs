@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// The transport the AgentX master agent listens on for the subagent connections (RFC 2741, 8)
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentxAddress {
+    Tcp(SocketAddr),
+    /// The path of the Unix domain socket
+    Unix(String),
+}
+
+impl fmt::Display for AgentxAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentxAddress::Tcp(address) => write!(f, "{address}"),
+            AgentxAddress::Unix(path) => write!(f, "unix:{path}"),
+        }
+    }
+}
+
+/// The AgentX master agent of a device. The requests for the variables of the subtrees registered by the connected
+/// subagents are dispatched to them, the rest of the variables is served from the SNMP data of the device
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct AgentxAttributes {
+    pub address: AgentxAddress,
+    /// The timeout of each dispatched request in milliseconds, unless the subagent specifies its own one
+    pub timeout: u32,
+}
+
+impl AgentxAttributes {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout.into())
+    }
+}
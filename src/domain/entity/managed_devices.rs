@@ -1,9 +1,12 @@
 use crate::data_access::entity::agents::Model as AgentsModel;
 use crate::data_access::entity::managed_devices::{ActiveModel, Model};
 use crate::domain::Agent;
+use crate::domain::AgentxAttributes;
+use crate::domain::ProxyAttributes;
 use crate::domain::SnmpProtocolAttributes;
 use chrono::{DateTime, Utc};
 use sea_orm::ActiveValue;
+use std::net::Ipv6Addr;
 use std::str::FromStr;
 use uuid_dev::Uuid;
 
@@ -19,6 +22,25 @@ pub(crate) struct ManagedDevice {
     pub snmp_protocol_attributes: SnmpProtocolAttributes,
     pub snmp_host: String,
     pub snmp_port: u16,
+    pub proxy: Option<ProxyAttributes>,
+    /// sysName.0 overriding the value of the SNMP data
+    pub sys_name: Option<String>,
+    /// sysLocation.0 overriding the value of the SNMP data
+    pub sys_location: Option<String>,
+    pub agentx: Option<AgentxAttributes>,
+    pub transport: SnmpTransport,
+    /// snmpEngineBoots, the number of times the device has been started
+    pub engine_boots: u32,
+}
+
+/// The transport the device receives the SNMP requests on
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(strum_macros::Display, strum_macros::EnumString, Debug, Clone, Copy, PartialEq, Eq)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum SnmpTransport {
+    Udp,
+    /// SNMP over TCP, the messages are framed by their BER encoded length (RFC 3430)
+    Tcp,
 }
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
@@ -35,6 +57,20 @@ impl ManagedDevice {
             ManagedDeviceAgent::Agent(agent) => &agent.id,
         }
     }
+
+    /// The address the device listener is bound to, in the form accepted by `ToSocketAddrs`
+    pub fn binding_address(&self) -> String {
+        host_port(&self.snmp_host, self.snmp_port)
+    }
+}
+
+/// Joins the host and the port, the IPv6 literal is enclosed in brackets (RFC 3986, 3.2.2)
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn host_port(host: &str, port: u16) -> String {
+    match host.parse::<Ipv6Addr>() {
+        Ok(address) => format!("[{address}]:{port}"),
+        Err(_) => format!("{host}:{port}"),
+    }
 }
 
 impl From<(Model, Option<AgentsModel>)> for ManagedDevice {
@@ -49,6 +85,16 @@ impl From<(Model, Option<AgentsModel>)> for ManagedDevice {
             snmp_protocol_attributes: serde_json::from_str(&model.snmp_protocol_attributes).unwrap(),
             snmp_host: model.snmp_host,
             snmp_port: model.snmp_port.try_into().unwrap(),
+            proxy: model
+                .proxy
+                .map(|proxy| serde_json::from_str(&proxy).unwrap()),
+            sys_name: model.sys_name,
+            sys_location: model.sys_location,
+            agentx: model
+                .agentx
+                .map(|agentx| serde_json::from_str(&agentx).unwrap()),
+            transport: SnmpTransport::from_str(&model.transport).unwrap(),
+            engine_boots: model.engine_boots.try_into().unwrap_or_default(),
         }
     }
 }
@@ -65,6 +111,16 @@ impl From<(Model, Vec<AgentsModel>)> for ManagedDevice {
             snmp_protocol_attributes: serde_json::from_str(&model.snmp_protocol_attributes).unwrap(),
             snmp_host: model.snmp_host,
             snmp_port: model.snmp_port.try_into().unwrap(),
+            proxy: model
+                .proxy
+                .map(|proxy| serde_json::from_str(&proxy).unwrap()),
+            sys_name: model.sys_name,
+            sys_location: model.sys_location,
+            agentx: model
+                .agentx
+                .map(|agentx| serde_json::from_str(&agentx).unwrap()),
+            transport: SnmpTransport::from_str(&model.transport).unwrap(),
+            engine_boots: model.engine_boots.try_into().unwrap_or_default(),
         }
     }
 }
@@ -81,6 +137,18 @@ impl From<(ActiveModel, Option<AgentsModel>)> for ManagedDevice {
             snmp_protocol_attributes: serde_json::from_str(&am.snmp_protocol_attributes.unwrap()).unwrap(),
             snmp_host: am.snmp_host.unwrap(),
             snmp_port: am.snmp_port.unwrap().try_into().unwrap(),
+            proxy: am
+                .proxy
+                .unwrap()
+                .map(|proxy| serde_json::from_str(&proxy).unwrap()),
+            sys_name: am.sys_name.unwrap(),
+            sys_location: am.sys_location.unwrap(),
+            agentx: am
+                .agentx
+                .unwrap()
+                .map(|agentx| serde_json::from_str(&agentx).unwrap()),
+            transport: SnmpTransport::from_str(&am.transport.unwrap()).unwrap(),
+            engine_boots: am.engine_boots.unwrap().try_into().unwrap_or_default(),
         }
     }
 }
@@ -98,6 +166,16 @@ impl From<ManagedDevice> for Model {
             snmp_protocol_attributes: serde_json::to_string(&managed_device.snmp_protocol_attributes).unwrap(),
             snmp_host: managed_device.snmp_host,
             snmp_port: managed_device.snmp_port.try_into().unwrap(),
+            proxy: managed_device
+                .proxy
+                .map(|proxy| serde_json::to_string(&proxy).unwrap()),
+            sys_name: managed_device.sys_name,
+            sys_location: managed_device.sys_location,
+            agentx: managed_device
+                .agentx
+                .map(|agentx| serde_json::to_string(&agentx).unwrap()),
+            transport: managed_device.transport.to_string(),
+            engine_boots: managed_device.engine_boots.try_into().unwrap_or(i32::MAX),
         }
     }
 }
@@ -117,6 +195,20 @@ impl From<ManagedDevice> for ActiveModel {
             ),
             snmp_host: ActiveValue::set(managed_device.snmp_host),
             snmp_port: ActiveValue::set(managed_device.snmp_port.try_into().unwrap()),
+            proxy: ActiveValue::set(
+                managed_device
+                    .proxy
+                    .map(|proxy| serde_json::to_string(&proxy).unwrap()),
+            ),
+            sys_name: ActiveValue::set(managed_device.sys_name),
+            sys_location: ActiveValue::set(managed_device.sys_location),
+            agentx: ActiveValue::set(
+                managed_device
+                    .agentx
+                    .map(|agentx| serde_json::to_string(&agentx).unwrap()),
+            ),
+            transport: ActiveValue::set(managed_device.transport.to_string()),
+            engine_boots: ActiveValue::set(managed_device.engine_boots.try_into().unwrap_or(i32::MAX)),
         }
     }
 }
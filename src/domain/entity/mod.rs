@@ -1,6 +1,9 @@
 mod agents;
+mod agentx_attributes;
 mod create_result;
 mod managed_devices;
+mod notifications;
+mod proxy_attributes;
 mod snmp;
 mod snmp_protocol_attributes;
 mod update_result;
@@ -11,6 +14,9 @@ pub(crate) use agents::*;
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use managed_devices::*;
 
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use notifications::*;
+
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use create_result::*;
 
@@ -20,5 +26,11 @@ pub(crate) use update_result::*;
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use snmp_protocol_attributes::*;
 
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use proxy_attributes::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use agentx_attributes::*;
+
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use snmp::*;
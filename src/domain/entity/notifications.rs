@@ -0,0 +1,121 @@
+use crate::data_access::entity::notifications::Model;
+use crate::domain::Variable;
+use chrono::{DateTime, Utc};
+use rasn::prelude::ObjectIdentifier;
+use serde::{Deserialize, Serialize};
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, string_to_oid, DataType};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+use uuid_dev::Uuid;
+
+/// The SNMP version of the message carrying the received notification
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(strum_macros::Display, strum_macros::EnumString, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotificationVersion {
+    #[strum(serialize = "SNMPv1")]
+    SnmpV1,
+    #[strum(serialize = "SNMPv2c")]
+    SnmpV2c,
+    #[strum(serialize = "SNMPv3")]
+    SnmpV3,
+}
+
+/// The PDU type of the received notification
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(strum_macros::Display, strum_macros::EnumString, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotificationPduType {
+    Trap,
+    InformRequest,
+}
+
+/// Notification received by the trap receiver, the SNMPv1 traps are translated
+/// to the snmpTrapOID and the variable bindings of SNMPv2 (RFC 3584, 3.1)
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(Debug, Clone)]
+pub(crate) struct ReceivedNotification {
+    pub id: Uuid,
+    pub received_at: DateTime<Utc>,
+    pub source: SocketAddr,
+    pub snmp_version: NotificationVersion,
+    pub pdu_type: NotificationPduType,
+    /// The community of SNMPv1/SNMPv2c, or the USM user name of SNMPv3
+    pub security_name: String,
+    pub request_id: i32,
+    pub trap_oid: ObjectIdentifier,
+    pub variables: Vec<Variable>,
+}
+
+/// The criteria of the notifications listed from the notification log, all of them are optional
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NotificationFilter {
+    pub source_address: Option<IpAddr>,
+    pub source_port: Option<u16>,
+    pub trap_oid: Option<ObjectIdentifier>,
+    pub received_from: Option<DateTime<Utc>>,
+    pub received_to: Option<DateTime<Utc>>,
+}
+
+// the variable bindings are stored as a JSON array
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredVariable {
+    oid: String,
+    data_type: String,
+    value: String,
+}
+
+impl From<Model> for ReceivedNotification {
+    fn from(model: Model) -> ReceivedNotification {
+        let variables: Vec<StoredVariable> = serde_json::from_str(&model.variables).unwrap();
+        Self {
+            id: Uuid::from_str(&model.id).unwrap(),
+            received_at: model.received_at,
+            source: SocketAddr::new(
+                model
+                    .source_address
+                    .parse()
+                    .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
+                model.source_port.try_into().unwrap(),
+            ),
+            snmp_version: NotificationVersion::from_str(&model.snmp_version).unwrap(),
+            pdu_type: NotificationPduType::from_str(&model.pdu_type).unwrap(),
+            security_name: model.security_name,
+            request_id: model.request_id,
+            trap_oid: string_to_oid(&model.trap_oid),
+            variables: variables
+                .into_iter()
+                .map(|variable| Variable {
+                    name: string_to_oid(&variable.oid),
+                    data_type: DataType::from_str(&variable.data_type).unwrap_or(DataType::Null),
+                    value: variable.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<ReceivedNotification> for Model {
+    fn from(notification: ReceivedNotification) -> Self {
+        let variables: Vec<StoredVariable> = notification
+            .variables
+            .into_iter()
+            .map(|variable| StoredVariable {
+                oid: oid_to_string(variable.name),
+                data_type: variable.data_type.to_string(),
+                value: variable.value,
+            })
+            .collect();
+        Self {
+            id: notification.id.to_string(),
+            received_at: notification.received_at,
+            source_address: notification.source.ip().to_string(),
+            source_port: notification.source.port().into(),
+            snmp_version: notification.snmp_version.to_string(),
+            pdu_type: notification.pdu_type.to_string(),
+            security_name: notification.security_name,
+            request_id: notification.request_id,
+            trap_oid: oid_to_string(notification.trap_oid),
+            variables: serde_json::to_string(&variables).unwrap(),
+        }
+    }
+}
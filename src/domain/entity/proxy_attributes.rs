@@ -0,0 +1,42 @@
+use crate::domain::SnmpProtocolAttributes;
+use rasn::prelude::ObjectIdentifier;
+use serde::{Deserialize, Serialize};
+use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// The upstream agent of a partially simulated device. The requests for the variables of the configured subtrees,
+/// or for the variables absent from the SNMP data of the device if no subtree is configured, are forwarded to it
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ProxyAttributes {
+    pub target: SocketAddr,
+    pub snmp_protocol_attributes: SnmpProtocolAttributes,
+    pub subtrees: Vec<String>,
+    /// The number of seconds the upstream values are cached for, they are not cached if None
+    pub cache_ttl: Option<u32>,
+    /// The timeout of each forwarded request in milliseconds
+    pub timeout: u32,
+    pub retries: u32,
+}
+
+impl ProxyAttributes {
+    /// The roots of the forwarded subtrees in the lexicographic order
+    pub fn subtrees(&self) -> Vec<ObjectIdentifier> {
+        let mut subtrees: Vec<ObjectIdentifier> = self
+            .subtrees
+            .iter()
+            .map(|subtree| string_to_oid(subtree))
+            .collect();
+        subtrees.sort();
+        subtrees
+    }
+
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        self.cache_ttl
+            .map(|cache_ttl| Duration::from_secs(cache_ttl.into()))
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout.into())
+    }
+}
@@ -0,0 +1,90 @@
+use crate::domain::{octet_string_to_value, to_hex_string, VarBindException, Variable, VariableBinding};
+use crate::snmp::agentx::{AgentxValue, AgentxVarBind};
+use rasn_smi::v2::*;
+use rasn_snmp::v2::VarBindValue;
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+use snmp_data_parser::parser::snmp_data::component::DataType;
+
+impl From<&VariableBinding> for AgentxVarBind {
+    fn from(variable_binding: &VariableBinding) -> Self {
+        // the variable is encoded by the SNMPv2 SMI first, the AgentX values are the same (RFC 2741, 5.4)
+        let var_bind = rasn_snmp::v2::VarBind::from(variable_binding);
+        let value = match var_bind.value {
+            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(value))) => {
+                AgentxValue::Integer(i32::try_from(value).unwrap_or_default())
+            }
+            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(value))) => {
+                AgentxValue::OctetString(value.to_vec())
+            }
+            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::ObjectId(value))) => {
+                AgentxValue::ObjectIdentifier(value)
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(value))) => {
+                AgentxValue::IpAddress(value.0.to_vec())
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(value))) => {
+                AgentxValue::Counter32(value.0)
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(value))) => {
+                AgentxValue::Gauge32(value.0)
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(value))) => {
+                AgentxValue::TimeTicks(value.0)
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Arbitrary(value))) => {
+                AgentxValue::Opaque(value.as_ref().to_vec())
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::BigCounter(value))) => {
+                AgentxValue::Counter64(value.0)
+            }
+            VarBindValue::Unspecified => AgentxValue::Null,
+            VarBindValue::NoSuchObject => AgentxValue::NoSuchObject,
+            VarBindValue::NoSuchInstance => AgentxValue::NoSuchInstance,
+            VarBindValue::EndOfMibView => AgentxValue::EndOfMibView,
+        };
+
+        AgentxVarBind {
+            name: var_bind.name,
+            value,
+        }
+    }
+}
+
+impl From<AgentxVarBind> for VariableBinding {
+    fn from(var_bind: AgentxVarBind) -> Self {
+        let (data_type, value) = match var_bind.value {
+            AgentxValue::Integer(value) => (DataType::Integer, value.to_string()),
+            AgentxValue::OctetString(value) => octet_string_to_value(&value),
+            AgentxValue::Null => (DataType::Null, String::default()),
+            AgentxValue::ObjectIdentifier(value) => (DataType::Oid, oid_to_string(value)),
+            AgentxValue::IpAddress(value) => (
+                DataType::IpAddress,
+                value
+                    .iter()
+                    .map(|octet| octet.to_string())
+                    .collect::<Vec<String>>()
+                    .join("."),
+            ),
+            AgentxValue::Counter32(value) => (DataType::Counter32, value.to_string()),
+            AgentxValue::Gauge32(value) => (DataType::Gauge32, value.to_string()),
+            AgentxValue::TimeTicks(value) => (DataType::Timeticks, value.to_string()),
+            AgentxValue::Opaque(value) => (DataType::Opaque, to_hex_string(&value)),
+            AgentxValue::Counter64(value) => (DataType::Counter64, value.to_string()),
+            AgentxValue::NoSuchObject => {
+                return VariableBinding::Exception(var_bind.name, VarBindException::NoSuchObject)
+            }
+            AgentxValue::NoSuchInstance => {
+                return VariableBinding::Exception(var_bind.name, VarBindException::NoSuchInstance)
+            }
+            AgentxValue::EndOfMibView => {
+                return VariableBinding::Exception(var_bind.name, VarBindException::EndOfMibView)
+            }
+        };
+
+        VariableBinding::Value(Variable {
+            name: var_bind.name,
+            data_type,
+            value,
+        })
+    }
+}
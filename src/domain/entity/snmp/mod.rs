@@ -1,5 +1,7 @@
+mod agentx;
 mod snmp_v1;
 mod snmp_v2;
+mod snmp_v3;
 
 // #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 // pub(crate) use snmp_v1::*;
@@ -7,11 +9,173 @@ mod snmp_v2;
 // #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 // pub(crate) use snmp_v2::*;
 
+pub use snmp_v3::*;
+
+use crate::domain::SnmpProtocolAttributes;
 use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
 use crate::snmp::codec::snmp_codec::SnmpCodec;
 use bytes::Bytes;
 use rasn::prelude::{ObjectIdentifier, OctetString};
 use snmp_data_parser::parser::snmp_data::component::DataType;
+use std::borrow::Cow;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+// sysUpTime.0 (RFC 3418, 2)
+pub const SYS_UP_TIME_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 2, 1, 1, 3, 0]));
+// sysName.0 (RFC 3418, 2)
+pub const SYS_NAME_OID: ObjectIdentifier = ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 2, 1, 1, 5, 0]));
+// sysLocation.0 (RFC 3418, 2)
+pub const SYS_LOCATION_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 2, 1, 1, 6, 0]));
+// snmpEngineTime.0 (RFC 3411, 5)
+pub const SNMP_ENGINE_TIME_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 6, 3, 10, 2, 1, 3, 0]));
+// snmpTrapOID.0 (RFC 3418, 2)
+pub const SNMP_TRAP_OID_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0]));
+// snmpTrapEnterprise.0 (RFC 3418, 2)
+pub const SNMP_TRAP_ENTERPRISE_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 6, 3, 1, 1, 4, 3, 0]));
+// snmpTraps, the prefix of the generic notifications (RFC 3418, 2)
+pub const SNMP_TRAPS_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unchecked(Cow::Borrowed(&[1, 3, 6, 1, 6, 3, 1, 1, 5]));
+
+#[inline]
+pub fn to_hex_string(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Maps the raw octet string value to the data type used by the SNMP data store,
+/// the printable values are kept as a String, others as a Hex-STRING.
+pub fn octet_string_to_value(bytes: &[u8]) -> (DataType, String) {
+    match std::str::from_utf8(bytes) {
+        Ok(value) if !value.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+            (DataType::String, value.to_string())
+        }
+        _ => (DataType::HexString, to_hex_string(bytes)),
+    }
+}
+
+/// Parses the INTEGER value, either the number or the snmpwalk format of the enumerated value, f.e. "up(1)"
+pub fn integer_value(value: &str) -> Option<i32> {
+    let value = value.trim();
+    match value
+        .strip_suffix(')')
+        .and_then(|value| value.rsplit_once('('))
+    {
+        Some((_, number)) => number.trim().parse().ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Parses the Timeticks value, either the number of hundredths of a second or the snmpwalk format,
+/// f.e. "(328918409) 38 days, 1:39:44.09"
+pub fn timeticks_value(value: &str) -> Option<u32> {
+    let value = value.trim();
+    match value.strip_prefix('(') {
+        Some(value) => value.split(')').next()?.trim().parse().ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Parses the Hex-STRING value, the hexadecimal octets separated by spaces, f.e. "00 1B 53 B8 09 D9"
+pub fn hex_string_value(value: &str) -> Option<Vec<u8>> {
+    value.split_whitespace().map(hex_octet).collect()
+}
+
+/// Parses the Network Address value, either the hexadecimal octets separated by colons, f.e. "C0:A8:68:01", or the
+/// dotted IPv4 address
+pub fn network_address_value(value: &str) -> Option<Ipv4Addr> {
+    let value = value.trim();
+    value.parse().ok().or_else(|| {
+        let octets: [u8; 4] = value
+            .split(':')
+            .map(hex_octet)
+            .collect::<Option<Vec<u8>>>()?
+            .try_into()
+            .ok()?;
+        Some(Ipv4Addr::from(octets))
+    })
+}
+
+/// Parses the BITS value, the hexadecimal octets followed by the numbers of the set bits, f.e. "80 0" or
+/// "80 linkDown(0)", either of them may be omitted. The most significant bit of the first octet is the bit 0.
+pub fn bits_value(value: &str) -> Option<Vec<u8>> {
+    let tokens = value.split_whitespace().collect::<Vec<&str>>();
+    let bit = |token: &str| match token.strip_suffix(')') {
+        Some(named_bit) => named_bit.rsplit_once('(')?.1.parse::<usize>().ok(),
+        None => token.parse::<usize>().ok(),
+    };
+    let bits_to_octets = |bits: &[usize], length: usize| {
+        let mut octets = vec![0u8; length];
+        for &bit in bits {
+            *octets.get_mut(bit / 8)? |= 0x80 >> (bit % 8);
+        }
+        Some(octets)
+    };
+
+    // the octets of Net-SNMP are always 2 hexadecimal digits, but the bit numbers may look the same, so the octets
+    // are those matching the bits following them
+    for count in (1..=tokens.len()).rev() {
+        let octets = tokens[..count]
+            .iter()
+            .map(|token| {
+                Some(token)
+                    .filter(|token| token.len() == 2)
+                    .and_then(|token| hex_octet(token))
+            })
+            .collect::<Option<Vec<u8>>>();
+        let bits = tokens[count..]
+            .iter()
+            .map(|token| bit(token))
+            .collect::<Option<Vec<usize>>>();
+        if let (Some(octets), Some(bits)) = (octets, bits) {
+            if bits_to_octets(&bits, octets.len()).as_ref() == Some(&octets) {
+                return Some(octets);
+            }
+        }
+    }
+
+    match hex_string_value(value) {
+        Some(octets) => Some(octets),
+        None => {
+            let bits = tokens
+                .iter()
+                .map(|token| bit(token))
+                .collect::<Option<Vec<usize>>>()?;
+            let length = bits.iter().max().map_or(0, |bit| bit / 8 + 1);
+            bits_to_octets(&bits, length)
+        }
+    }
+}
+
+/// Parses the Opaque value, either the hexadecimal octets of the BER encoded value or the float and double of
+/// Net-SNMP, f.e. "Float: 1.5", which are wrapped into the opaque types of the draft-perkins-opaque-01
+pub fn opaque_value(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim();
+    if let Some(float) = value.strip_prefix("Float:") {
+        let float: f32 = float.trim().parse().ok()?;
+        return Some([&[0x9F, 0x78, 0x04], &float.to_be_bytes()[..]].concat());
+    }
+    if let Some(double) = value.strip_prefix("Double:") {
+        let double: f64 = double.trim().parse().ok()?;
+        return Some([&[0x9F, 0x79, 0x08], &double.to_be_bytes()[..]].concat());
+    }
+    hex_string_value(value)
+}
+
+fn hex_octet(octet: &str) -> Option<u8> {
+    match octet.len() {
+        1 | 2 if octet.chars().all(|digit| digit.is_ascii_hexdigit()) => u8::from_str_radix(octet, 16).ok(),
+        _ => None,
+    }
+}
 
 #[inline]
 pub fn to_string_default(bytes: &OctetString, default: &str) -> String {
@@ -38,7 +202,7 @@ pub enum ValidationError {
 pub enum SnmpProtocolVersion {
     SNMPV1(String),
     SNMPV2C(String),
-    //    SNMPV3,
+    SNMPV3(SnmpV3Parameters),
 }
 
 #[derive(strum_macros::Display, Debug)]
@@ -49,15 +213,58 @@ pub enum ErrorStatus {
     BadValue = 3,
     ReadOnly = 4,
     GenErr = 5,
+    // SNMPv2 error status values (RFC 3416)
+    NoAccess = 6,
+    WrongType = 7,
+    WrongLength = 8,
+    WrongEncoding = 9,
+    WrongValue = 10,
+    NoCreation = 11,
+    InconsistentValue = 12,
+    ResourceUnavailable = 13,
+    CommitFailed = 14,
+    UndoFailed = 15,
+    AuthorizationError = 16,
+    NotWritable = 17,
+    InconsistentName = 18,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Variable {
     pub name: ObjectIdentifier,
     pub data_type: DataType,
     pub value: String,
 }
 
+/// SNMPv2 exception values returned in place of the variable value (RFC 3416)
+#[derive(strum_macros::Display, Debug, Clone, PartialEq)]
+pub enum VarBindException {
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+#[derive(Debug, Clone)]
+pub enum VariableBinding {
+    Value(Variable),
+    Exception(ObjectIdentifier, VarBindException),
+}
+
+impl VariableBinding {
+    pub fn name(&self) -> &ObjectIdentifier {
+        match self {
+            VariableBinding::Value(variable) => &variable.name,
+            VariableBinding::Exception(name, _) => name,
+        }
+    }
+}
+
+impl From<Variable> for VariableBinding {
+    fn from(variable: Variable) -> Self {
+        VariableBinding::Value(variable)
+    }
+}
+
 #[derive(Debug)]
 pub struct GetRequest {
     pub request_id: i32,
@@ -70,10 +277,126 @@ pub struct GetNextRequest {
     pub objects: Vec<ObjectIdentifier>,
 }
 
+#[derive(Debug)]
+pub struct GetBulkRequest {
+    pub request_id: i32,
+    pub non_repeaters: u32,
+    pub max_repetitions: u32,
+    pub objects: Vec<ObjectIdentifier>,
+}
+
+#[derive(Debug)]
+pub struct SetRequest {
+    pub request_id: i32,
+    pub variables: Vec<Variable>,
+}
+
 #[derive(Debug)]
 pub struct GetResponse {
     pub request_id: i32,
-    pub variable_values: Vec<Variable>,
+    pub variable_values: Vec<VariableBinding>,
+}
+
+/// Report PDU sent by the SNMPv3 engine when the processing of a message fails (RFC 3412, 7.1)
+#[derive(Debug)]
+pub struct Report {
+    pub request_id: i32,
+    pub variables: Vec<Variable>,
+}
+
+/// The generic traps of SNMPv1, each of them is represented by the snmpTraps.(value + 1)
+/// notification in SNMPv2 (RFC 3584, 3.1)
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum GenericTrap {
+    ColdStart = 0,
+    WarmStart = 1,
+    LinkDown = 2,
+    LinkUp = 3,
+    AuthenticationFailure = 4,
+    EgpNeighborLoss = 5,
+    EnterpriseSpecific = 6,
+}
+
+impl GenericTrap {
+    /// The snmpTrapOID of the generic trap, e.g. authenticationFailure = .1.3.6.1.6.3.1.1.5.5
+    pub fn oid(&self) -> ObjectIdentifier {
+        let mut oid = SNMP_TRAPS_OID.to_vec();
+        oid.push(*self as u32 + 1);
+        ObjectIdentifier::new_unchecked(oid.into())
+    }
+}
+
+/// Notification originated by the device, it is carried by the SNMPv2-Trap-PDU,
+/// or translated to the SNMPv1 Trap-PDU for the SNMPv1 managers (RFC 3584, 3.2)
+#[derive(Debug, Clone)]
+pub struct Trap {
+    pub request_id: i32,
+    pub sys_up_time: u32,
+    pub trap_oid: ObjectIdentifier,
+    pub agent_address: Ipv4Addr,
+    pub variables: Vec<Variable>,
+}
+
+/// Notification requested to be sent by a running device to the managers,
+/// the sysUpTime.0 is supplied by the device when the notification is sent
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub request_id: i32,
+    pub trap_oid: ObjectIdentifier,
+    pub variables: Vec<Variable>,
+    pub targets: Vec<SocketAddr>,
+}
+
+/// Confirmed notification sent by the device, the InformRequest-PDU carries the same variable
+/// bindings as the SNMPv2-Trap-PDU (RFC 3416, 4.2.7)
+#[derive(Debug, Clone)]
+pub struct InformRequest {
+    pub request_id: i32,
+    pub sys_up_time: u32,
+    pub trap_oid: ObjectIdentifier,
+    pub variables: Vec<Variable>,
+}
+
+/// The delivery status of the InformRequest sent by the device
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum InformStatus {
+    Pending,
+    Acknowledged,
+    Failed,
+}
+
+/// InformRequest requested to be sent by a running device to the manager, it is retransmitted
+/// after each timeout until the manager acknowledges it by the Response-PDU, or the retries are exhausted
+#[derive(Debug, Clone)]
+pub struct Inform {
+    pub request_id: i32,
+    pub trap_oid: ObjectIdentifier,
+    pub variables: Vec<Variable>,
+    pub target: SocketAddr,
+    pub timeout: Duration,
+    pub retries: u32,
+    pub attempts: u32,
+    pub status: InformStatus,
+}
+
+/// The PDU used to walk the subtree of a remote agent
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum WalkMethod {
+    GetNext,
+    GetBulk,
+}
+
+/// Walk of a remote agent, the variables of the subtree are retrieved by a sequence of
+/// the GetNextRequests or the GetBulkRequests until the agent returns a variable outside of the subtree
+#[derive(Debug, Clone)]
+pub struct Walk {
+    pub target: SocketAddr,
+    pub snmp_protocol_attributes: SnmpProtocolAttributes,
+    pub method: WalkMethod,
+    pub root: ObjectIdentifier,
+    pub max_repetitions: u32,
+    pub timeout: Duration,
+    pub retries: u32,
 }
 
 #[derive(Debug)]
@@ -97,6 +420,9 @@ impl From<(&SnmpProtocolVersion, GetRequest)> for GenericSnmpMessage {
                 community: Bytes::from(community.as_bytes().to_vec()),
                 data: request.into(),
             }),
+            SnmpProtocolVersion::SNMPV3(parameters) => {
+                GenericSnmpMessage::V3Message(Box::new(parameters.to_message(request.into()).into()))
+            }
         }
     }
 }
@@ -114,6 +440,29 @@ impl From<(&SnmpProtocolVersion, GetNextRequest)> for GenericSnmpMessage {
                 community: Bytes::from(community.as_bytes().to_vec()),
                 data: request.into(),
             }),
+            SnmpProtocolVersion::SNMPV3(parameters) => {
+                GenericSnmpMessage::V3Message(Box::new(parameters.to_message(request.into()).into()))
+            }
+        }
+    }
+}
+
+impl From<(&SnmpProtocolVersion, SetRequest)> for GenericSnmpMessage {
+    fn from((protocol_version, request): (&SnmpProtocolVersion, SetRequest)) -> Self {
+        match protocol_version {
+            SnmpProtocolVersion::SNMPV1(community) => GenericSnmpMessage::V1Message(rasn_snmp::v1::Message {
+                version: SnmpCodec::SNMP_VERSION1.into(),
+                community: Bytes::from(community.as_bytes().to_vec()),
+                data: request.into(),
+            }),
+            SnmpProtocolVersion::SNMPV2C(community) => GenericSnmpMessage::V2Message(rasn_snmp::v2c::Message {
+                version: SnmpCodec::SNMP_VERSION2.into(),
+                community: Bytes::from(community.as_bytes().to_vec()),
+                data: request.into(),
+            }),
+            SnmpProtocolVersion::SNMPV3(parameters) => {
+                GenericSnmpMessage::V3Message(Box::new(parameters.to_message(request.into()).into()))
+            }
         }
     }
 }
@@ -131,6 +480,9 @@ impl From<(&SnmpProtocolVersion, GetResponse)> for GenericSnmpMessage {
                 community: Bytes::from(community.as_bytes().to_vec()),
                 data: response.into(),
             }),
+            SnmpProtocolVersion::SNMPV3(parameters) => {
+                GenericSnmpMessage::V3Message(Box::new(parameters.to_message(response.into()).into()))
+            }
         }
     }
 }
@@ -148,6 +500,29 @@ impl From<(&SnmpProtocolVersion, GetResponseError)> for GenericSnmpMessage {
                 community: Bytes::from(community.as_bytes().to_vec()),
                 data: response.into(),
             }),
+            SnmpProtocolVersion::SNMPV3(parameters) => {
+                GenericSnmpMessage::V3Message(Box::new(parameters.to_message(response.into()).into()))
+            }
+        }
+    }
+}
+
+impl From<(&SnmpProtocolVersion, Trap)> for GenericSnmpMessage {
+    fn from((protocol_version, trap): (&SnmpProtocolVersion, Trap)) -> Self {
+        match protocol_version {
+            SnmpProtocolVersion::SNMPV1(community) => GenericSnmpMessage::V1Message(rasn_snmp::v1::Message {
+                version: SnmpCodec::SNMP_VERSION1.into(),
+                community: Bytes::from(community.as_bytes().to_vec()),
+                data: trap.into(),
+            }),
+            SnmpProtocolVersion::SNMPV2C(community) => GenericSnmpMessage::V2Message(rasn_snmp::v2c::Message {
+                version: SnmpCodec::SNMP_VERSION2.into(),
+                community: Bytes::from(community.as_bytes().to_vec()),
+                data: trap.into(),
+            }),
+            SnmpProtocolVersion::SNMPV3(parameters) => {
+                GenericSnmpMessage::V3Message(Box::new(parameters.to_message(trap.into()).into()))
+            }
         }
     }
 }
@@ -1,9 +1,19 @@
-use crate::domain::try_to_i32;
-use crate::domain::{GetNextRequest, GetRequest, GetResponse, GetResponseError, ValidationError, Variable};
+use crate::domain::{
+    bits_value, hex_string_value, integer_value, network_address_value, octet_string_to_value, opaque_value,
+    timeticks_value, to_hex_string, try_to_i32,
+};
+use crate::domain::{GenericTrap, SNMP_TRAPS_OID, SNMP_TRAP_ENTERPRISE_OID};
+use crate::domain::{
+    GetNextRequest, GetRequest, GetResponse, GetResponseError, SetRequest, Trap, ValidationError, Variable,
+    VariableBinding,
+};
 use num_bigint::ToBigInt;
+use num_traits::ToPrimitive;
+use rasn::prelude::ObjectIdentifier;
 use rasn_smi::v1::*;
-use snmp_data_parser::parser::snmp_data::component::string_to_oid;
 use snmp_data_parser::parser::snmp_data::component::DataType;
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, string_to_oid};
+use std::net::Ipv4Addr;
 
 impl TryFrom<rasn_snmp::v1::GetRequest> for GetRequest {
     type Error = ValidationError;
@@ -39,6 +49,110 @@ impl TryFrom<rasn_snmp::v1::GetNextRequest> for GetNextRequest {
     }
 }
 
+impl TryFrom<rasn_snmp::v1::SetRequest> for SetRequest {
+    type Error = ValidationError;
+
+    fn try_from(rasn_req: rasn_snmp::v1::SetRequest) -> Result<Self, Self::Error> {
+        let pdu = rasn_req.0;
+        Ok(SetRequest {
+            request_id: try_to_i32(&pdu.request_id)?,
+            variables: pdu
+                .variable_bindings
+                .into_iter()
+                .map(Variable::from)
+                .collect(),
+        })
+    }
+}
+
+// translation of the SNMPv1 Trap-PDU to the SNMPv2 notification parameters (RFC 3584, 3.1)
+impl TryFrom<rasn_snmp::v1::Trap> for Trap {
+    type Error = ValidationError;
+
+    fn try_from(rasn_req: rasn_snmp::v1::Trap) -> Result<Self, Self::Error> {
+        let generic_trap = rasn_req
+            .generic_trap
+            .to_u32()
+            .filter(|generic_trap| *generic_trap <= GenericTrap::EnterpriseSpecific as u32)
+            .ok_or_else(|| ValidationError::Invalid(format!("generic-trap is not valid {}", rasn_req.generic_trap)))?;
+        let specific_trap = rasn_req.specific_trap.to_u32().ok_or_else(|| {
+            ValidationError::Invalid(format!("specific-trap is not valid {}", rasn_req.specific_trap))
+        })?;
+        let NetworkAddress::Internet(IpAddress(agent_address)) = rasn_req.agent_addr;
+        let agent_address = <[u8; 4]>::try_from(agent_address.as_ref())
+            .map(Ipv4Addr::from)
+            .unwrap_or(Ipv4Addr::UNSPECIFIED);
+
+        let mut variables: Vec<Variable> = rasn_req
+            .variable_bindings
+            .into_iter()
+            .map(Variable::from)
+            .collect();
+        let trap_oid = if generic_trap == GenericTrap::EnterpriseSpecific as u32 {
+            // the enterprise specific trap is the enterprise.0.specific-trap
+            let mut trap_oid = rasn_req.enterprise.to_vec();
+            trap_oid.extend([0, specific_trap]);
+            ObjectIdentifier::new_unchecked(trap_oid.into())
+        } else {
+            // the generic traps are the snmpTraps.(generic-trap + 1), the enterprise is kept in snmpTrapEnterprise.0
+            variables.push(Variable {
+                name: SNMP_TRAP_ENTERPRISE_OID,
+                data_type: DataType::Oid,
+                value: oid_to_string(rasn_req.enterprise),
+            });
+            let mut trap_oid = SNMP_TRAPS_OID.to_vec();
+            trap_oid.push(generic_trap + 1);
+            ObjectIdentifier::new_unchecked(trap_oid.into())
+        };
+
+        Ok(Trap {
+            // the Trap-PDU does not carry any request-id
+            request_id: 0,
+            sys_up_time: rasn_req.time_stamp.0,
+            trap_oid,
+            agent_address,
+            variables,
+        })
+    }
+}
+
+impl From<rasn_snmp::v1::VarBind> for Variable {
+    fn from(var_bind: rasn_snmp::v1::VarBind) -> Self {
+        let (data_type, value) = match var_bind.value {
+            ObjectSyntax::Simple(SimpleSyntax::Number(value)) => (DataType::Integer, value.to_string()),
+            ObjectSyntax::Simple(SimpleSyntax::String(value)) => octet_string_to_value(&value),
+            ObjectSyntax::Simple(SimpleSyntax::Object(value)) => (DataType::Oid, oid_to_string(value)),
+            ObjectSyntax::Simple(SimpleSyntax::Empty) => (DataType::Null, String::default()),
+            ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(NetworkAddress::Internet(IpAddress(value)))) => (
+                DataType::IpAddress,
+                value
+                    .iter()
+                    .map(|octet| octet.to_string())
+                    .collect::<Vec<String>>()
+                    .join("."),
+            ),
+            ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(Counter(value))) => {
+                (DataType::Counter32, value.to_string())
+            }
+            ObjectSyntax::ApplicationWide(ApplicationSyntax::Gauge(Gauge(value))) => {
+                (DataType::Gauge32, value.to_string())
+            }
+            ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(TimeTicks(value))) => {
+                (DataType::Timeticks, value.to_string())
+            }
+            ObjectSyntax::ApplicationWide(ApplicationSyntax::Arbitrary(value)) => {
+                (DataType::Opaque, to_hex_string(value.as_ref()))
+            }
+        };
+
+        Variable {
+            name: var_bind.name,
+            data_type,
+            value,
+        }
+    }
+}
+
 impl From<GetRequest> for rasn_snmp::v1::Pdu {
     fn from(request: GetRequest) -> Self {
         rasn_snmp::v1::Pdu {
@@ -83,6 +197,21 @@ impl From<GetNextRequest> for rasn_snmp::v1::Pdu {
     }
 }
 
+impl From<SetRequest> for rasn_snmp::v1::Pdu {
+    fn from(request: SetRequest) -> Self {
+        rasn_snmp::v1::Pdu {
+            request_id: request.request_id.to_bigint().unwrap(),
+            error_status: rasn_snmp::v1::Pdu::ERROR_STATUS_NO_ERROR.into(),
+            error_index: 0.into(),
+            variable_bindings: request
+                .variables
+                .iter()
+                .map(rasn_snmp::v1::VarBind::from)
+                .collect(),
+        }
+    }
+}
+
 impl From<GetResponse> for rasn_snmp::v1::Pdu {
     fn from(response: GetResponse) -> Self {
         rasn_snmp::v1::Pdu {
@@ -117,6 +246,19 @@ impl From<GetResponseError> for rasn_snmp::v1::Pdu {
     }
 }
 
+impl From<&VariableBinding> for rasn_snmp::v1::VarBind {
+    fn from(variable_binding: &VariableBinding) -> Self {
+        match variable_binding {
+            VariableBinding::Value(variable) => variable.into(),
+            // SNMPv1 does not support the exception values
+            VariableBinding::Exception(name, _) => rasn_snmp::v1::VarBind {
+                name: name.clone(),
+                value: ObjectSyntax::Simple(SimpleSyntax::Empty),
+            },
+        }
+    }
+}
+
 impl From<&Variable> for rasn_snmp::v1::VarBind {
     fn from(variable: &Variable) -> Self {
         let value = match variable.value.as_str() {
@@ -125,16 +267,20 @@ impl From<&Variable> for rasn_snmp::v1::VarBind {
                 DataType::String => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
                 DataType::Oid => ObjectSyntax::Simple(SimpleSyntax::Object(string_to_oid(&variable.value))),
                 DataType::Null => ObjectSyntax::Simple(SimpleSyntax::Empty),
-                DataType::Integer => {
-                    ObjectSyntax::Simple(SimpleSyntax::Number(variable.value.clone().parse().unwrap()))
-                }
-                DataType::UInteger32 => {
-                    ObjectSyntax::Simple(SimpleSyntax::Number(variable.value.clone().parse().unwrap()))
-                }
-                DataType::Counter32 => {
-                    let value: u32 = variable.value.clone().parse().unwrap();
-                    ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(Counter(value)))
-                }
+                // the value which doesn't fit its data type is served as the octet string, the same way as
+                // the unparsable values of the other data types
+                DataType::Integer => match integer_value(&variable.value) {
+                    Some(value) => ObjectSyntax::Simple(SimpleSyntax::Number(value.into())),
+                    None => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::UInteger32 => match variable.value.parse() {
+                    Ok(value) => ObjectSyntax::Simple(SimpleSyntax::Number(value)),
+                    Err(_) => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::Counter32 => match variable.value.parse() {
+                    Ok(value) => ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(Counter(value))),
+                    Err(_) => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
                 DataType::Counter64 => {
                     if let Ok(value) = variable.value.clone().parse() {
                         ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(Counter(value)))
@@ -142,27 +288,41 @@ impl From<&Variable> for rasn_snmp::v1::VarBind {
                         ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into()))
                     }
                 }
-                DataType::Gauge32 => {
-                    let value: u32 = variable.value.clone().parse().unwrap();
-                    ObjectSyntax::ApplicationWide(ApplicationSyntax::Gauge(Gauge(value)))
-                }
-                DataType::IpAddress => {
-                    use std::net::Ipv4Addr;
-                    let value: Ipv4Addr = variable.value.clone().parse().unwrap();
-                    ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(NetworkAddress::Internet(IpAddress(
-                        bytes::Bytes::from(value.octets().to_vec()),
-                    ))))
-                }
+                DataType::Gauge32 => match variable.value.parse() {
+                    Ok(value) => ObjectSyntax::ApplicationWide(ApplicationSyntax::Gauge(Gauge(value))),
+                    Err(_) => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::IpAddress => match variable.value.parse::<Ipv4Addr>() {
+                    Ok(value) => ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(NetworkAddress::Internet(
+                        IpAddress(bytes::Bytes::from(value.octets().to_vec())),
+                    ))),
+                    Err(_) => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::Timeticks => match timeticks_value(&variable.value) {
+                    Some(value) => ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(TimeTicks(value))),
+                    None => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
 
-                _ => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
-                // DataType::NetworkAddress => ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(
-                //     NetworkAddress::Internet(IpAddress(variable.value.clone().into())),
-                // )),
-                // DataType::HexString => {}
-                // DataType::Timeticks => {}
-                // DataType::Bits => {}
-                // DataType::Opaque => {}
-                // DataType::OctetString => {}
+                DataType::HexString => match hex_string_value(&variable.value) {
+                    Some(value) => ObjectSyntax::Simple(SimpleSyntax::String(value.into())),
+                    None => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::NetworkAddress => match network_address_value(&variable.value) {
+                    Some(value) => ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(NetworkAddress::Internet(
+                        IpAddress(bytes::Bytes::from(value.octets().to_vec())),
+                    ))),
+                    None => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                // the BITS construct is encoded as the OCTET STRING (RFC 2578, 7.1.4)
+                DataType::Bits => match bits_value(&variable.value) {
+                    Some(value) => ObjectSyntax::Simple(SimpleSyntax::String(value.into())),
+                    None => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::Opaque => match opaque_value(&variable.value) {
+                    Some(value) => ObjectSyntax::ApplicationWide(ApplicationSyntax::Arbitrary(Opaque::new(value))),
+                    None => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
+                },
+                DataType::OctetString => ObjectSyntax::Simple(SimpleSyntax::String(variable.value.clone().into())),
             },
         };
 
@@ -173,6 +333,55 @@ impl From<&Variable> for rasn_snmp::v1::VarBind {
     }
 }
 
+// translation of the SNMPv2 notification parameters to the SNMPv1 Trap-PDU (RFC 3584, 3.2)
+impl From<Trap> for rasn_snmp::v1::Trap {
+    fn from(trap: Trap) -> Self {
+        let enterprise = trap
+            .variables
+            .iter()
+            .find(|variable| variable.name == SNMP_TRAP_ENTERPRISE_OID)
+            .map(|variable| string_to_oid(&variable.value));
+
+        let (enterprise, generic_trap, specific_trap) = match trap.trap_oid.strip_prefix(&SNMP_TRAPS_OID[..]) {
+            // the generic traps are sent with the enterprise of snmpTrapEnterprise.0 or snmpTraps
+            Some([generic_trap]) if (1..=GenericTrap::EnterpriseSpecific as u32).contains(generic_trap) => {
+                (enterprise.unwrap_or(SNMP_TRAPS_OID), generic_trap - 1, 0)
+            }
+            // the enterprise specific traps are sent with the prefix of the snmpTrapOID.0,
+            // the next-to-last sub-identifier is omitted if it is zero
+            _ => {
+                let (specific_trap, prefix) = trap.trap_oid.split_last().unwrap_or((&0, &[]));
+                let prefix = prefix.strip_suffix(&[0]).unwrap_or(prefix);
+                (
+                    ObjectIdentifier::new_unchecked(prefix.to_vec().into()),
+                    GenericTrap::EnterpriseSpecific as u32,
+                    *specific_trap,
+                )
+            }
+        };
+
+        rasn_snmp::v1::Trap {
+            enterprise,
+            agent_addr: NetworkAddress::Internet(IpAddress(trap.agent_address.octets().to_vec().into())),
+            generic_trap: generic_trap.into(),
+            specific_trap: specific_trap.into(),
+            time_stamp: TimeTicks(trap.sys_up_time),
+            variable_bindings: trap
+                .variables
+                .iter()
+                .filter(|variable| variable.name != SNMP_TRAP_ENTERPRISE_OID)
+                .map(rasn_snmp::v1::VarBind::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<Trap> for rasn_snmp::v1::Pdus {
+    fn from(trap: Trap) -> Self {
+        rasn_snmp::v1::Pdus::Trap(trap.into())
+    }
+}
+
 impl From<GetRequest> for rasn_snmp::v1::Pdus {
     fn from(request: GetRequest) -> Self {
         rasn_snmp::v1::Pdus::GetRequest(rasn_snmp::v1::GetRequest(request.into()))
@@ -185,6 +394,12 @@ impl From<GetNextRequest> for rasn_snmp::v1::Pdus {
     }
 }
 
+impl From<SetRequest> for rasn_snmp::v1::Pdus {
+    fn from(request: SetRequest) -> Self {
+        rasn_snmp::v1::Pdus::SetRequest(rasn_snmp::v1::SetRequest(request.into()))
+    }
+}
+
 impl From<GetResponse> for rasn_snmp::v1::Pdus {
     fn from(response: GetResponse) -> Self {
         rasn_snmp::v1::Pdus::GetResponse(rasn_snmp::v1::GetResponse(response.into()))
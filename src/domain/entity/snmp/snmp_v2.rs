@@ -1,13 +1,22 @@
-use crate::domain::try_to_i32;
-use crate::domain::{GetNextRequest, GetRequest, GetResponse, GetResponseError, ValidationError, Variable};
+use crate::domain::{
+    bits_value, hex_string_value, integer_value, network_address_value, octet_string_to_value, opaque_value,
+    timeticks_value, to_hex_string, try_to_i32,
+};
+use crate::domain::{
+    GetBulkRequest, GetNextRequest, GetRequest, GetResponse, GetResponseError, InformRequest, Report, SetRequest, Trap,
+    ValidationError, VarBindException, Variable, VariableBinding,
+};
+use crate::domain::{SNMP_TRAP_OID_OID, SYS_UP_TIME_OID};
 use num_traits::ToPrimitive;
 use num_traits::Zero;
+use rasn::prelude::ObjectIdentifier;
 use rasn_smi::v1::Gauge;
 use rasn_smi::v1::IpAddress;
 use rasn_smi::v2::*;
 use rasn_snmp::v2::VarBindValue;
-use snmp_data_parser::parser::snmp_data::component::string_to_oid;
 use snmp_data_parser::parser::snmp_data::component::DataType;
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, string_to_oid};
+use std::net::Ipv4Addr;
 
 impl TryFrom<rasn_snmp::v2::GetRequest> for GetRequest {
     type Error = ValidationError;
@@ -43,6 +52,153 @@ impl TryFrom<rasn_snmp::v2::GetNextRequest> for GetNextRequest {
     }
 }
 
+impl TryFrom<rasn_snmp::v2::GetBulkRequest> for GetBulkRequest {
+    type Error = ValidationError;
+
+    fn try_from(rasn_req: rasn_snmp::v2::GetBulkRequest) -> Result<Self, Self::Error> {
+        let pdu = rasn_req.0;
+        let objects = pdu
+            .variable_bindings
+            .into_iter()
+            .map(|item| item.name)
+            .collect();
+        Ok(GetBulkRequest {
+            request_id: pdu.request_id,
+            non_repeaters: pdu.non_repeaters,
+            max_repetitions: pdu.max_repetitions,
+            objects,
+        })
+    }
+}
+
+impl TryFrom<rasn_snmp::v2::SetRequest> for SetRequest {
+    type Error = ValidationError;
+
+    fn try_from(rasn_req: rasn_snmp::v2::SetRequest) -> Result<Self, Self::Error> {
+        let pdu = rasn_req.0;
+        Ok(SetRequest {
+            request_id: try_to_i32(&pdu.request_id)?,
+            variables: pdu
+                .variable_bindings
+                .into_iter()
+                .map(Variable::from)
+                .collect(),
+        })
+    }
+}
+
+impl TryFrom<rasn_snmp::v2::Trap> for Trap {
+    type Error = ValidationError;
+
+    fn try_from(rasn_req: rasn_snmp::v2::Trap) -> Result<Self, Self::Error> {
+        let (request_id, sys_up_time, trap_oid, variables) = notification_parameters(rasn_req.0)?;
+        Ok(Trap {
+            request_id,
+            sys_up_time,
+            trap_oid,
+            // the SNMPv2-Trap-PDU does not carry the agent address, the source of the datagram identifies the agent
+            agent_address: Ipv4Addr::UNSPECIFIED,
+            variables,
+        })
+    }
+}
+
+impl TryFrom<rasn_snmp::v2::InformRequest> for InformRequest {
+    type Error = ValidationError;
+
+    fn try_from(rasn_req: rasn_snmp::v2::InformRequest) -> Result<Self, Self::Error> {
+        let (request_id, sys_up_time, trap_oid, variables) = notification_parameters(rasn_req.0)?;
+        Ok(InformRequest {
+            request_id,
+            sys_up_time,
+            trap_oid,
+            variables,
+        })
+    }
+}
+
+// the first two variable bindings of the notification are the sysUpTime.0 and snmpTrapOID.0 (RFC 3416, 4.2.6)
+fn notification_parameters(
+    pdu: rasn_snmp::v2::Pdu,
+) -> Result<(i32, u32, ObjectIdentifier, Vec<Variable>), ValidationError> {
+    let request_id = try_to_i32(&pdu.request_id)?;
+    let mut variable_bindings = pdu.variable_bindings.into_iter();
+
+    let sys_up_time = match variable_bindings.next() {
+        Some(rasn_snmp::v2::VarBind {
+            name,
+            value: VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(value))),
+        }) if name == SYS_UP_TIME_OID => value.0,
+        _ => {
+            return Err(ValidationError::Invalid(
+                "the first variable binding of the notification is not sysUpTime.0".into(),
+            ))
+        }
+    };
+    let trap_oid = match variable_bindings.next() {
+        Some(rasn_snmp::v2::VarBind {
+            name,
+            value: VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::ObjectId(value))),
+        }) if name == SNMP_TRAP_OID_OID => value,
+        _ => {
+            return Err(ValidationError::Invalid(
+                "the second variable binding of the notification is not snmpTrapOID.0".into(),
+            ))
+        }
+    };
+
+    Ok((
+        request_id,
+        sys_up_time,
+        trap_oid,
+        variable_bindings.map(Variable::from).collect(),
+    ))
+}
+
+impl From<rasn_snmp::v2::VarBind> for Variable {
+    fn from(var_bind: rasn_snmp::v2::VarBind) -> Self {
+        let (data_type, value) = match var_bind.value {
+            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(value))) => {
+                (DataType::Integer, value.to_string())
+            }
+            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(value))) => octet_string_to_value(&value),
+            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::ObjectId(value))) => {
+                (DataType::Oid, oid_to_string(value))
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(IpAddress(value)))) => (
+                DataType::IpAddress,
+                value
+                    .iter()
+                    .map(|octet| octet.to_string())
+                    .collect::<Vec<String>>()
+                    .join("."),
+            ),
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(value))) => {
+                (DataType::Counter32, value.0.to_string())
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(value))) => {
+                (DataType::Timeticks, value.0.to_string())
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Arbitrary(value))) => {
+                (DataType::Opaque, to_hex_string(value.as_ref()))
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::BigCounter(value))) => {
+                (DataType::Counter64, value.0.to_string())
+            }
+            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(value))) => {
+                (DataType::Gauge32, value.0.to_string())
+            }
+            _ => (DataType::Null, String::default()),
+        };
+
+        Variable {
+            name: var_bind.name,
+            data_type,
+            value,
+        }
+    }
+}
+
 impl From<GetRequest> for rasn_snmp::v2::Pdu {
     fn from(request: GetRequest) -> Self {
         rasn_snmp::v2::Pdu {
@@ -87,6 +243,39 @@ impl From<GetNextRequest> for rasn_snmp::v2::Pdu {
     }
 }
 
+impl From<GetBulkRequest> for rasn_snmp::v2::BulkPdu {
+    fn from(request: GetBulkRequest) -> Self {
+        rasn_snmp::v2::BulkPdu {
+            request_id: request.request_id,
+            non_repeaters: request.non_repeaters,
+            max_repetitions: request.max_repetitions,
+            variable_bindings: request
+                .objects
+                .into_iter()
+                .map(|name| rasn_snmp::v2::VarBind {
+                    name,
+                    value: VarBindValue::Unspecified,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<SetRequest> for rasn_snmp::v2::Pdu {
+    fn from(request: SetRequest) -> Self {
+        rasn_snmp::v2::Pdu {
+            request_id: request.request_id.to_i32().unwrap(),
+            error_status: rasn_snmp::v2::Pdu::ERROR_STATUS_NO_ERROR,
+            error_index: Zero::zero(),
+            variable_bindings: request
+                .variables
+                .iter()
+                .map(rasn_snmp::v2::VarBind::from)
+                .collect(),
+        }
+    }
+}
+
 impl From<GetResponse> for rasn_snmp::v2::Pdu {
     fn from(response: GetResponse) -> Self {
         rasn_snmp::v2::Pdu {
@@ -102,6 +291,68 @@ impl From<GetResponse> for rasn_snmp::v2::Pdu {
     }
 }
 
+impl From<Report> for rasn_snmp::v2::Pdu {
+    fn from(report: Report) -> Self {
+        rasn_snmp::v2::Pdu {
+            request_id: report.request_id,
+            error_status: rasn_snmp::v2::Pdu::ERROR_STATUS_NO_ERROR,
+            error_index: Zero::zero(),
+            variable_bindings: report
+                .variables
+                .into_iter()
+                .map(|variable| rasn_snmp::v2::VarBind::from(&VariableBinding::from(variable)))
+                .collect(),
+        }
+    }
+}
+
+// the first two variable bindings of the SNMPv2-Trap-PDU are sysUpTime.0 and snmpTrapOID.0 (RFC 3416, 4.2.6)
+impl From<Trap> for rasn_snmp::v2::Pdu {
+    fn from(trap: Trap) -> Self {
+        notification_pdu(trap.request_id, trap.sys_up_time, trap.trap_oid, &trap.variables)
+    }
+}
+
+// the InformRequest-PDU carries the same variable bindings as the SNMPv2-Trap-PDU (RFC 3416, 4.2.7)
+impl From<InformRequest> for rasn_snmp::v2::Pdu {
+    fn from(inform: InformRequest) -> Self {
+        notification_pdu(
+            inform.request_id,
+            inform.sys_up_time,
+            inform.trap_oid,
+            &inform.variables,
+        )
+    }
+}
+
+fn notification_pdu(
+    request_id: i32,
+    sys_up_time: u32,
+    trap_oid: ObjectIdentifier,
+    variables: &[Variable],
+) -> rasn_snmp::v2::Pdu {
+    let mut variable_bindings = vec![
+        rasn_snmp::v2::VarBind {
+            name: SYS_UP_TIME_OID,
+            value: VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(
+                rasn_smi::v1::TimeTicks(sys_up_time),
+            ))),
+        },
+        rasn_snmp::v2::VarBind {
+            name: SNMP_TRAP_OID_OID,
+            value: VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::ObjectId(trap_oid))),
+        },
+    ];
+    variable_bindings.extend(variables.iter().map(rasn_snmp::v2::VarBind::from));
+
+    rasn_snmp::v2::Pdu {
+        request_id,
+        error_status: rasn_snmp::v2::Pdu::ERROR_STATUS_NO_ERROR,
+        error_index: Zero::zero(),
+        variable_bindings,
+    }
+}
+
 impl From<GetRequest> for rasn_snmp::v2::Pdus {
     fn from(response: GetRequest) -> Self {
         rasn_snmp::v2::Pdus::GetRequest(rasn_snmp::v2::GetRequest(response.into()))
@@ -114,12 +365,42 @@ impl From<GetNextRequest> for rasn_snmp::v2::Pdus {
     }
 }
 
+impl From<GetBulkRequest> for rasn_snmp::v2::Pdus {
+    fn from(request: GetBulkRequest) -> Self {
+        rasn_snmp::v2::Pdus::GetBulkRequest(rasn_snmp::v2::GetBulkRequest(request.into()))
+    }
+}
+
+impl From<SetRequest> for rasn_snmp::v2::Pdus {
+    fn from(request: SetRequest) -> Self {
+        rasn_snmp::v2::Pdus::SetRequest(rasn_snmp::v2::SetRequest(request.into()))
+    }
+}
+
 impl From<GetResponse> for rasn_snmp::v2::Pdus {
     fn from(response: GetResponse) -> Self {
         rasn_snmp::v2::Pdus::Response(rasn_snmp::v2::Response(response.into()))
     }
 }
 
+impl From<Report> for rasn_snmp::v2::Pdus {
+    fn from(report: Report) -> Self {
+        rasn_snmp::v2::Pdus::Report(rasn_snmp::v2::Report(report.into()))
+    }
+}
+
+impl From<Trap> for rasn_snmp::v2::Pdus {
+    fn from(trap: Trap) -> Self {
+        rasn_snmp::v2::Pdus::Trap(rasn_snmp::v2::Trap(trap.into()))
+    }
+}
+
+impl From<InformRequest> for rasn_snmp::v2::Pdus {
+    fn from(inform: InformRequest) -> Self {
+        rasn_snmp::v2::Pdus::InformRequest(rasn_snmp::v2::InformRequest(inform.into()))
+    }
+}
+
 impl From<GetResponseError> for rasn_snmp::v2::Pdus {
     fn from(response: GetResponseError) -> Self {
         rasn_snmp::v2::Pdus::Response(rasn_snmp::v2::Response(response.into()))
@@ -145,6 +426,22 @@ impl From<GetResponseError> for rasn_snmp::v2::Pdu {
     }
 }
 
+impl From<&VariableBinding> for rasn_snmp::v2::VarBind {
+    fn from(variable_binding: &VariableBinding) -> Self {
+        match variable_binding {
+            VariableBinding::Value(variable) => variable.into(),
+            VariableBinding::Exception(name, exception) => rasn_snmp::v2::VarBind {
+                name: name.clone(),
+                value: match exception {
+                    VarBindException::NoSuchObject => VarBindValue::NoSuchObject,
+                    VarBindException::NoSuchInstance => VarBindValue::NoSuchInstance,
+                    VarBindException::EndOfMibView => VarBindValue::EndOfMibView,
+                },
+            },
+        }
+    }
+}
+
 impl From<&Variable> for rasn_snmp::v2::VarBind {
     fn from(variable: &Variable) -> Self {
         let value = match variable.data_type {
@@ -155,44 +452,94 @@ impl From<&Variable> for rasn_snmp::v2::VarBind {
                 &variable.value,
             )))),
             DataType::Null => VarBindValue::Unspecified,
-            DataType::Integer => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(
-                variable.value.clone().parse().unwrap(),
-            ))),
-            DataType::UInteger32 => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(
-                rasn_smi::v1::Gauge(variable.value.clone().parse().unwrap()),
-            ))),
-            DataType::Counter32 => {
-                let value: u32 = variable.value.clone().parse().unwrap();
-                VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(
+            // the value which doesn't fit its data type is served as the octet string, the same way as the unparsable
+            // values of the other data types
+            DataType::Integer => match integer_value(&variable.value) {
+                Some(value) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(value.into()))),
+                None => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::UInteger32 => match variable.value.parse() {
+                Ok(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(
+                    rasn_smi::v1::Gauge(value),
+                ))),
+                Err(_) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::Counter32 => match variable.value.parse() {
+                Ok(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(
                     rasn_smi::v1::Counter(value),
-                )))
-            }
-            DataType::Counter64 => {
-                let value: u64 = variable.value.clone().parse().unwrap();
-                VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::BigCounter(Counter64(
-                    value,
-                ))))
-            }
-            DataType::Gauge32 => {
-                let value: u32 = variable.value.clone().parse().unwrap();
-                VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(Gauge(value))))
-            }
-            DataType::IpAddress => {
-                use std::net::Ipv4Addr;
-                let value: Ipv4Addr = variable.value.clone().parse().unwrap();
-                VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(IpAddress(
+                ))),
+                Err(_) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::Counter64 => match variable.value.parse() {
+                Ok(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::BigCounter(
+                    Counter64(value),
+                ))),
+                Err(_) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::Gauge32 => match variable.value.parse() {
+                Ok(value) => {
+                    VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(Gauge(value))))
+                }
+                Err(_) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::IpAddress => match variable.value.parse::<Ipv4Addr>() {
+                Ok(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(IpAddress(
                     bytes::Bytes::from(value.octets().to_vec()),
-                ))))
-            }
-            _ => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                )))),
+                Err(_) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::Timeticks => match timeticks_value(&variable.value) {
+                Some(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(
+                    rasn_smi::v1::TimeTicks(value),
+                ))),
+                None => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::HexString => match hex_string_value(&variable.value) {
+                Some(value) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(value.into()))),
+                None => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::NetworkAddress => match network_address_value(&variable.value) {
+                Some(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Address(
+                    IpAddress(bytes::Bytes::from(value.octets().to_vec())),
+                ))),
+                None => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            // the BITS construct is encoded as the OCTET STRING (RFC 2578, 7.1.4)
+            DataType::Bits => match bits_value(&variable.value) {
+                Some(value) => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(value.into()))),
+                None => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::Opaque => match opaque_value(&variable.value) {
+                Some(value) => VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Arbitrary(
+                    rasn_smi::v1::Opaque::new(value),
+                ))),
+                None => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                    variable.value.clone().into(),
+                ))),
+            },
+            DataType::OctetString => VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
                 variable.value.clone().into(),
             ))),
-            // DataType::HexString => {}
-            // DataType::Timeticks => {}
-            // DataType::NetworkAddress => {}
-            // DataType::Bits => {}
-            // DataType::Opaque => {}
-            // DataType::OctetString => {}
         };
 
         rasn_snmp::v2::VarBind {
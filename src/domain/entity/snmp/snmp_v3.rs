@@ -0,0 +1,100 @@
+use crate::snmp::codec::snmp_codec::SnmpCodec;
+use crate::snmp::usm::{authenticate_message, encrypt_scoped_pdu, AuthenticationKey, PrivacyKey};
+use std::fmt;
+
+// msgFlags bits (RFC 3412, 6.4)
+pub const AUTH_FLAG: u8 = 0x01;
+pub const PRIV_FLAG: u8 = 0x02;
+pub const REPORTABLE_FLAG: u8 = 0x04;
+
+// msgSecurityModel of the User-based Security Model (RFC 3411, 5)
+pub const USM_SECURITY_MODEL: u32 = 3;
+
+/// Parameters of the SNMPv3 message exchange with the peer, the agent acts as the authoritative
+/// SNMP engine except for the InformRequests, where the receiver is the authoritative one
+#[derive(Clone)]
+pub struct SnmpV3Parameters {
+    pub message_id: i32,
+    pub max_size: i32,
+    pub engine_id: Vec<u8>,
+    pub engine_boots: u32,
+    pub engine_time: u32,
+    pub user_name: Vec<u8>,
+    pub context_engine_id: Vec<u8>,
+    pub context_name: Vec<u8>,
+    pub authentication_key: Option<AuthenticationKey>,
+    pub privacy_key: Option<PrivacyKey>,
+}
+
+impl fmt::Debug for SnmpV3Parameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "message_id={}, user_name={:?}, authentication={:?}, privacy={:?}",
+            self.message_id,
+            String::from_utf8_lossy(&self.user_name),
+            self.authentication_key,
+            self.privacy_key
+        )
+    }
+}
+
+impl SnmpV3Parameters {
+    /// Builds the SNMPv3 message carrying the PDU, the message is authenticated
+    /// if the authentication key is known and encrypted if the privacy key is known as well
+    pub fn to_message(&self, data: rasn_snmp::v2::Pdus) -> rasn_snmp::v3::Message {
+        let mut flags = 0;
+        if self.authentication_key.is_some() {
+            flags |= AUTH_FLAG;
+            if self.privacy_key.is_some() {
+                flags |= PRIV_FLAG;
+            }
+        }
+        if matches!(
+            data,
+            rasn_snmp::v2::Pdus::GetRequest(_)
+                | rasn_snmp::v2::Pdus::GetNextRequest(_)
+                | rasn_snmp::v2::Pdus::GetBulkRequest(_)
+                | rasn_snmp::v2::Pdus::SetRequest(_)
+                | rasn_snmp::v2::Pdus::InformRequest(_)
+        ) {
+            flags |= REPORTABLE_FLAG;
+        }
+
+        let mut message = rasn_snmp::v3::Message {
+            version: SnmpCodec::SNMP_VERSION3.into(),
+            global_data: rasn_snmp::v3::HeaderData {
+                message_id: self.message_id.into(),
+                max_size: self.max_size.into(),
+                flags: vec![flags].into(),
+                security_model: USM_SECURITY_MODEL.into(),
+            },
+            security_parameters: rasn_snmp::v3::USMSecurityParameters {
+                authoritative_engine_id: self.engine_id.clone().into(),
+                authoritative_engine_boots: self.engine_boots.into(),
+                authoritative_engine_time: self.engine_time.into(),
+                user_name: self.user_name.clone().into(),
+                authentication_parameters: Default::default(),
+                privacy_parameters: Default::default(),
+            },
+            scoped_data: rasn_snmp::v3::ScopedPduData::CleartextPdu(rasn_snmp::v3::ScopedPdu {
+                engine_id: self.context_engine_id.clone().into(),
+                name: self.context_name.clone().into(),
+                data,
+            }),
+        };
+
+        if let Some(authentication_key) = &self.authentication_key {
+            if let Some(privacy_key) = &self.privacy_key {
+                if let Err(error) = encrypt_scoped_pdu(&mut message, privacy_key) {
+                    tracing::error!("Failed to encrypt the SNMPv3 message: {error}");
+                }
+            }
+            if let Err(error) = authenticate_message(&mut message, authentication_key) {
+                tracing::error!("Failed to authenticate the SNMPv3 message: {error}");
+            }
+        }
+
+        message
+    }
+}
@@ -10,11 +10,17 @@ pub enum SnmpProtocolAttributes {
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct SnmpV1Attributes {
     pub community: String,
+    pub read_write_community: Option<String>,
+    pub authentication_failure_trap: Option<String>,
+    pub max_message_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct SnmpV2cAttributes {
     pub community: String,
+    pub read_write_community: Option<String>,
+    pub authentication_failure_trap: Option<String>,
+    pub max_message_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
@@ -30,10 +36,61 @@ pub struct SnmpV3Attributes {
 pub enum AuthenticationAlgorithm {
     Md5,
     Sha,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub enum EncryptionAlgorithm {
     Des,
     Aes,
+    Aes192,
+    Aes256,
+}
+
+/// The access granted to the requests of an SNMP community
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CommunityAccess {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl SnmpProtocolAttributes {
+    /// Returns the access granted to the community of the SNMPv1/v2c request,
+    /// None if the community is not known to the device
+    pub fn community_access(&self, community: &[u8]) -> Option<CommunityAccess> {
+        let (read_only_community, read_write_community) = match self {
+            SnmpProtocolAttributes::SnmpV1(attributes) => (&attributes.community, &attributes.read_write_community),
+            SnmpProtocolAttributes::SnmpV2c(attributes) => (&attributes.community, &attributes.read_write_community),
+            SnmpProtocolAttributes::SnmpV3(_) => return None,
+        };
+
+        if read_write_community.as_ref().map(|c| c.as_bytes()) == Some(community) {
+            Some(CommunityAccess::ReadWrite)
+        } else if read_only_community.as_bytes() == community {
+            Some(CommunityAccess::ReadOnly)
+        } else {
+            None
+        }
+    }
+
+    /// The address of the manager the authenticationFailure trap is sent to
+    pub fn authentication_failure_trap(&self) -> Option<&str> {
+        match self {
+            SnmpProtocolAttributes::SnmpV1(attributes) => attributes.authentication_failure_trap.as_deref(),
+            SnmpProtocolAttributes::SnmpV2c(attributes) => attributes.authentication_failure_trap.as_deref(),
+            SnmpProtocolAttributes::SnmpV3(_) => None,
+        }
+    }
+
+    /// The largest SNMPv1/v2c message the device is able to send, the SNMPv3 limit is negotiated by msgMaxSize
+    pub fn max_message_size(&self) -> Option<usize> {
+        match self {
+            SnmpProtocolAttributes::SnmpV1(attributes) => attributes.max_message_size.map(|size| size as usize),
+            SnmpProtocolAttributes::SnmpV2c(attributes) => attributes.max_message_size.map(|size| size as usize),
+            SnmpProtocolAttributes::SnmpV3(_) => None,
+        }
+    }
 }
@@ -1,5 +1,7 @@
-use crate::domain::{Agent, CreateResult, DomainError, UpdateResult};
+use crate::domain::{to_hex_string, walk_agent, Agent, CreateResult, DomainError, UpdateResult, Variable, Walk};
 use sea_orm::ConnectionTrait;
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, DataType};
+use std::io::{BufReader, Write};
 use uuid_dev::Uuid;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
@@ -14,6 +16,7 @@ pub(crate) async fn create_agent<'db>(
         &agent.name,
         &agent.description,
         &agent.snmp_data_url,
+        &agent.data_format.map(|data_format| data_format.to_string()),
     )
     .await
     .map_err(DomainError::from)?;
@@ -77,8 +80,155 @@ pub(crate) async fn update_agent<'db>(
         &agent.name,
         &agent.description,
         &agent.snmp_data_url,
+        &agent.data_format.map(|data_format| data_format.to_string()),
     )
     .await?;
 
     Ok(UpdateResult::Updated(result.into()))
 }
+
+/// Validates the values of the SNMP data file against their data types, so the invalid data are rejected before a device
+/// fails to load them. The file which does not exist yet is accepted, as it may be provided later.
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(level = "debug", name = "[BL] Validating the SNMP data")]
+pub(crate) async fn validate_snmp_data(agent: &Agent) -> Result<(), DomainError> {
+    let snmp_data_url = agent.snmp_data_url.clone();
+    let data_format = agent.snmp_data_format();
+    run_blocking(move || {
+        let file = match std::fs::File::open(&snmp_data_url) {
+            Ok(file) => file,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                tracing::warn!("The SNMP data file {snmp_data_url} does not exist, its values are not validated");
+                return Ok(());
+            }
+            Err(error) => {
+                return Err(DomainError::Validation(format!(
+                    "The SNMP data file {snmp_data_url} can't be read: {error}"
+                )))
+            }
+        };
+
+        data_format
+            .parse(BufReader::new(file))
+            .map(|_| ())
+            .map_err(|error| DomainError::Validation(format!("The SNMP data file {snmp_data_url} is invalid: {error}")))
+    })
+    .await
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(level = "debug", name = "[BL] Recording an agent", skip(conn))]
+pub(crate) async fn record_agent<'db>(
+    conn: &'db impl ConnectionTrait,
+    agent: &Agent,
+    walk: &Walk,
+) -> Result<CreateResult<Agent>, DomainError> {
+    // the SNMP data file of an existing agent is never overwritten, the file is created before the walk
+    let snmp_data_url = agent.snmp_data_url.clone();
+    let file = run_blocking(move || {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&snmp_data_url)
+            .map_err(|error| match error.kind() {
+                std::io::ErrorKind::AlreadyExists => {
+                    DomainError::Conflict(format!("The SNMP data file {snmp_data_url} already exists"))
+                }
+                _ => DomainError::Validation(format!("The SNMP data file {snmp_data_url} can't be written: {error}")),
+            })
+    })
+    .await?;
+
+    let result = record_snmp_data(conn, agent, walk, file).await;
+    if result.is_err() {
+        // the file of the agent which was not created is removed
+        let snmp_data_url = agent.snmp_data_url.clone();
+        run_blocking(move || {
+            std::fs::remove_file(&snmp_data_url).map_err(|error| {
+                DomainError::Validation(format!("The SNMP data file {snmp_data_url} can't be removed: {error}"))
+            })
+        })
+        .await
+        .unwrap_or_else(|error| tracing::warn!("{error}"));
+    }
+    result
+}
+
+// walks the remote agent, writes its variables to the SNMP data file and creates the agent
+async fn record_snmp_data<'db>(
+    conn: &'db impl ConnectionTrait,
+    agent: &Agent,
+    walk: &Walk,
+    mut file: std::fs::File,
+) -> Result<CreateResult<Agent>, DomainError> {
+    let variables = walk_agent(walk).await?;
+    tracing::info!("{} variables recorded from {}", variables.len(), walk.target);
+
+    let snmp_data = variables
+        .iter()
+        .map(snmp_data_line)
+        .collect::<Vec<String>>()
+        .join("\n");
+    let snmp_data_url = agent.snmp_data_url.clone();
+    run_blocking(move || {
+        file.write_all((snmp_data + "\n").as_bytes())
+            .map_err(|error| {
+                DomainError::Validation(format!("The SNMP data file {snmp_data_url} can't be written: {error}"))
+            })
+    })
+    .await?;
+
+    create_agent(conn, agent).await
+}
+
+// runs the blocking file operation on the thread pool, so it doesn't block the async worker
+async fn run_blocking<T, F>(operation: F) -> Result<T, DomainError>
+where
+    F: FnOnce() -> Result<T, DomainError> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|error| DomainError::Unexpected(error.into()))?
+}
+
+// Formats the variable as the line of the snmpwalk output read by the SnmpDataParser, f.e.
+// ".1.3.6.1.2.1.1.3.0 = Timeticks: (328918409) 38 days, 1:39:44.09"
+fn snmp_data_line(variable: &Variable) -> String {
+    let name = oid_to_string(variable.name.clone());
+    match variable.data_type {
+        // the parser reads a value per line and trims it
+        DataType::String if variable.value.contains(['\r', '\n']) || variable.value.trim() != variable.value => {
+            format!(
+                "{name} = {}: {}",
+                DataType::HexString,
+                to_hex_string(variable.value.as_bytes())
+            )
+        }
+        DataType::Timeticks => match variable.value.parse::<u32>() {
+            Ok(ticks) => format!(
+                "{name} = {}: ({ticks}) {}",
+                DataType::Timeticks,
+                time_ticks_to_string(ticks)
+            ),
+            Err(_) => format!("{name} = {}: {}", DataType::Timeticks, variable.value),
+        },
+        _ => format!("{name} = {}: {}", variable.data_type, variable.value)
+            .trim_end()
+            .to_string(),
+    }
+}
+
+// the hundredths of a second displayed as by the net-snmp tools, f.e. "38 days, 1:39:44.09"
+fn time_ticks_to_string(ticks: u32) -> String {
+    let (days, ticks) = (ticks / 8_640_000, ticks % 8_640_000);
+    let (hours, ticks) = (ticks / 360_000, ticks % 360_000);
+    let (minutes, ticks) = (ticks / 6_000, ticks % 6_000);
+    let (seconds, hundredths) = (ticks / 100, ticks % 100);
+    let time = format!("{hours}:{minutes:02}:{seconds:02}.{hundredths:02}");
+    match days {
+        0 => time,
+        1 => format!("1 day, {time}"),
+        days => format!("{days} days, {time}"),
+    }
+}
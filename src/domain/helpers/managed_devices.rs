@@ -1,4 +1,8 @@
-use crate::domain::{CreateResult, DomainError, ManagedDevice, UpdateResult};
+use crate::domain::{
+    CreateResult, DomainError, Inform, ManagedDevice, Notification, SnmpProtocolAttributes, UpdateResult,
+    ENGINE_MAX_VALUE,
+};
+use crate::snmp::snmp_statistics::SnmpStatisticsSnapshot;
 use crate::udp_server::udp_server_delegate::UdpServerDelegate;
 use sea_orm::ConnectionTrait;
 use uuid_dev::Uuid;
@@ -21,6 +25,17 @@ pub(crate) async fn create_managed_device<'db>(
             .as_ref(),
         &managed_device.snmp_host,
         managed_device.snmp_port,
+        &managed_device
+            .proxy
+            .as_ref()
+            .map(|proxy| serde_json::to_string(proxy).unwrap()),
+        &managed_device.sys_name,
+        &managed_device.sys_location,
+        &managed_device
+            .agentx
+            .as_ref()
+            .map(|agentx| serde_json::to_string(agentx).unwrap()),
+        &managed_device.transport.to_string(),
     )
     .await
     .map_err(DomainError::from)?;
@@ -92,6 +107,17 @@ pub(crate) async fn update_managed_device<'db>(
             .as_ref(),
         &managed_device.snmp_host,
         managed_device.snmp_port,
+        &managed_device
+            .proxy
+            .as_ref()
+            .map(|proxy| serde_json::to_string(proxy).unwrap()),
+        &managed_device.sys_name,
+        &managed_device.sys_location,
+        &managed_device
+            .agentx
+            .as_ref()
+            .map(|agentx| serde_json::to_string(agentx).unwrap()),
+        &managed_device.transport.to_string(),
     )
     .await?;
 
@@ -105,6 +131,8 @@ pub(crate) async fn start_managed_device<'db>(
     id: &Uuid,
     udp_server: &UdpServerDelegate,
 ) -> Result<UpdateResult<bool>, DomainError> {
+    // snmpEngineBoots is non-volatile, it's kept with the device across the restarts of the service (RFC 3414, 2.2.2)
+    crate::data_access::helpers::increment_engine_boots(conn, id, ENGINE_MAX_VALUE).await?;
     let device = get_managed_device(conn, id).await?;
 
     tracing::debug!("Start device: {:?}", device);
@@ -135,3 +163,93 @@ pub(crate) async fn stop_managed_device<'db>(
 
     Ok(UpdateResult::Updated(true))
 }
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(
+    level = "debug",
+    name = "[BL] Sending notification of managed device",
+    skip(conn, udp_server)
+)]
+pub(crate) async fn send_managed_device_notification<'db>(
+    conn: &'db impl ConnectionTrait,
+    id: &Uuid,
+    notification: Notification,
+    udp_server: &UdpServerDelegate,
+) -> Result<Notification, DomainError> {
+    let device = get_managed_device(conn, id).await?;
+
+    // ManagedDevice exists => send the notification from the running device
+    udp_server
+        .send_notification(device.id, notification.clone())
+        .await
+        .map_err(DomainError::from)?;
+
+    Ok(notification)
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(
+    level = "debug",
+    name = "[BL] Sending InformRequest of managed device",
+    skip(conn, udp_server)
+)]
+pub(crate) async fn send_managed_device_inform<'db>(
+    conn: &'db impl ConnectionTrait,
+    id: &Uuid,
+    inform: Inform,
+    udp_server: &UdpServerDelegate,
+) -> Result<Inform, DomainError> {
+    let device = get_managed_device(conn, id).await?;
+
+    // the InformRequest-PDU is not defined by SNMPv1
+    if let SnmpProtocolAttributes::SnmpV1(_) = device.snmp_protocol_attributes {
+        return Err(DomainError::Validation(
+            "the InformRequests are not supported by the SNMPv1 devices".to_string(),
+        ));
+    }
+
+    // ManagedDevice exists => send the InformRequest from the running device
+    udp_server
+        .send_inform(device.id, inform.clone())
+        .await
+        .map_err(DomainError::from)?;
+
+    Ok(inform)
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(
+    level = "debug",
+    name = "[BL] Finding an InformRequest of managed device",
+    skip(conn)
+)]
+pub(crate) async fn get_managed_device_inform<'db>(
+    conn: &'db impl ConnectionTrait,
+    id: &Uuid,
+    request_id: i32,
+) -> Result<Inform, DomainError> {
+    let device = get_managed_device(conn, id).await?;
+
+    crate::domain::get_inform(device.id, request_id)
+        .ok_or_else(|| DomainError::NotFound(format!("InformRequestId={} not exists", request_id)))
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(
+    level = "debug",
+    name = "[BL] Getting SNMP statistics of managed device",
+    skip(conn, udp_server)
+)]
+pub(crate) async fn get_managed_device_statistics<'db>(
+    conn: &'db impl ConnectionTrait,
+    id: &Uuid,
+    udp_server: &UdpServerDelegate,
+) -> Result<SnmpStatisticsSnapshot, DomainError> {
+    let device = get_managed_device(conn, id).await?;
+
+    // ManagedDevice exists => read the counters of the running device
+    udp_server
+        .get_snmp_statistics(device.id)
+        .await
+        .map_err(DomainError::from)
+}
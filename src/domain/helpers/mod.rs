@@ -1,8 +1,12 @@
 mod agents;
 mod managed_devices;
+mod notifications;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use agents::*;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use managed_devices::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use notifications::*;
@@ -0,0 +1,43 @@
+use crate::domain::{DomainError, NotificationFilter, ReceivedNotification};
+use sea_orm::ConnectionTrait;
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(level = "debug", name = "[BL] Recording a received notification", skip(conn))]
+pub(crate) async fn create_notification<'db>(
+    conn: &'db impl ConnectionTrait,
+    notification: ReceivedNotification,
+) -> Result<ReceivedNotification, DomainError> {
+    let result = crate::data_access::helpers::create_notification(conn, notification.into()).await?;
+
+    Ok(result.into())
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[tracing::instrument(level = "debug", name = "[BL] Listing notifications", skip(conn))]
+pub(crate) async fn list_notifications<'db>(
+    conn: &'db impl ConnectionTrait,
+    filter: &NotificationFilter,
+    page: usize,
+    page_size: usize,
+) -> Result<(usize, Vec<ReceivedNotification>), DomainError> {
+    let (count, notifications) = crate::data_access::helpers::list_notifications(
+        conn,
+        &filter.source_address.map(|address| address.to_string()),
+        &filter.source_port,
+        &filter.trap_oid.clone().map(oid_to_string),
+        &filter.received_from,
+        &filter.received_to,
+        page,
+        page_size,
+    )
+    .await?;
+
+    Ok((
+        count,
+        notifications
+            .into_iter()
+            .map(ReceivedNotification::from)
+            .collect(),
+    ))
+}
@@ -1,12 +1,14 @@
 use crate::domain::ManagedDevice;
+use crate::domain::SnmpEngine;
 use crate::domain::SnmpProtocolVersion;
+use crate::snmp::codec::snmp_codec::SnmpCodec;
 use crate::udp_server::udp_stream_handler::UdpStreamHandler;
 
 use actix_async::address::Addr;
 use snmp_data_parser::parser::snmp_data::component::SnmpData;
 use std::fmt;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) struct AgentContext {
@@ -14,7 +16,8 @@ pub(crate) struct AgentContext {
     pub peer: SocketAddr,
     pub stream_handler_actor: Addr<UdpStreamHandler>,
     pub version: SnmpProtocolVersion,
-    pub snmp_data: Arc<SnmpData>,
+    pub snmp_data: Arc<RwLock<SnmpData>>,
+    pub snmp_engine: Arc<SnmpEngine>,
 }
 
 impl AgentContext {
@@ -23,7 +26,8 @@ impl AgentContext {
         peer: SocketAddr,
         stream_handler_actor: Addr<UdpStreamHandler>,
         version: SnmpProtocolVersion,
-        snmp_data: Arc<SnmpData>,
+        snmp_data: Arc<RwLock<SnmpData>>,
+        snmp_engine: Arc<SnmpEngine>,
     ) -> Self {
         AgentContext {
             device,
@@ -31,8 +35,23 @@ impl AgentContext {
             stream_handler_actor,
             version,
             snmp_data,
+            snmp_engine,
         }
     }
+
+    /// The maximum size of the response message, i.e. msgMaxSize of the SNMPv3 request
+    /// or the limit configured on the SNMPv1/v2c device
+    pub fn max_message_size(&self) -> usize {
+        match &self.version {
+            SnmpProtocolVersion::SNMPV3(parameters) => parameters.max_size.max(0) as usize,
+            _ => self
+                .device
+                .snmp_protocol_attributes
+                .max_message_size()
+                .unwrap_or(usize::MAX),
+        }
+        .min(SnmpCodec::MAX_MESSAGE_SIZE)
+    }
 }
 
 impl fmt::Debug for AgentContext {
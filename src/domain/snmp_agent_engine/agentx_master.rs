@@ -0,0 +1,1078 @@
+use super::command_responder::{
+    apply_set_variables, encoded_var_bind_len, fit_to_message_size, missing_variable_exception, send_response,
+    validate_set_variable,
+};
+use super::proxy_forwarder::response_message;
+use crate::domain::data_item_variable;
+use crate::domain::{AgentContext, AgentxAddress, AgentxAttributes, ManagedDevice, SnmpEngine, SnmpProtocolVersion};
+use crate::domain::{
+    ErrorStatus, GetBulkRequest, GetNextRequest, GetRequest, GetResponse, GetResponseError, SetRequest,
+    VarBindException, Variable, VariableBinding,
+};
+use crate::snmp::agentx::{
+    AgentxCodec, AgentxError, AgentxErrorStatus, AgentxHeader, AgentxPayload, AgentxPdu, AgentxVarBind, CloseReason,
+    SearchRange, SubtreeRegion, NETWORK_BYTE_ORDER,
+};
+use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
+
+use futures::stream::StreamExt;
+use futures::SinkExt;
+use lazy_static::lazy_static;
+use rasn::prelude::ObjectIdentifier;
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, SnmpData};
+use std::collections::HashMap;
+use std::ops::Bound::{Excluded, Unbounded};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_util::codec::Framed;
+use uuid_dev::Uuid;
+
+// the ranged registration is expanded to the individual subtrees, the larger ranges are refused
+const MAX_RANGE_SUBTREES: u32 = 1024;
+
+lazy_static! {
+    // the AgentX master agents of the running devices, indexed by the device id
+    static ref AGENTX_MASTERS: Mutex<HashMap<Uuid, Arc<AgentxMaster>>> = Mutex::new(HashMap::new());
+}
+
+// The session opened by a subagent (RFC 2741, 7.1.1), the PDUs are sent through the writer of its connection
+struct Session {
+    connection_id: u32,
+    sender: mpsc::UnboundedSender<AgentxPdu>,
+    // the master agent uses the byte order of the subagent's Open-PDU
+    flags: u8,
+    timeout: Option<Duration>,
+    // the requests waiting for the Response-PDU, indexed by the packet id
+    pending: HashMap<u32, oneshot::Sender<AgentxPdu>>,
+}
+
+#[derive(Debug, Clone)]
+struct Registration {
+    session_id: u32,
+    subtree: ObjectIdentifier,
+    priority: u8,
+    timeout: Option<Duration>,
+}
+
+// The region of the MIB view dispatched to a single subagent, i.e. the range of the OIDs
+// between two boundaries of the registered subtrees. The end is unbounded if None
+struct Region {
+    start: ObjectIdentifier,
+    end: Option<ObjectIdentifier>,
+    owner: Registration,
+}
+
+impl Region {
+    fn contains(&self, name: &ObjectIdentifier) -> bool {
+        *name >= self.start && self.end.as_ref().map(|end| name < end).unwrap_or(true)
+    }
+}
+
+#[derive(Default)]
+struct MasterState {
+    // the session ids, transaction ids and packet ids are all drawn from this counter
+    last_id: u32,
+    sessions: HashMap<u32, Session>,
+    // in the order of the registration, the earlier one takes precedence among the equal ones
+    registrations: Vec<Registration>,
+    listener: Option<JoinHandle<()>>,
+    // the reader of each subagent connection, indexed by the connection id
+    connections: HashMap<u32, JoinHandle<()>>,
+}
+
+impl MasterState {
+    fn next_id(&mut self) -> u32 {
+        self.last_id = self.last_id.checked_add(1).unwrap_or(1);
+        self.last_id
+    }
+
+    // The registration dispatching the variable, the most specific subtree takes precedence,
+    // then the one of the higher priority, i.e. of the lower priority value (RFC 2741, 7.1.5.1)
+    fn owner(&self, name: &ObjectIdentifier) -> Option<&Registration> {
+        self.registrations
+            .iter()
+            .rev()
+            .filter(|registration| name.starts_with(&registration.subtree))
+            .max_by(|first, second| {
+                first
+                    .subtree
+                    .len()
+                    .cmp(&second.subtree.len())
+                    .then(second.priority.cmp(&first.priority))
+            })
+    }
+
+    // the regions of the MIB view dispatched to the subagents, in the lexicographical order
+    fn regions(&self) -> Vec<Region> {
+        let mut boundaries: Vec<ObjectIdentifier> = self
+            .registrations
+            .iter()
+            .flat_map(|registration| [Some(registration.subtree.clone()), subtree_end(&registration.subtree)])
+            .flatten()
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+
+        boundaries
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, start)| {
+                let owner = self.owner(start)?;
+                Some(Region {
+                    start: start.clone(),
+                    end: boundaries.get(idx + 1).cloned(),
+                    owner: owner.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The AgentX master agent of a running device (RFC 2741), the subagents connected to its listener
+/// register the subtrees, and the requests of the device for their variables are dispatched to them
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct AgentxMaster {
+    agentx: AgentxAttributes,
+    snmp_engine: Arc<SnmpEngine>,
+    state: Mutex<MasterState>,
+}
+
+impl std::fmt::Debug for AgentxMaster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "address={}", self.agentx.address)
+    }
+}
+
+impl AgentxMaster {
+    fn next_id(&self) -> u32 {
+        self.state.lock().unwrap().next_id()
+    }
+
+    fn has_registrations(&self) -> bool {
+        !self.state.lock().unwrap().registrations.is_empty()
+    }
+
+    fn owner(&self, name: &ObjectIdentifier) -> Option<Registration> {
+        self.state.lock().unwrap().owner(name).cloned()
+    }
+
+    fn regions(&self) -> Vec<Region> {
+        self.state.lock().unwrap().regions()
+    }
+
+    // the PDU sent to the subagent session, the packet id is assigned by the master agent
+    fn session_pdu(
+        state: &mut MasterState,
+        session_id: u32,
+        transaction_id: u32,
+        payload: AgentxPayload,
+    ) -> Result<(&Session, AgentxPdu), AgentxError> {
+        let packet_id = state.next_id();
+        let session = state
+            .sessions
+            .get(&session_id)
+            .ok_or(AgentxError::SessionClosed(session_id))?;
+        let pdu = AgentxPdu {
+            header: AgentxHeader {
+                flags: session.flags,
+                session_id,
+                transaction_id,
+                packet_id,
+            },
+            context: None,
+            payload,
+        };
+        Ok((session, pdu))
+    }
+
+    // Sends the request to the subagent and waits for its Response-PDU, the timeout of the registration
+    // takes precedence over the one of the session, then the one configured on the device (RFC 2741, 7.2.1)
+    async fn request(
+        &self,
+        registration: &Registration,
+        transaction_id: u32,
+        payload: AgentxPayload,
+    ) -> Result<(u16, u16, Vec<AgentxVarBind>), AgentxError> {
+        let session_id = registration.session_id;
+        let (received, packet_id, timeout) = {
+            let mut state = self.state.lock().unwrap();
+            let (session, pdu) = AgentxMaster::session_pdu(&mut state, session_id, transaction_id, payload)?;
+            let packet_id = pdu.header.packet_id;
+            let timeout = registration
+                .timeout
+                .or(session.timeout)
+                .unwrap_or_else(|| self.agentx.timeout());
+            session
+                .sender
+                .send(pdu)
+                .map_err(|_| AgentxError::SessionClosed(session_id))?;
+
+            let (sender, received) = oneshot::channel();
+            if let Some(session) = state.sessions.get_mut(&session_id) {
+                session.pending.insert(packet_id, sender);
+            }
+            (received, packet_id, timeout)
+        };
+
+        match tokio::time::timeout(timeout, received).await {
+            Ok(Ok(AgentxPdu {
+                payload:
+                    AgentxPayload::Response {
+                        error,
+                        index,
+                        var_binds,
+                        ..
+                    },
+                ..
+            })) => Ok((error, index, var_binds)),
+            Ok(_) => Err(AgentxError::SessionClosed(session_id)),
+            Err(_) => {
+                if let Some(session) = self.state.lock().unwrap().sessions.get_mut(&session_id) {
+                    session.pending.remove(&packet_id);
+                }
+                Err(AgentxError::Timeout(timeout.as_millis()))
+            }
+        }
+    }
+
+    // the CleanupSet-PDU is not answered by the subagent (RFC 2741, 7.2.4.4)
+    fn send(&self, session_id: u32, transaction_id: u32, payload: AgentxPayload) {
+        let mut state = self.state.lock().unwrap();
+        if let Ok((session, pdu)) = AgentxMaster::session_pdu(&mut state, session_id, transaction_id, payload) {
+            let _ = session.sender.send(pdu);
+        }
+    }
+
+    // Serves the connection of a subagent, the PDUs are written by a separate task, so the responses
+    // of the subagent are processed while the master agent's requests are waiting for them
+    fn serve_connection<T>(self: Arc<Self>, stream: T)
+    where
+        T: AsyncRead + AsyncWrite + Unpin + 'static,
+    {
+        let (mut sink, mut stream) = Framed::new(stream, AgentxCodec::new()).split();
+        let (sender, mut receiver) = mpsc::unbounded_channel::<AgentxPdu>();
+
+        // the writer ends once the connection and all its sessions are closed
+        actix::spawn(async move {
+            while let Some(pdu) = receiver.recv().await {
+                if let Err(error) = sink.send(pdu).await {
+                    tracing::warn!("Failed to send the AgentX PDU: {error}");
+                    break;
+                }
+            }
+        });
+
+        let mut state = self.state.lock().unwrap();
+        let connection_id = state.next_id();
+        let master = self.clone();
+        let reader = actix::spawn(async move {
+            while let Some(pdu) = stream.next().await {
+                match pdu {
+                    Ok(pdu) => master.handle_pdu(connection_id, &sender, pdu),
+                    Err(error) => {
+                        tracing::warn!("The AgentX connection is closed: {error}");
+                        break;
+                    }
+                }
+            }
+            master.close_connection(connection_id);
+        });
+        state.connections.insert(connection_id, reader);
+    }
+
+    // the sessions of the closed connection are closed as well, with all their registrations (RFC 2741, 7.1.9)
+    fn close_connection(&self, connection_id: u32) {
+        let mut state = self.state.lock().unwrap();
+        state.connections.remove(&connection_id);
+        let session_ids: Vec<u32> = state
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.connection_id == connection_id)
+            .map(|(session_id, _)| *session_id)
+            .collect();
+        for session_id in session_ids {
+            state.sessions.remove(&session_id);
+            state
+                .registrations
+                .retain(|registration| registration.session_id != session_id);
+        }
+    }
+
+    // Processes the PDU received from the subagent, the administrative PDUs are answered right away (RFC 2741, 7.1)
+    fn handle_pdu(&self, connection_id: u32, sender: &mpsc::UnboundedSender<AgentxPdu>, pdu: AgentxPdu) {
+        let sys_up_time = self.snmp_engine.sys_up_time();
+        let respond = |error: AgentxErrorStatus| Some(pdu.response(sys_up_time, error as u16, 0, vec![]));
+        let session_id = pdu.header.session_id;
+
+        let mut state = self.state.lock().unwrap();
+        let is_open = state
+            .sessions
+            .get(&session_id)
+            .map(|session| session.connection_id == connection_id)
+            .unwrap_or_default();
+
+        let response = match &pdu.payload {
+            AgentxPayload::Response { .. } => {
+                let pending = state
+                    .sessions
+                    .get_mut(&session_id)
+                    .and_then(|session| session.pending.remove(&pdu.header.packet_id));
+                match pending {
+                    Some(pending) => {
+                        let _ = pending.send(pdu.clone());
+                    }
+                    None => tracing::debug!("Unexpected AgentX response {:?}", pdu.header),
+                }
+                None
+            }
+            AgentxPayload::Malformed(reason) => {
+                tracing::warn!("Malformed AgentX PDU of the session {session_id}: {reason}");
+                respond(AgentxErrorStatus::ParseError)
+            }
+            AgentxPayload::Open { timeout, id, .. } => {
+                let session_id = state.next_id();
+                state.sessions.insert(
+                    session_id,
+                    Session {
+                        connection_id,
+                        sender: sender.clone(),
+                        flags: pdu.header.flags & NETWORK_BYTE_ORDER,
+                        timeout: seconds_timeout(*timeout),
+                        pending: HashMap::new(),
+                    },
+                );
+                tracing::info!(
+                    "The AgentX session {session_id} is opened by {}",
+                    oid_to_string(id.clone())
+                );
+                respond(AgentxErrorStatus::NoAgentxError).map(|mut response| {
+                    response.header.session_id = session_id;
+                    response
+                })
+            }
+            _ if !is_open => respond(AgentxErrorStatus::NotOpen),
+            _ if pdu.context.is_some() => respond(AgentxErrorStatus::UnsupportedContext),
+            AgentxPayload::Close { reason } => {
+                tracing::info!("The AgentX session {session_id} is closed by the subagent, reason {reason}");
+                state.sessions.remove(&session_id);
+                state
+                    .registrations
+                    .retain(|registration| registration.session_id != session_id);
+                respond(AgentxErrorStatus::NoAgentxError)
+            }
+            AgentxPayload::Register { timeout, region } => respond(register(&mut state, session_id, *timeout, region)),
+            AgentxPayload::Unregister { region } => respond(unregister(&mut state, session_id, region)),
+            AgentxPayload::Notify { var_binds } => {
+                tracing::info!("Notification of the AgentX session {session_id}: {var_binds:?}");
+                respond(AgentxErrorStatus::NoAgentxError)
+            }
+            AgentxPayload::Ping | AgentxPayload::AddAgentCaps { .. } | AgentxPayload::RemoveAgentCaps { .. } => {
+                respond(AgentxErrorStatus::NoAgentxError)
+            }
+            // the index allocation is not supported, neither the requests sent by the subagent
+            _ => respond(AgentxErrorStatus::ProcessingError),
+        };
+
+        if let Some(response) = response {
+            let _ = sender.send(response);
+        }
+    }
+
+    // the subagents are notified by the Close-PDU, then all the connections are closed
+    fn shutdown(&self) {
+        let mut state = self.state.lock().unwrap();
+        let session_ids: Vec<u32> = state.sessions.keys().copied().collect();
+        for session_id in session_ids {
+            let payload = AgentxPayload::Close {
+                reason: CloseReason::Shutdown as u8,
+            };
+            if let Ok((session, pdu)) = AgentxMaster::session_pdu(&mut state, session_id, 0, payload) {
+                let _ = session.sender.send(pdu);
+            }
+        }
+        state.sessions.clear();
+        state.registrations.clear();
+        if let Some(listener) = state.listener.take() {
+            listener.abort();
+        }
+        for (_, reader) in state.connections.drain() {
+            reader.abort();
+        }
+
+        if let AgentxAddress::Unix(path) = &self.agentx.address {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+// the first OID following the subtree, i.e. the subtree with its last sub-identifier incremented
+fn subtree_end(subtree: &ObjectIdentifier) -> Option<ObjectIdentifier> {
+    let mut end = subtree.to_vec();
+    while let Some(last) = end.pop() {
+        if let Some(next) = last.checked_add(1) {
+            end.push(next);
+            return Some(ObjectIdentifier::new_unchecked(end.into()));
+        }
+    }
+    None
+}
+
+fn null_oid() -> ObjectIdentifier {
+    ObjectIdentifier::new_unchecked(vec![].into())
+}
+
+// the zero timeout of the Open-PDU and the Register-PDU stands for the default one
+fn seconds_timeout(timeout: u8) -> Option<Duration> {
+    (timeout != 0).then(|| Duration::from_secs(timeout.into()))
+}
+
+// the subtrees of the registration, the ranged one is expanded to each value of the range sub-identifier
+fn region_subtrees(region: &SubtreeRegion) -> Result<Vec<ObjectIdentifier>, AgentxErrorStatus> {
+    let (range_idx, upper_bound) = match (region.range_subid, region.upper_bound) {
+        (0, _) | (_, None) => return Ok(vec![region.subtree.clone()]),
+        (range_subid, Some(upper_bound)) => (range_subid as usize - 1, upper_bound),
+    };
+    let lower_bound = *region
+        .subtree
+        .get(range_idx)
+        .filter(|lower_bound| **lower_bound <= upper_bound)
+        .ok_or(AgentxErrorStatus::ParseError)?;
+    if upper_bound - lower_bound >= MAX_RANGE_SUBTREES {
+        return Err(AgentxErrorStatus::RequestDenied);
+    }
+
+    Ok((lower_bound..=upper_bound)
+        .map(|sub_id| {
+            let mut subtree = region.subtree.to_vec();
+            subtree[range_idx] = sub_id;
+            ObjectIdentifier::new_unchecked(subtree.into())
+        })
+        .collect())
+}
+
+// the same subtree can't be registered twice with the same priority (RFC 2741, 7.1.5.1)
+fn register(state: &mut MasterState, session_id: u32, timeout: u8, region: &SubtreeRegion) -> AgentxErrorStatus {
+    let subtrees = match region_subtrees(region) {
+        Ok(subtrees) => subtrees,
+        Err(error) => return error,
+    };
+    let is_duplicate = state
+        .registrations
+        .iter()
+        .any(|registration| registration.priority == region.priority && subtrees.contains(&registration.subtree));
+    if is_duplicate {
+        return AgentxErrorStatus::DuplicateRegistration;
+    }
+
+    tracing::info!(
+        "The AgentX session {session_id} registered {}",
+        oid_to_string(region.subtree.clone())
+    );
+    state
+        .registrations
+        .extend(subtrees.into_iter().map(|subtree| Registration {
+            session_id,
+            subtree,
+            priority: region.priority,
+            timeout: seconds_timeout(timeout),
+        }));
+    AgentxErrorStatus::NoAgentxError
+}
+
+fn unregister(state: &mut MasterState, session_id: u32, region: &SubtreeRegion) -> AgentxErrorStatus {
+    let subtrees = match region_subtrees(region) {
+        Ok(subtrees) => subtrees,
+        Err(error) => return error,
+    };
+    let registered = state.registrations.len();
+    state.registrations.retain(|registration| {
+        registration.session_id != session_id
+            || registration.priority != region.priority
+            || !subtrees.contains(&registration.subtree)
+    });
+
+    if state.registrations.len() == registered {
+        AgentxErrorStatus::UnknownRegistration
+    } else {
+        AgentxErrorStatus::NoAgentxError
+    }
+}
+
+/// Starts the AgentX master agent of the device if it's configured, the one of the previous run is replaced
+#[tracing::instrument(level = "info", name = "start_agentx_master", skip(snmp_engine))]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn start_agentx_master(
+    device: &ManagedDevice,
+    snmp_engine: Arc<SnmpEngine>,
+) -> Result<(), AgentxError> {
+    let agentx = match &device.agentx {
+        Some(agentx) => agentx.clone(),
+        None => return Ok(()),
+    };
+    stop_agentx_master(device.id);
+
+    let master = Arc::new(AgentxMaster {
+        agentx: agentx.clone(),
+        snmp_engine,
+        state: Mutex::new(MasterState::default()),
+    });
+
+    tracing::debug!("Bind an AgentX listener to address: {}", agentx.address);
+    let listener = match &agentx.address {
+        AgentxAddress::Tcp(address) => {
+            let listener = TcpListener::bind(address).await?;
+            let master = master.clone();
+            actix::spawn(async move {
+                loop {
+                    match listener.accept().await {
+                        Ok((stream, peer)) => {
+                            tracing::debug!("AgentX subagent connected from {peer}");
+                            master.clone().serve_connection(stream);
+                        }
+                        Err(error) => tracing::warn!("Failed to accept the AgentX connection: {error}"),
+                    }
+                }
+            })
+        }
+        AgentxAddress::Unix(path) => unix_listener(path, master.clone())?,
+    };
+    master.state.lock().unwrap().listener = Some(listener);
+
+    AGENTX_MASTERS.lock().unwrap().insert(device.id, master);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn unix_listener(path: &str, master: Arc<AgentxMaster>) -> Result<JoinHandle<()>, AgentxError> {
+    use std::os::unix::fs::FileTypeExt;
+
+    // the socket left behind by the previous run is replaced, other files are never removed
+    if let Ok(metadata) = std::fs::metadata(path) {
+        if metadata.file_type().is_socket() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    let listener = tokio::net::UnixListener::bind(path)?;
+
+    Ok(actix::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tracing::debug!("AgentX subagent connected");
+                    master.clone().serve_connection(stream);
+                }
+                Err(error) => tracing::warn!("Failed to accept the AgentX connection: {error}"),
+            }
+        }
+    }))
+}
+
+#[cfg(not(unix))]
+fn unix_listener(_path: &str, _master: Arc<AgentxMaster>) -> Result<JoinHandle<()>, AgentxError> {
+    Err(AgentxError::IoError(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "the Unix domain sockets are not supported on this platform",
+    )))
+}
+
+/// Stops the AgentX master agent of the device, the connected subagents are notified by the Close-PDU
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn stop_agentx_master(device_id: Uuid) {
+    let master = AGENTX_MASTERS.lock().unwrap().remove(&device_id);
+    if let Some(master) = master {
+        master.shutdown();
+    }
+}
+
+/// The AgentX master agent of the device, as long as any subagent has registered a subtree
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn agentx_master(device_id: &Uuid) -> Option<Arc<AgentxMaster>> {
+    AGENTX_MASTERS
+        .lock()
+        .unwrap()
+        .get(device_id)
+        .filter(|master| master.has_registrations())
+        .cloned()
+}
+
+// Maps the error reported by the subagent to the error status of the response,
+// the SNMPv2 error status values are translated for the SNMPv1 managers (RFC 3584, 4.4)
+fn subagent_error_status(error: u16, version: &SnmpProtocolVersion) -> ErrorStatus {
+    let is_v1 = matches!(version, SnmpProtocolVersion::SNMPV1(_));
+    match error {
+        1 => ErrorStatus::TooBig,
+        2 => ErrorStatus::NoSuchName,
+        3 => ErrorStatus::BadValue,
+        4 => ErrorStatus::ReadOnly,
+        6 | 16..=18 | 11 if is_v1 => ErrorStatus::NoSuchName,
+        7..=10 | 12 if is_v1 => ErrorStatus::BadValue,
+        6 => ErrorStatus::NoAccess,
+        7 => ErrorStatus::WrongType,
+        8 => ErrorStatus::WrongLength,
+        9 => ErrorStatus::WrongEncoding,
+        10 => ErrorStatus::WrongValue,
+        11 => ErrorStatus::NoCreation,
+        12 => ErrorStatus::InconsistentValue,
+        13 if !is_v1 => ErrorStatus::ResourceUnavailable,
+        14 if !is_v1 => ErrorStatus::CommitFailed,
+        15 if !is_v1 => ErrorStatus::UndoFailed,
+        16 => ErrorStatus::AuthorizationError,
+        17 => ErrorStatus::NotWritable,
+        18 => ErrorStatus::InconsistentName,
+        _ => ErrorStatus::GenErr,
+    }
+}
+
+fn error_message(
+    version: &SnmpProtocolVersion,
+    request_id: i32,
+    error_status: ErrorStatus,
+    error_index: usize,
+    name: Option<ObjectIdentifier>,
+) -> GenericSnmpMessage {
+    let response = GetResponseError {
+        request_id,
+        error_status,
+        error_index,
+        name,
+    };
+    (version, response).into()
+}
+
+// the subagent's variable is accepted only if it follows the start of the search range and precedes its end
+fn is_in_range(variable_binding: &VariableBinding, name: &ObjectIdentifier, region: &Region) -> bool {
+    match variable_binding {
+        VariableBinding::Value(variable) => *name < variable.name && region.contains(&variable.name),
+        VariableBinding::Exception(..) => false,
+    }
+}
+
+// The successor of the name in the MIB view of the device, i.e. the SNMP data of the device merged with
+// the regions dispatched to the subagents. The simulated variables inside of the regions are hidden
+async fn next_variable(
+    master: &AgentxMaster,
+    request_context: &AgentContext,
+    transaction_id: u32,
+    name: &ObjectIdentifier,
+) -> Result<VariableBinding, AgentxError> {
+    let regions = master.regions();
+    let simulated: Option<Variable> = {
+        let snmp_data = request_context.snmp_data.read().unwrap();
+        snmp_data
+            .range((Excluded(name.clone()), Unbounded))
+            .find(|(name, _)| !regions.iter().any(|region| region.contains(name)))
+            .map(|(name, snmp_data_item)| data_item_variable(name, snmp_data_item, &request_context.snmp_engine))
+    };
+
+    for region in regions
+        .iter()
+        .filter(|region| region.end.as_ref().map(|end| name < end).unwrap_or(true))
+    {
+        if matches!(&simulated, Some(simulated) if simulated.name < region.start) {
+            break;
+        }
+
+        // the region is searched from its start, unless the name is inside of it
+        let include = *name < region.start;
+        let range = SearchRange {
+            start: if include { region.start.clone() } else { name.clone() },
+            include,
+            end: region.end.clone().unwrap_or_else(null_oid),
+        };
+        let (error, _, var_binds) = master
+            .request(
+                &region.owner,
+                transaction_id,
+                AgentxPayload::GetNext { ranges: vec![range] },
+            )
+            .await?;
+        if error != 0 {
+            return Err(AgentxError::Response { error, index: 0 });
+        }
+
+        if let Some(var_bind) = var_binds.into_iter().next() {
+            let variable_binding = VariableBinding::from(var_bind);
+            if is_in_range(&variable_binding, name, region) {
+                return Ok(variable_binding);
+            }
+        }
+    }
+
+    Ok(match simulated {
+        Some(simulated) => simulated.into(),
+        None => VariableBinding::Exception(name.clone(), VarBindException::EndOfMibView),
+    })
+}
+
+async fn dispatch_get(
+    master: &AgentxMaster,
+    request: GetRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, AgentxError> {
+    let transaction_id = master.next_id();
+
+    // the variables of each subagent session are requested by a single Get-PDU
+    let mut dispatched: Vec<(Registration, Vec<usize>)> = vec![];
+    let mut simulated = vec![];
+    for (idx, name) in request.objects.iter().enumerate() {
+        match master.owner(name) {
+            Some(owner) => match dispatched
+                .iter_mut()
+                .find(|(registration, _)| registration.session_id == owner.session_id)
+            {
+                Some((_, indexes)) => indexes.push(idx),
+                None => dispatched.push((owner, vec![idx])),
+            },
+            None => simulated.push(idx),
+        }
+    }
+
+    let mut variable_values: Vec<(usize, VariableBinding)> = vec![];
+    for (registration, indexes) in dispatched {
+        let ranges = indexes
+            .iter()
+            .map(|idx| SearchRange {
+                start: request.objects[*idx].clone(),
+                include: false,
+                end: null_oid(),
+            })
+            .collect();
+        let (error, index, var_binds) = master
+            .request(&registration, transaction_id, AgentxPayload::Get { ranges })
+            .await?;
+        if error != 0 || var_binds.len() < indexes.len() {
+            let index = (index as usize)
+                .checked_sub(1)
+                .and_then(|index| indexes.get(index))
+                .map(|idx| idx + 1)
+                .unwrap_or_default();
+            return Err(AgentxError::Response { error, index });
+        }
+        variable_values.extend(
+            indexes
+                .into_iter()
+                .zip(var_binds.into_iter().map(VariableBinding::from)),
+        );
+    }
+
+    {
+        let snmp_data = request_context.snmp_data.read().unwrap();
+        variable_values.extend(simulated.into_iter().map(|idx| {
+            let name = &request.objects[idx];
+            let variable_binding = match snmp_data.get(name) {
+                Some(snmp_data_item) => data_item_variable(name, snmp_data_item, &request_context.snmp_engine).into(),
+                None => VariableBinding::Exception(name.clone(), missing_variable_exception(&snmp_data, name)),
+            };
+            (idx, variable_binding)
+        }));
+    }
+    variable_values.sort_by_key(|(idx, _)| *idx);
+
+    Ok(response_message(
+        &request_context.version,
+        request.request_id,
+        variable_values
+            .into_iter()
+            .map(|(_, variable_binding)| variable_binding)
+            .collect(),
+    ))
+}
+
+async fn dispatch_get_next(
+    master: &AgentxMaster,
+    request: GetNextRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, AgentxError> {
+    let transaction_id = master.next_id();
+    let mut variable_values = vec![];
+    for (idx, name) in request.objects.iter().enumerate() {
+        let variable_binding = next_variable(master, request_context, transaction_id, name)
+            .await
+            .map_err(|error| match error {
+                AgentxError::Response { error, .. } => AgentxError::Response { error, index: idx + 1 },
+                error => error,
+            })?;
+        variable_values.push(variable_binding);
+    }
+
+    Ok(response_message(
+        &request_context.version,
+        request.request_id,
+        variable_values,
+    ))
+}
+
+async fn dispatch_get_bulk(
+    master: &AgentxMaster,
+    request: GetBulkRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, AgentxError> {
+    let transaction_id = master.next_id();
+    let max_message_size = request_context.max_message_size();
+    let non_repeaters = (request.non_repeaters as usize).min(request.objects.len());
+    let (non_repeating, repeating) = request.objects.split_at(non_repeaters);
+
+    // the non-repeating variables are processed as in the GetNextRequest
+    let mut variable_values = vec![];
+    for name in non_repeating {
+        variable_values.push(next_variable(master, request_context, transaction_id, name).await?);
+    }
+    let mut encoded_size: usize = variable_values.iter().map(encoded_var_bind_len).sum();
+
+    // the repeating variables are walked row by row as by the command responder
+    let mut cursors = repeating.to_vec();
+    for _ in 0..request.max_repetitions {
+        if cursors.is_empty() || encoded_size > max_message_size {
+            break;
+        }
+
+        let mut end_of_mib_view = true;
+        for cursor in cursors.iter_mut() {
+            let variable_binding = next_variable(master, request_context, transaction_id, cursor).await?;
+            if let VariableBinding::Value(variable) = &variable_binding {
+                *cursor = variable.name.clone();
+                end_of_mib_view = false;
+            }
+            encoded_size += encoded_var_bind_len(&variable_binding);
+            variable_values.push(variable_binding);
+        }
+
+        if end_of_mib_view {
+            break;
+        }
+    }
+
+    Ok(fit_to_message_size(
+        &request_context.version,
+        request.request_id,
+        variable_values,
+        max_message_size,
+    ))
+}
+
+// The SET operation is performed by the two-phase commit of all the subagents involved (RFC 2741, 7.2.1.2),
+// the simulated variables are validated along with the TestSet-PDUs and updated once all the subagents committed
+async fn dispatch_set(
+    master: &AgentxMaster,
+    request: SetRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, AgentxError> {
+    let version = &request_context.version;
+    let transaction_id = master.next_id();
+
+    let mut dispatched: Vec<(Registration, Vec<usize>)> = vec![];
+    let mut simulated = vec![];
+    for (idx, variable) in request.variables.iter().enumerate() {
+        match master.owner(&variable.name) {
+            Some(owner) => match dispatched
+                .iter_mut()
+                .find(|(registration, _)| registration.session_id == owner.session_id)
+            {
+                Some((_, indexes)) => indexes.push(idx),
+                None => dispatched.push((owner, vec![idx])),
+            },
+            None => simulated.push(variable.clone()),
+        }
+    }
+
+    // the first simulated variable which can't be set, with its index in the request
+    let invalid_variable = |snmp_data: &SnmpData| {
+        request
+            .variables
+            .iter()
+            .enumerate()
+            .filter(|(_, variable)| {
+                simulated
+                    .iter()
+                    .any(|simulated| simulated.name == variable.name)
+            })
+            .find_map(|(idx, variable)| {
+                validate_set_variable(snmp_data, variable, version)
+                    .err()
+                    .map(|error_status| (idx, error_status))
+            })
+    };
+
+    let invalid = invalid_variable(&request_context.snmp_data.read().unwrap());
+    if let Some((idx, error_status)) = invalid {
+        return Ok(error_message(
+            version,
+            request.request_id,
+            error_status,
+            idx + 1,
+            Some(request.variables[idx].name.clone()),
+        ));
+    }
+
+    // the original index of the variable reported by the subagent
+    let request_index = |indexes: &[usize], index: u16| {
+        (index as usize)
+            .checked_sub(1)
+            .and_then(|index| indexes.get(index))
+            .map(|idx| idx + 1)
+            .unwrap_or_default()
+    };
+    let cleanup = |dispatched: &[(Registration, Vec<usize>)]| {
+        for (registration, _) in dispatched {
+            master.send(registration.session_id, transaction_id, AgentxPayload::CleanupSet);
+        }
+    };
+
+    for (registration, indexes) in dispatched.iter() {
+        let var_binds = indexes
+            .iter()
+            .map(|idx| AgentxVarBind::from(&VariableBinding::from(request.variables[*idx].clone())))
+            .collect();
+        let response = master
+            .request(registration, transaction_id, AgentxPayload::TestSet { var_binds })
+            .await;
+        match response {
+            Ok((0, _, _)) => {}
+            Ok((error, index, _)) => {
+                cleanup(&dispatched);
+                return Err(AgentxError::Response {
+                    error,
+                    index: request_index(indexes, index),
+                });
+            }
+            Err(error) => {
+                cleanup(&dispatched);
+                return Err(error);
+            }
+        }
+    }
+
+    for (committed, (registration, indexes)) in dispatched.iter().enumerate() {
+        let response = master
+            .request(registration, transaction_id, AgentxPayload::CommitSet)
+            .await;
+        if !matches!(response, Ok((0, _, _))) {
+            // the subagents which have committed the changes already are asked to undo them
+            let error = undo_set(master, transaction_id, &dispatched[..=committed])
+                .await
+                .unwrap_or(ErrorStatus::CommitFailed);
+            cleanup(&dispatched);
+            let index = match response {
+                Ok((_, index, _)) => request_index(indexes, index),
+                Err(_) => 0,
+            };
+            return Err(AgentxError::Response {
+                error: error as u16,
+                index,
+            });
+        }
+    }
+
+    // the SNMP data could have been changed by another SET while the subagents were processing the request,
+    // the simulated variables are validated again under the write lock of their update
+    let invalid = {
+        let mut snmp_data = request_context.snmp_data.write().unwrap();
+        let invalid = invalid_variable(&snmp_data);
+        if invalid.is_none() {
+            apply_set_variables(&mut snmp_data, &simulated);
+        }
+        invalid
+    };
+    if let Some((idx, error_status)) = invalid {
+        let undo_error = undo_set(master, transaction_id, &dispatched).await;
+        cleanup(&dispatched);
+        return match undo_error {
+            Some(error) => Err(AgentxError::Response {
+                error: error as u16,
+                index: idx + 1,
+            }),
+            None => Ok(error_message(
+                version,
+                request.request_id,
+                error_status,
+                idx + 1,
+                Some(request.variables[idx].name.clone()),
+            )),
+        };
+    }
+    cleanup(&dispatched);
+
+    let response = GetResponse {
+        request_id: request.request_id,
+        variable_values: request
+            .variables
+            .into_iter()
+            .map(VariableBinding::from)
+            .collect(),
+    };
+    Ok((version, response).into())
+}
+
+/// Asks the subagents to undo the committed changes (RFC 2741, 7.2.4.4), the undoFailed error status
+/// is returned if any of them fails to do so
+async fn undo_set(
+    master: &AgentxMaster,
+    transaction_id: u32,
+    dispatched: &[(Registration, Vec<usize>)],
+) -> Option<ErrorStatus> {
+    let mut error = None;
+    for (registration, _) in dispatched {
+        let response = master
+            .request(registration, transaction_id, AgentxPayload::UndoSet)
+            .await;
+        if !matches!(response, Ok((0, _, _))) {
+            error = Some(ErrorStatus::UndoFailed);
+        }
+    }
+    error
+}
+
+/// The request of the device dispatched by the AgentX master agent
+#[derive(Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) enum DispatchedRequest {
+    Get(GetRequest),
+    GetNext(GetNextRequest),
+    GetBulk(GetBulkRequest),
+    Set(SetRequest),
+}
+
+/// Processes the request of the device with the subagents registered to its AgentX master agent (RFC 2741, 7.2),
+/// the variables of the registered subtrees are requested from the subagents, the rest of them from the SNMP data.
+/// The subagent failing to respond in time is reported by the genErr error status
+#[tracing::instrument(level = "info", name = "dispatch_request", skip(request_context))]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn dispatch_request(request: DispatchedRequest, master: Arc<AgentxMaster>, request_context: AgentContext) {
+    actix::spawn(async move {
+        let (request_id, response) = match request {
+            DispatchedRequest::Get(request) => (
+                request.request_id,
+                dispatch_get(&master, request, &request_context).await,
+            ),
+            DispatchedRequest::GetNext(request) => (
+                request.request_id,
+                dispatch_get_next(&master, request, &request_context).await,
+            ),
+            DispatchedRequest::GetBulk(request) => (
+                request.request_id,
+                dispatch_get_bulk(&master, request, &request_context).await,
+            ),
+            DispatchedRequest::Set(request) => (
+                request.request_id,
+                dispatch_set(&master, request, &request_context).await,
+            ),
+        };
+
+        let version = &request_context.version;
+        let response = match response {
+            Ok(response) => response,
+            Err(AgentxError::Response { error, index }) => {
+                error_message(version, request_id, subagent_error_status(error, version), index, None)
+            }
+            Err(error) => {
+                tracing::warn!("The request {request_id} dispatched to the subagent failed: {error}");
+                error_message(version, request_id, ErrorStatus::GenErr, 0, None)
+            }
+        };
+        send_response(response, request_id, &request_context);
+    });
+}
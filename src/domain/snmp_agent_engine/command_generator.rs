@@ -0,0 +1,535 @@
+use crate::domain::next_request_id;
+use crate::domain::SnmpProtocolAttributes;
+use crate::domain::SnmpProtocolVersion;
+use crate::domain::{GetBulkRequest, GetNextRequest, GetRequest, VarBindException, Variable, VariableBinding};
+use crate::domain::{SnmpV3Parameters, UsmUser, Walk, WalkMethod, AUTH_FLAG, PRIV_FLAG};
+use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
+use crate::snmp::codec::snmp_codec::SnmpCodec;
+use crate::snmp::codec::snmp_codec_error::CodecError;
+use crate::snmp::usm::{decrypt_scoped_pdu, verify_message, UsmReport};
+use crate::udp_client::{Client, ClientError};
+
+use bytes::Bytes;
+use num_traits::ToPrimitive;
+use rasn::prelude::ObjectIdentifier;
+use rasn_snmp::v2::VarBindValue;
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+// the error-status of the SNMPv1 agent at the end of its MIB view (RFC 1157, 4.1.3)
+const NO_SUCH_NAME: u32 = 2;
+
+// the security of the requests sent to the remote agent
+enum Session {
+    SnmpV1(String),
+    SnmpV2c(String),
+    Usm {
+        user_name: String,
+        agent: AuthoritativeAgent,
+    },
+}
+
+// the remote SNMPv3 agent acts as the authoritative SNMP engine (RFC 3414, 4),
+// the user keys are localized to its snmpEngineID after the discovery
+struct AuthoritativeAgent {
+    engine_id: Vec<u8>,
+    engine_boots: u32,
+    engine_time: u32,
+    synchronized_at: Instant,
+    usm_user: Option<UsmUser>,
+}
+
+impl AuthoritativeAgent {
+    fn from_report(security_parameters: &rasn_snmp::v3::USMSecurityParameters) -> Self {
+        let mut agent = AuthoritativeAgent {
+            engine_id: security_parameters.authoritative_engine_id.to_vec(),
+            engine_boots: 0,
+            engine_time: 0,
+            synchronized_at: Instant::now(),
+            usm_user: None,
+        };
+        agent.synchronize(security_parameters);
+        agent
+    }
+
+    // the snmpEngineTime of the agent estimated from the last synchronization
+    fn engine_time(&self) -> u32 {
+        self.engine_time
+            .saturating_add(self.synchronized_at.elapsed().as_secs() as u32)
+    }
+
+    fn synchronize(&mut self, security_parameters: &rasn_snmp::v3::USMSecurityParameters) {
+        self.engine_boots = security_parameters
+            .authoritative_engine_boots
+            .to_u32()
+            .unwrap_or_default();
+        self.engine_time = security_parameters
+            .authoritative_engine_time
+            .to_u32()
+            .unwrap_or_default();
+        self.synchronized_at = Instant::now();
+    }
+}
+
+// the outcome of a message received from the remote agent
+enum Received {
+    Response(AgentResponse),
+    // f.e. the notInTimeWindows Report, the request is retransmitted with the agent's time
+    Retransmit,
+    Ignored,
+}
+
+// the request sent to the remote agent, the request-id is assigned to each request by the command generator
+enum Request<'a> {
+    Get(&'a [ObjectIdentifier]),
+    GetNext(&'a [ObjectIdentifier]),
+    GetBulk {
+        non_repeaters: u32,
+        max_repetitions: u32,
+        objects: &'a [ObjectIdentifier],
+    },
+}
+
+/// The Response-PDU of the remote agent, the SNMPv2 exceptions are kept in the variable bindings
+#[derive(Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct AgentResponse {
+    pub error_status: u32,
+    pub error_index: u32,
+    pub variable_bindings: Vec<VariableBinding>,
+}
+
+/// The command generator (RFC 3413, 3.1) of the requests sent to a remote SNMP agent. Each request is retransmitted
+/// after the timeout until its response is received or the retries are exhausted
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct CommandGenerator {
+    client: Client,
+    session: Session,
+    target: SocketAddr,
+    timeout: Duration,
+    retries: u32,
+}
+
+impl CommandGenerator {
+    /// Connects to the remote agent, the snmpEngineID of the SNMPv3 agent is discovered and the user keys
+    /// are localized to it, the password to key algorithm is expensive, so it's done once per command generator
+    pub async fn connect(
+        target: SocketAddr,
+        snmp_protocol_attributes: &SnmpProtocolAttributes,
+        timeout: Duration,
+        retries: u32,
+    ) -> Result<Self, ClientError> {
+        let mut client = Client::connect(target).await?;
+        let session = match snmp_protocol_attributes {
+            SnmpProtocolAttributes::SnmpV1(attributes) => Session::SnmpV1(attributes.community.clone()),
+            SnmpProtocolAttributes::SnmpV2c(attributes) => Session::SnmpV2c(attributes.community.clone()),
+            SnmpProtocolAttributes::SnmpV3(attributes) => {
+                let mut agent = discover_agent(&mut client, target, timeout, retries).await?;
+                agent.usm_user = Some(UsmUser::new(attributes, &agent.engine_id));
+                Session::Usm {
+                    user_name: attributes.user.clone(),
+                    agent,
+                }
+            }
+        };
+
+        Ok(CommandGenerator {
+            client,
+            session,
+            target,
+            timeout,
+            retries,
+        })
+    }
+
+    /// The remote agent is accessed by SNMPv1, i.e. it reports the missing variables by the noSuchName error
+    pub fn is_snmp_v1(&self) -> bool {
+        matches!(self.session, Session::SnmpV1(_))
+    }
+
+    pub async fn get(&mut self, objects: &[ObjectIdentifier]) -> Result<AgentResponse, ClientError> {
+        self.send_request(Request::Get(objects)).await
+    }
+
+    pub async fn get_next(&mut self, objects: &[ObjectIdentifier]) -> Result<AgentResponse, ClientError> {
+        self.send_request(Request::GetNext(objects)).await
+    }
+
+    /// The GetBulkRequest is translated to the GetNextRequest for the SNMPv1 agent (RFC 3584, 4.1.1)
+    pub async fn get_bulk(
+        &mut self,
+        non_repeaters: u32,
+        max_repetitions: u32,
+        objects: &[ObjectIdentifier],
+    ) -> Result<AgentResponse, ClientError> {
+        self.send_request(Request::GetBulk {
+            non_repeaters,
+            max_repetitions,
+            objects,
+        })
+        .await
+    }
+
+    // Sends the request and retransmits it after each timeout until the response is received, or the retries
+    // are exhausted. The message is rebuilt for each retransmission, so the SNMPv3 one carries the agent's actual time
+    async fn send_request(&mut self, request: Request<'_>) -> Result<AgentResponse, ClientError> {
+        let request_id = next_request_id();
+        for _ in 0..=self.retries {
+            self.client
+                .send_message(request_message(&self.session, request_id, &request))
+                .await?;
+
+            let deadline = tokio::time::Instant::now() + self.timeout;
+            while let Ok(response) = tokio::time::timeout_at(deadline, self.client.recv_message()).await {
+                let response = match decoded(response)? {
+                    Some(response) => response,
+                    None => continue,
+                };
+                match receive_message(&mut self.session, response, request_id)? {
+                    Received::Response(response) => return Ok(response),
+                    Received::Retransmit => break,
+                    Received::Ignored => {}
+                }
+            }
+        }
+
+        Err(ClientError::Timeout(self.target.to_string()))
+    }
+}
+
+/// Walks the subtree of the remote agent and returns its variables in the lexicographic order.
+/// The SNMPv1 agents are always walked by the GetNextRequests, since the GetBulkRequest is not defined by SNMPv1,
+/// the SNMPv3 walk is preceded by the discovery of the agent's snmpEngineID
+#[tracing::instrument(level = "info", name = "walk_agent")]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn walk_agent(walk: &Walk) -> Result<Vec<Variable>, ClientError> {
+    let mut command_generator =
+        CommandGenerator::connect(walk.target, &walk.snmp_protocol_attributes, walk.timeout, walk.retries).await?;
+    let method = if command_generator.is_snmp_v1() {
+        WalkMethod::GetNext
+    } else {
+        walk.method
+    };
+
+    let mut variables: Vec<Variable> = vec![];
+    let mut last_name = walk.root.clone();
+    loop {
+        let objects = [last_name.clone()];
+        let response = match method {
+            WalkMethod::GetNext => command_generator.get_next(&objects).await?,
+            WalkMethod::GetBulk => {
+                command_generator
+                    .get_bulk(0, walk.max_repetitions, &objects)
+                    .await?
+            }
+        };
+
+        if response.error_status == NO_SUCH_NAME && matches!(method, WalkMethod::GetNext) {
+            return Ok(variables);
+        }
+        if response.error_status != 0 {
+            return Err(ClientError::ErrorStatus {
+                status: response.error_status,
+                index: response.error_index,
+            });
+        }
+        if response.variable_bindings.is_empty() {
+            return Ok(variables);
+        }
+
+        for variable_binding in response.variable_bindings {
+            let variable = match variable_binding {
+                VariableBinding::Value(variable) => variable,
+                // the endOfMibView, the other exceptions are never returned by the GetNextRequest
+                VariableBinding::Exception(..) => return Ok(variables),
+            };
+            if !variable.name.starts_with(&walk.root) {
+                return Ok(variables);
+            }
+            // the agent returning the same or a lower OID would be walked forever
+            if variable.name <= last_name {
+                return Err(ClientError::OidNotIncreasing(oid_to_string(variable.name)));
+            }
+            last_name = variable.name.clone();
+            variables.push(variable);
+        }
+    }
+}
+
+// Discovers the snmpEngineID, snmpEngineBoots and snmpEngineTime of the SNMPv3 agent by the unauthenticated
+// request with an empty msgAuthoritativeEngineID, reported back by the agent (RFC 3414, 4)
+async fn discover_agent(
+    client: &mut Client,
+    target: SocketAddr,
+    timeout: Duration,
+    retries: u32,
+) -> Result<AuthoritativeAgent, ClientError> {
+    let request_id = next_request_id();
+    for _ in 0..=retries {
+        client.send_message(discovery_message(request_id)).await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        while let Ok(message) = tokio::time::timeout_at(deadline, client.recv_message()).await {
+            if let Some(GenericSnmpMessage::V3Message(message)) = decoded(message)? {
+                if message.global_data.message_id.to_i32() == Some(request_id)
+                    && !message
+                        .security_parameters
+                        .authoritative_engine_id
+                        .is_empty()
+                {
+                    return Ok(AuthoritativeAgent::from_report(&message.security_parameters));
+                }
+            }
+        }
+    }
+
+    Err(ClientError::Timeout(target.to_string()))
+}
+
+// the datagram which is not a valid SNMP message is dropped and the response is awaited further,
+// the other errors of the client end the request
+fn decoded(message: Result<GenericSnmpMessage, ClientError>) -> Result<Option<GenericSnmpMessage>, ClientError> {
+    match message {
+        Ok(message) => Ok(Some(message)),
+        Err(ClientError::CodecError(error @ (CodecError::Decoder(_) | CodecError::InvalidVersion(_)))) => {
+            tracing::debug!("The message of the remote agent is dropped: {error}");
+            Ok(None)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+// the request-id of the SNMPv3 request is used as its msgID as well, so the response is correlated by both of them
+fn request_message(session: &Session, request_id: i32, request: &Request) -> GenericSnmpMessage {
+    match session {
+        Session::SnmpV1(community) => {
+            let version = SnmpProtocolVersion::SNMPV1(community.clone());
+            match request {
+                Request::Get(objects) => (
+                    &version,
+                    GetRequest {
+                        request_id,
+                        objects: objects.to_vec(),
+                    },
+                )
+                    .into(),
+                Request::GetNext(objects) | Request::GetBulk { objects, .. } => (
+                    &version,
+                    GetNextRequest {
+                        request_id,
+                        objects: objects.to_vec(),
+                    },
+                )
+                    .into(),
+            }
+        }
+        Session::SnmpV2c(community) => GenericSnmpMessage::V2Message(rasn_snmp::v2c::Message {
+            version: SnmpCodec::SNMP_VERSION2.into(),
+            community: Bytes::from(community.as_bytes().to_vec()),
+            data: request_pdu(request_id, request),
+        }),
+        Session::Usm { user_name, agent } => {
+            let usm_user = agent.usm_user.as_ref();
+            let parameters = SnmpV3Parameters {
+                message_id: request_id,
+                max_size: SnmpCodec::MAX_MESSAGE_SIZE as i32,
+                engine_id: agent.engine_id.clone(),
+                engine_boots: agent.engine_boots,
+                engine_time: agent.engine_time(),
+                user_name: user_name.as_bytes().to_vec(),
+                context_engine_id: agent.engine_id.clone(),
+                context_name: vec![],
+                authentication_key: usm_user.and_then(|usm_user| usm_user.authentication_key.clone()),
+                privacy_key: usm_user.and_then(|usm_user| usm_user.privacy_key.clone()),
+            };
+            GenericSnmpMessage::V3Message(Box::new(
+                parameters
+                    .to_message(request_pdu(request_id, request))
+                    .into(),
+            ))
+        }
+    }
+}
+
+fn request_pdu(request_id: i32, request: &Request) -> rasn_snmp::v2::Pdus {
+    match request {
+        Request::Get(objects) => GetRequest {
+            request_id,
+            objects: objects.to_vec(),
+        }
+        .into(),
+        Request::GetNext(objects) => GetNextRequest {
+            request_id,
+            objects: objects.to_vec(),
+        }
+        .into(),
+        Request::GetBulk {
+            non_repeaters,
+            max_repetitions,
+            objects,
+        } => GetBulkRequest {
+            request_id,
+            non_repeaters: *non_repeaters,
+            max_repetitions: *max_repetitions,
+            objects: objects.to_vec(),
+        }
+        .into(),
+    }
+}
+
+// the discovery request is the GetRequest without any variable bindings and security (RFC 3414, 4)
+fn discovery_message(request_id: i32) -> GenericSnmpMessage {
+    let parameters = SnmpV3Parameters {
+        message_id: request_id,
+        max_size: SnmpCodec::MAX_MESSAGE_SIZE as i32,
+        engine_id: vec![],
+        engine_boots: 0,
+        engine_time: 0,
+        user_name: vec![],
+        context_engine_id: vec![],
+        context_name: vec![],
+        authentication_key: None,
+        privacy_key: None,
+    };
+    (
+        &SnmpProtocolVersion::SNMPV3(parameters),
+        GetRequest {
+            request_id,
+            objects: vec![],
+        },
+    )
+        .into()
+}
+
+// Processes the message received from the remote agent, the messages not related to the request are ignored,
+// f.e. the late responses of the previous transmissions or the messages failing the authentication
+fn receive_message(
+    session: &mut Session,
+    message: GenericSnmpMessage,
+    request_id: i32,
+) -> Result<Received, ClientError> {
+    match (session, message) {
+        (Session::SnmpV1(_), GenericSnmpMessage::V1Message(message)) => match message.data {
+            rasn_snmp::v1::Pdus::GetResponse(response) if response.0.request_id.to_i32() == Some(request_id) => {
+                Ok(Received::Response(AgentResponse {
+                    error_status: response.0.error_status.to_u32().unwrap_or_default(),
+                    error_index: response.0.error_index.to_u32().unwrap_or_default(),
+                    variable_bindings: response
+                        .0
+                        .variable_bindings
+                        .into_iter()
+                        .map(|var_bind| VariableBinding::Value(var_bind.into()))
+                        .collect(),
+                }))
+            }
+            _ => Ok(Received::Ignored),
+        },
+        (Session::SnmpV2c(_), GenericSnmpMessage::V2Message(message)) => Ok(response_pdu(message.data, request_id)),
+        (Session::Usm { agent, .. }, GenericSnmpMessage::V3Message(mut message)) => {
+            if message.global_data.message_id.to_i32() != Some(request_id)
+                || agent.engine_id.as_slice() != message.security_parameters.authoritative_engine_id.as_ref()
+            {
+                return Ok(Received::Ignored);
+            }
+
+            // the Reports of the agent are unauthenticated, except for the notInTimeWindows one (RFC 3414, 3.2)
+            let flags = message
+                .global_data
+                .flags
+                .first()
+                .copied()
+                .unwrap_or_default();
+            let usm_user = agent.usm_user.as_ref();
+            let authentication_key = usm_user.and_then(|usm_user| usm_user.authentication_key.as_ref());
+            if let Some(authentication_key) = authentication_key {
+                if flags & AUTH_FLAG != 0 && !verify_message(&message, authentication_key) {
+                    tracing::debug!("The message of the remote agent is not authenticated");
+                    return Ok(Received::Ignored);
+                }
+            }
+            if flags & PRIV_FLAG != 0 {
+                let decrypted = match usm_user.and_then(|usm_user| usm_user.privacy_key.as_ref()) {
+                    Some(privacy_key) => decrypt_scoped_pdu(&mut message, privacy_key),
+                    None => false,
+                };
+                if !decrypted {
+                    tracing::debug!("The message of the remote agent is not decrypted");
+                    return Ok(Received::Ignored);
+                }
+            }
+
+            let scoped_pdu = match message.message.scoped_data {
+                rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) => scoped_pdu,
+                rasn_snmp::v3::ScopedPduData::EncryptedPdu(_) => return Ok(Received::Ignored),
+            };
+            match scoped_pdu.data {
+                rasn_snmp::v2::Pdus::Report(report) => {
+                    let name = report
+                        .0
+                        .variable_bindings
+                        .first()
+                        .map(|variable| variable.name.clone());
+                    match name {
+                        Some(name) if name == UsmReport::NotInTimeWindows.oid() && flags & AUTH_FLAG != 0 => {
+                            agent.synchronize(&message.message.security_parameters);
+                            Ok(Received::Retransmit)
+                        }
+                        Some(name) => Err(ClientError::Report(report_name(name))),
+                        None => Err(ClientError::Report("no variable bindings".into())),
+                    }
+                }
+                // the unauthenticated response is dropped if the user is authenticated
+                _ if flags & AUTH_FLAG == 0 && authentication_key.is_some() => Ok(Received::Ignored),
+                data => Ok(response_pdu(data, request_id)),
+            }
+        }
+        _ => Ok(Received::Ignored),
+    }
+}
+
+// the SNMPv2 Response-PDU of the request, the exceptions are kept so the end of the MIB view is recognized
+fn response_pdu(data: rasn_snmp::v2::Pdus, request_id: i32) -> Received {
+    match data {
+        rasn_snmp::v2::Pdus::Response(response) if response.0.request_id == request_id => {
+            Received::Response(AgentResponse {
+                error_status: response.0.error_status,
+                error_index: response.0.error_index,
+                variable_bindings: response
+                    .0
+                    .variable_bindings
+                    .into_iter()
+                    .map(|var_bind| match var_bind.value {
+                        VarBindValue::NoSuchObject => {
+                            VariableBinding::Exception(var_bind.name, VarBindException::NoSuchObject)
+                        }
+                        VarBindValue::NoSuchInstance => {
+                            VariableBinding::Exception(var_bind.name, VarBindException::NoSuchInstance)
+                        }
+                        VarBindValue::EndOfMibView => {
+                            VariableBinding::Exception(var_bind.name, VarBindException::EndOfMibView)
+                        }
+                        _ => VariableBinding::Value(var_bind.into()),
+                    })
+                    .collect(),
+            })
+        }
+        _ => Received::Ignored,
+    }
+}
+
+// the usmStats counter reported by the agent, f.e. "usmStatsWrongDigests", or the OID of any other report
+fn report_name(name: ObjectIdentifier) -> String {
+    [
+        UsmReport::UnsupportedSecLevels,
+        UsmReport::NotInTimeWindows,
+        UsmReport::UnknownUserNames,
+        UsmReport::UnknownEngineIds,
+        UsmReport::WrongDigests,
+        UsmReport::DecryptionErrors,
+    ]
+    .iter()
+    .find(|report| report.oid() == name)
+    .map(|report| format!("usmStats{report}"))
+    .unwrap_or_else(|| oid_to_string(name))
+}
@@ -1,12 +1,21 @@
 use crate::domain::AgentContext;
-use crate::domain::{ErrorStatus, GetNextRequest, GetRequest, GetResponse, GetResponseError, Variable};
+use crate::domain::SnmpEngine;
+use crate::domain::SnmpProtocolVersion;
+use crate::domain::{agentx_master, dispatch_request, DispatchedRequest};
+use crate::domain::{data_item_variable, forward_request, ForwardedRequest};
+use crate::domain::{
+    ErrorStatus, GetBulkRequest, GetNextRequest, GetRequest, GetResponse, GetResponseError, SetRequest,
+    VarBindException, Variable, VariableBinding, SNMP_ENGINE_TIME_OID, SYS_UP_TIME_OID,
+};
 use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
+use crate::snmp::snmp_statistics::SnmpCounter;
 use crate::udp_server::udp_stream_handler::send_data;
 
 use actix::prelude::*;
 use cached::proc_macro::cached;
 use rasn::prelude::ObjectIdentifier;
 use shared_common::error_chain_fmt;
+use snmp_data_parser::parser::snmp_data::component::{DataType, SnmpData};
 use snmp_data_parser::parser::ParserError;
 use std::convert::Infallible;
 use std::ops::Bound::{Excluded, Unbounded};
@@ -67,23 +76,28 @@ impl Handler<Get> for SnmpAgentCommandResponder {
     )]
     fn handle(&mut self, get_msg: Get, _ctx: &mut Self::Context) -> Self::Result {
         let get_request = get_msg.request;
+        let is_v1 = matches!(get_msg.request_context.version, SnmpProtocolVersion::SNMPV1(_));
+        let snmp_data = get_msg.request_context.snmp_data.read().unwrap();
         let variables = get_request
             .objects
             .into_iter()
             .enumerate()
-            .map(|(idx, item)| match get_msg.request_context.snmp_data.get(&item) {
-                Some(snmp_data_item) => Ok(Variable {
-                    name: item,
-                    data_type: snmp_data_item.data_type.clone(),
-                    value: snmp_data_item.data_value.clone(),
-                }),
-                _ => Err(SnmpAgentCommandResponderError::ProtocolError(
+            .map(|(idx, item)| match snmp_data.get(&item) {
+                Some(snmp_data_item) => {
+                    Ok(data_item_variable(&item, snmp_data_item, &get_msg.request_context.snmp_engine).into())
+                }
+                None if is_v1 => Err(SnmpAgentCommandResponderError::ProtocolError(
                     ErrorStatus::NoSuchName,
                     idx + 1,
                     item,
                 )),
+                None => {
+                    let exception = missing_variable_exception(&snmp_data, &item);
+                    Ok(VariableBinding::Exception(item, exception))
+                }
             })
             .collect();
+        drop(snmp_data);
 
         let response: GenericSnmpMessage = match variables {
             Ok(variable_values) => {
@@ -113,7 +127,7 @@ impl Handler<Get> for SnmpAgentCommandResponder {
             }
         };
 
-        send_data(response, &get_msg.request_context);
+        send_response(response, get_request.request_id, &get_msg.request_context);
 
         Ok(())
     }
@@ -137,30 +151,25 @@ impl Handler<GetNext> for SnmpAgentCommandResponder {
     )]
     fn handle(&mut self, get_next_msg: GetNext, _ctx: &mut Self::Context) -> Self::Result {
         let get_next_request = get_next_msg.request;
+        let is_v1 = matches!(get_next_msg.request_context.version, SnmpProtocolVersion::SNMPV1(_));
+        let snmp_data = get_next_msg.request_context.snmp_data.read().unwrap();
         let variables = get_next_request
             .objects
             .into_iter()
             .enumerate()
-            .map(|(idx, item)| {
-                match get_next_msg
-                    .request_context
-                    .snmp_data
-                    .range((Excluded(item.clone()), Unbounded))
-                    .next()
-                {
-                    Some((name, snmp_data_item)) => Ok(Variable {
-                        name: name.clone(),
-                        data_type: snmp_data_item.data_type.clone(),
-                        value: snmp_data_item.data_value.clone(),
-                    }),
-                    _ => Err(SnmpAgentCommandResponderError::ProtocolError(
+            .map(
+                |(idx, item)| match next_variable(&snmp_data, &item, &get_next_msg.request_context.snmp_engine) {
+                    // SNMPv1 has no exception values, the end of the MIB view is reported by the error status
+                    VariableBinding::Exception(..) if is_v1 => Err(SnmpAgentCommandResponderError::ProtocolError(
                         ErrorStatus::NoSuchName,
                         idx + 1,
                         item,
                     )),
-                }
-            })
+                    variable_binding => Ok(variable_binding),
+                },
+            )
             .collect();
+        drop(snmp_data);
 
         let response: GenericSnmpMessage = match variables {
             Ok(variable_values) => {
@@ -190,12 +199,325 @@ impl Handler<GetNext> for SnmpAgentCommandResponder {
             }
         };
 
-        send_data(response, &get_next_msg.request_context);
+        send_response(response, get_next_request.request_id, &get_next_msg.request_context);
 
         Ok(())
     }
 }
 
+// SNMP GetBulkRequest Handler
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), SnmpAgentCommandResponderError>")]
+struct GetBulk {
+    pub request: GetBulkRequest,
+    pub request_context: AgentContext,
+}
+
+impl Handler<GetBulk> for SnmpAgentCommandResponder {
+    type Result = Result<(), SnmpAgentCommandResponderError>;
+
+    #[tracing::instrument(
+        level = "info",
+        name = "SnmpAgentCommandResponder::handle_get_bulk_request",
+        skip(self, get_bulk_msg, _ctx)
+    )]
+    fn handle(&mut self, get_bulk_msg: GetBulk, _ctx: &mut Self::Context) -> Self::Result {
+        let get_bulk_request = get_bulk_msg.request;
+        let max_message_size = get_bulk_msg.request_context.max_message_size();
+        let snmp_data = get_bulk_msg.request_context.snmp_data.read().unwrap();
+
+        let non_repeaters = (get_bulk_request.non_repeaters as usize).min(get_bulk_request.objects.len());
+        let (non_repeating, repeating) = get_bulk_request.objects.split_at(non_repeaters);
+
+        // the non-repeating variables are processed as in the GetNextRequest
+        let mut variable_values: Vec<VariableBinding> = non_repeating
+            .iter()
+            .map(|item| next_variable(&snmp_data, item, &get_bulk_msg.request_context.snmp_engine))
+            .collect();
+        let mut encoded_size: usize = variable_values.iter().map(encoded_var_bind_len).sum();
+
+        // the repeating variables are walked row by row, until the max-repetitions is reached,
+        // all of them reached the end of the MIB view or the response would not fit into the message
+        let mut cursors = repeating.to_vec();
+        for _ in 0..get_bulk_request.max_repetitions {
+            if cursors.is_empty() || encoded_size > max_message_size {
+                break;
+            }
+
+            let mut end_of_mib_view = true;
+            for cursor in cursors.iter_mut() {
+                let variable_binding = next_variable(&snmp_data, cursor, &get_bulk_msg.request_context.snmp_engine);
+                if let VariableBinding::Value(variable) = &variable_binding {
+                    *cursor = variable.name.clone();
+                    end_of_mib_view = false;
+                }
+                encoded_size += encoded_var_bind_len(&variable_binding);
+                variable_values.push(variable_binding);
+            }
+
+            if end_of_mib_view {
+                break;
+            }
+        }
+        drop(snmp_data);
+
+        let response = fit_to_message_size(
+            &get_bulk_msg.request_context.version,
+            get_bulk_request.request_id,
+            variable_values,
+            max_message_size,
+        );
+
+        send_response(response, get_bulk_request.request_id, &get_bulk_msg.request_context);
+
+        Ok(())
+    }
+}
+
+fn next_variable(snmp_data: &SnmpData, name: &ObjectIdentifier, snmp_engine: &SnmpEngine) -> VariableBinding {
+    match snmp_data.range((Excluded(name.clone()), Unbounded)).next() {
+        Some((name, snmp_data_item)) => data_item_variable(name, snmp_data_item, snmp_engine).into(),
+        _ => VariableBinding::Exception(name.clone(), VarBindException::EndOfMibView),
+    }
+}
+
+// Chooses the exception value of the variable missing in the SNMP data (RFC 3416 4.2.1),
+// the object type is considered to exist when the requested name is a prefix of some variable
+// or there is a variable with the same parent and of the same length, i.e. another instance of the object
+pub(super) fn missing_variable_exception(snmp_data: &SnmpData, name: &ObjectIdentifier) -> VarBindException {
+    let is_object_type = || {
+        snmp_data
+            .range((Excluded(name.clone()), Unbounded))
+            .next()
+            .map(|(next_name, _)| next_name.starts_with(name))
+            .unwrap_or_default()
+    };
+
+    let has_other_instance = || match name.split_last() {
+        Some((_, parent)) if !parent.is_empty() => {
+            let parent = ObjectIdentifier::new_unchecked(parent.to_vec().into());
+            snmp_data
+                .range((Excluded(parent.clone()), Unbounded))
+                .take_while(|(other_name, _)| other_name.starts_with(&parent))
+                .any(|(other_name, _)| other_name.len() == name.len())
+        }
+        _ => false,
+    };
+
+    if is_object_type() || has_other_instance() {
+        VarBindException::NoSuchInstance
+    } else {
+        VarBindException::NoSuchObject
+    }
+}
+
+// Builds the response by removing the trailing variables which do not fit into the message,
+// the GetBulkRequest is never answered with the tooBig error (RFC 3416 4.2.3)
+pub(super) fn fit_to_message_size(
+    version: &SnmpProtocolVersion,
+    request_id: i32,
+    mut variable_values: Vec<VariableBinding>,
+    max_message_size: usize,
+) -> GenericSnmpMessage {
+    loop {
+        let response: GenericSnmpMessage = (
+            version,
+            GetResponse {
+                request_id,
+                variable_values: variable_values.clone(),
+            },
+        )
+            .into();
+
+        match response.encoded_len() {
+            Ok(len) if len > max_message_size && !variable_values.is_empty() => {
+                let mut excess = len - max_message_size;
+                while excess > 0 {
+                    match variable_values.pop() {
+                        Some(variable_binding) => {
+                            excess = excess.saturating_sub(encoded_var_bind_len(&variable_binding))
+                        }
+                        None => break,
+                    }
+                }
+            }
+            _ => return response,
+        }
+    }
+}
+
+// Sends the response, the response exceeding the maximum message size is replaced by the tooBig error
+// with no variable bindings, that one is dropped if it does not fit either (RFC 3416 4.2.1)
+pub(super) fn send_response(response: GenericSnmpMessage, request_id: i32, request_context: &AgentContext) {
+    let max_message_size = request_context.max_message_size();
+    let fits = |message: &GenericSnmpMessage| {
+        message
+            .encoded_len()
+            .map(|len| len <= max_message_size)
+            .unwrap_or_default()
+    };
+
+    let snmp_statistics = &request_context.snmp_engine.snmp_statistics;
+    if fits(&response) {
+        snmp_statistics.increment(SnmpCounter::OutGetResponses);
+        send_data(response, request_context);
+        return;
+    }
+
+    let response: GenericSnmpMessage = (
+        &request_context.version,
+        GetResponseError {
+            request_id,
+            error_status: ErrorStatus::TooBig,
+            error_index: 0,
+            name: None,
+        },
+    )
+        .into();
+    if fits(&response) {
+        snmp_statistics.increment(SnmpCounter::OutGetResponses);
+        snmp_statistics.increment(SnmpCounter::OutTooBigs);
+        send_data(response, request_context);
+    } else {
+        snmp_statistics.increment(SnmpCounter::SilentDrops);
+        tracing::debug!("The tooBig response exceeds the maximum message size {max_message_size}, it is dropped");
+    }
+}
+
+pub(super) fn encoded_var_bind_len(variable_binding: &VariableBinding) -> usize {
+    rasn::ber::encode(&rasn_snmp::v2::VarBind::from(variable_binding))
+        .map(|data| data.len())
+        .unwrap_or_default()
+}
+
+// SNMP SetRequest Handler
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), SnmpAgentCommandResponderError>")]
+struct Set {
+    pub request: SetRequest,
+    pub request_context: AgentContext,
+}
+
+impl Handler<Set> for SnmpAgentCommandResponder {
+    type Result = Result<(), SnmpAgentCommandResponderError>;
+
+    #[tracing::instrument(
+        level = "info",
+        name = "SnmpAgentCommandResponder::handle_set_request",
+        skip(self, set_msg, _ctx)
+    )]
+    fn handle(&mut self, set_msg: Set, _ctx: &mut Self::Context) -> Self::Result {
+        let set_request = set_msg.request;
+        let version = &set_msg.request_context.version;
+
+        // the SET operation is atomic, either all the variables are updated or none of them,
+        // the write lock is held during both the validation and the update of the SNMP data
+        let result = {
+            let mut snmp_data = set_msg.request_context.snmp_data.write().unwrap();
+            set_request
+                .variables
+                .iter()
+                .enumerate()
+                .try_for_each(|(idx, variable)| {
+                    validate_set_variable(&snmp_data, variable, version).map_err(|error_status| {
+                        SnmpAgentCommandResponderError::ProtocolError(error_status, idx + 1, variable.name.clone())
+                    })
+                })
+                .map(|_| apply_set_variables(&mut snmp_data, &set_request.variables))
+        };
+
+        let response: GenericSnmpMessage = match result {
+            Ok(_) => {
+                let response = GetResponse {
+                    request_id: set_request.request_id,
+                    variable_values: set_request
+                        .variables
+                        .into_iter()
+                        .map(VariableBinding::from)
+                        .collect(),
+                };
+                (version, response).into()
+            }
+            Err(SnmpAgentCommandResponderError::ProtocolError(error_status, error_index, name)) => {
+                let response = GetResponseError {
+                    request_id: set_request.request_id,
+                    error_status,
+                    error_index,
+                    name: Some(name),
+                };
+                (version, response).into()
+            }
+            _ => {
+                let response = GetResponseError {
+                    request_id: set_request.request_id,
+                    error_status: ErrorStatus::GenErr,
+                    error_index: 0,
+                    name: None,
+                };
+                (version, response).into()
+            }
+        };
+
+        send_response(response, set_request.request_id, &set_msg.request_context);
+
+        Ok(())
+    }
+}
+
+pub(super) fn validate_set_variable(
+    snmp_data: &SnmpData,
+    variable: &Variable,
+    version: &SnmpProtocolVersion,
+) -> Result<(), ErrorStatus> {
+    let is_v1 = matches!(version, SnmpProtocolVersion::SNMPV1(_));
+    match snmp_data.get(&variable.name) {
+        // the simulated agent does not support the creation of new objects
+        None if is_v1 => Err(ErrorStatus::NoSuchName),
+        None => Err(ErrorStatus::NoCreation),
+        // counters are only ever incremented by the agent itself, the uptimes are computed by the agent
+        Some(item) if is_read_only(&variable.name, &item.data_type) && is_v1 => Err(ErrorStatus::ReadOnly),
+        Some(item) if is_read_only(&variable.name, &item.data_type) => Err(ErrorStatus::NotWritable),
+        Some(item) if base_syntax(&item.data_type) != base_syntax(&variable.data_type) && is_v1 => {
+            Err(ErrorStatus::BadValue)
+        }
+        Some(item) if base_syntax(&item.data_type) != base_syntax(&variable.data_type) => Err(ErrorStatus::WrongType),
+        _ => Ok(()),
+    }
+}
+
+pub(super) fn apply_set_variables(snmp_data: &mut SnmpData, variables: &[Variable]) {
+    for variable in variables {
+        if let Some(item) = snmp_data.get_mut(&variable.name) {
+            // the octet strings are stored either in a printable or hexadecimal form, the BITS as the octet string set
+            if matches!(
+                item.data_type,
+                DataType::String | DataType::HexString | DataType::OctetString | DataType::Bits
+            ) {
+                item.data_type = variable.data_type.clone();
+            }
+            item.data_value = variable.value.clone();
+            // the value set by the manager takes precedence over the value modifier
+            item.post_loaded_mod = None;
+        }
+    }
+}
+
+fn is_read_only(name: &ObjectIdentifier, data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Counter32 | DataType::Counter64)
+        || *name == SYS_UP_TIME_OID
+        || *name == SNMP_ENGINE_TIME_OID
+}
+
+// Maps the data type to the ASN.1 syntax used to encode the value on the wire
+fn base_syntax(data_type: &DataType) -> DataType {
+    match data_type {
+        DataType::String | DataType::HexString | DataType::OctetString | DataType::Bits => DataType::OctetString,
+        DataType::UInteger32 | DataType::Gauge32 => DataType::Gauge32,
+        DataType::NetworkAddress | DataType::IpAddress => DataType::IpAddress,
+        data_type => data_type.clone(),
+    }
+}
+
 // delegates
 #[tracing::instrument(level = "info", name = "handle_get_request")]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
@@ -203,6 +525,21 @@ pub(crate) async fn handle_get_request(
     request: GetRequest,
     request_context: AgentContext,
 ) -> Result<(), SnmpAgentCommandResponderError> {
+    request_context
+        .snmp_engine
+        .snmp_statistics
+        .increment(SnmpCounter::InGetRequests);
+
+    if let Some(proxy) = request_context.device.proxy.clone() {
+        forward_request(ForwardedRequest::Get(request), &proxy, request_context);
+        return Ok(());
+    }
+
+    if let Some(master) = agentx_master(&request_context.device.id) {
+        dispatch_request(DispatchedRequest::Get(request), master, request_context);
+        return Ok(());
+    }
+
     command_responder_actor()
         .try_send(Get {
             request,
@@ -217,6 +554,21 @@ pub(crate) async fn handle_get_next_request(
     request: GetNextRequest,
     request_context: AgentContext,
 ) -> Result<(), SnmpAgentCommandResponderError> {
+    request_context
+        .snmp_engine
+        .snmp_statistics
+        .increment(SnmpCounter::InGetNexts);
+
+    if let Some(proxy) = request_context.device.proxy.clone() {
+        forward_request(ForwardedRequest::GetNext(request), &proxy, request_context);
+        return Ok(());
+    }
+
+    if let Some(master) = agentx_master(&request_context.device.id) {
+        dispatch_request(DispatchedRequest::GetNext(request), master, request_context);
+        return Ok(());
+    }
+
     command_responder_actor()
         .try_send(GetNext {
             request,
@@ -225,6 +577,54 @@ pub(crate) async fn handle_get_next_request(
         .map_err(|err| SnmpAgentCommandResponderError::SendError(err.to_string()))
 }
 
+#[tracing::instrument(level = "info", name = "handle_get_bulk_request")]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn handle_get_bulk_request(
+    request: GetBulkRequest,
+    request_context: AgentContext,
+) -> Result<(), SnmpAgentCommandResponderError> {
+    if let Some(proxy) = request_context.device.proxy.clone() {
+        forward_request(ForwardedRequest::GetBulk(request), &proxy, request_context);
+        return Ok(());
+    }
+
+    if let Some(master) = agentx_master(&request_context.device.id) {
+        dispatch_request(DispatchedRequest::GetBulk(request), master, request_context);
+        return Ok(());
+    }
+
+    command_responder_actor()
+        .try_send(GetBulk {
+            request,
+            request_context,
+        })
+        .map_err(|err| SnmpAgentCommandResponderError::SendError(err.to_string()))
+}
+
+#[tracing::instrument(level = "info", name = "handle_set_request")]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) async fn handle_set_request(
+    request: SetRequest,
+    request_context: AgentContext,
+) -> Result<(), SnmpAgentCommandResponderError> {
+    request_context
+        .snmp_engine
+        .snmp_statistics
+        .increment(SnmpCounter::InSetRequests);
+
+    if let Some(master) = agentx_master(&request_context.device.id) {
+        dispatch_request(DispatchedRequest::Set(request), master, request_context);
+        return Ok(());
+    }
+
+    command_responder_actor()
+        .try_send(Set {
+            request,
+            request_context,
+        })
+        .map_err(|err| SnmpAgentCommandResponderError::SendError(err.to_string()))
+}
+
 #[cached]
 fn command_responder_actor() -> Addr<SnmpAgentCommandResponder> {
     SyncArbiter::start(num_cpus::get(), || SnmpAgentCommandResponder)
@@ -0,0 +1,107 @@
+use crate::domain::ManagedDevice;
+use crate::domain::SnmpEngine;
+use crate::domain::Variable;
+use crate::domain::{SNMP_ENGINE_TIME_OID, SYS_LOCATION_OID, SYS_NAME_OID, SYS_UP_TIME_OID};
+use crate::snmp::snmp_statistics::SnmpStatistics;
+
+use rasn::prelude::ObjectIdentifier;
+use snmp_data_parser::parser::snmp_data::component::{DataType, SnmpData, SnmpDataItem};
+use snmp_data_parser::parser::snmp_data::{ModifierContext, ModifierExtractor, VeraxModifierExtractor};
+use uuid_dev::Uuid;
+
+/// The unique integer of the device evaluating the value modifiers. It's derived from the device id,
+/// so the device gets the same integer across its restarts and the restarts of the simulator
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn device_unique_id(device_id: &Uuid) -> u64 {
+    let id = device_id.as_u128();
+    (id >> 64) as u64 ^ id as u64
+}
+
+/// Evaluates the pre-loaded value modifiers of the SNMP data loaded by the device. Each device is given its own unique
+/// integer, so f.e. `int.unq()` makes the hostname of each device unique
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn apply_pre_loaded_modifiers<Extractor: ModifierExtractor>(
+    snmp_data: &mut SnmpData,
+    extractor: &Extractor,
+    device: &ManagedDevice,
+) {
+    let context = ModifierContext::new(device_unique_id(&device.id));
+    snmp_data.apply_pre_loaded_modifiers(extractor, &context);
+}
+
+/// Replaces sysName.0 and sysLocation.0 of the SNMP data by the values configured on the device.
+/// The variables are added if the SNMP data doesn't contain them, they remain writable by the SetRequest.
+/// sysUpTime.0, snmpEngineTime.0 and the counter instances of the snmp group are added as well, their values
+/// are served from the device start
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn apply_device_system_group(snmp_data: &mut SnmpData, device: &ManagedDevice) {
+    for (name, data_type) in [
+        (SYS_UP_TIME_OID, DataType::Timeticks),
+        (SNMP_ENGINE_TIME_OID, DataType::Integer),
+    ] {
+        snmp_data.entry(name).or_insert_with(|| SnmpDataItem {
+            data_type,
+            data_value: "0".to_string(),
+            pre_loaded_mod: vec![],
+            post_loaded_mod: None,
+        });
+    }
+
+    for name in SnmpStatistics::instance_names() {
+        snmp_data.entry(name).or_insert_with(|| SnmpDataItem {
+            data_type: DataType::Counter32,
+            data_value: "0".to_string(),
+            pre_loaded_mod: vec![],
+            post_loaded_mod: None,
+        });
+    }
+
+    let configured = [
+        (SYS_NAME_OID, &device.sys_name),
+        (SYS_LOCATION_OID, &device.sys_location),
+    ];
+
+    for (name, value) in configured {
+        if let Some(value) = value {
+            snmp_data.insert(
+                name,
+                SnmpDataItem {
+                    data_type: DataType::String,
+                    data_value: value.clone(),
+                    pre_loaded_mod: vec![],
+                    post_loaded_mod: None,
+                },
+            );
+        }
+    }
+}
+
+/// The variable served for the SNMP data item. The values of sysUpTime.0 and snmpEngineTime.0 are replaced
+/// by the time elapsed since the device start, so the device is seen as running,
+/// the counters of the snmp group are replaced by the ones maintained by the device and the post-loaded
+/// modifier of the item is evaluated each time the variable is served
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn data_item_variable(
+    name: &ObjectIdentifier,
+    snmp_data_item: &SnmpDataItem,
+    snmp_engine: &SnmpEngine,
+) -> Variable {
+    let (data_type, value) = if *name == SYS_UP_TIME_OID {
+        (DataType::Timeticks, snmp_engine.sys_up_time().to_string())
+    } else if *name == SNMP_ENGINE_TIME_OID {
+        (DataType::Integer, snmp_engine.engine_time().to_string())
+    } else if let Some(value) = snmp_engine.snmp_statistics.instance_value(name) {
+        (DataType::Counter32, value.to_string())
+    } else if let Some(expression) = &snmp_data_item.post_loaded_mod {
+        let value = snmp_engine.post_loaded_value(&VeraxModifierExtractor {}, name, snmp_data_item, expression);
+        (snmp_data_item.data_type.clone(), value)
+    } else {
+        (snmp_data_item.data_type.clone(), snmp_data_item.data_value.clone())
+    };
+
+    Variable {
+        name: name.clone(),
+        data_type,
+        value,
+    }
+}
@@ -1,8 +1,36 @@
 mod agent_context;
+mod agentx_master;
+mod command_generator;
 mod command_responder;
+mod dynamic_variables;
+mod notification_originator;
+mod notification_receiver;
+mod proxy_forwarder;
+mod snmp_engine;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use self::command_responder::*;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) use self::agent_context::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::snmp_engine::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::notification_originator::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::notification_receiver::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::command_generator::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::proxy_forwarder::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::dynamic_variables::*;
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) use self::agentx_master::*;
@@ -0,0 +1,593 @@
+use crate::domain::ManagedDevice;
+use crate::domain::SnmpEngine;
+use crate::domain::SnmpProtocolAttributes;
+use crate::domain::SnmpProtocolVersion;
+use crate::domain::{GenericTrap, GetRequest, Inform, InformRequest, InformStatus, Notification, Trap};
+use crate::domain::{SnmpV3Attributes, SnmpV3Parameters, UsmUser, AUTH_FLAG, PRIV_FLAG};
+use crate::snmp::codec::generic_snmp_message::{GenericSnmpMessage, SnmpV3Datagram};
+use crate::snmp::codec::snmp_codec::SnmpCodec;
+use crate::snmp::snmp_statistics::SnmpCounter;
+use crate::snmp::usm::{decrypt_scoped_pdu, verify_message};
+use crate::udp_server::udp_stream_handler::{SendData, UdpStreamHandler};
+
+use actix_async::address::Addr;
+use bytes::Bytes;
+use lazy_static::lazy_static;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use uuid_dev::Uuid;
+
+// the request-id of the notifications sent by all the devices
+static NOTIFICATION_REQUEST_ID: AtomicI32 = AtomicI32::new(1);
+
+// the number of the acknowledged or failed InformRequests whose delivery status is kept per device
+const MAX_FINISHED_INFORMS: usize = 100;
+
+lazy_static! {
+    // the InformRequests sent by all the running devices, indexed by the request-id
+    static ref INFORM_REQUESTS: RwLock<HashMap<i32, PendingInform>> = RwLock::new(HashMap::new());
+}
+
+struct PendingInform {
+    device_id: Uuid,
+    inform: Inform,
+    // notified whenever a message of the target is processed, i.e. the acknowledgement or a Report
+    received: Arc<Notify>,
+    // the authoritative engine of the SNMPv3 InformRequest, known once it's discovered
+    receiver: Option<InformReceiver>,
+}
+
+// the receiver of the SNMPv3 InformRequest acts as the authoritative SNMP engine (RFC 3414, 4),
+// the device's user keys are localized to the receiver's snmpEngineID after the discovery
+struct InformReceiver {
+    engine_id: Vec<u8>,
+    engine_boots: u32,
+    engine_time: u32,
+    synchronized_at: Instant,
+    usm_user: Option<UsmUser>,
+}
+
+impl InformReceiver {
+    // the snmpEngineTime of the receiver estimated from the last synchronization
+    fn engine_time(&self) -> u32 {
+        self.engine_time
+            .saturating_add(self.synchronized_at.elapsed().as_secs() as u32)
+    }
+
+    fn synchronize(&mut self, security_parameters: &rasn_snmp::v3::USMSecurityParameters) {
+        self.engine_boots = security_parameters
+            .authoritative_engine_boots
+            .to_u32()
+            .unwrap_or_default();
+        self.engine_time = security_parameters
+            .authoritative_engine_time
+            .to_u32()
+            .unwrap_or_default();
+        self.synchronized_at = Instant::now();
+    }
+}
+
+// the security of the InformRequest, the community of the SNMPv2c device or the user of the SNMPv3 device
+enum InformSecurity {
+    Community(String),
+    Usm {
+        attributes: SnmpV3Attributes,
+        context_engine_id: Vec<u8>,
+    },
+}
+
+/// Sends the trap from the device's socket to the target address
+#[tracing::instrument(level = "info", name = "send_trap", skip(stream_handler_actor))]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn send_trap(
+    trap: Trap,
+    version: &SnmpProtocolVersion,
+    target: SocketAddr,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    stream_handler_actor.do_send(SendData {
+        message: (version, trap).into(),
+        peer: target,
+    });
+}
+
+/// Sends the authenticationFailure trap to the manager configured on the device
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn send_authentication_failure_trap(
+    device: &ManagedDevice,
+    snmp_engine: &SnmpEngine,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    let target = match device
+        .snmp_protocol_attributes
+        .authentication_failure_trap()
+    {
+        Some(target) => target,
+        None => return,
+    };
+    let target: SocketAddr = match target.parse() {
+        Ok(target) => target,
+        Err(error) => {
+            tracing::warn!("Invalid authenticationFailure trap target {target}: {error}");
+            return;
+        }
+    };
+
+    let version = match notification_version(device, snmp_engine) {
+        Some(version) => version,
+        None => return,
+    };
+
+    let trap = Trap {
+        request_id: next_request_id(),
+        sys_up_time: snmp_engine.sys_up_time(),
+        trap_oid: GenericTrap::AuthenticationFailure.oid(),
+        agent_address: agent_address(device),
+        variables: vec![],
+    };
+    snmp_engine.snmp_statistics.increment(SnmpCounter::OutTraps);
+    send_trap(trap, &version, target, stream_handler_actor);
+}
+
+/// Sends the notification requested by the user to all its targets
+#[tracing::instrument(
+    level = "info",
+    name = "send_notification",
+    skip(device, snmp_engine, stream_handler_actor)
+)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn send_notification(
+    notification: Notification,
+    device: &ManagedDevice,
+    snmp_engine: &SnmpEngine,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    let version = match notification_version(device, snmp_engine) {
+        Some(version) => version,
+        None => {
+            tracing::warn!("SNMPv3 is not configured on the device, the notification is not sent");
+            return;
+        }
+    };
+
+    let trap = Trap {
+        request_id: notification.request_id,
+        sys_up_time: snmp_engine.sys_up_time(),
+        trap_oid: notification.trap_oid,
+        agent_address: agent_address(device),
+        variables: notification.variables,
+    };
+    for target in notification.targets {
+        snmp_engine.snmp_statistics.increment(SnmpCounter::OutTraps);
+        send_trap(trap.clone(), &version, target, stream_handler_actor);
+    }
+}
+
+/// Sends the InformRequest to its target and retransmits it after each timeout, until it's
+/// acknowledged or the retries are exhausted, the delivery status of the last finished InformRequests is kept,
+/// the SNMPv3 InformRequest is preceded by the discovery of the receiver's snmpEngineID
+#[tracing::instrument(
+    level = "info",
+    name = "send_inform",
+    skip(device, snmp_engine, stream_handler_actor)
+)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn send_inform(
+    inform: Inform,
+    device: &ManagedDevice,
+    snmp_engine: &SnmpEngine,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    let security = match &device.snmp_protocol_attributes {
+        SnmpProtocolAttributes::SnmpV2c(attributes) => InformSecurity::Community(attributes.community.clone()),
+        SnmpProtocolAttributes::SnmpV3(attributes) => InformSecurity::Usm {
+            attributes: attributes.clone(),
+            context_engine_id: snmp_engine.engine_id.clone(),
+        },
+        SnmpProtocolAttributes::SnmpV1(_) => {
+            tracing::warn!("The InformRequests are not supported by the SNMPv1 devices");
+            return;
+        }
+    };
+
+    let inform_request = InformRequest {
+        request_id: inform.request_id,
+        sys_up_time: snmp_engine.sys_up_time(),
+        trap_oid: inform.trap_oid.clone(),
+        variables: inform.variables.clone(),
+    };
+    let received = Arc::new(Notify::new());
+    let (request_id, target, timeout, retries) = (inform.request_id, inform.target, inform.timeout, inform.retries);
+    evict_finished_informs(device.id);
+    INFORM_REQUESTS.write().unwrap().insert(
+        request_id,
+        PendingInform {
+            device_id: device.id,
+            inform,
+            received: received.clone(),
+            receiver: None,
+        },
+    );
+
+    let stream_handler_actor = stream_handler_actor.clone();
+    actix::spawn(async move {
+        if let InformSecurity::Usm { attributes, .. } = &security {
+            let discovered = discover_receiver(
+                request_id,
+                attributes,
+                timeout,
+                retries,
+                &received,
+                &stream_handler_actor,
+            )
+            .await;
+            if !discovered {
+                fail_inform(request_id, "the snmpEngineID of the receiver was not discovered");
+                return;
+            }
+        }
+
+        // the retransmissions are sent with the same request-id, any of them can be acknowledged
+        for _ in 0..=retries {
+            let message = match INFORM_REQUESTS.write().unwrap().get_mut(&request_id) {
+                Some(pending) if pending.inform.status == InformStatus::Pending => {
+                    pending.inform.attempts += 1;
+                    inform_message(&security, pending.receiver.as_ref(), inform_request.clone())
+                }
+                _ => return,
+            };
+            stream_handler_actor.do_send(SendData { message, peer: target });
+
+            // the acknowledgement ends the retransmissions, a Report of the receiver speeds them up
+            let _ = tokio::time::timeout(timeout, received.notified()).await;
+        }
+
+        fail_inform(request_id, "it was not acknowledged");
+    });
+}
+
+/// Processes the message received from the target of an SNMPv3 InformRequest, i.e. the Report
+/// of the engine discovery, the Report of the time synchronization or the acknowledgement.
+/// The message is correlated by its msgID, returns false if it's not related to any InformRequest
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn receive_inform_message(device_id: Uuid, message: &mut SnmpV3Datagram, peer: SocketAddr) -> bool {
+    let request_id = match message.global_data.message_id.to_i32() {
+        Some(request_id) => request_id,
+        None => return false,
+    };
+    let mut informs = INFORM_REQUESTS.write().unwrap();
+    let pending = match informs.get_mut(&request_id) {
+        Some(pending) if pending.device_id == device_id && is_inform_target(&pending.inform, peer) => pending,
+        _ => return false,
+    };
+    if pending.inform.status != InformStatus::Pending {
+        return true;
+    }
+
+    let receiver = match &mut pending.receiver {
+        Some(receiver) => receiver,
+        None => {
+            // the Report of the discovery request carries the receiver's snmpEngineID, snmpEngineBoots and snmpEngineTime
+            let security_parameters = &message.security_parameters;
+            if is_report(message) && !security_parameters.authoritative_engine_id.is_empty() {
+                let mut receiver = InformReceiver {
+                    engine_id: security_parameters.authoritative_engine_id.to_vec(),
+                    engine_boots: 0,
+                    engine_time: 0,
+                    synchronized_at: Instant::now(),
+                    usm_user: None,
+                };
+                receiver.synchronize(security_parameters);
+                pending.receiver = Some(receiver);
+                pending.received.notify_one();
+            }
+            return true;
+        }
+    };
+    let usm_user = match &receiver.usm_user {
+        Some(usm_user)
+            if receiver.engine_id.as_slice() == message.security_parameters.authoritative_engine_id.as_ref() =>
+        {
+            usm_user
+        }
+        _ => return true,
+    };
+
+    // the messages are authenticated and decrypted by the keys localized to the receiver
+    let flags = message
+        .global_data
+        .flags
+        .first()
+        .copied()
+        .unwrap_or_default();
+    if let Some(authentication_key) = &usm_user.authentication_key {
+        if flags & AUTH_FLAG == 0 || !verify_message(message, authentication_key) {
+            tracing::debug!("The message of the InformRequest {request_id} receiver is not authenticated");
+            return true;
+        }
+    }
+    if flags & PRIV_FLAG != 0 {
+        let decrypted = match &usm_user.privacy_key {
+            Some(privacy_key) => decrypt_scoped_pdu(message, privacy_key),
+            None => false,
+        };
+        if !decrypted {
+            tracing::debug!("The message of the InformRequest {request_id} receiver is not decrypted");
+            return true;
+        }
+    }
+
+    match &message.scoped_data {
+        rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) => match &scoped_pdu.data {
+            rasn_snmp::v2::Pdus::Response(response) if response.0.request_id == request_id => {
+                pending.inform.status = InformStatus::Acknowledged;
+                pending.received.notify_one();
+            }
+            // f.e. the notInTimeWindows Report, the InformRequest is retransmitted with the receiver's time
+            rasn_snmp::v2::Pdus::Report(report) => {
+                tracing::debug!(
+                    "The InformRequest {request_id} is reported by {:?}",
+                    report
+                        .0
+                        .variable_bindings
+                        .first()
+                        .map(|variable| &variable.name)
+                );
+                receiver.synchronize(&message.security_parameters);
+                pending.received.notify_one();
+            }
+            _ => {}
+        },
+        rasn_snmp::v3::ScopedPduData::EncryptedPdu(_) => {}
+    }
+    true
+}
+
+/// Marks the InformRequest as acknowledged by the Response-PDU received from its target
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn acknowledge_inform(device_id: Uuid, request_id: i32, peer: SocketAddr) {
+    if let Some(pending) = INFORM_REQUESTS.write().unwrap().get_mut(&request_id) {
+        if pending.device_id == device_id
+            && is_inform_target(&pending.inform, peer)
+            && pending.inform.status == InformStatus::Pending
+        {
+            pending.inform.status = InformStatus::Acknowledged;
+            pending.received.notify_one();
+        }
+    }
+}
+
+/// Returns the InformRequest sent by the device, including its delivery status
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn get_inform(device_id: Uuid, request_id: i32) -> Option<Inform> {
+    INFORM_REQUESTS
+        .read()
+        .unwrap()
+        .get(&request_id)
+        .filter(|pending| pending.device_id == device_id)
+        .map(|pending| pending.inform.clone())
+}
+
+/// Removes the InformRequests of the stopped device
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn remove_informs(device_id: Uuid) {
+    INFORM_REQUESTS
+        .write()
+        .unwrap()
+        .retain(|_, pending| pending.device_id != device_id);
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn next_request_id() -> i32 {
+    NOTIFICATION_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+// the message of the IPv4 target is received by the device bound to an IPv6 address from the IPv4-mapped address
+fn is_inform_target(inform: &Inform, peer: SocketAddr) -> bool {
+    let canonical = |address: SocketAddr| match address.ip() {
+        IpAddr::V6(ip) => match ip.octets() {
+            [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => {
+                SocketAddr::new(Ipv4Addr::new(a, b, c, d).into(), address.port())
+            }
+            _ => address,
+        },
+        IpAddr::V4(_) => address,
+    };
+    canonical(inform.target) == canonical(peer)
+}
+
+// the agent-addr of the SNMPv1 Trap-PDU, unspecified if the device is not bound to an IPv4 address
+fn agent_address(device: &ManagedDevice) -> Ipv4Addr {
+    device.snmp_host.parse().unwrap_or(Ipv4Addr::UNSPECIFIED)
+}
+
+// the notifications are sent with the read-only community and the protocol version of the device,
+// the SNMPv3 device is the authoritative engine of the traps and uses its own localized keys
+fn notification_version(device: &ManagedDevice, snmp_engine: &SnmpEngine) -> Option<SnmpProtocolVersion> {
+    match &device.snmp_protocol_attributes {
+        SnmpProtocolAttributes::SnmpV1(attributes) => Some(SnmpProtocolVersion::SNMPV1(attributes.community.clone())),
+        SnmpProtocolAttributes::SnmpV2c(attributes) => Some(SnmpProtocolVersion::SNMPV2C(attributes.community.clone())),
+        SnmpProtocolAttributes::SnmpV3(_) => snmp_engine.usm_user.as_ref().map(|usm_user| {
+            SnmpProtocolVersion::SNMPV3(SnmpV3Parameters {
+                message_id: next_request_id(),
+                max_size: SnmpCodec::MAX_MESSAGE_SIZE as i32,
+                engine_id: snmp_engine.engine_id.clone(),
+                engine_boots: snmp_engine.engine_boots,
+                engine_time: snmp_engine.engine_time(),
+                user_name: usm_user.name.as_bytes().to_vec(),
+                context_engine_id: snmp_engine.engine_id.clone(),
+                context_name: vec![],
+                authentication_key: usm_user.authentication_key.clone(),
+                privacy_key: usm_user.privacy_key.clone(),
+            })
+        }),
+    }
+}
+
+// Discovers the snmpEngineID of the SNMPv3 InformRequest receiver by the unauthenticated request
+// with an empty msgAuthoritativeEngineID (RFC 3414, 4), the discovery request is retransmitted
+// the same way as the InformRequest, the user keys are localized to the discovered engine
+async fn discover_receiver(
+    request_id: i32,
+    attributes: &SnmpV3Attributes,
+    timeout: Duration,
+    retries: u32,
+    received: &Notify,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) -> bool {
+    for _ in 0..=retries {
+        let target = match INFORM_REQUESTS.read().unwrap().get(&request_id) {
+            Some(pending) if pending.inform.status == InformStatus::Pending && pending.receiver.is_none() => {
+                pending.inform.target
+            }
+            _ => break,
+        };
+        stream_handler_actor.do_send(SendData {
+            message: discovery_message(request_id),
+            peer: target,
+        });
+        let _ = tokio::time::timeout(timeout, received.notified()).await;
+    }
+
+    let engine_id = match INFORM_REQUESTS.read().unwrap().get(&request_id) {
+        Some(pending) => pending
+            .receiver
+            .as_ref()
+            .map(|receiver| receiver.engine_id.clone()),
+        None => None,
+    };
+    let engine_id = match engine_id {
+        Some(engine_id) => engine_id,
+        None => return false,
+    };
+
+    // the password to key algorithm is expensive, the registry is not locked meanwhile
+    let usm_user = UsmUser::new(attributes, &engine_id);
+    match INFORM_REQUESTS
+        .write()
+        .unwrap()
+        .get_mut(&request_id)
+        .and_then(|pending| pending.receiver.as_mut())
+    {
+        Some(receiver) => {
+            receiver.usm_user = Some(usm_user);
+            true
+        }
+        None => false,
+    }
+}
+
+// removes the oldest acknowledged or failed InformRequests of the device exceeding MAX_FINISHED_INFORMS,
+// the request-ids are assigned in the ascending order
+fn evict_finished_informs(device_id: Uuid) {
+    let mut informs = INFORM_REQUESTS.write().unwrap();
+    let mut finished: Vec<i32> = informs
+        .iter()
+        .filter(|(_, pending)| pending.device_id == device_id && pending.inform.status != InformStatus::Pending)
+        .map(|(request_id, _)| *request_id)
+        .collect();
+    if finished.len() < MAX_FINISHED_INFORMS {
+        return;
+    }
+
+    finished.sort_unstable();
+    for request_id in &finished[..=finished.len() - MAX_FINISHED_INFORMS] {
+        informs.remove(request_id);
+    }
+}
+
+// marks the InformRequest as failed, unless it has been acknowledged meanwhile
+fn fail_inform(request_id: i32, reason: &str) {
+    if let Some(pending) = INFORM_REQUESTS.write().unwrap().get_mut(&request_id) {
+        if pending.inform.status == InformStatus::Pending {
+            tracing::warn!(
+                "The InformRequest {request_id} sent to {} failed, {reason}",
+                pending.inform.target
+            );
+            pending.inform.status = InformStatus::Failed;
+        }
+    }
+}
+
+// the InformRequest-PDU is not defined by SNMPv1, it's sent within the SNMPv2c or SNMPv3 message,
+// the msgID of the SNMPv3 message is the request-id, so the messages of the receiver are correlated by it
+fn inform_message(
+    security: &InformSecurity,
+    receiver: Option<&InformReceiver>,
+    inform_request: InformRequest,
+) -> GenericSnmpMessage {
+    match (security, receiver) {
+        (InformSecurity::Community(community), _) => GenericSnmpMessage::V2Message(rasn_snmp::v2c::Message {
+            version: SnmpCodec::SNMP_VERSION2.into(),
+            community: Bytes::from(community.as_bytes().to_vec()),
+            data: inform_request.into(),
+        }),
+        (
+            InformSecurity::Usm {
+                attributes,
+                context_engine_id,
+            },
+            receiver,
+        ) => {
+            let usm_user = receiver.and_then(|receiver| receiver.usm_user.as_ref());
+            let parameters = SnmpV3Parameters {
+                message_id: inform_request.request_id,
+                max_size: SnmpCodec::MAX_MESSAGE_SIZE as i32,
+                engine_id: receiver
+                    .map(|receiver| receiver.engine_id.clone())
+                    .unwrap_or_default(),
+                engine_boots: receiver
+                    .map(|receiver| receiver.engine_boots)
+                    .unwrap_or_default(),
+                engine_time: receiver
+                    .map(|receiver| receiver.engine_time())
+                    .unwrap_or_default(),
+                user_name: attributes.user.as_bytes().to_vec(),
+                context_engine_id: context_engine_id.clone(),
+                context_name: vec![],
+                authentication_key: usm_user.and_then(|usm_user| usm_user.authentication_key.clone()),
+                privacy_key: usm_user.and_then(|usm_user| usm_user.privacy_key.clone()),
+            };
+            GenericSnmpMessage::V3Message(Box::new(parameters.to_message(inform_request.into()).into()))
+        }
+    }
+}
+
+// the discovery request is the GetRequest without any variable bindings and security (RFC 3414, 4)
+fn discovery_message(request_id: i32) -> GenericSnmpMessage {
+    let parameters = SnmpV3Parameters {
+        message_id: request_id,
+        max_size: SnmpCodec::MAX_MESSAGE_SIZE as i32,
+        engine_id: vec![],
+        engine_boots: 0,
+        engine_time: 0,
+        user_name: vec![],
+        context_engine_id: vec![],
+        context_name: vec![],
+        authentication_key: None,
+        privacy_key: None,
+    };
+    (
+        &SnmpProtocolVersion::SNMPV3(parameters),
+        GetRequest {
+            request_id,
+            objects: vec![],
+        },
+    )
+        .into()
+}
+
+fn is_report(message: &rasn_snmp::v3::Message) -> bool {
+    matches!(
+        &message.scoped_data,
+        rasn_snmp::v3::ScopedPduData::CleartextPdu(rasn_snmp::v3::ScopedPdu {
+            data: rasn_snmp::v2::Pdus::Report(_),
+            ..
+        })
+    )
+}
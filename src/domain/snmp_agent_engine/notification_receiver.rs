@@ -0,0 +1,407 @@
+use crate::domain::{InformRequest, Report, Trap, Variable};
+use crate::domain::{NotificationPduType, NotificationVersion, ReceivedNotification};
+use crate::domain::{SnmpV3Attributes, SnmpV3Parameters, UsmUser, ENGINE_ID_PREFIX};
+use crate::domain::{AUTH_FLAG, PRIV_FLAG, REPORTABLE_FLAG, USM_SECURITY_MODEL};
+use crate::snmp::codec::generic_snmp_message::{GenericSnmpMessage, SnmpV3Datagram};
+use crate::snmp::codec::snmp_codec::SnmpCodec;
+use crate::snmp::usm::{decrypt_scoped_pdu, verify_message, UsmReport, UsmStatistics};
+
+use chrono::Utc;
+use num_traits::ToPrimitive;
+use snmp_data_parser::parser::snmp_data::component::DataType;
+use std::collections::HashMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::Instant;
+use uuid_dev::Uuid;
+
+// the time window of the authenticated messages in seconds (RFC 3414, 2.2.3)
+const TIME_WINDOW: i64 = 150;
+
+/// The notification receiver application (RFC 3413, 3.4) of the built-in trap receiver. It accepts
+/// the SNMPv1/SNMPv2c/SNMPv3 traps and InformRequests, the InformRequests are acknowledged by the Response-PDU.
+/// The receiver is the authoritative SNMP engine of the SNMPv3 InformRequests, the senders of the SNMPv3 traps
+/// are the authoritative engines of their traps (RFC 3414, 1.5.1)
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct NotificationReceiver {
+    pub engine_id: Vec<u8>,
+    pub engine_boots: u32,
+    pub usm_statistics: UsmStatistics,
+    communities: Vec<String>,
+    users: Vec<SnmpV3Attributes>,
+    // the users' keys localized to the authoritative snmpEngineIDs, the password to key algorithm is expensive
+    usm_users: Mutex<HashMap<(Vec<u8>, String), UsmUser>>,
+    started_at: Instant,
+}
+
+impl fmt::Debug for NotificationReceiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "engine_id={:02X?}, engine_boots={}, engine_time={}",
+            self.engine_id,
+            self.engine_boots,
+            self.engine_time()
+        )
+    }
+}
+
+impl NotificationReceiver {
+    /// The notifications of any community are accepted if no community is configured
+    pub fn new(communities: Vec<String>, users: Vec<SnmpV3Attributes>) -> Self {
+        NotificationReceiver {
+            engine_id: [&ENGINE_ID_PREFIX[..], Uuid::new_v4().as_bytes()].concat(),
+            engine_boots: 1,
+            usm_statistics: UsmStatistics::default(),
+            communities,
+            users,
+            usm_users: Mutex::new(HashMap::new()),
+            started_at: Instant::now(),
+        }
+    }
+
+    /// snmpEngineTime, the number of seconds since the receiver was started
+    pub fn engine_time(&self) -> u32 {
+        self.started_at.elapsed().as_secs().min(i32::MAX as u64) as u32
+    }
+
+    /// Processes the message received from the peer, returns the received notification, if any,
+    /// and the message sent back to the peer, i.e. the acknowledgement of the InformRequest or a Report
+    pub fn receive_message(
+        &self,
+        message: GenericSnmpMessage,
+        peer: SocketAddr,
+    ) -> (Option<ReceivedNotification>, Option<GenericSnmpMessage>) {
+        match message {
+            GenericSnmpMessage::V1Message(message) => (self.receive_v1_message(message, peer), None),
+            GenericSnmpMessage::V2Message(message) => self.receive_v2_message(message, peer),
+            GenericSnmpMessage::V3Message(message) => self.receive_v3_message(*message, peer),
+        }
+    }
+
+    fn receive_v1_message(
+        &self,
+        message: rasn_snmp::v1::Message<rasn_snmp::v1::Pdus>,
+        peer: SocketAddr,
+    ) -> Option<ReceivedNotification> {
+        if !self.is_known_community(&message.community) {
+            tracing::debug!("Unknown community of the SNMPv1 message, the message is dropped");
+            return None;
+        }
+
+        match message.data {
+            rasn_snmp::v1::Pdus::Trap(trap) => Trap::try_from(trap)
+                .map_err(|error| tracing::debug!("Invalid SNMPv1 trap, the message is dropped: {error}"))
+                .ok()
+                .map(|trap| trap_notification(peer, NotificationVersion::SnmpV1, &message.community, trap)),
+            _ => {
+                tracing::debug!("Not a notification, the SNMPv1 message is dropped");
+                None
+            }
+        }
+    }
+
+    fn receive_v2_message(
+        &self,
+        message: rasn_snmp::v2c::Message<rasn_snmp::v2::Pdus>,
+        peer: SocketAddr,
+    ) -> (Option<ReceivedNotification>, Option<GenericSnmpMessage>) {
+        if !self.is_known_community(&message.community) {
+            tracing::debug!("Unknown community of the SNMPv2c message, the message is dropped");
+            return (None, None);
+        }
+
+        match message.data {
+            rasn_snmp::v2::Pdus::Trap(trap) => {
+                let notification = Trap::try_from(trap)
+                    .map_err(|error| tracing::debug!("Invalid SNMPv2 trap, the message is dropped: {error}"))
+                    .ok()
+                    .map(|trap| trap_notification(peer, NotificationVersion::SnmpV2c, &message.community, trap));
+                (notification, None)
+            }
+            rasn_snmp::v2::Pdus::InformRequest(inform) => {
+                // the Response-PDU carries the same request-id and variable bindings (RFC 3416, 4.2.7)
+                let response = rasn_snmp::v2::Pdus::Response(rasn_snmp::v2::Response(inform.0.clone()));
+                match InformRequest::try_from(inform) {
+                    Ok(inform) => (
+                        Some(inform_notification(
+                            peer,
+                            NotificationVersion::SnmpV2c,
+                            &message.community,
+                            inform,
+                        )),
+                        Some(GenericSnmpMessage::V2Message(rasn_snmp::v2c::Message {
+                            version: message.version,
+                            community: message.community,
+                            data: response,
+                        })),
+                    ),
+                    Err(error) => {
+                        tracing::debug!("Invalid SNMPv2 InformRequest, the message is dropped: {error}");
+                        (None, None)
+                    }
+                }
+            }
+            _ => {
+                tracing::debug!("Not a notification, the SNMPv2c message is dropped");
+                (None, None)
+            }
+        }
+    }
+
+    fn receive_v3_message(
+        &self,
+        mut message: SnmpV3Datagram,
+        peer: SocketAddr,
+    ) -> (Option<ReceivedNotification>, Option<GenericSnmpMessage>) {
+        let flags = message
+            .global_data
+            .flags
+            .first()
+            .copied()
+            .unwrap_or_default();
+        if message.global_data.security_model.to_u32() != Some(USM_SECURITY_MODEL)
+            || (flags & PRIV_FLAG != 0 && flags & AUTH_FLAG == 0)
+        {
+            tracing::debug!("Unsupported security model or invalid msgFlags, the message is dropped");
+            return (None, None);
+        }
+
+        let message_id = match message.global_data.message_id.to_i32() {
+            Some(message_id) => message_id,
+            None => return (None, None),
+        };
+        let user_name = message.security_parameters.user_name.to_vec();
+        let mut parameters = SnmpV3Parameters {
+            message_id,
+            max_size: SnmpCodec::MAX_MESSAGE_SIZE as i32,
+            engine_id: self.engine_id.clone(),
+            engine_boots: self.engine_boots,
+            engine_time: self.engine_time(),
+            user_name: user_name.clone(),
+            context_engine_id: self.engine_id.clone(),
+            context_name: vec![],
+            authentication_key: None,
+            privacy_key: None,
+        };
+
+        let usm_user = match self.process_incoming_message(&mut message, flags) {
+            Ok(usm_user) => usm_user,
+            Err(report) => {
+                let counter = self.usm_statistics.increment(report);
+                tracing::debug!("USM processing of the message failed: {report}");
+
+                // the unconfirmed notifications are never reported (RFC 3412, 7.1)
+                if flags & REPORTABLE_FLAG == 0 {
+                    return (None, None);
+                }
+                // the report is authenticated only in case of notInTimeWindows (RFC 3414, 3.2 step 7)
+                if report == UsmReport::NotInTimeWindows {
+                    parameters.authentication_key = self
+                        .usm_user(&self.engine_id, &user_name)
+                        .and_then(|user| user.authentication_key);
+                }
+                let request_id = match &message.scoped_data {
+                    rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) => request_id(&scoped_pdu.data),
+                    rasn_snmp::v3::ScopedPduData::EncryptedPdu(_) => 0,
+                };
+                let report = Report {
+                    request_id,
+                    variables: vec![Variable {
+                        name: report.oid(),
+                        data_type: DataType::Counter32,
+                        value: counter.to_string(),
+                    }],
+                };
+                return (
+                    None,
+                    Some(GenericSnmpMessage::V3Message(Box::new(
+                        parameters.to_message(report.into()).into(),
+                    ))),
+                );
+            }
+        };
+
+        let scoped_pdu = match message.message.scoped_data {
+            rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) => scoped_pdu,
+            rasn_snmp::v3::ScopedPduData::EncryptedPdu(_) => {
+                tracing::debug!("Encrypted scopedPDU without privacy, the message is dropped");
+                return (None, None);
+            }
+        };
+
+        match scoped_pdu.data {
+            rasn_snmp::v2::Pdus::Trap(trap) => {
+                let notification = Trap::try_from(trap)
+                    .map_err(|error| tracing::debug!("Invalid SNMPv2 trap, the message is dropped: {error}"))
+                    .ok()
+                    .map(|trap| trap_notification(peer, NotificationVersion::SnmpV3, &user_name, trap));
+                (notification, None)
+            }
+            rasn_snmp::v2::Pdus::InformRequest(inform) => {
+                // the response is sent with the same security level and context as the InformRequest
+                let response = rasn_snmp::v2::Pdus::Response(rasn_snmp::v2::Response(inform.0.clone()));
+                parameters.authentication_key = usm_user.authentication_key;
+                parameters.privacy_key = usm_user.privacy_key;
+                parameters.context_engine_id = scoped_pdu.engine_id.to_vec();
+                parameters.context_name = scoped_pdu.name.to_vec();
+                match InformRequest::try_from(inform) {
+                    Ok(inform) => (
+                        Some(inform_notification(
+                            peer,
+                            NotificationVersion::SnmpV3,
+                            &user_name,
+                            inform,
+                        )),
+                        Some(GenericSnmpMessage::V3Message(Box::new(
+                            parameters.to_message(response).into(),
+                        ))),
+                    ),
+                    Err(error) => {
+                        tracing::debug!("Invalid SNMPv2 InformRequest, the message is dropped: {error}");
+                        (None, None)
+                    }
+                }
+            }
+            _ => {
+                tracing::debug!("Not a notification, the SNMPv3 message is dropped");
+                (None, None)
+            }
+        }
+    }
+
+    /// Processing of the incoming message by the User-based Security Model (RFC 3414, 3.2),
+    /// returns the user whose keys are used by the message, the encrypted scopedPDU is replaced
+    /// by the decrypted one
+    fn process_incoming_message(&self, message: &mut SnmpV3Datagram, flags: u8) -> Result<UsmUser, UsmReport> {
+        let security_parameters = &message.security_parameters;
+        let confirmed = flags & REPORTABLE_FLAG != 0;
+
+        // the InformRequest has to be sent to the receiver's snmpEngineID, the engine discovery
+        // request is sent with an empty msgAuthoritativeEngineID
+        if confirmed && security_parameters.authoritative_engine_id.as_ref() != self.engine_id.as_slice() {
+            return Err(UsmReport::UnknownEngineIds);
+        }
+
+        let user = self
+            .usm_user(
+                &security_parameters.authoritative_engine_id,
+                &security_parameters.user_name,
+            )
+            .ok_or(UsmReport::UnknownUserNames)?;
+
+        // the security level of the notification has to match the level configured for the user
+        let (authentication_key, privacy_key) = match (
+            &user.authentication_key,
+            &user.privacy_key,
+            flags & AUTH_FLAG != 0,
+            flags & PRIV_FLAG != 0,
+        ) {
+            (None, None, false, false) => return Ok(user),
+            (Some(authentication_key), None, true, false) => (authentication_key, None),
+            (Some(authentication_key), Some(privacy_key), true, true) => (authentication_key, Some(privacy_key)),
+            _ => return Err(UsmReport::UnsupportedSecLevels),
+        };
+
+        if !verify_message(message, authentication_key) {
+            return Err(UsmReport::WrongDigests);
+        }
+
+        // the timeliness is checked only by the authoritative engine, i.e. for the InformRequests
+        if confirmed {
+            let engine_boots = security_parameters.authoritative_engine_boots.to_i64();
+            let engine_time = security_parameters.authoritative_engine_time.to_i64();
+            let in_time_window = match (engine_boots, engine_time) {
+                (Some(engine_boots), Some(engine_time)) => {
+                    engine_boots == self.engine_boots as i64
+                        && (engine_time - self.engine_time() as i64).abs() <= TIME_WINDOW
+                }
+                _ => false,
+            };
+            if !in_time_window {
+                return Err(UsmReport::NotInTimeWindows);
+            }
+        }
+
+        if let Some(privacy_key) = privacy_key {
+            if !decrypt_scoped_pdu(message, privacy_key) {
+                return Err(UsmReport::DecryptionErrors);
+            }
+        }
+
+        Ok(user)
+    }
+
+    // the user's keys localized to the authoritative snmpEngineID of the message
+    fn usm_user(&self, engine_id: &[u8], user_name: &[u8]) -> Option<UsmUser> {
+        let attributes = self
+            .users
+            .iter()
+            .find(|attributes| attributes.user.as_bytes() == user_name)?;
+        let mut usm_users = self.usm_users.lock().unwrap();
+        let usm_user = usm_users
+            .entry((engine_id.to_vec(), attributes.user.clone()))
+            .or_insert_with(|| UsmUser::new(attributes, engine_id));
+        Some(usm_user.clone())
+    }
+
+    fn is_known_community(&self, community: &[u8]) -> bool {
+        self.communities.is_empty()
+            || self
+                .communities
+                .iter()
+                .any(|known_community| known_community.as_bytes() == community)
+    }
+}
+
+fn trap_notification(
+    peer: SocketAddr,
+    snmp_version: NotificationVersion,
+    security_name: &[u8],
+    trap: Trap,
+) -> ReceivedNotification {
+    ReceivedNotification {
+        id: Uuid::new_v4(),
+        received_at: Utc::now(),
+        source: peer,
+        snmp_version,
+        pdu_type: NotificationPduType::Trap,
+        security_name: String::from_utf8_lossy(security_name).to_string(),
+        request_id: trap.request_id,
+        trap_oid: trap.trap_oid,
+        variables: trap.variables,
+    }
+}
+
+fn inform_notification(
+    peer: SocketAddr,
+    snmp_version: NotificationVersion,
+    security_name: &[u8],
+    inform: InformRequest,
+) -> ReceivedNotification {
+    ReceivedNotification {
+        id: Uuid::new_v4(),
+        received_at: Utc::now(),
+        source: peer,
+        snmp_version,
+        pdu_type: NotificationPduType::InformRequest,
+        security_name: String::from_utf8_lossy(security_name).to_string(),
+        request_id: inform.request_id,
+        trap_oid: inform.trap_oid,
+        variables: inform.variables,
+    }
+}
+
+fn request_id(pdus: &rasn_snmp::v2::Pdus) -> i32 {
+    match pdus {
+        rasn_snmp::v2::Pdus::GetRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::GetNextRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::GetBulkRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::SetRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::InformRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::Response(response) => response.0.request_id,
+        rasn_snmp::v2::Pdus::Trap(trap) => trap.0.request_id,
+        rasn_snmp::v2::Pdus::Report(report) => report.0.request_id,
+    }
+}
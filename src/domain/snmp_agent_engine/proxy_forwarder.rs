@@ -0,0 +1,469 @@
+use super::command_responder::{encoded_var_bind_len, fit_to_message_size, missing_variable_exception, send_response};
+use crate::domain::data_item_variable;
+use crate::domain::AgentContext;
+use crate::domain::ProxyAttributes;
+use crate::domain::SnmpProtocolVersion;
+use crate::domain::{AgentResponse, CommandGenerator};
+use crate::domain::{
+    ErrorStatus, GetBulkRequest, GetNextRequest, GetRequest, GetResponse, GetResponseError, VarBindException, Variable,
+    VariableBinding,
+};
+use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
+use crate::snmp::snmp_statistics::SnmpCounter;
+use crate::udp_client::ClientError;
+
+use lazy_static::lazy_static;
+use rasn::prelude::ObjectIdentifier;
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, SnmpData};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound::{Excluded, Unbounded};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use uuid_dev::Uuid;
+
+// the upstream agent shared by the requests of the device
+type SharedUpstreamAgent = Arc<tokio::sync::Mutex<UpstreamAgent>>;
+
+lazy_static! {
+    // the upstream agents of the proxy devices and their configuration, indexed by the device id
+    static ref UPSTREAM_AGENTS: Mutex<HashMap<Uuid, (ProxyAttributes, SharedUpstreamAgent)>> =
+        Mutex::new(HashMap::new());
+}
+
+// The upstream agent of a proxy device, the requests forwarded by the device are processed one by one.
+// The values received from the upstream agent are kept for the processing of the whole request,
+// and for the cache TTL if configured
+struct UpstreamAgent {
+    proxy: ProxyAttributes,
+    subtrees: Vec<ObjectIdentifier>,
+    // connected by the first forwarded request, and once again after a failed one
+    command_generator: Option<CommandGenerator>,
+    values: HashMap<ObjectIdentifier, (VariableBinding, Instant)>,
+    // the successor of each key in the MIB view of the upstream agent, i.e. there is no variable between them
+    successors: BTreeMap<ObjectIdentifier, (VariableBinding, Instant)>,
+}
+
+impl UpstreamAgent {
+    fn new(proxy: ProxyAttributes) -> Self {
+        UpstreamAgent {
+            subtrees: proxy.subtrees(),
+            proxy,
+            command_generator: None,
+            values: HashMap::new(),
+            successors: BTreeMap::new(),
+        }
+    }
+
+    // the forwarded variables are either the ones of the configured subtrees, or the ones absent from the SNMP data
+    fn is_forwarded(&self, snmp_data: &SnmpData, name: &ObjectIdentifier) -> bool {
+        if self.subtrees.is_empty() {
+            !snmp_data.contains_key(name)
+        } else {
+            self.in_subtrees(name)
+        }
+    }
+
+    fn in_subtrees(&self, name: &ObjectIdentifier) -> bool {
+        self.subtrees
+            .iter()
+            .any(|subtree| name.starts_with(subtree))
+    }
+
+    // removes the values received by the previous requests, unless they are cached
+    fn expire(&mut self) {
+        match self.proxy.cache_ttl() {
+            Some(cache_ttl) => {
+                self.values
+                    .retain(|_, (_, received_at)| received_at.elapsed() < cache_ttl);
+                self.successors
+                    .retain(|_, (_, received_at)| received_at.elapsed() < cache_ttl);
+            }
+            None => {
+                self.values.clear();
+                self.successors.clear();
+            }
+        }
+    }
+
+    async fn command_generator(&mut self) -> Result<&mut CommandGenerator, ClientError> {
+        if self.command_generator.is_none() {
+            let command_generator = CommandGenerator::connect(
+                self.proxy.target,
+                &self.proxy.snmp_protocol_attributes,
+                self.proxy.timeout(),
+                self.proxy.retries,
+            )
+            .await?;
+            self.command_generator = Some(command_generator);
+        }
+
+        Ok(self.command_generator.as_mut().unwrap())
+    }
+
+    // Gets the values of the variables from the upstream agent. The variables reported by the noSuchName error
+    // of the SNMPv1 agent are translated to the noSuchObject exception and the rest of them is requested again
+    async fn get(&mut self, names: &[ObjectIdentifier]) -> Result<Vec<VariableBinding>, ClientError> {
+        let mut requested: Vec<ObjectIdentifier> = names
+            .iter()
+            .filter(|name| !self.values.contains_key(*name))
+            .cloned()
+            .collect();
+
+        while !requested.is_empty() {
+            let command_generator = self.command_generator().await?;
+            let is_snmp_v1 = command_generator.is_snmp_v1();
+            let response = command_generator.get(&requested).await?;
+
+            if is_snmp_v1 && response.error_status == ErrorStatus::NoSuchName as u32 {
+                let index = (response.error_index as usize)
+                    .checked_sub(1)
+                    .filter(|index| *index < requested.len())
+                    .ok_or(ClientError::ErrorStatus {
+                        status: response.error_status,
+                        index: response.error_index,
+                    })?;
+                let name = requested.remove(index);
+                let exception = VariableBinding::Exception(name.clone(), VarBindException::NoSuchObject);
+                self.values.insert(name, (exception, Instant::now()));
+                continue;
+            }
+            let variable_bindings = response_variable_bindings(response, requested.len())?;
+            for (name, variable_binding) in requested.drain(..).zip(variable_bindings) {
+                self.values.insert(name, (variable_binding, Instant::now()));
+            }
+        }
+
+        Ok(names
+            .iter()
+            .map(|name| match self.values.get(name) {
+                Some((variable_binding, _)) => variable_binding.clone(),
+                None => VariableBinding::Exception(name.clone(), VarBindException::NoSuchObject),
+            })
+            .collect())
+    }
+
+    // Gets the successor of the name in the MIB view of the upstream agent. It's retrieved by the GetBulkRequest,
+    // so the successors of the following variables are known as well, up to the max-repetitions
+    async fn next(&mut self, name: &ObjectIdentifier, max_repetitions: u32) -> Result<VariableBinding, ClientError> {
+        if let Some(successor) = self.known_successor(name) {
+            return Ok(successor);
+        }
+
+        let objects = [name.clone()];
+        let command_generator = self.command_generator().await?;
+        let is_snmp_v1 = command_generator.is_snmp_v1();
+        let response = command_generator
+            .get_bulk(0, max_repetitions.max(1), &objects)
+            .await?;
+
+        // the SNMPv1 agent reports the end of its MIB view by the noSuchName error
+        let variable_bindings = if is_snmp_v1 && response.error_status == ErrorStatus::NoSuchName as u32 {
+            vec![VariableBinding::Exception(name.clone(), VarBindException::EndOfMibView)]
+        } else {
+            response_variable_bindings(response, 1)?
+        };
+
+        let mut predecessor = name.clone();
+        for variable_binding in variable_bindings {
+            let successor = match &variable_binding {
+                VariableBinding::Value(variable) if variable.name <= predecessor => {
+                    return Err(ClientError::OidNotIncreasing(oid_to_string(variable.name.clone())));
+                }
+                VariableBinding::Value(variable) => Some(variable.name.clone()),
+                VariableBinding::Exception(..) => None,
+            };
+            self.successors
+                .insert(predecessor, (variable_binding, Instant::now()));
+            match successor {
+                Some(successor) => predecessor = successor,
+                None => break,
+            }
+        }
+
+        Ok(self
+            .known_successor(name)
+            .unwrap_or_else(|| VariableBinding::Exception(name.clone(), VarBindException::EndOfMibView)))
+    }
+
+    // the successor of the closest known predecessor is the successor of the name as well, if it follows the name
+    fn known_successor(&self, name: &ObjectIdentifier) -> Option<VariableBinding> {
+        let (_, (successor, _)) = self.successors.range(..=name.clone()).next_back()?;
+        match successor {
+            VariableBinding::Value(variable) if &variable.name <= name => None,
+            successor => Some(successor.clone()),
+        }
+    }
+
+    // Gets the successor of the name among the forwarded variables of the upstream agent,
+    // the variables outside of the configured subtrees are skipped
+    async fn next_forwarded(
+        &mut self,
+        name: &ObjectIdentifier,
+        max_repetitions: u32,
+    ) -> Result<Option<Variable>, ClientError> {
+        let mut name = name.clone();
+        loop {
+            if !self.subtrees.is_empty() && !self.in_subtrees(&name) {
+                name = match self.subtrees.iter().find(|subtree| **subtree > name) {
+                    Some(subtree) => subtree.clone(),
+                    None => return Ok(None),
+                };
+            }
+
+            match self.next(&name, max_repetitions).await? {
+                VariableBinding::Value(variable) if self.subtrees.is_empty() || self.in_subtrees(&variable.name) => {
+                    return Ok(Some(variable))
+                }
+                VariableBinding::Value(variable) => name = variable.name,
+                VariableBinding::Exception(..) => return Ok(None),
+            }
+        }
+    }
+}
+
+// the variable bindings of the successful response, at least the expected number of them
+fn response_variable_bindings(response: AgentResponse, expected: usize) -> Result<Vec<VariableBinding>, ClientError> {
+    if response.error_status != 0 || response.variable_bindings.len() < expected {
+        return Err(ClientError::ErrorStatus {
+            status: response.error_status,
+            index: response.error_index,
+        });
+    }
+
+    Ok(response.variable_bindings)
+}
+
+// the upstream agent of the device, the one of the previous device configuration is replaced
+fn upstream_agent(device_id: Uuid, proxy: &ProxyAttributes) -> SharedUpstreamAgent {
+    let mut upstream_agents = UPSTREAM_AGENTS.lock().unwrap();
+    match upstream_agents.get(&device_id) {
+        Some((configured, upstream_agent)) if configured == proxy => upstream_agent.clone(),
+        _ => {
+            let upstream_agent = Arc::new(tokio::sync::Mutex::new(UpstreamAgent::new(proxy.clone())));
+            upstream_agents.insert(device_id, (proxy.clone(), upstream_agent.clone()));
+            upstream_agent
+        }
+    }
+}
+
+/// Removes the upstream agent of the stopped device, its socket is closed once the forwarded requests are processed
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn remove_upstream_agent(device_id: Uuid) {
+    UPSTREAM_AGENTS.lock().unwrap().remove(&device_id);
+}
+
+// The successor of the name in the MIB view of the proxy device, i.e. the SNMP data of the device merged with
+// the forwarded variables of the upstream agent. The simulated value takes precedence over the upstream one
+async fn next_variable(
+    upstream_agent: &mut UpstreamAgent,
+    request_context: &AgentContext,
+    name: &ObjectIdentifier,
+    max_repetitions: u32,
+) -> Result<VariableBinding, ClientError> {
+    let simulated = {
+        let snmp_data = request_context.snmp_data.read().unwrap();
+        snmp_data
+            .range((Excluded(name.clone()), Unbounded))
+            .find(|(name, _)| upstream_agent.subtrees.is_empty() || !upstream_agent.in_subtrees(name))
+            .map(|(name, snmp_data_item)| data_item_variable(name, snmp_data_item, &request_context.snmp_engine))
+    };
+    let forwarded = upstream_agent.next_forwarded(name, max_repetitions).await?;
+
+    Ok(match (simulated, forwarded) {
+        (Some(simulated), Some(forwarded)) if forwarded.name < simulated.name => forwarded.into(),
+        (Some(simulated), _) => simulated.into(),
+        (None, Some(forwarded)) => forwarded.into(),
+        (None, None) => VariableBinding::Exception(name.clone(), VarBindException::EndOfMibView),
+    })
+}
+
+// SNMPv1 has no exception values, the first one is reported by the noSuchName error status instead
+pub(super) fn response_message(
+    version: &SnmpProtocolVersion,
+    request_id: i32,
+    variable_values: Vec<VariableBinding>,
+) -> GenericSnmpMessage {
+    if matches!(version, SnmpProtocolVersion::SNMPV1(_)) {
+        let exception = variable_values
+            .iter()
+            .enumerate()
+            .find(|(_, variable_binding)| matches!(variable_binding, VariableBinding::Exception(..)));
+        if let Some((idx, variable_binding)) = exception {
+            let response = GetResponseError {
+                request_id,
+                error_status: ErrorStatus::NoSuchName,
+                error_index: idx + 1,
+                name: Some(variable_binding.name().clone()),
+            };
+            return (version, response).into();
+        }
+    }
+
+    let response = GetResponse {
+        request_id,
+        variable_values,
+    };
+    (version, response).into()
+}
+
+async fn forward_get(
+    upstream_agent: &mut UpstreamAgent,
+    request: GetRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, ClientError> {
+    let (simulated, forwarded): (Vec<_>, Vec<_>) = {
+        let snmp_data = request_context.snmp_data.read().unwrap();
+        request
+            .objects
+            .iter()
+            .enumerate()
+            .partition(|(_, name)| !upstream_agent.is_forwarded(&snmp_data, name))
+    };
+    let forwarded_names: Vec<ObjectIdentifier> = forwarded.iter().map(|(_, name)| (*name).clone()).collect();
+    let forwarded_values = upstream_agent.get(&forwarded_names).await?;
+
+    let mut variable_values: Vec<(usize, VariableBinding)> = forwarded
+        .iter()
+        .map(|(idx, _)| *idx)
+        .zip(forwarded_values)
+        .collect();
+    {
+        let snmp_data = request_context.snmp_data.read().unwrap();
+        variable_values.extend(simulated.into_iter().map(|(idx, name)| {
+            let variable_binding = match snmp_data.get(name) {
+                Some(snmp_data_item) => data_item_variable(name, snmp_data_item, &request_context.snmp_engine).into(),
+                None => VariableBinding::Exception(name.clone(), missing_variable_exception(&snmp_data, name)),
+            };
+            (idx, variable_binding)
+        }));
+    }
+    variable_values.sort_by_key(|(idx, _)| *idx);
+
+    Ok(response_message(
+        &request_context.version,
+        request.request_id,
+        variable_values
+            .into_iter()
+            .map(|(_, variable_binding)| variable_binding)
+            .collect(),
+    ))
+}
+
+async fn forward_get_next(
+    upstream_agent: &mut UpstreamAgent,
+    request: GetNextRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, ClientError> {
+    let mut variable_values = vec![];
+    for name in request.objects.iter() {
+        variable_values.push(next_variable(upstream_agent, request_context, name, 1).await?);
+    }
+
+    Ok(response_message(
+        &request_context.version,
+        request.request_id,
+        variable_values,
+    ))
+}
+
+async fn forward_get_bulk(
+    upstream_agent: &mut UpstreamAgent,
+    request: GetBulkRequest,
+    request_context: &AgentContext,
+) -> Result<GenericSnmpMessage, ClientError> {
+    let max_message_size = request_context.max_message_size();
+    let non_repeaters = (request.non_repeaters as usize).min(request.objects.len());
+    let (non_repeating, repeating) = request.objects.split_at(non_repeaters);
+
+    // the non-repeating variables are processed as in the GetNextRequest
+    let mut variable_values = vec![];
+    for name in non_repeating {
+        variable_values.push(next_variable(upstream_agent, request_context, name, 1).await?);
+    }
+    let mut encoded_size: usize = variable_values.iter().map(encoded_var_bind_len).sum();
+
+    // the repeating variables are walked row by row as by the command responder,
+    // the successors of each column are requested from the upstream agent by the GetBulkRequest
+    let mut cursors = repeating.to_vec();
+    for _ in 0..request.max_repetitions {
+        if cursors.is_empty() || encoded_size > max_message_size {
+            break;
+        }
+
+        let mut end_of_mib_view = true;
+        for cursor in cursors.iter_mut() {
+            let variable_binding =
+                next_variable(upstream_agent, request_context, cursor, request.max_repetitions).await?;
+            if let VariableBinding::Value(variable) = &variable_binding {
+                *cursor = variable.name.clone();
+                end_of_mib_view = false;
+            }
+            encoded_size += encoded_var_bind_len(&variable_binding);
+            variable_values.push(variable_binding);
+        }
+
+        if end_of_mib_view {
+            break;
+        }
+    }
+
+    Ok(fit_to_message_size(
+        &request_context.version,
+        request.request_id,
+        variable_values,
+        max_message_size,
+    ))
+}
+
+/// The request of the proxy device, the GetRequest, GetNextRequest or GetBulkRequest
+#[derive(Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) enum ForwardedRequest {
+    Get(GetRequest),
+    GetNext(GetNextRequest),
+    GetBulk(GetBulkRequest),
+}
+
+/// Processes the request of the proxy device (RFC 3413, 3.5), the values of the forwarded variables are requested
+/// from the upstream agent, and the response is sent to the manager with the original request-id.
+/// The request is dropped if the upstream agent doesn't respond, so the manager times out as well
+#[tracing::instrument(level = "info", name = "forward_request", skip(request_context))]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) fn forward_request(request: ForwardedRequest, proxy: &ProxyAttributes, request_context: AgentContext) {
+    let upstream_agent = upstream_agent(request_context.device.id, proxy);
+    actix::spawn(async move {
+        let mut upstream_agent = upstream_agent.lock().await;
+        upstream_agent.expire();
+
+        let (request_id, response) = match request {
+            ForwardedRequest::Get(request) => (
+                request.request_id,
+                forward_get(&mut upstream_agent, request, &request_context).await,
+            ),
+            ForwardedRequest::GetNext(request) => (
+                request.request_id,
+                forward_get_next(&mut upstream_agent, request, &request_context).await,
+            ),
+            ForwardedRequest::GetBulk(request) => (
+                request.request_id,
+                forward_get_bulk(&mut upstream_agent, request, &request_context).await,
+            ),
+        };
+
+        match response {
+            Ok(response) => send_response(response, request_id, &request_context),
+            Err(error) => {
+                // the upstream agent is connected once again by the next request, f.e. it could have been restarted
+                upstream_agent.command_generator = None;
+                request_context
+                    .snmp_engine
+                    .snmp_statistics
+                    .increment(SnmpCounter::ProxyDrops);
+                tracing::warn!(
+                    "The request {request_id} forwarded to {} is dropped: {error}",
+                    upstream_agent.proxy.target
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,167 @@
+use crate::domain::device_unique_id;
+use crate::domain::ManagedDevice;
+use crate::domain::SnmpProtocolAttributes;
+use crate::domain::SnmpV3Attributes;
+use crate::snmp::snmp_statistics::SnmpStatistics;
+use crate::snmp::usm::{AuthenticationKey, PrivacyKey, UsmStatistics};
+
+use rasn::prelude::ObjectIdentifier;
+use snmp_data_parser::parser::snmp_data::component::{DataType, SnmpDataItem};
+use snmp_data_parser::parser::snmp_data::{ModifierContext, ModifierExtractor, ModifierState};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Instant;
+
+// snmpEngineID of the format 4 (octets) prefixed by the enterprise number of net-snmp (RFC 3411, 5)
+pub const ENGINE_ID_PREFIX: [u8; 5] = [0x80, 0x00, 0x1F, 0x88, 0x05];
+
+// the upper limit of both snmpEngineBoots and snmpEngineTime (RFC 3414, 2.2.1)
+pub const ENGINE_MAX_VALUE: u32 = 2147483647;
+
+/// The USM user configured on the device, its keys are localized to the authoritative snmpEngineID,
+/// i.e. the device's one or the one of the InformRequest receiver
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+#[derive(Debug, Clone)]
+pub(crate) struct UsmUser {
+    pub name: String,
+    pub authentication_key: Option<AuthenticationKey>,
+    pub privacy_key: Option<PrivacyKey>,
+}
+
+impl UsmUser {
+    /// Localizes the user's keys to the authoritative snmpEngineID
+    pub fn new(attributes: &SnmpV3Attributes, engine_id: &[u8]) -> Self {
+        let authentication_key = AuthenticationKey::new(
+            attributes.authentication.clone(),
+            &attributes.authentication_password,
+            engine_id,
+        );
+        // privacy requires authentication (RFC 3414, 1.4.3)
+        let privacy_key = authentication_key.as_ref().and_then(|_| {
+            PrivacyKey::new(
+                attributes.encryption.clone(),
+                &attributes.authentication,
+                &attributes.encryption_key,
+                engine_id,
+            )
+        });
+        UsmUser {
+            name: attributes.user.clone(),
+            authentication_key,
+            privacy_key,
+        }
+    }
+}
+
+/// The SNMP engine of a running device, i.e. the authoritative SNMP engine for SNMPv3 requests
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct SnmpEngine {
+    pub engine_id: Vec<u8>,
+    pub engine_boots: u32,
+    pub usm_user: Option<UsmUser>,
+    pub usm_statistics: UsmStatistics,
+    pub snmp_statistics: SnmpStatistics,
+    started_at: Instant,
+    modifier_context: ModifierContext,
+    // the states of the post-loaded modifiers, indexed by the name of the variable
+    modifier_states: Mutex<HashMap<ObjectIdentifier, ModifierState>>,
+}
+
+impl fmt::Debug for SnmpEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "engine_id={:02X?}, engine_boots={}, engine_time={}",
+            self.engine_id,
+            self.engine_boots,
+            self.engine_time()
+        )
+    }
+}
+
+impl SnmpEngine {
+    pub fn new(device: &ManagedDevice) -> Self {
+        let engine_id = [&ENGINE_ID_PREFIX[..], device.id.as_bytes()].concat();
+
+        // snmpEngineBoots is incremented with the device each time it's started, the engine is initialized at least once
+        let engine_boots = device.engine_boots.clamp(1, ENGINE_MAX_VALUE);
+
+        // the keys are localized just once, the password to key algorithm is expensive
+        let usm_user = match &device.snmp_protocol_attributes {
+            SnmpProtocolAttributes::SnmpV3(attributes) => Some(UsmUser::new(attributes, &engine_id)),
+            _ => None,
+        };
+
+        SnmpEngine {
+            engine_id,
+            engine_boots,
+            usm_user,
+            usm_statistics: UsmStatistics::default(),
+            snmp_statistics: SnmpStatistics::default(),
+            started_at: Instant::now(),
+            modifier_context: ModifierContext::new(device_unique_id(&device.id)),
+            modifier_states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// snmpEngineTime, the number of seconds since the engine was (re-)initialized
+    pub fn engine_time(&self) -> u32 {
+        self.started_at
+            .elapsed()
+            .as_secs()
+            .min(ENGINE_MAX_VALUE as u64) as u32
+    }
+
+    /// sysUpTime, the time in hundredths of a second since the engine was (re-)initialized
+    pub fn sys_up_time(&self) -> u32 {
+        (self.started_at.elapsed().as_millis() / 10) as u32
+    }
+
+    /// snmpEngineBoots has reached its maximum value, the engine is not able to authenticate requests
+    pub fn is_engine_boots_exhausted(&self) -> bool {
+        self.engine_boots == ENGINE_MAX_VALUE
+    }
+
+    /// The value of the data item modified by its post-loaded modifier, the time the modifier is evaluated at is the
+    /// time since the engine was (re-)initialized. The value is bounded by the range of the data type of the item,
+    /// the data value is served as is if the modifier cannot be evaluated
+    pub fn post_loaded_value<Extractor: ModifierExtractor>(
+        &self,
+        extractor: &Extractor,
+        name: &ObjectIdentifier,
+        snmp_data_item: &SnmpDataItem,
+        expression: &str,
+    ) -> String {
+        let elapsed = self.started_at.elapsed().as_millis() as u64 / 10;
+        let mut modifier_states = self.modifier_states.lock().unwrap();
+        let state = modifier_states.entry(name.clone()).or_default();
+        extractor
+            .evaluate_post_loaded_modifier(
+                &snmp_data_item.data_value,
+                expression,
+                state,
+                elapsed,
+                &self.modifier_context,
+            )
+            .map(|value| bounded_value(&snmp_data_item.data_type, value))
+            .unwrap_or_else(|error| {
+                tracing::debug!("The post-loaded modifier {expression} is not applied: {error}");
+                snmp_data_item.data_value.clone()
+            })
+    }
+}
+
+// the numeric value clamped to the range of the data type, f.e. the modifier's range of a Gauge32 exceeding 0..2^32-1
+fn bounded_value(data_type: &DataType, value: String) -> String {
+    let (min, max): (i128, i128) = match data_type {
+        DataType::Integer => (i32::MIN.into(), i32::MAX.into()),
+        DataType::Counter32 | DataType::Gauge32 | DataType::UInteger32 | DataType::Timeticks => (0, u32::MAX.into()),
+        DataType::Counter64 => (0, u64::MAX.into()),
+        _ => return value,
+    };
+    match value.parse::<i128>() {
+        Ok(number) => number.clamp(min, max).to_string(),
+        Err(_) => value,
+    }
+}
@@ -13,7 +13,9 @@ use std::convert::Infallible;
     code = 409,
     description = "Conflict",
     code = 500,
-    description = "Internal server error"
+    description = "Internal server error",
+    code = 502,
+    description = "Bad gateway"
 )]
 #[derive(thiserror::Error)]
 pub enum AgentError {
@@ -29,6 +31,9 @@ pub enum AgentError {
     #[error("{0}")]
     Conflict(String),
 
+    #[error("{0}")]
+    BadGateway(String),
+
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
 }
@@ -52,6 +57,7 @@ impl ResponseError for AgentError {
             AgentError::Validation(_) => StatusCode::BAD_REQUEST,
             AgentError::NotFound(_) => StatusCode::NOT_FOUND,
             AgentError::Conflict(_) => StatusCode::CONFLICT,
+            AgentError::BadGateway(_) => StatusCode::BAD_GATEWAY,
             AgentError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -65,6 +71,7 @@ impl From<DomainError> for AgentError {
             DomainError::Conflict(details) => AgentError::Conflict(details),
             DomainError::Unexpected(details) => AgentError::Unexpected(details),
             DomainError::UdpServerError(details) => AgentError::Conflict(details.to_string()),
+            DomainError::ClientError(details) => AgentError::BadGateway(details.to_string()),
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::routes::AgentError;
 use crate::routes::{first, twenty};
 use paperclip::actix::Apiv2Schema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 use uuid_dev::Uuid;
 
@@ -16,6 +16,35 @@ pub struct Agent {
     snmp_data_url: String,
 
     description: Option<String>,
+
+    /// The format of the SNMP data file, "snmpwalk" or "snmprec", picked by the extension of the file by default,
+    /// the "*.snmprec" files are the recordings of snmpsim
+    data_format: Option<SnmpDataFormat>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnmpDataFormat {
+    Snmpwalk,
+    Snmprec,
+}
+
+impl From<crate::domain::SnmpDataFormat> for SnmpDataFormat {
+    fn from(data_format: crate::domain::SnmpDataFormat) -> Self {
+        match data_format {
+            crate::domain::SnmpDataFormat::Snmpwalk => SnmpDataFormat::Snmpwalk,
+            crate::domain::SnmpDataFormat::Snmprec => SnmpDataFormat::Snmprec,
+        }
+    }
+}
+
+impl From<SnmpDataFormat> for crate::domain::SnmpDataFormat {
+    fn from(data_format: SnmpDataFormat) -> Self {
+        match data_format {
+            SnmpDataFormat::Snmpwalk => crate::domain::SnmpDataFormat::Snmpwalk,
+            SnmpDataFormat::Snmprec => crate::domain::SnmpDataFormat::Snmprec,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Apiv2Schema)]
@@ -40,6 +69,7 @@ impl TryFrom<Agent> for crate::domain::Agent {
             name: value.name,
             description: value.description,
             snmp_data_url: value.snmp_data_url,
+            data_format: value.data_format.map(crate::domain::SnmpDataFormat::from),
         })
     }
 }
@@ -55,6 +85,7 @@ impl TryFrom<(Uuid, Agent)> for crate::domain::Agent {
             created_at: chrono::Utc::now(),
             modified_at: chrono::Utc::now(),
             snmp_data_url: value.snmp_data_url,
+            data_format: value.data_format.map(crate::domain::SnmpDataFormat::from),
         })
     }
 }
@@ -1,3 +1,5 @@
 pub mod agent;
+pub mod record;
 
 pub use agent::*;
+pub use record::*;
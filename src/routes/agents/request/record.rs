@@ -0,0 +1,119 @@
+use crate::routes::{parse_oid, AgentError, SnmpProtocolAttributes};
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::net::SocketAddr;
+use std::time::Duration;
+use uuid_dev::Uuid;
+
+// the defaults of the net-snmp snmpwalk and snmpbulkwalk tools
+const DEFAULT_TIMEOUT: u32 = 1000;
+const DEFAULT_RETRIES: u32 = 5;
+const DEFAULT_MAX_REPETITIONS: u32 = 10;
+// the whole internet subtree, so the enterprise specific variables are recorded as well
+const DEFAULT_ROOT_OID: &str = ".1.3.6.1";
+
+#[derive(Debug, Deserialize, Apiv2Schema)]
+#[openapi(rename = "RequestRecordAgent")]
+/// An agent recorded by walking a remote SNMP agent.
+pub struct RecordAgent {
+    /// The name of this agent.
+    name: String,
+
+    /// The path of the SNMP data file the walked variables are written to, f.e. "./os/linux.dat"
+    snmp_data_url: String,
+
+    description: Option<String>,
+
+    /// The socket address of the walked agent, f.e. "192.168.1.1:161"
+    target: String,
+
+    /// The SNMP version and the community or the USM user used to walk the agent
+    snmp_protocol_attributes: SnmpProtocolAttributes,
+
+    /// The requests used to walk the agent, GETBULK by default, the SNMPv1 agent is walked by GETNEXT
+    method: Option<WalkMethod>,
+
+    /// The root of the walked subtree, ".1.3.6.1" by default
+    root_oid: Option<String>,
+
+    /// The max-repetitions of the GETBULK requests, 10 by default
+    max_repetitions: Option<u32>,
+
+    /// The timeout of each request in milliseconds, 1000 by default
+    timeout: Option<u32>,
+
+    /// The number of retransmissions of each request, 5 by default
+    retries: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
+pub enum WalkMethod {
+    #[serde(rename = "GETNEXT")]
+    GetNext,
+    #[serde(rename = "GETBULK")]
+    GetBulk,
+}
+
+impl TryFrom<RecordAgent> for (crate::domain::Agent, crate::domain::Walk) {
+    type Error = AgentError;
+
+    fn try_from(record: RecordAgent) -> Result<Self, Self::Error> {
+        let target = record.target.parse::<SocketAddr>().map_err(|_| {
+            AgentError::Validation(format!("'target' must be a socket address, got '{}'", record.target))
+        })?;
+
+        let snmp_protocol_attributes = crate::domain::SnmpProtocolAttributes::try_from(record.snmp_protocol_attributes)
+            .map_err(|error| AgentError::Validation(error.to_string()))?;
+        let method = match (&snmp_protocol_attributes, record.method) {
+            (crate::domain::SnmpProtocolAttributes::SnmpV1(_), Some(WalkMethod::GetBulk)) => {
+                return Err(AgentError::Validation(
+                    "'method' GETBULK is not supported by SNMPv1".into(),
+                ))
+            }
+            (crate::domain::SnmpProtocolAttributes::SnmpV1(_), _) | (_, Some(WalkMethod::GetNext)) => {
+                crate::domain::WalkMethod::GetNext
+            }
+            (_, _) => crate::domain::WalkMethod::GetBulk,
+        };
+
+        let root = parse_oid("root_oid", record.root_oid.as_deref().unwrap_or(DEFAULT_ROOT_OID))
+            .map_err(|error| AgentError::Validation(error.to_string()))?;
+
+        let max_repetitions = record.max_repetitions.unwrap_or(DEFAULT_MAX_REPETITIONS);
+        if max_repetitions == 0 {
+            return Err(AgentError::Validation(
+                "'max_repetitions' must be greater than 0".into(),
+            ));
+        }
+        let timeout = record.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        if timeout == 0 {
+            return Err(AgentError::Validation("'timeout' must be greater than 0".into()));
+        }
+        if record.snmp_data_url.trim().is_empty() {
+            return Err(AgentError::Validation("'snmp_data_url' must not be empty".into()));
+        }
+
+        let agent = crate::domain::Agent {
+            id: Uuid::new_v4(),
+            created_at: chrono::Utc::now(),
+            modified_at: chrono::Utc::now(),
+            name: record.name,
+            description: record.description,
+            snmp_data_url: record.snmp_data_url,
+            // the walked variables are written in the snmpwalk format whatever the extension of the file
+            data_format: Some(crate::domain::SnmpDataFormat::Snmpwalk),
+        };
+        let walk = crate::domain::Walk {
+            target,
+            snmp_protocol_attributes,
+            method,
+            root,
+            max_repetitions,
+            timeout: Duration::from_millis(timeout.into()),
+            retries: record.retries.unwrap_or(DEFAULT_RETRIES),
+        };
+
+        Ok((agent, walk))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::domain;
+use crate::routes::SnmpDataFormat;
 use paperclip::actix::Apiv2Schema;
 use uuid_dev::Uuid;
 
@@ -17,6 +18,10 @@ pub struct Agent {
 
     /// The URL to the SNMP data f.e. "file://./os/linux.dat"
     pub snmp_data_url: String,
+
+    /// The format of the SNMP data file, picked by the extension of the file if not present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_format: Option<SnmpDataFormat>,
 }
 
 impl From<crate::domain::Agent> for Agent {
@@ -26,6 +31,7 @@ impl From<crate::domain::Agent> for Agent {
             name: agent.name,
             description: agent.description,
             snmp_data_url: agent.snmp_data_url,
+            data_format: agent.data_format.map(SnmpDataFormat::from),
         }
     }
 }
@@ -37,6 +43,7 @@ impl From<&domain::Agent> for Agent {
             name: agent.name.clone(),
             description: agent.description.clone(),
             snmp_data_url: agent.snmp_data_url.clone(),
+            data_format: agent.data_format.map(SnmpDataFormat::from),
         }
     }
 }
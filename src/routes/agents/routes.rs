@@ -18,6 +18,9 @@ async fn post_agent(
     conn: Data<DatabaseConnection>,
 ) -> Result<PostResponse<response::Agent>, JsonError<AgentError>> {
     let agent: crate::domain::Agent = form.0.try_into()?;
+    crate::domain::validate_snmp_data(&agent)
+        .await
+        .map_err(AgentError::from)?;
 
     let result = crate::domain::create_agent(conn.as_ref(), &agent)
         .await
@@ -28,6 +31,24 @@ async fn post_agent(
     }
 }
 
+#[post("/agents/record")]
+#[api_v2_operation(tags("Agents"), consumes = "application/json")]
+/// Create a new agent by walking a remote SNMP agent
+async fn record_agent(
+    form: Json<request::RecordAgent>,
+    conn: Data<DatabaseConnection>,
+) -> Result<PostResponse<response::Agent>, JsonError<AgentError>> {
+    let (agent, walk): (crate::domain::Agent, crate::domain::Walk) = form.0.try_into()?;
+
+    let result = crate::domain::record_agent(conn.as_ref(), &agent, &walk)
+        .await
+        .map_err(AgentError::from)?;
+    match result {
+        CreateResult::Created(x) => Ok(PostResponse::Created(response::Agent::from(x))),
+        CreateResult::Duplicate(x) => Ok(PostResponse::Exists(response::Agent::from(x))),
+    }
+}
+
 #[get("/agents/{id}")]
 #[api_v2_operation(tags("Agents"))]
 /// Get agent by ID
@@ -83,7 +104,10 @@ async fn update_agent(
     id: web::Path<Uuid>,
     form: Json<request::Agent>,
 ) -> Result<PutResponse<response::Agent>, JsonError<AgentError>> {
-    let requested_agent = (id.into_inner(), form.0).try_into()?;
+    let requested_agent: crate::domain::Agent = (id.into_inner(), form.0).try_into()?;
+    crate::domain::validate_snmp_data(&requested_agent)
+        .await
+        .map_err(AgentError::from)?;
     let result = crate::domain::update_agent(conn.as_ref(), requested_agent)
         .await
         .map_err(AgentError::from)?;
@@ -96,6 +120,7 @@ async fn update_agent(
 
 pub fn agents_config(cfg: &mut ServiceConfig) {
     cfg.service(post_agent);
+    cfg.service(record_agent);
     cfg.service(get_agent);
     cfg.service(delete_agent);
     cfg.service(list_agents);
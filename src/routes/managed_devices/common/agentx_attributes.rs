@@ -0,0 +1,61 @@
+use crate::routes::DeviceError;
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+// the subagent is expected to respond well before the manager retransmits its own request
+const DEFAULT_TIMEOUT: u32 = 1000;
+
+// the prefix of the Unix domain socket address, f.e. "unix:/var/agentx/master"
+const UNIX_PREFIX: &str = "unix:";
+
+#[derive(Debug, Clone, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
+pub struct AgentxAttributes {
+    /// The address the master agent listens on for the subagents, either a TCP socket address, f.e. "127.0.0.1:705", or the path of a Unix domain socket, f.e. "unix:/var/agentx/master"
+    pub address: String,
+
+    /// The timeout of each request dispatched to a subagent in milliseconds, 1000 by default. The timeout of the subagent session or registration takes precedence if specified by the subagent
+    #[serde(default = "default_timeout")]
+    pub timeout: Option<u32>,
+}
+
+fn default_timeout() -> Option<u32> {
+    Some(DEFAULT_TIMEOUT)
+}
+
+impl From<crate::domain::AgentxAttributes> for AgentxAttributes {
+    fn from(agentx: crate::domain::AgentxAttributes) -> Self {
+        AgentxAttributes {
+            address: agentx.address.to_string(),
+            timeout: Some(agentx.timeout),
+        }
+    }
+}
+
+impl TryFrom<AgentxAttributes> for crate::domain::AgentxAttributes {
+    type Error = DeviceError;
+
+    fn try_from(agentx: AgentxAttributes) -> Result<Self, Self::Error> {
+        let address = match agentx.address.strip_prefix(UNIX_PREFIX) {
+            Some(path) if !path.is_empty() => crate::domain::AgentxAddress::Unix(path.to_string()),
+            _ => agentx
+                .address
+                .parse::<SocketAddr>()
+                .map(crate::domain::AgentxAddress::Tcp)
+                .map_err(|_| {
+                    DeviceError::Validation(format!(
+                        "'agentx.address' must be a socket address or a Unix socket path prefixed by '{UNIX_PREFIX}', got '{}'",
+                        agentx.address
+                    ))
+                })?,
+        };
+        let timeout = agentx.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        if timeout == 0 {
+            return Err(DeviceError::Validation(
+                "'agentx.timeout' must be greater than 0".into(),
+            ));
+        }
+
+        Ok(crate::domain::AgentxAttributes { address, timeout })
+    }
+}
@@ -1,3 +1,9 @@
+pub mod agentx_attributes;
+pub mod proxy_attributes;
 pub mod snmp_protocol_attributes;
+pub mod snmp_transport;
 
+pub use agentx_attributes::*;
+pub use proxy_attributes::*;
 pub use snmp_protocol_attributes::*;
+pub use snmp_transport::*;
@@ -0,0 +1,86 @@
+use crate::routes::{parse_oid, DeviceError, SnmpProtocolAttributes};
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+use std::net::SocketAddr;
+
+// the upstream agent is expected to respond well before the manager retransmits its own request
+const DEFAULT_TIMEOUT: u32 = 1000;
+const DEFAULT_RETRIES: u32 = 1;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
+pub struct ProxyAttributes {
+    /// The socket address of the upstream agent, f.e. "192.168.1.1:161"
+    pub target: String,
+
+    /// The SNMP version and the community or the USM user of the requests forwarded to the upstream agent
+    pub snmp_protocol_attributes: SnmpProtocolAttributes,
+
+    /// The forwarded subtrees, f.e. [".1.3.6.1.2.1.2"]. The variables absent from the SNMP data are forwarded if empty
+    #[serde(default)]
+    pub subtrees: Vec<String>,
+
+    /// The number of seconds the upstream values are cached for, they are not cached by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_ttl: Option<u32>,
+
+    /// The timeout of each forwarded request in milliseconds, 1000 by default
+    #[serde(default = "default_timeout")]
+    pub timeout: Option<u32>,
+
+    /// The number of retransmissions of each forwarded request, 1 by default
+    #[serde(default = "default_retries")]
+    pub retries: Option<u32>,
+}
+
+fn default_timeout() -> Option<u32> {
+    Some(DEFAULT_TIMEOUT)
+}
+
+fn default_retries() -> Option<u32> {
+    Some(DEFAULT_RETRIES)
+}
+
+impl From<crate::domain::ProxyAttributes> for ProxyAttributes {
+    fn from(proxy: crate::domain::ProxyAttributes) -> Self {
+        ProxyAttributes {
+            target: proxy.target.to_string(),
+            snmp_protocol_attributes: proxy.snmp_protocol_attributes.into(),
+            subtrees: proxy.subtrees,
+            cache_ttl: proxy.cache_ttl,
+            timeout: Some(proxy.timeout),
+            retries: Some(proxy.retries),
+        }
+    }
+}
+
+impl TryFrom<ProxyAttributes> for crate::domain::ProxyAttributes {
+    type Error = DeviceError;
+
+    fn try_from(proxy: ProxyAttributes) -> Result<Self, Self::Error> {
+        let target = proxy.target.parse::<SocketAddr>().map_err(|_| {
+            DeviceError::Validation(format!(
+                "'proxy.target' must be a socket address, got '{}'",
+                proxy.target
+            ))
+        })?;
+        let subtrees = proxy
+            .subtrees
+            .iter()
+            .map(|subtree| parse_oid("proxy.subtrees", subtree).map(oid_to_string))
+            .collect::<Result<Vec<String>, DeviceError>>()?;
+        let timeout = proxy.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        if timeout == 0 {
+            return Err(DeviceError::Validation("'proxy.timeout' must be greater than 0".into()));
+        }
+
+        Ok(crate::domain::ProxyAttributes {
+            target,
+            snmp_protocol_attributes: crate::domain::SnmpProtocolAttributes::try_from(proxy.snmp_protocol_attributes)?,
+            subtrees,
+            cache_ttl: proxy.cache_ttl.filter(|cache_ttl| *cache_ttl > 0),
+            timeout,
+            retries: proxy.retries.unwrap_or(DEFAULT_RETRIES),
+        })
+    }
+}
@@ -1,6 +1,10 @@
 use crate::routes::DeviceError;
+use crate::snmp::codec::snmp_codec::SnmpCodec;
 use paperclip::actix::Apiv2Schema;
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+const MIN_MESSAGE_SIZE: u32 = 484;
 
 #[derive(Default, Debug, Clone, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
 pub struct SnmpProtocolAttributes {
@@ -18,12 +22,30 @@ pub struct SnmpProtocolAttributes {
 pub struct SnmpV1Attributes {
     #[serde(default = "default_public")]
     pub community: Option<String>,
+
+    /// The community allowing the set requests, the device is read-only if it is missing.
+    pub read_write_community: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_failure_trap: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
 pub struct SnmpV2cAttributes {
     #[serde(default = "default_public")]
     pub community: Option<String>,
+
+    /// The community allowing the set requests, the device is read-only if it is missing.
+    pub read_write_community: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_failure_trap: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_size: Option<u32>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
@@ -47,6 +69,10 @@ pub struct SnmpV3Attributes {
 pub enum AuthenticationAlgorithm {
     Md5,
     Sha,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
@@ -54,6 +80,8 @@ pub enum AuthenticationAlgorithm {
 pub enum EncryptionAlgorithm {
     Des,
     Aes,
+    Aes192,
+    Aes256,
 }
 
 fn default_public() -> Option<String> {
@@ -74,6 +102,9 @@ impl From<crate::domain::SnmpProtocolAttributes> for SnmpProtocolAttributes {
             crate::domain::SnmpProtocolAttributes::SnmpV1(attr) => SnmpProtocolAttributes {
                 snmp_v1: Some(SnmpV1Attributes {
                     community: Some(attr.community),
+                    read_write_community: attr.read_write_community,
+                    authentication_failure_trap: attr.authentication_failure_trap,
+                    max_message_size: attr.max_message_size,
                 }),
                 snmp_v2c: None,
                 snmp_v3: None,
@@ -82,6 +113,9 @@ impl From<crate::domain::SnmpProtocolAttributes> for SnmpProtocolAttributes {
                 snmp_v1: None,
                 snmp_v2c: Some(SnmpV2cAttributes {
                     community: Some(attr.community),
+                    read_write_community: attr.read_write_community,
+                    authentication_failure_trap: attr.authentication_failure_trap,
+                    max_message_size: attr.max_message_size,
                 }),
                 snmp_v3: None,
             },
@@ -108,11 +142,17 @@ impl TryFrom<SnmpProtocolAttributes> for crate::domain::SnmpProtocolAttributes {
             (Some(attr), None, None) => Ok(crate::domain::SnmpProtocolAttributes::SnmpV1(
                 crate::domain::SnmpV1Attributes {
                     community: attr.community.unwrap(),
+                    read_write_community: attr.read_write_community,
+                    authentication_failure_trap: validate_trap_target(attr.authentication_failure_trap)?,
+                    max_message_size: validate_max_message_size(attr.max_message_size)?,
                 },
             )),
             (None, Some(attr), None) => Ok(crate::domain::SnmpProtocolAttributes::SnmpV2c(
                 crate::domain::SnmpV2cAttributes {
                     community: attr.community.unwrap(),
+                    read_write_community: attr.read_write_community,
+                    authentication_failure_trap: validate_trap_target(attr.authentication_failure_trap)?,
+                    max_message_size: validate_max_message_size(attr.max_message_size)?,
                 },
             )),
             (None, None, Some(attr)) => Ok(crate::domain::SnmpProtocolAttributes::SnmpV3(
@@ -131,11 +171,39 @@ impl TryFrom<SnmpProtocolAttributes> for crate::domain::SnmpProtocolAttributes {
     }
 }
 
+// the trap target is the socket address of the manager, e.g. "127.0.0.1:162"
+fn validate_trap_target(target: Option<String>) -> Result<Option<String>, DeviceError> {
+    match target {
+        Some(target) if target.parse::<SocketAddr>().is_err() => Err(DeviceError::Validation(format!(
+            "'authentication_failure_trap' must be a socket address, got '{target}'"
+        ))),
+        target => Ok(target),
+    }
+}
+
+// every SNMP entity has to accept messages of at least 484 octets (RFC 3417, 3.2),
+// the upper limit is the maximum payload of a UDP datagram
+fn validate_max_message_size(max_message_size: Option<u32>) -> Result<Option<u32>, DeviceError> {
+    match max_message_size {
+        Some(size) if !(MIN_MESSAGE_SIZE..=SnmpCodec::MAX_MESSAGE_SIZE as u32).contains(&size) => {
+            Err(DeviceError::Validation(format!(
+                "'max_message_size' must be between {MIN_MESSAGE_SIZE} and {}, got {size}",
+                SnmpCodec::MAX_MESSAGE_SIZE
+            )))
+        }
+        max_message_size => Ok(max_message_size),
+    }
+}
+
 impl From<crate::domain::AuthenticationAlgorithm> for AuthenticationAlgorithm {
     fn from(tt: crate::domain::AuthenticationAlgorithm) -> Self {
         match tt {
             crate::domain::AuthenticationAlgorithm::Md5 => Self::Md5,
             crate::domain::AuthenticationAlgorithm::Sha => Self::Sha,
+            crate::domain::AuthenticationAlgorithm::Sha224 => Self::Sha224,
+            crate::domain::AuthenticationAlgorithm::Sha256 => Self::Sha256,
+            crate::domain::AuthenticationAlgorithm::Sha384 => Self::Sha384,
+            crate::domain::AuthenticationAlgorithm::Sha512 => Self::Sha512,
         }
     }
 }
@@ -145,6 +213,10 @@ impl From<AuthenticationAlgorithm> for crate::domain::AuthenticationAlgorithm {
         match tt {
             AuthenticationAlgorithm::Md5 => Self::Md5,
             AuthenticationAlgorithm::Sha => Self::Sha,
+            AuthenticationAlgorithm::Sha224 => Self::Sha224,
+            AuthenticationAlgorithm::Sha256 => Self::Sha256,
+            AuthenticationAlgorithm::Sha384 => Self::Sha384,
+            AuthenticationAlgorithm::Sha512 => Self::Sha512,
         }
     }
 }
@@ -154,6 +226,8 @@ impl From<crate::domain::EncryptionAlgorithm> for EncryptionAlgorithm {
         match sa {
             crate::domain::EncryptionAlgorithm::Des => Self::Des,
             crate::domain::EncryptionAlgorithm::Aes => Self::Aes,
+            crate::domain::EncryptionAlgorithm::Aes192 => Self::Aes192,
+            crate::domain::EncryptionAlgorithm::Aes256 => Self::Aes256,
         }
     }
 }
@@ -163,6 +237,8 @@ impl From<EncryptionAlgorithm> for crate::domain::EncryptionAlgorithm {
         match sa {
             EncryptionAlgorithm::Des => Self::Des,
             EncryptionAlgorithm::Aes => Self::Aes,
+            EncryptionAlgorithm::Aes192 => Self::Aes192,
+            EncryptionAlgorithm::Aes256 => Self::Aes256,
         }
     }
 }
@@ -0,0 +1,27 @@
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Apiv2Schema, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnmpTransport {
+    Udp,
+    Tcp,
+}
+
+impl From<crate::domain::SnmpTransport> for SnmpTransport {
+    fn from(transport: crate::domain::SnmpTransport) -> Self {
+        match transport {
+            crate::domain::SnmpTransport::Udp => SnmpTransport::Udp,
+            crate::domain::SnmpTransport::Tcp => SnmpTransport::Tcp,
+        }
+    }
+}
+
+impl From<SnmpTransport> for crate::domain::SnmpTransport {
+    fn from(transport: SnmpTransport) -> Self {
+        match transport {
+            SnmpTransport::Udp => crate::domain::SnmpTransport::Udp,
+            SnmpTransport::Tcp => crate::domain::SnmpTransport::Tcp,
+        }
+    }
+}
@@ -65,6 +65,7 @@ impl From<DomainError> for DeviceError {
             DomainError::Conflict(details) => DeviceError::Conflict(details),
             DomainError::Unexpected(details) => DeviceError::Unexpected(details),
             DomainError::UdpServerError(details) => DeviceError::Conflict(details.to_string()),
+            DomainError::ClientError(details) => DeviceError::Unexpected(details.into()),
         }
     }
 }
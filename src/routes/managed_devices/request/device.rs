@@ -1,7 +1,11 @@
-use crate::routes::{first, twenty, DeviceError, SnmpProtocolAttributes};
+use crate::domain::host_port;
+use crate::routes::{
+    first, twenty, AgentxAttributes, DeviceError, ProxyAttributes, SnmpProtocolAttributes, SnmpTransport,
+};
 use paperclip::actix::Apiv2Schema;
 use serde::Deserialize;
 use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
 use uuid_dev::Uuid;
 
 #[derive(Debug, Deserialize, Apiv2Schema)]
@@ -17,9 +21,26 @@ pub struct Device {
 
     snmp_protocol_attributes: SnmpProtocolAttributes,
 
+    /// The IPv4 address, the IPv6 address or the host name the device is bound to, the device bound to "::" is reachable
+    /// on both the IPv4 and IPv6 addresses of the host
     snmp_host: String,
 
     snmp_port: u16,
+
+    /// The upstream agent serving the variables not simulated by the device
+    proxy: Option<ProxyAttributes>,
+
+    /// The sysName.0 value served by the device instead of the one of the SNMP data
+    sys_name: Option<String>,
+
+    /// The sysLocation.0 value served by the device instead of the one of the SNMP data
+    sys_location: Option<String>,
+
+    /// The AgentX master agent dispatching the requests for the subtrees registered by the subagents
+    agentx: Option<AgentxAttributes>,
+
+    /// The transport the device receives the SNMP requests on, "udp" by default or "tcp" (RFC 3430)
+    transport: Option<SnmpTransport>,
 }
 
 #[derive(Debug, Deserialize, Apiv2Schema)]
@@ -45,6 +66,9 @@ impl TryFrom<Device> for crate::domain::ManagedDevice {
     type Error = DeviceError;
 
     fn try_from(managed_device: Device) -> Result<Self, Self::Error> {
+        let snmp_host = validate_snmp_host(&managed_device.snmp_host)?;
+        let proxy = validate_proxy(&managed_device, &snmp_host)?;
+        let agentx = validate_agentx(&managed_device)?;
         Ok(Self {
             id: Uuid::new_v4(),
             created_at: chrono::Utc::now(),
@@ -55,8 +79,16 @@ impl TryFrom<Device> for crate::domain::ManagedDevice {
             snmp_protocol_attributes: crate::domain::SnmpProtocolAttributes::try_from(
                 managed_device.snmp_protocol_attributes,
             )?,
-            snmp_host: managed_device.snmp_host,
+            proxy,
+            snmp_host,
             snmp_port: managed_device.snmp_port,
+            sys_name: managed_device.sys_name,
+            sys_location: managed_device.sys_location,
+            agentx,
+            transport: managed_device
+                .transport
+                .map_or(crate::domain::SnmpTransport::Udp, crate::domain::SnmpTransport::from),
+            engine_boots: 0,
         })
     }
 }
@@ -65,6 +97,9 @@ impl TryFrom<(Uuid, Device)> for crate::domain::ManagedDevice {
     type Error = DeviceError;
 
     fn try_from((id, managed_device): (Uuid, Device)) -> Result<Self, Self::Error> {
+        let snmp_host = validate_snmp_host(&managed_device.snmp_host)?;
+        let proxy = validate_proxy(&managed_device, &snmp_host)?;
+        let agentx = validate_agentx(&managed_device)?;
         Ok(Self {
             id,
             name: managed_device.name,
@@ -75,8 +110,83 @@ impl TryFrom<(Uuid, Device)> for crate::domain::ManagedDevice {
             snmp_protocol_attributes: crate::domain::SnmpProtocolAttributes::try_from(
                 managed_device.snmp_protocol_attributes,
             )?,
-            snmp_host: managed_device.snmp_host,
+            proxy,
+            snmp_host,
             snmp_port: managed_device.snmp_port,
+            sys_name: managed_device.sys_name,
+            sys_location: managed_device.sys_location,
+            agentx,
+            transport: managed_device
+                .transport
+                .map_or(crate::domain::SnmpTransport::Udp, crate::domain::SnmpTransport::from),
+            engine_boots: 0,
         })
     }
 }
+
+// the IP address is stored in its canonical form without the brackets of the IPv6 literal, so "[::1]" is "::1"
+fn validate_snmp_host(snmp_host: &str) -> Result<String, DeviceError> {
+    let address = snmp_host
+        .strip_prefix('[')
+        .and_then(|host| host.strip_suffix(']'))
+        .map_or_else(
+            || snmp_host.parse::<IpAddr>(),
+            |host| host.parse::<Ipv6Addr>().map(IpAddr::V6),
+        );
+    match address {
+        Ok(address) => Ok(address.to_string()),
+        Err(_) if is_host_name(snmp_host) => Ok(snmp_host.to_string()),
+        Err(_) => Err(DeviceError::Validation(format!(
+            "'snmp_host' must be an IPv4 address, an IPv6 address or a host name, not '{snmp_host}'"
+        ))),
+    }
+}
+
+// the host name syntax of RFC 1123, 2.1, the top-level label is never all-numeric
+fn is_host_name(host: &str) -> bool {
+    let labels: Vec<&str> = host.strip_suffix('.').unwrap_or(host).split('.').collect();
+    host.len() <= 253
+        && labels.iter().all(|label| {
+            (1..=63).contains(&label.len())
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+        })
+        && !labels
+            .last()
+            .map_or(true, |label| label.chars().all(|c| c.is_ascii_digit()))
+}
+
+// the device forwarding the requests to itself would never respond
+fn validate_proxy(
+    managed_device: &Device,
+    snmp_host: &str,
+) -> Result<Option<crate::domain::ProxyAttributes>, DeviceError> {
+    let proxy = match &managed_device.proxy {
+        Some(proxy) => crate::domain::ProxyAttributes::try_from(proxy.clone())?,
+        None => return Ok(None),
+    };
+    let device_address = host_port(snmp_host, managed_device.snmp_port).parse::<SocketAddr>();
+    if device_address.ok() == Some(proxy.target) {
+        return Err(DeviceError::Validation(
+            "'proxy.target' must not be the address of the device itself".into(),
+        ));
+    }
+
+    Ok(Some(proxy))
+}
+
+// the requests of the proxy device are forwarded to the upstream agent, never dispatched to the subagents
+fn validate_agentx(managed_device: &Device) -> Result<Option<crate::domain::AgentxAttributes>, DeviceError> {
+    let agentx = match &managed_device.agentx {
+        Some(agentx) => crate::domain::AgentxAttributes::try_from(agentx.clone())?,
+        None => return Ok(None),
+    };
+    if managed_device.proxy.is_some() {
+        return Err(DeviceError::Validation(
+            "'agentx' and 'proxy' must not be configured together".into(),
+        ));
+    }
+
+    Ok(Some(agentx))
+}
@@ -0,0 +1,64 @@
+use super::trap::{parse_oid, TrapVariable};
+use crate::domain::InformStatus;
+use crate::routes::DeviceError;
+use paperclip::actix::Apiv2Schema;
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+// the defaults of the net-snmp notification originator
+const DEFAULT_TIMEOUT: u32 = 1000;
+const DEFAULT_RETRIES: u32 = 5;
+
+#[derive(Debug, Deserialize, Apiv2Schema)]
+#[openapi(rename = "RequestInform")]
+/// A confirmed notification sent by a running managed device.
+pub struct Inform {
+    /// The socket address of the manager, f.e. "127.0.0.1:162"
+    target: String,
+
+    /// The snmpTrapOID of the notification, f.e. ".1.3.6.1.6.3.1.1.5.3"
+    trap_oid: String,
+
+    #[serde(default)]
+    variables: Vec<TrapVariable>,
+
+    /// The timeout of the acknowledgement in milliseconds, 1000 by default
+    timeout: Option<u32>,
+
+    /// The number of retransmissions of the unacknowledged notification, 5 by default
+    retries: Option<u32>,
+}
+
+impl TryFrom<Inform> for crate::domain::Inform {
+    type Error = DeviceError;
+
+    fn try_from(inform: Inform) -> Result<Self, Self::Error> {
+        let target = inform.target.parse::<SocketAddr>().map_err(|_| {
+            DeviceError::Validation(format!("'target' must be a socket address, got '{}'", inform.target))
+        })?;
+
+        let timeout = inform.timeout.unwrap_or(DEFAULT_TIMEOUT);
+        if timeout == 0 {
+            return Err(DeviceError::Validation("'timeout' must be greater than 0".into()));
+        }
+
+        let variables = inform
+            .variables
+            .into_iter()
+            .map(crate::domain::Variable::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            request_id: crate::domain::next_request_id(),
+            trap_oid: parse_oid("trap_oid", &inform.trap_oid)?,
+            variables,
+            target,
+            timeout: Duration::from_millis(timeout.into()),
+            retries: inform.retries.unwrap_or(DEFAULT_RETRIES),
+            attempts: 0,
+            status: InformStatus::Pending,
+        })
+    }
+}
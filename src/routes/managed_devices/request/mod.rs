@@ -1,3 +1,7 @@
 pub mod device;
+pub mod inform;
+pub mod trap;
 
 pub use device::*;
+pub use inform::*;
+pub use trap::*;
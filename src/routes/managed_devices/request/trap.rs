@@ -0,0 +1,182 @@
+use crate::domain::{GenericTrap, SNMP_TRAP_ENTERPRISE_OID};
+use crate::routes::DeviceError;
+use paperclip::actix::Apiv2Schema;
+use rasn::prelude::ObjectIdentifier;
+use serde::Deserialize;
+use snmp_data_parser::parser::snmp_data::component::{oid_to_string, DataType};
+use std::convert::TryFrom;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize, Apiv2Schema)]
+#[openapi(rename = "RequestTrap")]
+/// A notification sent by a running managed device.
+pub struct Trap {
+    /// The socket addresses of the managers, f.e. "127.0.0.1:162"
+    targets: Vec<String>,
+
+    /// The snmpTrapOID of the notification, f.e. ".1.3.6.1.6.3.1.1.5.3"
+    trap_oid: Option<String>,
+
+    /// The SNMPv1 enterprise, used together with the generic and specific trap instead of the trap_oid
+    enterprise: Option<String>,
+
+    /// The SNMPv1 generic trap, 0 (coldStart) - 6 (enterpriseSpecific)
+    generic_trap: Option<u32>,
+
+    /// The SNMPv1 specific trap code of the enterpriseSpecific trap
+    specific_trap: Option<u32>,
+
+    #[serde(default)]
+    variables: Vec<TrapVariable>,
+}
+
+#[derive(Debug, Deserialize, Apiv2Schema)]
+#[openapi(rename = "RequestTrapVariable")]
+/// A variable binding of the notification.
+pub struct TrapVariable {
+    /// The OID of the variable, f.e. ".1.3.6.1.2.1.2.2.1.1.1"
+    oid: String,
+
+    /// The data type as used in the SNMP data files, f.e. "INTEGER", "STRING", "OID"
+    data_type: String,
+
+    value: String,
+}
+
+impl TryFrom<Trap> for crate::domain::Notification {
+    type Error = DeviceError;
+
+    fn try_from(trap: Trap) -> Result<Self, Self::Error> {
+        if trap.targets.is_empty() {
+            return Err(DeviceError::Validation(
+                "at least one of 'targets' must be supplied".into(),
+            ));
+        }
+        let targets = trap
+            .targets
+            .iter()
+            .map(|target| {
+                target
+                    .parse::<SocketAddr>()
+                    .map_err(|_| DeviceError::Validation(format!("'targets' must be socket addresses, got '{target}'")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut variables = trap
+            .variables
+            .into_iter()
+            .map(crate::domain::Variable::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let trap_oid = match (trap.trap_oid, trap.enterprise, trap.generic_trap) {
+            (Some(trap_oid), None, None) => parse_oid("trap_oid", &trap_oid)?,
+            (None, enterprise, Some(generic_trap)) => {
+                let enterprise = enterprise
+                    .map(|enterprise| parse_oid("enterprise", &enterprise))
+                    .transpose()?;
+                let (trap_oid, enterprise) = v1_trap_oid(generic_trap, trap.specific_trap, enterprise)?;
+                if let Some(enterprise) = enterprise {
+                    variables.push(crate::domain::Variable {
+                        name: SNMP_TRAP_ENTERPRISE_OID,
+                        data_type: DataType::Oid,
+                        value: oid_to_string(enterprise),
+                    });
+                }
+                trap_oid
+            }
+            _ => {
+                return Err(DeviceError::Validation(
+                    "either 'trap_oid' or 'generic_trap' must be supplied in Trap".into(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            request_id: crate::domain::next_request_id(),
+            trap_oid,
+            variables,
+            targets,
+        })
+    }
+}
+
+impl TryFrom<TrapVariable> for crate::domain::Variable {
+    type Error = DeviceError;
+
+    fn try_from(variable: TrapVariable) -> Result<Self, Self::Error> {
+        let data_type = DataType::from_str(&variable.data_type).map_err(|_| {
+            DeviceError::Validation(format!("'data_type' is not supported, got '{}'", variable.data_type))
+        })?;
+        if !is_valid_value(&data_type, &variable.value) {
+            return Err(DeviceError::Validation(format!(
+                "'value' is not a valid {}, got '{}'",
+                variable.data_type, variable.value
+            )));
+        }
+
+        Ok(Self {
+            name: parse_oid("oid", &variable.oid)?,
+            data_type,
+            value: variable.value,
+        })
+    }
+}
+
+// the value has to be encodable by the BER encoder of the data type
+fn is_valid_value(data_type: &DataType, value: &str) -> bool {
+    match data_type {
+        DataType::Integer => value.parse::<i32>().is_ok(),
+        DataType::UInteger32 | DataType::Counter32 | DataType::Gauge32 | DataType::Timeticks => {
+            value.parse::<u32>().is_ok()
+        }
+        DataType::Counter64 => value.parse::<u64>().is_ok(),
+        DataType::IpAddress => value.parse::<Ipv4Addr>().is_ok(),
+        DataType::Oid => parse_oid("value", value).is_ok(),
+        _ => true,
+    }
+}
+
+// Maps the SNMPv1 trap to the snmpTrapOID (RFC 3584, 3.1), the generic traps keep the enterprise
+// in the snmpTrapEnterprise.0 variable, which is translated back when the SNMPv1 trap is sent
+fn v1_trap_oid(
+    generic_trap: u32,
+    specific_trap: Option<u32>,
+    enterprise: Option<ObjectIdentifier>,
+) -> Result<(ObjectIdentifier, Option<ObjectIdentifier>), DeviceError> {
+    let generic_traps = [
+        GenericTrap::ColdStart,
+        GenericTrap::WarmStart,
+        GenericTrap::LinkDown,
+        GenericTrap::LinkUp,
+        GenericTrap::AuthenticationFailure,
+        GenericTrap::EgpNeighborLoss,
+    ];
+
+    match (generic_traps.get(generic_trap as usize), enterprise) {
+        (Some(generic_trap), enterprise) => Ok((generic_trap.oid(), enterprise)),
+        (None, Some(enterprise)) if generic_trap == GenericTrap::EnterpriseSpecific as u32 => {
+            let specific_trap = specific_trap.ok_or_else(|| {
+                DeviceError::Validation("'specific_trap' must be supplied for the enterpriseSpecific trap".into())
+            })?;
+            let mut trap_oid = enterprise.to_vec();
+            trap_oid.extend([0, specific_trap]);
+            Ok((ObjectIdentifier::new_unchecked(trap_oid.into()), None))
+        }
+        (None, None) if generic_trap == GenericTrap::EnterpriseSpecific as u32 => Err(DeviceError::Validation(
+            "'enterprise' must be supplied for the enterpriseSpecific trap".into(),
+        )),
+        _ => Err(DeviceError::Validation(format!(
+            "'generic_trap' must be between 0 and 6, got {generic_trap}"
+        ))),
+    }
+}
+
+pub(crate) fn parse_oid(field: &str, oid: &str) -> Result<ObjectIdentifier, DeviceError> {
+    oid.trim_start_matches('.')
+        .split('.')
+        .map(|arc| arc.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()
+        .and_then(ObjectIdentifier::new)
+        .ok_or_else(|| DeviceError::Validation(format!("'{field}' must be an OID, got '{oid}'")))
+}
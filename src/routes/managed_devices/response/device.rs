@@ -1,6 +1,6 @@
 use crate::domain;
 use crate::routes::agents::response::Agent;
-use crate::routes::SnmpProtocolAttributes;
+use crate::routes::{AgentxAttributes, ProxyAttributes, SnmpProtocolAttributes, SnmpTransport};
 use paperclip::actix::Apiv2Schema;
 use uuid_dev::Uuid;
 
@@ -24,6 +24,25 @@ pub struct Device {
     pub snmp_port: u16,
 
     pub snmp_protocol_attributes: SnmpProtocolAttributes,
+
+    /// The upstream agent serving the variables not simulated by the device
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxyAttributes>,
+
+    /// The sysName.0 value served by the device instead of the one of the SNMP data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sys_name: Option<String>,
+
+    /// The sysLocation.0 value served by the device instead of the one of the SNMP data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sys_location: Option<String>,
+
+    /// The AgentX master agent dispatching the requests for the subtrees registered by the subagents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agentx: Option<AgentxAttributes>,
+
+    /// The transport the device receives the SNMP requests on
+    pub transport: SnmpTransport,
 }
 
 impl From<domain::ManagedDevice> for Device {
@@ -40,6 +59,11 @@ impl From<domain::ManagedDevice> for Device {
             snmp_protocol_attributes: managed_device.snmp_protocol_attributes.into(),
             snmp_host: managed_device.snmp_host,
             snmp_port: managed_device.snmp_port,
+            proxy: managed_device.proxy.map(ProxyAttributes::from),
+            sys_name: managed_device.sys_name,
+            sys_location: managed_device.sys_location,
+            agentx: managed_device.agentx.map(AgentxAttributes::from),
+            transport: managed_device.transport.into(),
         }
     }
 }
@@ -0,0 +1,54 @@
+use crate::domain;
+use paperclip::actix::Apiv2Schema;
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InformStatus {
+    Pending,
+    #[serde(rename = "acked")]
+    Acked,
+    Failed,
+}
+
+impl From<domain::InformStatus> for InformStatus {
+    fn from(status: domain::InformStatus) -> Self {
+        match status {
+            domain::InformStatus::Pending => InformStatus::Pending,
+            domain::InformStatus::Acknowledged => InformStatus::Acked,
+            domain::InformStatus::Failed => InformStatus::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema)]
+#[openapi(rename = "ResponseInform")]
+/// A confirmed notification sent by a managed device and its delivery status as a response body.
+pub struct Inform {
+    /// The request-id of the InformRequest PDU, used to query the delivery status.
+    pub request_id: i32,
+
+    /// The snmpTrapOID of the notification.
+    pub trap_oid: String,
+
+    /// The socket address of the manager the notification is sent to.
+    pub target: String,
+
+    /// The delivery status, pending until the manager acknowledges it or all the retransmissions time out.
+    pub status: InformStatus,
+
+    /// The number of times the notification was sent.
+    pub attempts: u32,
+}
+
+impl From<domain::Inform> for Inform {
+    fn from(inform: domain::Inform) -> Self {
+        Self {
+            request_id: inform.request_id,
+            trap_oid: oid_to_string(inform.trap_oid),
+            target: inform.target.to_string(),
+            status: inform.status.into(),
+            attempts: inform.attempts,
+        }
+    }
+}
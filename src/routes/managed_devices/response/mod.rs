@@ -1,3 +1,9 @@
 pub mod device;
+pub mod inform;
+pub mod statistics;
+pub mod trap;
 
 pub use device::*;
+pub use inform::*;
+pub use statistics::*;
+pub use trap::*;
@@ -0,0 +1,74 @@
+use crate::snmp::snmp_statistics::{SnmpCounter, SnmpStatisticsSnapshot};
+use paperclip::actix::Apiv2Schema;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema)]
+#[openapi(rename = "ResponseStatistics")]
+/// The counters of the snmp group (RFC 3418) maintained by a running managed device as a response body.
+pub struct Statistics {
+    /// snmpInPkts, the number of messages delivered to the device.
+    pub in_pkts: u32,
+
+    /// snmpOutPkts, the number of messages sent by the device.
+    pub out_pkts: u32,
+
+    /// snmpInBadVersions, the number of messages of an unsupported SNMP version.
+    pub in_bad_versions: u32,
+
+    /// snmpInBadCommunityNames, the number of messages of a community not known to the device.
+    pub in_bad_community_names: u32,
+
+    /// snmpInBadCommunityUses, the number of messages requesting an operation not allowed by the community.
+    pub in_bad_community_uses: u32,
+
+    /// snmpInASNParseErrs, the number of messages which could not be decoded.
+    pub in_asn_parse_errs: u32,
+
+    /// snmpInGetRequests, the number of processed GetRequest PDUs.
+    pub in_get_requests: u32,
+
+    /// snmpInGetNexts, the number of processed GetNextRequest PDUs.
+    pub in_get_nexts: u32,
+
+    /// snmpInSetRequests, the number of processed SetRequest PDUs.
+    pub in_set_requests: u32,
+
+    /// snmpInGetResponses, the number of received Response PDUs.
+    pub in_get_responses: u32,
+
+    /// snmpOutTooBigs, the number of Response PDUs sent with the tooBig error.
+    pub out_too_bigs: u32,
+
+    /// snmpOutGetResponses, the number of Response PDUs sent.
+    pub out_get_responses: u32,
+
+    /// snmpOutTraps, the number of notifications sent.
+    pub out_traps: u32,
+
+    /// snmpSilentDrops, the number of requests dropped because even the empty response exceeds the maximum message size.
+    pub silent_drops: u32,
+
+    /// snmpProxyDrops, the number of requests dropped because the upstream agent did not respond.
+    pub proxy_drops: u32,
+}
+
+impl From<SnmpStatisticsSnapshot> for Statistics {
+    fn from(statistics: SnmpStatisticsSnapshot) -> Self {
+        Self {
+            in_pkts: statistics.value(SnmpCounter::InPkts),
+            out_pkts: statistics.value(SnmpCounter::OutPkts),
+            in_bad_versions: statistics.value(SnmpCounter::InBadVersions),
+            in_bad_community_names: statistics.value(SnmpCounter::InBadCommunityNames),
+            in_bad_community_uses: statistics.value(SnmpCounter::InBadCommunityUses),
+            in_asn_parse_errs: statistics.value(SnmpCounter::InASNParseErrs),
+            in_get_requests: statistics.value(SnmpCounter::InGetRequests),
+            in_get_nexts: statistics.value(SnmpCounter::InGetNexts),
+            in_set_requests: statistics.value(SnmpCounter::InSetRequests),
+            in_get_responses: statistics.value(SnmpCounter::InGetResponses),
+            out_too_bigs: statistics.value(SnmpCounter::OutTooBigs),
+            out_get_responses: statistics.value(SnmpCounter::OutGetResponses),
+            out_traps: statistics.value(SnmpCounter::OutTraps),
+            silent_drops: statistics.value(SnmpCounter::SilentDrops),
+            proxy_drops: statistics.value(SnmpCounter::ProxyDrops),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::domain;
+use paperclip::actix::Apiv2Schema;
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema)]
+#[openapi(rename = "ResponseTrap")]
+/// A notification sent by a managed device as a response body.
+pub struct Trap {
+    /// The request-id of the notification PDU.
+    pub request_id: i32,
+
+    /// The snmpTrapOID of the notification.
+    pub trap_oid: String,
+
+    /// The socket addresses of the managers the notification was sent to.
+    pub targets: Vec<String>,
+}
+
+impl From<domain::Notification> for Trap {
+    fn from(notification: domain::Notification) -> Self {
+        Self {
+            request_id: notification.request_id,
+            trap_oid: oid_to_string(notification.trap_oid),
+            targets: notification
+                .targets
+                .iter()
+                .map(|target| target.to_string())
+                .collect(),
+        }
+    }
+}
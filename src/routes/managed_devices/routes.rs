@@ -136,6 +136,73 @@ async fn post_device_stop(
     }
 }
 
+#[post("/devices/{id}/traps")]
+#[api_v2_operation(tags("Devices"), consumes = "application/json")]
+/// Send a notification from a running managed device
+async fn post_device_trap(
+    id: web::Path<Uuid>,
+    form: Json<request::Trap>,
+    conn: Data<DatabaseConnection>,
+    udp_server: web::Data<UdpServerDelegate>,
+) -> Result<PostResponse<response::Trap>, JsonError<DeviceError>> {
+    let notification: crate::domain::Notification = form.0.try_into()?;
+
+    let notification =
+        crate::domain::send_managed_device_notification(conn.as_ref(), id.as_ref(), notification, udp_server.as_ref())
+            .await
+            .map_err(DeviceError::from)?;
+
+    Ok(PostResponse::Created(response::Trap::from(notification)))
+}
+
+#[post("/devices/{id}/informs")]
+#[api_v2_operation(tags("Devices"), consumes = "application/json")]
+/// Send a confirmed notification from a running managed device
+async fn post_device_inform(
+    id: web::Path<Uuid>,
+    form: Json<request::Inform>,
+    conn: Data<DatabaseConnection>,
+    udp_server: web::Data<UdpServerDelegate>,
+) -> Result<PostResponse<response::Inform>, JsonError<DeviceError>> {
+    let inform: crate::domain::Inform = form.0.try_into()?;
+
+    let inform = crate::domain::send_managed_device_inform(conn.as_ref(), id.as_ref(), inform, udp_server.as_ref())
+        .await
+        .map_err(DeviceError::from)?;
+
+    Ok(PostResponse::Created(response::Inform::from(inform)))
+}
+
+#[get("/devices/{id}/informs/{request_id}")]
+#[api_v2_operation(tags("Devices"))]
+/// Get the delivery status of a confirmed notification sent by a managed device
+async fn get_device_inform(
+    path: web::Path<(Uuid, i32)>,
+    conn: Data<DatabaseConnection>,
+) -> Result<GetResponse<response::Inform>, JsonError<DeviceError>> {
+    let (id, request_id) = path.into_inner();
+    let inform = crate::domain::get_managed_device_inform(conn.as_ref(), &id, request_id)
+        .await
+        .map_err(DeviceError::from)?;
+
+    Ok(GetResponse::Ok(response::Inform::from(inform)))
+}
+
+#[get("/devices/{id}/statistics")]
+#[api_v2_operation(tags("Devices"))]
+/// Get the SNMP statistics of a running managed device
+async fn get_device_statistics(
+    id: web::Path<Uuid>,
+    conn: Data<DatabaseConnection>,
+    udp_server: web::Data<UdpServerDelegate>,
+) -> Result<GetResponse<response::Statistics>, JsonError<DeviceError>> {
+    let statistics = crate::domain::get_managed_device_statistics(conn.as_ref(), id.as_ref(), udp_server.as_ref())
+        .await
+        .map_err(DeviceError::from)?;
+
+    Ok(GetResponse::Ok(response::Statistics::from(statistics)))
+}
+
 pub fn devices_config(cfg: &mut ServiceConfig) {
     cfg.service(post_device);
     cfg.service(get_device);
@@ -144,4 +211,8 @@ pub fn devices_config(cfg: &mut ServiceConfig) {
     cfg.service(update_device);
     cfg.service(post_device_start);
     cfg.service(post_device_stop);
+    cfg.service(post_device_trap);
+    cfg.service(post_device_inform);
+    cfg.service(get_device_inform);
+    cfg.service(get_device_statistics);
 }
@@ -1,8 +1,10 @@
 pub mod agents;
 pub mod managed_devices;
+pub mod notifications;
 
 pub use agents::*;
 pub use managed_devices::*;
+pub use notifications::*;
 
 use paperclip::actix::Apiv2Schema;
 use serde::Deserialize;
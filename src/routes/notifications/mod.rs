@@ -0,0 +1,9 @@
+mod notification_error;
+pub mod request;
+pub mod response;
+mod routes;
+
+pub use notification_error::*;
+pub use request::*;
+pub use response::*;
+pub use routes::*;
@@ -0,0 +1,71 @@
+use crate::domain::DomainError;
+use actix_web::http::StatusCode;
+use actix_web::ResponseError;
+use paperclip::actix::api_v2_errors;
+use shared_common::error_chain_fmt;
+use std::convert::Infallible;
+
+#[api_v2_errors(
+    code = 400,
+    description = "Bad request format",
+    code = 404,
+    description = "Not Found",
+    code = 409,
+    description = "Conflict",
+    code = 500,
+    description = "Internal server error"
+)]
+#[derive(thiserror::Error)]
+pub enum NotificationError {
+    #[error("Forbidden")]
+    Forbidden,
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error(transparent)]
+    Unexpected(#[from] anyhow::Error),
+}
+
+impl std::fmt::Debug for NotificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl From<Infallible> for NotificationError {
+    fn from(_: Infallible) -> Self {
+        unreachable!("could not convert Infallible to NotificationError")
+    }
+}
+
+impl ResponseError for NotificationError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            NotificationError::Forbidden => StatusCode::FORBIDDEN,
+            NotificationError::Validation(_) => StatusCode::BAD_REQUEST,
+            NotificationError::NotFound(_) => StatusCode::NOT_FOUND,
+            NotificationError::Conflict(_) => StatusCode::CONFLICT,
+            NotificationError::Unexpected(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<DomainError> for NotificationError {
+    fn from(domain_error: DomainError) -> Self {
+        match domain_error {
+            DomainError::Validation(details) => NotificationError::Validation(details),
+            DomainError::NotFound(details) => NotificationError::NotFound(details),
+            DomainError::Conflict(details) => NotificationError::Conflict(details),
+            DomainError::Unexpected(details) => NotificationError::Unexpected(details),
+            DomainError::UdpServerError(details) => NotificationError::Conflict(details.to_string()),
+            DomainError::ClientError(details) => NotificationError::Unexpected(details.into()),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::routes::{first, twenty, NotificationError};
+use chrono::{DateTime, Utc};
+use paperclip::actix::Apiv2Schema;
+use rasn::prelude::ObjectIdentifier;
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::net::{IpAddr, SocketAddr};
+
+#[derive(Debug, Deserialize, Apiv2Schema)]
+pub struct GetNotificationsQuery {
+    #[serde(default = "first")]
+    /// Page index starts from one, default value is 1.
+    pub page: Option<usize>,
+
+    /// Number of results on a page, default value is 20.
+    #[serde(default = "twenty")]
+    pub page_size: Option<usize>,
+
+    /// The IP address or the socket address of the notification sender, f.e. "127.0.0.1" or "127.0.0.1:30161"
+    pub source: Option<String>,
+
+    /// The snmpTrapOID of the notification, f.e. ".1.3.6.1.6.3.1.1.5.3"
+    pub trap_oid: Option<String>,
+
+    /// The notifications received at or after the RFC 3339 time, f.e. "2022-10-18T09:35:12Z"
+    pub from: Option<String>,
+
+    /// The notifications received at or before the RFC 3339 time, f.e. "2022-10-18T10:35:12Z"
+    pub to: Option<String>,
+}
+
+impl TryFrom<&GetNotificationsQuery> for crate::domain::NotificationFilter {
+    type Error = NotificationError;
+
+    fn try_from(query: &GetNotificationsQuery) -> Result<Self, Self::Error> {
+        let (source_address, source_port) = match &query.source {
+            Some(source) => match (source.parse::<SocketAddr>(), source.parse::<IpAddr>()) {
+                (Ok(source), _) => (Some(source.ip()), Some(source.port())),
+                (_, Ok(source)) => (Some(source), None),
+                _ => {
+                    return Err(NotificationError::Validation(format!(
+                        "'source' must be an IP address or a socket address, got '{source}'"
+                    )))
+                }
+            },
+            None => (None, None),
+        };
+
+        Ok(Self {
+            source_address,
+            source_port,
+            trap_oid: query
+                .trap_oid
+                .as_ref()
+                .map(|trap_oid| parse_oid("trap_oid", trap_oid))
+                .transpose()?,
+            received_from: query
+                .from
+                .as_ref()
+                .map(|from| parse_time("from", from))
+                .transpose()?,
+            received_to: query
+                .to
+                .as_ref()
+                .map(|to| parse_time("to", to))
+                .transpose()?,
+        })
+    }
+}
+
+fn parse_oid(field: &str, oid: &str) -> Result<ObjectIdentifier, NotificationError> {
+    oid.trim_start_matches('.')
+        .split('.')
+        .map(|arc| arc.parse::<u32>().ok())
+        .collect::<Option<Vec<u32>>>()
+        .and_then(ObjectIdentifier::new)
+        .ok_or_else(|| NotificationError::Validation(format!("'{field}' must be an OID, got '{oid}'")))
+}
+
+fn parse_time(field: &str, time: &str) -> Result<DateTime<Utc>, NotificationError> {
+    DateTime::parse_from_rfc3339(time)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| NotificationError::Validation(format!("'{field}' must be an RFC 3339 time, got '{time}'")))
+}
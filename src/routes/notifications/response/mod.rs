@@ -0,0 +1,3 @@
+pub mod notification;
+
+pub use notification::*;
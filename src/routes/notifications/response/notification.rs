@@ -0,0 +1,87 @@
+use crate::domain;
+use chrono::SecondsFormat;
+use paperclip::actix::Apiv2Schema;
+use snmp_data_parser::parser::snmp_data::component::oid_to_string;
+use uuid_dev::Uuid;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema)]
+#[openapi(rename = "ResponseNotification")]
+/// A notification received by the trap receiver as a response body.
+pub struct Notification {
+    /// The unique identifier of this notification.
+    pub id: Uuid,
+
+    /// The RFC 3339 time the notification was received at.
+    pub received_at: String,
+
+    /// The socket address of the notification sender.
+    pub source: String,
+
+    /// The SNMP version of the notification, i.e. "SNMPv1", "SNMPv2c" or "SNMPv3".
+    pub snmp_version: String,
+
+    /// The PDU type of the notification, i.e. "Trap" or "InformRequest".
+    pub pdu_type: String,
+
+    /// The community of SNMPv1/SNMPv2c, or the user name of SNMPv3.
+    pub security_name: String,
+
+    /// The request-id of the notification PDU, the SNMPv1 traps have no request-id.
+    pub request_id: i32,
+
+    /// The snmpTrapOID of the notification, the SNMPv1 traps are translated as described by RFC 3584.
+    pub trap_oid: String,
+
+    /// The variable bindings following the sysUpTime.0 and snmpTrapOID.0.
+    pub variables: Vec<NotificationVariable>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema)]
+#[openapi(rename = "ResponseNotificationVariable")]
+/// A variable binding of the received notification.
+pub struct NotificationVariable {
+    /// The OID of the variable, f.e. ".1.3.6.1.2.1.2.2.1.1.1"
+    pub oid: String,
+
+    /// The data type as used in the SNMP data files, f.e. "INTEGER", "STRING", "OID"
+    pub data_type: String,
+
+    pub value: String,
+}
+
+impl From<domain::ReceivedNotification> for Notification {
+    fn from(notification: domain::ReceivedNotification) -> Self {
+        Self {
+            id: notification.id,
+            received_at: notification
+                .received_at
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+            source: notification.source.to_string(),
+            snmp_version: notification.snmp_version.to_string(),
+            pdu_type: notification.pdu_type.to_string(),
+            security_name: notification.security_name,
+            request_id: notification.request_id,
+            trap_oid: oid_to_string(notification.trap_oid),
+            variables: notification
+                .variables
+                .into_iter()
+                .map(|variable| NotificationVariable {
+                    oid: oid_to_string(variable.name),
+                    data_type: variable.data_type.to_string(),
+                    value: variable.value,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Apiv2Schema)]
+#[openapi(rename = "ResponseNotifications")]
+/// List of received notifications and the number of all items as a response body.
+pub struct Notifications {
+    /// The number of all items
+    pub count: usize,
+
+    /// List of notifications, the latest first
+    pub items: Vec<Notification>,
+}
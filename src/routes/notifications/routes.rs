@@ -0,0 +1,36 @@
+use super::response;
+use crate::routes::{GetNotificationsQuery, NotificationError};
+use paperclip::actix::{
+    api_v2_operation, get,
+    web::{self, Data, ServiceConfig},
+};
+use paperclip_restful::{GetResponse, JsonError};
+use sea_orm::DatabaseConnection;
+use std::convert::TryInto;
+
+#[get("/notifications")]
+#[api_v2_operation(tags("Notifications"))]
+/// List notifications received by the trap receiver
+async fn list_notifications(
+    conn: Data<DatabaseConnection>,
+    web::Query(query): web::Query<GetNotificationsQuery>,
+) -> Result<GetResponse<response::Notifications>, JsonError<NotificationError>> {
+    let filter: crate::domain::NotificationFilter = (&query).try_into()?;
+
+    let (count, notifications) =
+        crate::domain::list_notifications(conn.as_ref(), &filter, query.page.unwrap(), query.page_size.unwrap())
+            .await
+            .map_err(NotificationError::from)?;
+
+    Ok(GetResponse::Ok(response::Notifications {
+        count,
+        items: notifications
+            .into_iter()
+            .map(response::Notification::from)
+            .collect(),
+    }))
+}
+
+pub fn notifications_config(cfg: &mut ServiceConfig) {
+    cfg.service(list_notifications);
+}
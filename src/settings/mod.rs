@@ -1,5 +1,7 @@
 mod app_settings;
 mod database_settings;
+mod trap_receiver_settings;
 
 pub use app_settings::*;
 pub use database_settings::*;
+pub use trap_receiver_settings::*;
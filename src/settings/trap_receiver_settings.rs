@@ -0,0 +1,19 @@
+use crate::domain::SnmpV3Attributes;
+use serde_aux::field_attributes::deserialize_number_from_string;
+
+#[derive(serde::Deserialize, Clone)]
+/// Structure representing the settings of the built-in trap receiver
+pub struct TrapReceiverSettings {
+    /// The IP address the trap receiver is listening on for the notifications.
+    pub host: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    /// The UDP port the trap receiver is listening on, f.e. 162.
+    pub port: u16,
+    #[serde(default)]
+    /// The communities of the accepted SNMPv1/SNMPv2c notifications, the
+    /// notifications of any community are accepted if no community is listed.
+    pub communities: Vec<String>,
+    #[serde(default)]
+    /// The USM users of the accepted SNMPv3 notifications.
+    pub(crate) users: Vec<SnmpV3Attributes>,
+}
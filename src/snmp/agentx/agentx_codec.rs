@@ -0,0 +1,71 @@
+use crate::snmp::agentx::agentx_error::AgentxError;
+use crate::snmp::agentx::agentx_pdu::{payload_length, AgentxPdu, AGENTX_VERSION, HEADER_LEN};
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// The codec of the AgentX PDUs exchanged over the stream connection of a subagent (RFC 2741, 6)
+#[derive(Debug)]
+pub struct AgentxCodec {
+    // the decoder refuses the PDUs exceeding the limit, the connection is closed then
+    max_payload_size: usize,
+}
+
+impl AgentxCodec {
+    pub const MAX_PAYLOAD_SIZE: usize = 65536;
+
+    pub fn new() -> Self {
+        AgentxCodec::default()
+    }
+}
+
+impl Default for AgentxCodec {
+    fn default() -> Self {
+        AgentxCodec {
+            max_payload_size: AgentxCodec::MAX_PAYLOAD_SIZE,
+        }
+    }
+}
+
+impl Decoder for AgentxCodec {
+    type Item = AgentxPdu;
+    type Error = AgentxError;
+
+    #[tracing::instrument(level = "info", name = "AgentxCodec::decode", skip(self, data))]
+    fn decode(&mut self, data: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if data.len() < HEADER_LEN {
+            // more bytes needs to be read
+            data.reserve(HEADER_LEN - data.len());
+            return Ok(None);
+        }
+
+        // the stream can't be resynchronized after an invalid header => the connection is closed
+        if data[0] != AGENTX_VERSION {
+            return Err(AgentxError::UnsupportedVersion(data[0]));
+        }
+        let payload_length = payload_length(data);
+        if payload_length > self.max_payload_size {
+            return Err(AgentxError::PayloadTooLarge(payload_length, self.max_payload_size));
+        }
+        if data.len() < HEADER_LEN + payload_length {
+            data.reserve(HEADER_LEN + payload_length - data.len());
+            return Ok(None);
+        }
+
+        let frame = data.split_to(HEADER_LEN + payload_length);
+        tracing::debug!("Received AgentX PDU: {:02X?}", frame.as_ref());
+
+        // the payload errors are reported in-band by the Malformed payload, the session continues
+        Ok(Some(AgentxPdu::decode(&frame)))
+    }
+}
+
+impl Encoder<AgentxPdu> for AgentxCodec {
+    type Error = AgentxError;
+
+    #[tracing::instrument(level = "info", name = "AgentxCodec::encode", skip(self, pdu))]
+    fn encode(&mut self, pdu: AgentxPdu, result: &mut BytesMut) -> Result<(), Self::Error> {
+        pdu.encode(result)?;
+        tracing::debug!("Sending AgentX PDU: {:02X?}", result.as_ref());
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use shared_common::error_chain_fmt;
+use std::convert::Infallible;
+
+#[derive(thiserror::Error)]
+pub enum AgentxError {
+    #[error("Unsupported AgentX protocol version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("The AgentX payload of {0} octets exceeds the maximum payload size {1}")]
+    PayloadTooLarge(usize, usize),
+
+    #[error("Malformed AgentX PDU: {0}")]
+    Malformed(String),
+
+    #[error("The subagent did not respond within {0} ms")]
+    Timeout(u128),
+
+    #[error("The subagent session {0} is closed")]
+    SessionClosed(u32),
+
+    #[error("The subagent responded with the error {error} at the index {index}")]
+    Response { error: u16, index: usize },
+
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+}
+
+impl std::fmt::Debug for AgentxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        error_chain_fmt(self, f)
+    }
+}
+
+impl From<Infallible> for AgentxError {
+    fn from(_: Infallible) -> Self {
+        unreachable!("could not convert Infallible to AgentxError")
+    }
+}
@@ -0,0 +1,771 @@
+use crate::snmp::agentx::agentx_error::AgentxError;
+use bytes::{BufMut, BytesMut};
+use rasn::prelude::ObjectIdentifier;
+
+// the only version of the AgentX protocol (RFC 2741, 6.1)
+pub const AGENTX_VERSION: u8 = 1;
+
+pub const HEADER_LEN: usize = 20;
+
+// h.flags (RFC 2741, 6.1)
+pub const INSTANCE_REGISTRATION: u8 = 0x01;
+pub const NON_DEFAULT_CONTEXT: u8 = 0x08;
+pub const NETWORK_BYTE_ORDER: u8 = 0x10;
+
+// the OIDs of the internet subtree are shortened by the prefix field, i.e. 1.3.6.1.<prefix> (RFC 2741, 5.1)
+const INTERNET: [u32; 4] = [1, 3, 6, 1];
+
+/// The type of the AgentX PDU (RFC 2741, 6.1)
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum PduType {
+    Open = 1,
+    Close = 2,
+    Register = 3,
+    Unregister = 4,
+    Get = 5,
+    GetNext = 6,
+    GetBulk = 7,
+    TestSet = 8,
+    CommitSet = 9,
+    UndoSet = 10,
+    CleanupSet = 11,
+    Notify = 12,
+    Ping = 13,
+    IndexAllocate = 14,
+    IndexDeallocate = 15,
+    AddAgentCaps = 16,
+    RemoveAgentCaps = 17,
+    Response = 18,
+}
+
+impl TryFrom<u8> for PduType {
+    type Error = AgentxError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        const TYPES: [PduType; 18] = [
+            PduType::Open,
+            PduType::Close,
+            PduType::Register,
+            PduType::Unregister,
+            PduType::Get,
+            PduType::GetNext,
+            PduType::GetBulk,
+            PduType::TestSet,
+            PduType::CommitSet,
+            PduType::UndoSet,
+            PduType::CleanupSet,
+            PduType::Notify,
+            PduType::Ping,
+            PduType::IndexAllocate,
+            PduType::IndexDeallocate,
+            PduType::AddAgentCaps,
+            PduType::RemoveAgentCaps,
+            PduType::Response,
+        ];
+        TYPES
+            .iter()
+            .find(|pdu_type| **pdu_type as u8 == value)
+            .copied()
+            .ok_or_else(|| AgentxError::Malformed(format!("unknown PDU type {value}")))
+    }
+}
+
+impl PduType {
+    // the PDUs of the non-default context are prefixed by the context octet string (RFC 2741, 6.1.1)
+    fn has_context(&self) -> bool {
+        !matches!(
+            self,
+            PduType::Open
+                | PduType::Close
+                | PduType::CommitSet
+                | PduType::UndoSet
+                | PduType::CleanupSet
+                | PduType::Response
+        )
+    }
+}
+
+/// The res.error values of the Response-PDU (RFC 2741, 6.2.16), the SNMP error status values are reported as well
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum AgentxErrorStatus {
+    NoAgentxError = 0,
+    OpenFailed = 256,
+    NotOpen = 257,
+    IndexWrongType = 258,
+    IndexAlreadyAllocated = 259,
+    IndexNoneAvailable = 260,
+    IndexNotAllocated = 261,
+    UnsupportedContext = 262,
+    DuplicateRegistration = 263,
+    UnknownRegistration = 264,
+    UnknownAgentCaps = 265,
+    ParseError = 266,
+    RequestDenied = 267,
+    ProcessingError = 268,
+}
+
+/// The reason of the Close-PDU (RFC 2741, 6.2.2)
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum CloseReason {
+    Other = 1,
+    ParseError = 2,
+    ProtocolError = 3,
+    Timeouts = 4,
+    Shutdown = 5,
+    ByManager = 6,
+}
+
+/// The value of the variable binding (RFC 2741, 5.4)
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentxValue {
+    Integer(i32),
+    OctetString(Vec<u8>),
+    Null,
+    ObjectIdentifier(ObjectIdentifier),
+    IpAddress(Vec<u8>),
+    Counter32(u32),
+    Gauge32(u32),
+    TimeTicks(u32),
+    Opaque(Vec<u8>),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+}
+
+impl AgentxValue {
+    fn type_code(&self) -> u16 {
+        match self {
+            AgentxValue::Integer(_) => 2,
+            AgentxValue::OctetString(_) => 4,
+            AgentxValue::Null => 5,
+            AgentxValue::ObjectIdentifier(_) => 6,
+            AgentxValue::IpAddress(_) => 64,
+            AgentxValue::Counter32(_) => 65,
+            AgentxValue::Gauge32(_) => 66,
+            AgentxValue::TimeTicks(_) => 67,
+            AgentxValue::Opaque(_) => 68,
+            AgentxValue::Counter64(_) => 70,
+            AgentxValue::NoSuchObject => 128,
+            AgentxValue::NoSuchInstance => 129,
+            AgentxValue::EndOfMibView => 130,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentxVarBind {
+    pub name: ObjectIdentifier,
+    pub value: AgentxValue,
+}
+
+/// The range of the variables requested by the master agent, the end is unbounded if it is the null OID
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchRange {
+    pub start: ObjectIdentifier,
+    pub include: bool,
+    pub end: ObjectIdentifier,
+}
+
+/// The registered subtree, the sub-identifier at the range_subid position spans up to the upper_bound (RFC 2741, 6.2.3)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtreeRegion {
+    pub priority: u8,
+    pub range_subid: u8,
+    pub subtree: ObjectIdentifier,
+    pub upper_bound: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgentxPayload {
+    Open {
+        timeout: u8,
+        id: ObjectIdentifier,
+        description: Vec<u8>,
+    },
+    Close {
+        reason: u8,
+    },
+    Register {
+        timeout: u8,
+        region: SubtreeRegion,
+    },
+    Unregister {
+        region: SubtreeRegion,
+    },
+    Get {
+        ranges: Vec<SearchRange>,
+    },
+    GetNext {
+        ranges: Vec<SearchRange>,
+    },
+    GetBulk {
+        non_repeaters: u16,
+        max_repetitions: u16,
+        ranges: Vec<SearchRange>,
+    },
+    TestSet {
+        var_binds: Vec<AgentxVarBind>,
+    },
+    CommitSet,
+    UndoSet,
+    CleanupSet,
+    Notify {
+        var_binds: Vec<AgentxVarBind>,
+    },
+    Ping,
+    IndexAllocate {
+        var_binds: Vec<AgentxVarBind>,
+    },
+    IndexDeallocate {
+        var_binds: Vec<AgentxVarBind>,
+    },
+    AddAgentCaps {
+        id: ObjectIdentifier,
+        description: Vec<u8>,
+    },
+    RemoveAgentCaps {
+        id: ObjectIdentifier,
+    },
+    Response {
+        sys_up_time: u32,
+        error: u16,
+        index: u16,
+        var_binds: Vec<AgentxVarBind>,
+    },
+    /// The PDU of a valid header which could not be decoded, it is answered by the parseError
+    Malformed(String),
+}
+
+impl AgentxPayload {
+    pub fn pdu_type(&self) -> Option<PduType> {
+        Some(match self {
+            AgentxPayload::Open { .. } => PduType::Open,
+            AgentxPayload::Close { .. } => PduType::Close,
+            AgentxPayload::Register { .. } => PduType::Register,
+            AgentxPayload::Unregister { .. } => PduType::Unregister,
+            AgentxPayload::Get { .. } => PduType::Get,
+            AgentxPayload::GetNext { .. } => PduType::GetNext,
+            AgentxPayload::GetBulk { .. } => PduType::GetBulk,
+            AgentxPayload::TestSet { .. } => PduType::TestSet,
+            AgentxPayload::CommitSet => PduType::CommitSet,
+            AgentxPayload::UndoSet => PduType::UndoSet,
+            AgentxPayload::CleanupSet => PduType::CleanupSet,
+            AgentxPayload::Notify { .. } => PduType::Notify,
+            AgentxPayload::Ping => PduType::Ping,
+            AgentxPayload::IndexAllocate { .. } => PduType::IndexAllocate,
+            AgentxPayload::IndexDeallocate { .. } => PduType::IndexDeallocate,
+            AgentxPayload::AddAgentCaps { .. } => PduType::AddAgentCaps,
+            AgentxPayload::RemoveAgentCaps { .. } => PduType::RemoveAgentCaps,
+            AgentxPayload::Response { .. } => PduType::Response,
+            AgentxPayload::Malformed(_) => return None,
+        })
+    }
+}
+
+/// The header fields identifying the session, the transaction and the PDU itself (RFC 2741, 6.1)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentxHeader {
+    pub flags: u8,
+    pub session_id: u32,
+    pub transaction_id: u32,
+    pub packet_id: u32,
+}
+
+impl AgentxHeader {
+    pub fn is_network_byte_order(&self) -> bool {
+        self.flags & NETWORK_BYTE_ORDER != 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentxPdu {
+    pub header: AgentxHeader,
+    /// The non-default context of the PDU, the default context if None
+    pub context: Option<Vec<u8>>,
+    pub payload: AgentxPayload,
+}
+
+impl AgentxPdu {
+    /// The Response-PDU to this PDU, in the byte order of this PDU (RFC 2741, 6.2.16)
+    pub fn response(&self, sys_up_time: u32, error: u16, index: u16, var_binds: Vec<AgentxVarBind>) -> AgentxPdu {
+        AgentxPdu {
+            header: AgentxHeader {
+                flags: self.header.flags & NETWORK_BYTE_ORDER,
+                ..self.header.clone()
+            },
+            context: None,
+            payload: AgentxPayload::Response {
+                sys_up_time,
+                error,
+                index,
+                var_binds,
+            },
+        }
+    }
+
+    /// Decodes the whole PDU, the header is expected to be validated by the codec already
+    pub fn decode(data: &[u8]) -> AgentxPdu {
+        let flags = data[2];
+        let mut header_reader = Reader::new(&data[4..HEADER_LEN], flags & NETWORK_BYTE_ORDER != 0);
+        let header = AgentxHeader {
+            flags,
+            session_id: header_reader.u32().unwrap_or_default(),
+            transaction_id: header_reader.u32().unwrap_or_default(),
+            packet_id: header_reader.u32().unwrap_or_default(),
+        };
+
+        let mut reader = Reader::new(&data[HEADER_LEN..], header.is_network_byte_order());
+        match decode_payload(data[1], flags, &mut reader) {
+            Ok((context, payload)) => AgentxPdu {
+                header,
+                context,
+                payload,
+            },
+            Err(error) => AgentxPdu {
+                header,
+                context: None,
+                payload: AgentxPayload::Malformed(error.to_string()),
+            },
+        }
+    }
+
+    pub fn encode(&self, dst: &mut BytesMut) -> Result<(), AgentxError> {
+        let pdu_type = self
+            .payload
+            .pdu_type()
+            .ok_or_else(|| AgentxError::Malformed("the malformed PDU can't be encoded".into()))?;
+        let context = self.context.as_ref().filter(|_| pdu_type.has_context());
+
+        let mut payload = Writer::new(self.header.is_network_byte_order());
+        if let Some(context) = context {
+            payload.octet_string(context);
+        }
+        encode_payload(&self.payload, &mut payload);
+
+        let mut flags = self.header.flags & !NON_DEFAULT_CONTEXT;
+        if context.is_some() {
+            flags |= NON_DEFAULT_CONTEXT;
+        }
+        let mut header = Writer::new(self.header.is_network_byte_order());
+        header.u8(AGENTX_VERSION);
+        header.u8(pdu_type as u8);
+        header.u8(flags);
+        header.u8(0);
+        header.u32(self.header.session_id);
+        header.u32(self.header.transaction_id);
+        header.u32(self.header.packet_id);
+        header.u32(payload.data.len() as u32);
+
+        dst.reserve(HEADER_LEN + payload.data.len());
+        dst.extend_from_slice(&header.data);
+        dst.extend_from_slice(&payload.data);
+        Ok(())
+    }
+}
+
+/// Reads the payload length of the header, in the byte order given by its flags
+pub fn payload_length(header: &[u8]) -> usize {
+    let bytes = [header[16], header[17], header[18], header[19]];
+    if header[2] & NETWORK_BYTE_ORDER != 0 {
+        u32::from_be_bytes(bytes) as usize
+    } else {
+        u32::from_le_bytes(bytes) as usize
+    }
+}
+
+fn decode_payload(
+    pdu_type: u8,
+    flags: u8,
+    reader: &mut Reader,
+) -> Result<(Option<Vec<u8>>, AgentxPayload), AgentxError> {
+    let pdu_type = PduType::try_from(pdu_type)?;
+    let context = if pdu_type.has_context() && flags & NON_DEFAULT_CONTEXT != 0 {
+        Some(reader.octet_string()?)
+    } else {
+        None
+    };
+
+    let payload = match pdu_type {
+        PduType::Open => {
+            let timeout = reader.u8()?;
+            reader.skip(3)?;
+            AgentxPayload::Open {
+                timeout,
+                id: reader.oid()?.0,
+                description: reader.octet_string()?,
+            }
+        }
+        PduType::Close => {
+            let reason = reader.u8()?;
+            reader.skip(3)?;
+            AgentxPayload::Close { reason }
+        }
+        PduType::Register => {
+            let timeout = reader.u8()?;
+            AgentxPayload::Register {
+                timeout,
+                region: reader.region()?,
+            }
+        }
+        PduType::Unregister => {
+            reader.skip(1)?;
+            AgentxPayload::Unregister {
+                region: reader.region()?,
+            }
+        }
+        PduType::Get => AgentxPayload::Get {
+            ranges: reader.search_ranges()?,
+        },
+        PduType::GetNext => AgentxPayload::GetNext {
+            ranges: reader.search_ranges()?,
+        },
+        PduType::GetBulk => AgentxPayload::GetBulk {
+            non_repeaters: reader.u16()?,
+            max_repetitions: reader.u16()?,
+            ranges: reader.search_ranges()?,
+        },
+        PduType::TestSet => AgentxPayload::TestSet {
+            var_binds: reader.var_binds()?,
+        },
+        PduType::CommitSet => AgentxPayload::CommitSet,
+        PduType::UndoSet => AgentxPayload::UndoSet,
+        PduType::CleanupSet => AgentxPayload::CleanupSet,
+        PduType::Notify => AgentxPayload::Notify {
+            var_binds: reader.var_binds()?,
+        },
+        PduType::Ping => AgentxPayload::Ping,
+        PduType::IndexAllocate => AgentxPayload::IndexAllocate {
+            var_binds: reader.var_binds()?,
+        },
+        PduType::IndexDeallocate => AgentxPayload::IndexDeallocate {
+            var_binds: reader.var_binds()?,
+        },
+        PduType::AddAgentCaps => AgentxPayload::AddAgentCaps {
+            id: reader.oid()?.0,
+            description: reader.octet_string()?,
+        },
+        PduType::RemoveAgentCaps => AgentxPayload::RemoveAgentCaps { id: reader.oid()?.0 },
+        PduType::Response => AgentxPayload::Response {
+            sys_up_time: reader.u32()?,
+            error: reader.u16()?,
+            index: reader.u16()?,
+            var_binds: reader.var_binds()?,
+        },
+    };
+
+    Ok((context, payload))
+}
+
+fn encode_payload(payload: &AgentxPayload, writer: &mut Writer) {
+    match payload {
+        AgentxPayload::Open {
+            timeout,
+            id,
+            description,
+        } => {
+            writer.u8(*timeout);
+            writer.reserved(3);
+            writer.oid(id, false);
+            writer.octet_string(description);
+        }
+        AgentxPayload::Close { reason } => {
+            writer.u8(*reason);
+            writer.reserved(3);
+        }
+        AgentxPayload::Register { timeout, region } => {
+            writer.u8(*timeout);
+            writer.region(region);
+        }
+        AgentxPayload::Unregister { region } => {
+            writer.reserved(1);
+            writer.region(region);
+        }
+        AgentxPayload::Get { ranges } | AgentxPayload::GetNext { ranges } => writer.search_ranges(ranges),
+        AgentxPayload::GetBulk {
+            non_repeaters,
+            max_repetitions,
+            ranges,
+        } => {
+            writer.u16(*non_repeaters);
+            writer.u16(*max_repetitions);
+            writer.search_ranges(ranges);
+        }
+        AgentxPayload::TestSet { var_binds }
+        | AgentxPayload::Notify { var_binds }
+        | AgentxPayload::IndexAllocate { var_binds }
+        | AgentxPayload::IndexDeallocate { var_binds } => writer.var_binds(var_binds),
+        AgentxPayload::CommitSet
+        | AgentxPayload::UndoSet
+        | AgentxPayload::CleanupSet
+        | AgentxPayload::Ping
+        | AgentxPayload::Malformed(_) => {}
+        AgentxPayload::AddAgentCaps { id, description } => {
+            writer.oid(id, false);
+            writer.octet_string(description);
+        }
+        AgentxPayload::RemoveAgentCaps { id } => writer.oid(id, false),
+        AgentxPayload::Response {
+            sys_up_time,
+            error,
+            index,
+            var_binds,
+        } => {
+            writer.u32(*sys_up_time);
+            writer.u16(*error);
+            writer.u16(*index);
+            writer.var_binds(var_binds);
+        }
+    }
+}
+
+// Reads the payload fields in the byte order of the PDU (RFC 2741, 5)
+struct Reader<'a> {
+    data: &'a [u8],
+    network_byte_order: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], network_byte_order: bool) -> Self {
+        Reader {
+            data,
+            network_byte_order,
+        }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], AgentxError> {
+        if self.data.len() < N {
+            return Err(AgentxError::Malformed("unexpected end of the payload".into()));
+        }
+        let (bytes, rest) = self.data.split_at(N);
+        self.data = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), AgentxError> {
+        if self.data.len() < len {
+            return Err(AgentxError::Malformed("unexpected end of the payload".into()));
+        }
+        self.data = &self.data[len..];
+        Ok(())
+    }
+
+    fn u8(&mut self) -> Result<u8, AgentxError> {
+        self.take::<1>().map(|bytes| bytes[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, AgentxError> {
+        let bytes = self.take::<2>()?;
+        Ok(match self.network_byte_order {
+            true => u16::from_be_bytes(bytes),
+            false => u16::from_le_bytes(bytes),
+        })
+    }
+
+    fn u32(&mut self) -> Result<u32, AgentxError> {
+        let bytes = self.take::<4>()?;
+        Ok(match self.network_byte_order {
+            true => u32::from_be_bytes(bytes),
+            false => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn u64(&mut self) -> Result<u64, AgentxError> {
+        let bytes = self.take::<8>()?;
+        Ok(match self.network_byte_order {
+            true => u64::from_be_bytes(bytes),
+            false => u64::from_le_bytes(bytes),
+        })
+    }
+
+    // the OID and its include field (RFC 2741, 5.1)
+    fn oid(&mut self) -> Result<(ObjectIdentifier, bool), AgentxError> {
+        let n_subid = self.u8()?;
+        let prefix = self.u8()?;
+        let include = self.u8()? != 0;
+        self.skip(1)?;
+
+        let mut sub_ids = vec![];
+        if prefix != 0 {
+            sub_ids.extend_from_slice(&INTERNET);
+            sub_ids.push(prefix.into());
+        }
+        for _ in 0..n_subid {
+            sub_ids.push(self.u32()?);
+        }
+        Ok((ObjectIdentifier::new_unchecked(sub_ids.into()), include))
+    }
+
+    // the octet string padded to the multiple of 4 octets (RFC 2741, 5.3)
+    fn octet_string(&mut self) -> Result<Vec<u8>, AgentxError> {
+        let len = self.u32()? as usize;
+        let padded_len = len
+            .checked_add(3)
+            .map(|len| len & !3)
+            .ok_or_else(|| AgentxError::Malformed("invalid octet string length".into()))?;
+        if self.data.len() < padded_len {
+            return Err(AgentxError::Malformed("unexpected end of the payload".into()));
+        }
+        let value = self.data[..len].to_vec();
+        self.data = &self.data[padded_len..];
+        Ok(value)
+    }
+
+    fn region(&mut self) -> Result<SubtreeRegion, AgentxError> {
+        let priority = self.u8()?;
+        let range_subid = self.u8()?;
+        self.skip(1)?;
+        let (subtree, _) = self.oid()?;
+        let upper_bound = match range_subid {
+            0 => None,
+            _ => Some(self.u32()?),
+        };
+        Ok(SubtreeRegion {
+            priority,
+            range_subid,
+            subtree,
+            upper_bound,
+        })
+    }
+
+    fn search_ranges(&mut self) -> Result<Vec<SearchRange>, AgentxError> {
+        let mut ranges = vec![];
+        while !self.data.is_empty() {
+            let (start, include) = self.oid()?;
+            let (end, _) = self.oid()?;
+            ranges.push(SearchRange { start, include, end });
+        }
+        Ok(ranges)
+    }
+
+    fn var_binds(&mut self) -> Result<Vec<AgentxVarBind>, AgentxError> {
+        let mut var_binds = vec![];
+        while !self.data.is_empty() {
+            let type_code = self.u16()?;
+            self.skip(2)?;
+            let (name, _) = self.oid()?;
+            let value = match type_code {
+                2 => AgentxValue::Integer(self.u32()? as i32),
+                4 => AgentxValue::OctetString(self.octet_string()?),
+                5 => AgentxValue::Null,
+                6 => AgentxValue::ObjectIdentifier(self.oid()?.0),
+                64 => AgentxValue::IpAddress(self.octet_string()?),
+                65 => AgentxValue::Counter32(self.u32()?),
+                66 => AgentxValue::Gauge32(self.u32()?),
+                67 => AgentxValue::TimeTicks(self.u32()?),
+                68 => AgentxValue::Opaque(self.octet_string()?),
+                70 => AgentxValue::Counter64(self.u64()?),
+                128 => AgentxValue::NoSuchObject,
+                129 => AgentxValue::NoSuchInstance,
+                130 => AgentxValue::EndOfMibView,
+                type_code => return Err(AgentxError::Malformed(format!("unknown value type {type_code}"))),
+            };
+            var_binds.push(AgentxVarBind { name, value });
+        }
+        Ok(var_binds)
+    }
+}
+
+// Writes the payload fields in the byte order of the PDU (RFC 2741, 5)
+struct Writer {
+    data: BytesMut,
+    network_byte_order: bool,
+}
+
+impl Writer {
+    fn new(network_byte_order: bool) -> Self {
+        Writer {
+            data: BytesMut::new(),
+            network_byte_order,
+        }
+    }
+
+    fn reserved(&mut self, len: usize) {
+        self.data.put_bytes(0, len);
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.data.put_u8(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        match self.network_byte_order {
+            true => self.data.put_u16(value),
+            false => self.data.put_u16_le(value),
+        }
+    }
+
+    fn u32(&mut self, value: u32) {
+        match self.network_byte_order {
+            true => self.data.put_u32(value),
+            false => self.data.put_u32_le(value),
+        }
+    }
+
+    fn u64(&mut self, value: u64) {
+        match self.network_byte_order {
+            true => self.data.put_u64(value),
+            false => self.data.put_u64_le(value),
+        }
+    }
+
+    fn oid(&mut self, oid: &ObjectIdentifier, include: bool) {
+        let (prefix, sub_ids) = match oid.strip_prefix(&INTERNET[..]) {
+            Some([prefix, sub_ids @ ..]) if (1..=255).contains(prefix) => (*prefix as u8, sub_ids),
+            _ => (0, &oid[..]),
+        };
+        self.u8(sub_ids.len() as u8);
+        self.u8(prefix);
+        self.u8(include.into());
+        self.reserved(1);
+        for sub_id in sub_ids {
+            self.u32(*sub_id);
+        }
+    }
+
+    fn octet_string(&mut self, value: &[u8]) {
+        self.u32(value.len() as u32);
+        self.data.extend_from_slice(value);
+        self.reserved((4 - value.len() % 4) % 4);
+    }
+
+    fn region(&mut self, region: &SubtreeRegion) {
+        self.u8(region.priority);
+        self.u8(region.range_subid);
+        self.reserved(1);
+        self.oid(&region.subtree, false);
+        if let (true, Some(upper_bound)) = (region.range_subid != 0, region.upper_bound) {
+            self.u32(upper_bound);
+        }
+    }
+
+    fn search_ranges(&mut self, ranges: &[SearchRange]) {
+        for range in ranges {
+            self.oid(&range.start, range.include);
+            self.oid(&range.end, false);
+        }
+    }
+
+    fn var_binds(&mut self, var_binds: &[AgentxVarBind]) {
+        for var_bind in var_binds {
+            self.u16(var_bind.value.type_code());
+            self.reserved(2);
+            self.oid(&var_bind.name, false);
+            match &var_bind.value {
+                AgentxValue::Integer(value) => self.u32(*value as u32),
+                AgentxValue::OctetString(value) | AgentxValue::IpAddress(value) | AgentxValue::Opaque(value) => {
+                    self.octet_string(value)
+                }
+                AgentxValue::ObjectIdentifier(value) => self.oid(value, false),
+                AgentxValue::Counter32(value) | AgentxValue::Gauge32(value) | AgentxValue::TimeTicks(value) => {
+                    self.u32(*value)
+                }
+                AgentxValue::Counter64(value) => self.u64(*value),
+                AgentxValue::Null
+                | AgentxValue::NoSuchObject
+                | AgentxValue::NoSuchInstance
+                | AgentxValue::EndOfMibView => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod agentx_codec;
+pub mod agentx_error;
+pub mod agentx_pdu;
+
+pub use agentx_codec::*;
+pub use agentx_error::*;
+pub use agentx_pdu::*;
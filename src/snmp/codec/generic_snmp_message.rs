@@ -1,14 +1,60 @@
+use bytes::Bytes;
 use num_traits::ToPrimitive;
 use rasn_snmp::v3::Message as SnmpV3Message;
 use rasn_snmp::{v1::Message as SnmpV1Message, v1::Pdus as SnmpV1Pdus};
 use rasn_snmp::{v2::Pdus as SnmpV2Pdus, v2c::Message as SnmpV2CMessage};
+use std::ops::{Deref, DerefMut};
 
 #[derive(Debug)]
 pub enum GenericSnmpMessage {
     V1Message(SnmpV1Message<SnmpV1Pdus>),
     V2Message(SnmpV2CMessage<SnmpV2Pdus>),
-    V3Message(Box<SnmpV3Message>), /* Large variant size differnce => use boxing to prevent the memory layout
-                                    * penalization of that enum */
+    V3Message(Box<SnmpV3Datagram>), /* Large variant size differnce => use boxing to prevent the memory layout
+                                     * penalization of that enum */
+}
+
+impl GenericSnmpMessage {
+    /// Returns the length of the BER encoded message
+    pub fn encoded_len(&self) -> Result<usize, rasn::ber::enc::Error> {
+        match self {
+            GenericSnmpMessage::V1Message(content) => rasn::ber::encode(content),
+            GenericSnmpMessage::V2Message(content) => rasn::ber::encode(content),
+            GenericSnmpMessage::V3Message(content) => rasn::ber::encode(&content.message),
+        }
+        .map(|data| data.len())
+    }
+}
+
+/// The SNMPv3 message together with the octets it was received in, the digest of an authenticated message is
+/// computed over the received wholeMsg (RFC 3414, 3.2 step 6) and not over the message encoded again
+#[derive(Debug)]
+pub struct SnmpV3Datagram {
+    pub message: SnmpV3Message,
+    // None for the messages which were not received
+    pub whole_msg: Option<Bytes>,
+}
+
+impl From<SnmpV3Message> for SnmpV3Datagram {
+    fn from(message: SnmpV3Message) -> Self {
+        SnmpV3Datagram {
+            message,
+            whole_msg: None,
+        }
+    }
+}
+
+impl Deref for SnmpV3Datagram {
+    type Target = SnmpV3Message;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+impl DerefMut for SnmpV3Datagram {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.message
+    }
 }
 
 pub trait Id {
@@ -1,7 +1,9 @@
 pub mod generic_snmp_message;
 pub mod snmp_codec;
 pub mod snmp_codec_error;
+pub mod snmp_stream_codec;
 
 pub use generic_snmp_message::*;
 pub use snmp_codec::*;
 pub use snmp_codec_error::*;
+pub use snmp_stream_codec::*;
@@ -1,14 +1,18 @@
 use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
 use crate::snmp::codec::snmp_codec_error::CodecError;
 use bytes::Buf;
+use bytes::Bytes;
 use bytes::BytesMut;
 use num_traits::ToPrimitive;
 use rasn::ber::de::DecoderOptions;
 use rasn::Decode;
 use tokio_util::codec::{Decoder, Encoder};
 
-#[derive(Default, Debug)]
-pub struct SnmpCodec {}
+#[derive(Debug)]
+pub struct SnmpCodec {
+    // the encoder refuses the messages exceeding the limit
+    max_message_size: usize,
+}
 
 mod header {
     use rasn::{types::Integer, AsnType, Decode};
@@ -24,8 +28,23 @@ impl SnmpCodec {
     pub const SNMP_VERSION2: u32 = 1;
     pub const SNMP_VERSION3: u32 = 3;
 
+    // the maximum payload of a UDP datagram
+    pub const MAX_MESSAGE_SIZE: usize = 65507;
+
     pub fn new() -> Self {
-        SnmpCodec {}
+        SnmpCodec::default()
+    }
+
+    pub fn with_max_message_size(max_message_size: usize) -> Self {
+        SnmpCodec {
+            max_message_size: max_message_size.min(SnmpCodec::MAX_MESSAGE_SIZE),
+        }
+    }
+}
+
+impl Default for SnmpCodec {
+    fn default() -> Self {
+        SnmpCodec::with_max_message_size(SnmpCodec::MAX_MESSAGE_SIZE)
     }
 }
 
@@ -52,16 +71,19 @@ impl Decoder for SnmpCodec {
         let mut decoder = rasn::ber::de::Decoder::new(data, DecoderOptions::ber());
         let version_header: Result<rasn_snmp::SnmpMessageHeader, CodecError> =
             decode(&mut decoder).map_err(CodecError::Decoder);
-        let version = version_header
-            .unwrap()
-            .version
-            .to_u32()
-            .unwrap_or(std::u32::MIN);
+        let version = match version_header {
+            Ok(version_header) => version_header.version.to_u32().unwrap_or(u32::MAX),
+            Err(error) => {
+                // the datagram is not an SNMP message, it's discarded as a whole
+                data.clear();
+                return Err(error);
+            }
+        };
         tracing::debug!("Version: {:02X?}", version);
 
         let mut decoder = rasn::ber::de::Decoder::new(data, DecoderOptions::ber());
 
-        let result = match version {
+        let mut result = match version {
             SnmpCodec::SNMP_VERSION1 => decode(&mut decoder)
                 .map(|de| Some(GenericSnmpMessage::V1Message(de)))
                 .map_err(CodecError::Decoder),
@@ -71,7 +93,7 @@ impl Decoder for SnmpCodec {
                 .map_err(CodecError::Decoder),
 
             SnmpCodec::SNMP_VERSION3 => decode(&mut decoder)
-                .map(|de| Some(GenericSnmpMessage::V3Message(Box::new(de))))
+                .map(|de: rasn_snmp::v3::Message| Some(GenericSnmpMessage::V3Message(Box::new(de.into()))))
                 .map_err(CodecError::Decoder),
 
             ver_u32 => Err(CodecError::InvalidVersion(ver_u32)),
@@ -82,7 +104,16 @@ impl Decoder for SnmpCodec {
         // changed to accept the data as immutable then the input data needs to
         // be shorten by the decoced data length => the snmp-parser crate
         // implements that feature correctly
-        data.advance(decoder.decoded_len());
+        let decoded_len = decoder.decoded_len();
+        // the authentication of the SNMPv3 message is verified over the octets as received
+        if let Ok(Some(GenericSnmpMessage::V3Message(datagram))) = &mut result {
+            datagram.whole_msg = Some(Bytes::copy_from_slice(&data[..decoded_len]));
+        }
+        match &result {
+            Ok(_) => data.advance(decoded_len),
+            // the undecodable datagram would be decoded again by the UdpFramed otherwise
+            Err(_) => data.clear(),
+        }
 
         result
     }
@@ -94,36 +125,23 @@ impl Encoder<GenericSnmpMessage> for SnmpCodec {
     #[tracing::instrument(level = "info", name = "SnmpCodec::encode", skip(self, message))]
     fn encode(&mut self, message: GenericSnmpMessage, result: &mut BytesMut) -> Result<(), Self::Error> {
         let response = match message {
-            GenericSnmpMessage::V1Message(content) => rasn::ber::encode(&content)
-                .map(|data| {
-                    // Reserve space in the buffer
-                    result.reserve(data.len());
-
-                    // Write the encoded message into the buffer
-                    result.extend_from_slice(&data);
-                })
-                .map_err(CodecError::Encoder),
-
-            GenericSnmpMessage::V2Message(content) => rasn::ber::encode(&content)
-                .map(|data| {
-                    // Reserve space in the buffer
-                    result.reserve(data.len());
-
-                    // Write the encoded message into the buffer
-                    result.extend_from_slice(&data);
-                })
-                .map_err(CodecError::Encoder),
-
-            GenericSnmpMessage::V3Message(content) => rasn::ber::encode(&content)
-                .map(|data| {
-                    // Reserve space in the buffer
-                    result.reserve(data.len());
-
-                    // Write the encoded message into the buffer
-                    result.extend_from_slice(&data);
-                })
-                .map_err(CodecError::Encoder),
-        };
+            GenericSnmpMessage::V1Message(content) => rasn::ber::encode(&content),
+            GenericSnmpMessage::V2Message(content) => rasn::ber::encode(&content),
+            GenericSnmpMessage::V3Message(content) => rasn::ber::encode(&content.message),
+        }
+        .map_err(CodecError::Encoder)
+        .and_then(|data| {
+            if data.len() > self.max_message_size {
+                return Err(CodecError::MessageTooLarge(data.len(), self.max_message_size));
+            }
+
+            // Reserve space in the buffer
+            result.reserve(data.len());
+
+            // Write the encoded message into the buffer
+            result.extend_from_slice(&data);
+            Ok(())
+        });
         tracing::debug!("Sending data: {:02X?}", result.as_ref());
         response
     }
@@ -12,6 +12,12 @@ pub enum CodecError {
     #[error("Invalid protocol version {0}")]
     InvalidVersion(u32),
 
+    #[error("The encoded message of {0} octets exceeds the maximum message size {1}")]
+    MessageTooLarge(usize, usize),
+
+    #[error("Invalid message framing: {0}")]
+    InvalidFrame(String),
+
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
@@ -0,0 +1,100 @@
+use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
+use crate::snmp::codec::snmp_codec::SnmpCodec;
+use crate::snmp::codec::snmp_codec_error::CodecError;
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// The codec of the SNMP messages sent over a stream transport (RFC 3430, 2.1). The stream doesn't preserve the message
+/// boundaries, each message is delimited by the definite length of its BER encoded SEQUENCE.
+///
+/// A complete message which cannot be decoded is yielded as an error item and the stream continues by the next one,
+/// the stream which cannot be framed anymore fails with an error.
+#[derive(Debug)]
+pub struct SnmpStreamCodec {
+    codec: SnmpCodec,
+}
+
+impl SnmpStreamCodec {
+    // the universal tag of the SEQUENCE enclosing each SNMP message
+    const SEQUENCE_TAG: u8 = 0x30;
+
+    // the length of the message is encoded by up to 4 octets of the long form
+    const MAX_LENGTH_OCTETS: usize = 4;
+
+    pub fn new(codec: SnmpCodec) -> Self {
+        SnmpStreamCodec { codec }
+    }
+
+    // the length of the whole message including its tag and length octets, None if more data needs to be read
+    fn frame_length(data: &[u8]) -> Result<Option<usize>, CodecError> {
+        match data.first() {
+            None => return Ok(None),
+            Some(&SnmpStreamCodec::SEQUENCE_TAG) => {}
+            Some(tag) => {
+                return Err(CodecError::InvalidFrame(format!(
+                    "the message starts by the tag {tag:#04X} instead of the SEQUENCE"
+                )))
+            }
+        }
+
+        let first_length_octet = match data.get(1) {
+            Some(octet) => *octet as usize,
+            None => return Ok(None),
+        };
+        // the short form
+        if first_length_octet < 0x80 {
+            return Ok(Some(2 + first_length_octet));
+        }
+
+        // the long form, the indefinite form is never used by SNMP (RFC 3417, 8)
+        let length_octets = first_length_octet & 0x7F;
+        if length_octets == 0 || length_octets > SnmpStreamCodec::MAX_LENGTH_OCTETS {
+            return Err(CodecError::InvalidFrame(format!(
+                "unsupported length form {first_length_octet:#04X}"
+            )));
+        }
+        match data.get(2..2 + length_octets) {
+            Some(octets) => {
+                let length = octets
+                    .iter()
+                    .fold(0usize, |length, octet| (length << 8) | *octet as usize);
+                Ok(Some(2 + length_octets + length))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Decoder for SnmpStreamCodec {
+    type Item = Result<GenericSnmpMessage, CodecError>;
+    type Error = CodecError;
+
+    #[tracing::instrument(level = "info", name = "SnmpStreamCodec::decode", skip(self, data))]
+    fn decode(&mut self, data: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let frame_length = match SnmpStreamCodec::frame_length(data)? {
+            Some(frame_length) => frame_length,
+            None => return Ok(None),
+        };
+        if frame_length > SnmpCodec::MAX_MESSAGE_SIZE {
+            return Err(CodecError::MessageTooLarge(frame_length, SnmpCodec::MAX_MESSAGE_SIZE));
+        }
+        if data.len() < frame_length {
+            // more bytes needs to be read
+            data.reserve(frame_length - data.len());
+            return Ok(None);
+        }
+
+        // the frame is decoded as a whole, the same way as a datagram
+        let mut frame = data.split_to(frame_length);
+        Ok(self.codec.decode(&mut frame).transpose())
+    }
+}
+
+impl Encoder<GenericSnmpMessage> for SnmpStreamCodec {
+    type Error = CodecError;
+
+    // the BER encoded message is self-delimiting, it's written to the stream as is
+    fn encode(&mut self, message: GenericSnmpMessage, result: &mut BytesMut) -> Result<(), Self::Error> {
+        self.codec.encode(message, result)
+    }
+}
@@ -1,3 +1,4 @@
+pub mod snmp_community;
 pub mod snmp_generic_handler;
 pub mod snmp_v1_handler;
 pub mod snmp_v2_handler;
@@ -0,0 +1,63 @@
+use crate::domain::send_authentication_failure_trap;
+use crate::domain::ManagedDevice;
+use crate::domain::SnmpEngine;
+use crate::domain::SnmpProtocolVersion;
+use crate::domain::{ErrorStatus, GetResponseError, SetRequest};
+use crate::snmp::snmp_statistics::SnmpCounter;
+use crate::udp_server::udp_stream_handler::{SendData, UdpStreamHandler};
+
+use actix_async::address::Addr;
+use std::net::SocketAddr;
+
+/// The message of a community not known to the device is dropped,
+/// the manager is notified by the authenticationFailure trap if configured (RFC 1157, 4.1)
+pub(crate) fn reject_unknown_community(
+    device: &ManagedDevice,
+    snmp_engine: &SnmpEngine,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    snmp_engine
+        .snmp_statistics
+        .increment(SnmpCounter::InBadCommunityNames);
+    tracing::debug!("Unknown community, the message is dropped");
+
+    send_authentication_failure_trap(device, snmp_engine, stream_handler_actor);
+}
+
+/// The SetRequest of a read-only community is answered by noAccess,
+/// translated to noSuchName for SNMPv1 (RFC 3584, 4.4)
+pub(crate) fn reject_read_only_community(
+    request: SetRequest,
+    version: &SnmpProtocolVersion,
+    peer: SocketAddr,
+    snmp_engine: &SnmpEngine,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    snmp_engine
+        .snmp_statistics
+        .increment(SnmpCounter::InBadCommunityUses);
+    tracing::debug!("SetRequest of a read-only community");
+
+    let error_status = match version {
+        SnmpProtocolVersion::SNMPV1(_) => ErrorStatus::NoSuchName,
+        _ => ErrorStatus::NoAccess,
+    };
+    let name = request
+        .variables
+        .first()
+        .map(|variable| variable.name.clone());
+    let response = GetResponseError {
+        request_id: request.request_id,
+        error_status,
+        error_index: usize::from(name.is_some()),
+        name,
+    };
+
+    snmp_engine
+        .snmp_statistics
+        .increment(SnmpCounter::OutGetResponses);
+    stream_handler_actor.do_send(SendData {
+        message: (version, response).into(),
+        peer,
+    });
+}
@@ -1,5 +1,6 @@
 use crate::domain::ManagedDevice;
 use crate::domain::SnmpAgentCommandResponderError;
+use crate::domain::SnmpEngine;
 use crate::domain::ValidationError;
 use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
 use crate::udp_server::udp_stream_handler::UdpStreamHandler;
@@ -11,7 +12,7 @@ use shared_common::error_chain_fmt;
 use snmp_data_parser::parser::snmp_data::component::SnmpData;
 use std::convert::Infallible;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use super::snmp_v1_handler::*;
 use super::snmp_v2_handler::*;
@@ -42,7 +43,7 @@ impl From<Infallible> for GenericHandlerError {
 #[tracing::instrument(
     level = "debug",
     name = "generic_snmp_message_handler",
-    skip(generic_request, stream_handler_actor, snmp_data)
+    skip(generic_request, stream_handler_actor, snmp_data, snmp_engine)
 )]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 #[macro_rules_attribute(dyn_async!)]
@@ -51,18 +52,19 @@ pub(crate) async fn generic_snmp_message_handler<'fut>(
     device: ManagedDevice,
     peer: SocketAddr,
     stream_handler_actor: Addr<UdpStreamHandler>,
-    snmp_data: Arc<SnmpData>,
+    snmp_data: Arc<RwLock<SnmpData>>,
+    snmp_engine: Arc<SnmpEngine>,
 ) {
     // Handle the generic_request
     if let Err(error) = match generic_request {
         GenericSnmpMessage::V1Message(message) => {
-            handle_snmp_message_v1(message, device, peer, stream_handler_actor, snmp_data).await
+            handle_snmp_message_v1(message, device, peer, stream_handler_actor, snmp_data, snmp_engine).await
         }
         GenericSnmpMessage::V2Message(message) => {
-            handle_snmp_message_v2(message, device, peer, stream_handler_actor, snmp_data).await
+            handle_snmp_message_v2(message, device, peer, stream_handler_actor, snmp_data, snmp_engine).await
         }
         GenericSnmpMessage::V3Message(message) => {
-            handle_snmp_message_v3(*message, device, peer, stream_handler_actor, snmp_data).await
+            handle_snmp_message_v3(*message, device, peer, stream_handler_actor, snmp_data, snmp_engine).await
         }
     } {
         tracing::error!("Request handler failed: {error}");
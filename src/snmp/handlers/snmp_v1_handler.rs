@@ -1,25 +1,40 @@
 use crate::domain::to_string_default;
 use crate::domain::AgentContext;
 use crate::domain::ManagedDevice;
+use crate::domain::SnmpEngine;
 use crate::domain::SnmpProtocolVersion;
-use crate::domain::{handle_get_next_request, handle_get_request};
+use crate::domain::{handle_get_next_request, handle_get_request, handle_set_request};
+use crate::domain::{CommunityAccess, SetRequest};
+use crate::snmp::handlers::snmp_community::{reject_read_only_community, reject_unknown_community};
 use crate::snmp::handlers::snmp_generic_handler::GenericHandlerError;
 use crate::udp_server::udp_stream_handler::UdpStreamHandler;
 
 use actix_async::address::Addr;
 use snmp_data_parser::parser::snmp_data::component::SnmpData;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-#[tracing::instrument(level = "debug", name = "handle_snmp_message_v1", skip(snmp_data))]
+#[tracing::instrument(level = "debug", name = "handle_snmp_message_v1", skip(snmp_data, snmp_engine))]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) async fn handle_snmp_message_v1(
     v1_request: rasn_snmp::v1::Message<rasn_snmp::v1::Pdus>,
     device: ManagedDevice,
     peer: SocketAddr,
     stream_handler_actor: Addr<UdpStreamHandler>,
-    snmp_data: Arc<SnmpData>,
+    snmp_data: Arc<RwLock<SnmpData>>,
+    snmp_engine: Arc<SnmpEngine>,
 ) -> Result<(), GenericHandlerError> {
+    let access = match device
+        .snmp_protocol_attributes
+        .community_access(&v1_request.community)
+    {
+        Some(access) => access,
+        None => {
+            reject_unknown_community(&device, &snmp_engine, &stream_handler_actor);
+            return Ok(());
+        }
+    };
+
     match v1_request.data {
         rasn_snmp::v1::Pdus::GetRequest(snmp_get_request) => {
             handle_get_request(
@@ -30,6 +45,7 @@ pub(crate) async fn handle_snmp_message_v1(
                     stream_handler_actor,
                     SnmpProtocolVersion::SNMPV1(to_string_default(&v1_request.community, "public")),
                     snmp_data,
+                    snmp_engine,
                 ),
             )
             .await?;
@@ -43,12 +59,25 @@ pub(crate) async fn handle_snmp_message_v1(
                     stream_handler_actor,
                     SnmpProtocolVersion::SNMPV1(to_string_default(&v1_request.community, "public")),
                     snmp_data,
+                    snmp_engine,
                 ),
             )
             .await?;
         }
         rasn_snmp::v1::Pdus::GetResponse(_) => { /* not handled by Agent */ }
-        rasn_snmp::v1::Pdus::SetRequest(_) => {}
+        rasn_snmp::v1::Pdus::SetRequest(snmp_set_request) => {
+            let request: SetRequest = snmp_set_request.try_into()?;
+            let version = SnmpProtocolVersion::SNMPV1(to_string_default(&v1_request.community, "public"));
+            if access == CommunityAccess::ReadOnly {
+                reject_read_only_community(request, &version, peer, &snmp_engine, &stream_handler_actor);
+            } else {
+                handle_set_request(
+                    request,
+                    AgentContext::new(device, peer, stream_handler_actor, version, snmp_data, snmp_engine),
+                )
+                .await?;
+            }
+        }
         rasn_snmp::v1::Pdus::Trap(_trap_request) => {}
     };
 
@@ -1,24 +1,41 @@
+use crate::domain::acknowledge_inform;
 use crate::domain::AgentContext;
 use crate::domain::ManagedDevice;
+use crate::domain::SnmpEngine;
 use crate::domain::SnmpProtocolVersion;
-use crate::domain::{handle_get_next_request, handle_get_request};
+use crate::domain::{handle_get_bulk_request, handle_get_next_request, handle_get_request, handle_set_request};
+use crate::domain::{CommunityAccess, SetRequest};
+use crate::snmp::handlers::snmp_community::{reject_read_only_community, reject_unknown_community};
 use crate::snmp::handlers::snmp_generic_handler::GenericHandlerError;
+use crate::snmp::snmp_statistics::SnmpCounter;
 use crate::udp_server::udp_stream_handler::UdpStreamHandler;
 
 use actix_async::address::Addr;
 use snmp_data_parser::parser::snmp_data::component::SnmpData;
 use std::net::SocketAddr;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-#[tracing::instrument(level = "debug", name = "handle_snmp_message_v2", skip(snmp_data))]
+#[tracing::instrument(level = "debug", name = "handle_snmp_message_v2", skip(snmp_data, snmp_engine))]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) async fn handle_snmp_message_v2(
     v2_request: rasn_snmp::v2c::Message<rasn_snmp::v2::Pdus>,
     device: ManagedDevice,
     peer: SocketAddr,
     stream_handler_actor: Addr<UdpStreamHandler>,
-    snmp_data: Arc<SnmpData>,
+    snmp_data: Arc<RwLock<SnmpData>>,
+    snmp_engine: Arc<SnmpEngine>,
 ) -> Result<(), GenericHandlerError> {
+    let access = match device
+        .snmp_protocol_attributes
+        .community_access(&v2_request.community)
+    {
+        Some(access) => access,
+        None => {
+            reject_unknown_community(&device, &snmp_engine, &stream_handler_actor);
+            return Ok(());
+        }
+    };
+
     match v2_request.data {
         rasn_snmp::v2::Pdus::GetRequest(snmp_get_request) => {
             handle_get_request(
@@ -33,6 +50,7 @@ pub(crate) async fn handle_snmp_message_v2(
                             .to_string(),
                     ),
                     snmp_data,
+                    snmp_engine,
                 ),
             )
             .await?;
@@ -50,13 +68,53 @@ pub(crate) async fn handle_snmp_message_v2(
                             .to_string(),
                     ),
                     snmp_data,
+                    snmp_engine,
+                ),
+            )
+            .await?;
+        }
+        rasn_snmp::v2::Pdus::Response(response) => {
+            // the manager acknowledges the InformRequest sent by the device
+            snmp_engine
+                .snmp_statistics
+                .increment(SnmpCounter::InGetResponses);
+            acknowledge_inform(device.id, response.0.request_id, peer);
+        }
+        rasn_snmp::v2::Pdus::SetRequest(set_request) => {
+            let request: SetRequest = set_request.try_into()?;
+            let version = SnmpProtocolVersion::SNMPV2C(
+                std::str::from_utf8(&v2_request.community)
+                    .unwrap_or("public")
+                    .to_string(),
+            );
+            if access == CommunityAccess::ReadOnly {
+                reject_read_only_community(request, &version, peer, &snmp_engine, &stream_handler_actor);
+            } else {
+                handle_set_request(
+                    request,
+                    AgentContext::new(device, peer, stream_handler_actor, version, snmp_data, snmp_engine),
+                )
+                .await?;
+            }
+        }
+        rasn_snmp::v2::Pdus::GetBulkRequest(bulk_request) => {
+            handle_get_bulk_request(
+                bulk_request.try_into()?,
+                AgentContext::new(
+                    device,
+                    peer,
+                    stream_handler_actor,
+                    SnmpProtocolVersion::SNMPV2C(
+                        std::str::from_utf8(&v2_request.community)
+                            .unwrap_or("public")
+                            .to_string(),
+                    ),
+                    snmp_data,
+                    snmp_engine,
                 ),
             )
             .await?;
         }
-        rasn_snmp::v2::Pdus::Response(_) => { /* not handled by Agent */ }
-        rasn_snmp::v2::Pdus::SetRequest(_) => {}
-        rasn_snmp::v2::Pdus::GetBulkRequest(_bulk_request) => {}
         rasn_snmp::v2::Pdus::InformRequest(_) => {}
         rasn_snmp::v2::Pdus::Trap(_trap_request) => {}
         rasn_snmp::v2::Pdus::Report(_report) => {}
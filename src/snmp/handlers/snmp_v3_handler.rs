@@ -1,29 +1,276 @@
+use crate::domain::receive_inform_message;
+use crate::domain::AgentContext;
 use crate::domain::ManagedDevice;
-use std::sync::Arc;
-//use crate::domain::SnmpProtocolVersion;
-//use crate::snmp::handlers::snmp_generic_handler::AgentContext;
+use crate::domain::SnmpEngine;
+use crate::domain::SnmpProtocolVersion;
+use crate::domain::{handle_get_bulk_request, handle_get_next_request, handle_get_request, handle_set_request};
+use crate::domain::{try_to_i32, Report, SnmpV3Parameters, Variable};
+use crate::domain::{AUTH_FLAG, PRIV_FLAG, REPORTABLE_FLAG, USM_SECURITY_MODEL};
+use crate::snmp::codec::generic_snmp_message::{GenericSnmpMessage, SnmpV3Datagram};
 use crate::snmp::handlers::snmp_generic_handler::GenericHandlerError;
-use crate::udp_server::udp_stream_handler::UdpStreamHandler;
+use crate::snmp::snmp_statistics::SnmpCounter;
+use crate::snmp::usm::{decrypt_scoped_pdu, verify_message, AuthenticationKey, PrivacyKey, UsmReport};
+use crate::udp_server::udp_stream_handler::{SendData, UdpStreamHandler};
 
 use actix_async::address::Addr;
+use num_traits::ToPrimitive;
+use snmp_data_parser::parser::snmp_data::component::DataType;
 use snmp_data_parser::parser::snmp_data::component::SnmpData;
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
 
-// use futures::prelude::*;
-// use rasn::prelude::Integer;
-// use rasn_smi::v2::ObjectSyntax;
-// use rasn_smi::v2::SimpleSyntax;
-// use rasn_snmp::v1::GetResponse;
-// use rasn_snmp::v1::Pdus::GetRequest;
+// the time window of the authenticated messages in seconds (RFC 3414, 2.2.3)
+const TIME_WINDOW: i64 = 150;
 
-#[tracing::instrument(level = "debug", name = "handle_snmp_message_v3", skip(_snmp_data))]
+#[tracing::instrument(level = "debug", name = "handle_snmp_message_v3", skip(snmp_data, snmp_engine))]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) async fn handle_snmp_message_v3(
-    _message: rasn_snmp::v3::Message,
-    _device: ManagedDevice,
-    _peer: SocketAddr,
-    _stream_handler_actor: Addr<UdpStreamHandler>,
-    _snmp_data: Arc<SnmpData>,
+    mut message: SnmpV3Datagram,
+    device: ManagedDevice,
+    peer: SocketAddr,
+    stream_handler_actor: Addr<UdpStreamHandler>,
+    snmp_data: Arc<RwLock<SnmpData>>,
+    snmp_engine: Arc<SnmpEngine>,
 ) -> Result<(), GenericHandlerError> {
+    if snmp_engine.usm_user.is_none() {
+        tracing::debug!("SNMPv3 is not configured on the device, the message is dropped");
+        return Ok(());
+    }
+
+    let flags = message
+        .global_data
+        .flags
+        .first()
+        .copied()
+        .unwrap_or_default();
+    if message.global_data.security_model.to_u32() != Some(USM_SECURITY_MODEL)
+        || (flags & PRIV_FLAG != 0 && flags & AUTH_FLAG == 0)
+    {
+        tracing::debug!("Unsupported security model or invalid msgFlags, the message is dropped");
+        return Ok(());
+    }
+
+    // the Reports and Responses of the InformRequest receivers are never reportable (RFC 3412, 7.1)
+    if flags & REPORTABLE_FLAG == 0 && receive_inform_message(device.id, &mut message, peer) {
+        snmp_engine
+            .snmp_statistics
+            .increment(SnmpCounter::InGetResponses);
+        return Ok(());
+    }
+
+    let request_id = match &message.scoped_data {
+        rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) => request_id(&scoped_pdu.data),
+        rasn_snmp::v3::ScopedPduData::EncryptedPdu(_) => 0,
+    };
+
+    let mut parameters = SnmpV3Parameters {
+        message_id: try_to_i32(&message.global_data.message_id)?,
+        max_size: try_to_i32(&message.global_data.max_size)?,
+        engine_id: snmp_engine.engine_id.clone(),
+        engine_boots: snmp_engine.engine_boots,
+        engine_time: snmp_engine.engine_time(),
+        user_name: message.security_parameters.user_name.to_vec(),
+        context_engine_id: snmp_engine.engine_id.clone(),
+        context_name: vec![],
+        authentication_key: None,
+        privacy_key: None,
+    };
+
+    let (authentication_key, privacy_key) = match process_incoming_message(&mut message, flags, &snmp_engine) {
+        Ok(keys) => keys,
+        Err(report) => {
+            let counter = snmp_engine.usm_statistics.increment(report);
+            tracing::debug!("USM processing of the message failed: {report}");
+
+            if flags & REPORTABLE_FLAG != 0 {
+                // the report is authenticated only in case of notInTimeWindows (RFC 3414, 3.2 step 7)
+                if report == UsmReport::NotInTimeWindows {
+                    parameters.authentication_key = user_authentication_key(&snmp_engine);
+                }
+                send_report(report, counter, request_id, &parameters, peer, &stream_handler_actor);
+            }
+            return Ok(());
+        }
+    };
+    // the response is sent with the same security level as the request
+    parameters.authentication_key = authentication_key;
+    parameters.privacy_key = privacy_key;
+
+    let scoped_pdu = match message.message.scoped_data {
+        rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) => scoped_pdu,
+        rasn_snmp::v3::ScopedPduData::EncryptedPdu(_) => {
+            tracing::debug!("Encrypted scopedPDU without privacy, the message is dropped");
+            return Ok(());
+        }
+    };
+    parameters.context_engine_id = scoped_pdu.engine_id.to_vec();
+    parameters.context_name = scoped_pdu.name.to_vec();
+
+    match scoped_pdu.data {
+        rasn_snmp::v2::Pdus::GetRequest(snmp_get_request) => {
+            handle_get_request(
+                snmp_get_request.try_into()?,
+                AgentContext::new(
+                    device,
+                    peer,
+                    stream_handler_actor,
+                    SnmpProtocolVersion::SNMPV3(parameters),
+                    snmp_data,
+                    snmp_engine,
+                ),
+            )
+            .await?;
+        }
+        rasn_snmp::v2::Pdus::GetNextRequest(get_next_request) => {
+            handle_get_next_request(
+                get_next_request.try_into()?,
+                AgentContext::new(
+                    device,
+                    peer,
+                    stream_handler_actor,
+                    SnmpProtocolVersion::SNMPV3(parameters),
+                    snmp_data,
+                    snmp_engine,
+                ),
+            )
+            .await?;
+        }
+        rasn_snmp::v2::Pdus::Response(_) => { /* not handled by Agent */ }
+        rasn_snmp::v2::Pdus::SetRequest(set_request) => {
+            handle_set_request(
+                set_request.try_into()?,
+                AgentContext::new(
+                    device,
+                    peer,
+                    stream_handler_actor,
+                    SnmpProtocolVersion::SNMPV3(parameters),
+                    snmp_data,
+                    snmp_engine,
+                ),
+            )
+            .await?;
+        }
+        rasn_snmp::v2::Pdus::GetBulkRequest(bulk_request) => {
+            handle_get_bulk_request(
+                bulk_request.try_into()?,
+                AgentContext::new(
+                    device,
+                    peer,
+                    stream_handler_actor,
+                    SnmpProtocolVersion::SNMPV3(parameters),
+                    snmp_data,
+                    snmp_engine,
+                ),
+            )
+            .await?;
+        }
+        rasn_snmp::v2::Pdus::InformRequest(_) => {}
+        rasn_snmp::v2::Pdus::Trap(_trap_request) => {}
+        rasn_snmp::v2::Pdus::Report(_report) => {}
+    }
+
     Ok(())
 }
+
+/// Processing of the incoming message by the User-based Security Model (RFC 3414, 3.2),
+/// returns the user's authentication and privacy keys used by the message, the encrypted
+/// scopedPDU is replaced by the decrypted one
+fn process_incoming_message(
+    message: &mut SnmpV3Datagram,
+    flags: u8,
+    snmp_engine: &SnmpEngine,
+) -> Result<(Option<AuthenticationKey>, Option<PrivacyKey>), UsmReport> {
+    let security_parameters = &message.security_parameters;
+
+    // the engine discovery request is sent with an empty msgAuthoritativeEngineID
+    if security_parameters.authoritative_engine_id.as_ref() != snmp_engine.engine_id.as_slice() {
+        return Err(UsmReport::UnknownEngineIds);
+    }
+
+    let user = match &snmp_engine.usm_user {
+        Some(user) if user.name.as_bytes() == security_parameters.user_name.as_ref() => user,
+        _ => return Err(UsmReport::UnknownUserNames),
+    };
+
+    // the security level of the request has to match the level configured for the user
+    let (authentication_key, privacy_key) = match (
+        &user.authentication_key,
+        &user.privacy_key,
+        flags & AUTH_FLAG != 0,
+        flags & PRIV_FLAG != 0,
+    ) {
+        (None, None, false, false) => return Ok((None, None)),
+        (Some(authentication_key), None, true, false) => (authentication_key, None),
+        (Some(authentication_key), Some(privacy_key), true, true) => (authentication_key, Some(privacy_key)),
+        _ => return Err(UsmReport::UnsupportedSecLevels),
+    };
+
+    if !verify_message(message, authentication_key) {
+        return Err(UsmReport::WrongDigests);
+    }
+
+    let engine_boots = security_parameters.authoritative_engine_boots.to_i64();
+    let engine_time = security_parameters.authoritative_engine_time.to_i64();
+    let in_time_window = match (engine_boots, engine_time) {
+        (Some(engine_boots), Some(engine_time)) => {
+            !snmp_engine.is_engine_boots_exhausted()
+                && engine_boots == snmp_engine.engine_boots as i64
+                && (engine_time - snmp_engine.engine_time() as i64).abs() <= TIME_WINDOW
+        }
+        _ => false,
+    };
+    if !in_time_window {
+        return Err(UsmReport::NotInTimeWindows);
+    }
+
+    if let Some(privacy_key) = privacy_key {
+        if !decrypt_scoped_pdu(message, privacy_key) {
+            return Err(UsmReport::DecryptionErrors);
+        }
+    }
+
+    Ok((Some(authentication_key.clone()), privacy_key.cloned()))
+}
+
+fn user_authentication_key(snmp_engine: &SnmpEngine) -> Option<AuthenticationKey> {
+    snmp_engine
+        .usm_user
+        .as_ref()
+        .and_then(|user| user.authentication_key.clone())
+}
+
+fn request_id(pdus: &rasn_snmp::v2::Pdus) -> i32 {
+    match pdus {
+        rasn_snmp::v2::Pdus::GetRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::GetNextRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::GetBulkRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::SetRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::InformRequest(request) => request.0.request_id,
+        rasn_snmp::v2::Pdus::Response(response) => response.0.request_id,
+        rasn_snmp::v2::Pdus::Trap(trap) => trap.0.request_id,
+        rasn_snmp::v2::Pdus::Report(report) => report.0.request_id,
+    }
+}
+
+fn send_report(
+    report: UsmReport,
+    counter: u32,
+    request_id: i32,
+    parameters: &SnmpV3Parameters,
+    peer: SocketAddr,
+    stream_handler_actor: &Addr<UdpStreamHandler>,
+) {
+    let report = Report {
+        request_id,
+        variables: vec![Variable {
+            name: report.oid(),
+            data_type: DataType::Counter32,
+            value: counter.to_string(),
+        }],
+    };
+
+    stream_handler_actor.do_send(SendData {
+        message: GenericSnmpMessage::V3Message(Box::new(parameters.to_message(report.into()).into())),
+        peer,
+    });
+}
@@ -1,2 +1,5 @@
+pub mod agentx;
 pub mod codec;
 pub mod handlers;
+pub mod snmp_statistics;
+pub mod usm;
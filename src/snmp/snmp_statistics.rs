@@ -0,0 +1,101 @@
+use rasn::prelude::ObjectIdentifier;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// the number of objects registered under the snmp group, snmpProxyDrops is the last one (RFC 3418, 2)
+const SNMP_GROUP_LEN: usize = 32;
+
+// the OID of the snmp group, the counter instances are registered as .1.3.6.1.2.1.11.<counter>.0
+const SNMP_GROUP_OID: [u32; 7] = [1, 3, 6, 1, 2, 1, 11];
+
+// snmpEnableAuthenTraps is the only object of the snmp group which is not a counter
+const SNMP_ENABLE_AUTHEN_TRAPS: u32 = 30;
+
+/// The counters of the snmp group (RFC 3418, 2), the value is the sub-identifier of the counter.
+/// The counters obsoleted by RFC 3418 are maintained as well, the pollers still rely on them
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum SnmpCounter {
+    InPkts = 1,
+    OutPkts = 2,
+    InBadVersions = 3,
+    InBadCommunityNames = 4,
+    InBadCommunityUses = 5,
+    InASNParseErrs = 6,
+    InGetRequests = 15,
+    InGetNexts = 16,
+    InSetRequests = 17,
+    InGetResponses = 18,
+    OutTooBigs = 20,
+    OutGetResponses = 28,
+    OutTraps = 29,
+    SilentDrops = 31,
+    ProxyDrops = 32,
+}
+
+impl SnmpCounter {
+    /// The OID of the counter instance, e.g. snmpInBadCommunityNames.0 = .1.3.6.1.2.1.11.4.0
+    pub fn oid(&self) -> ObjectIdentifier {
+        ObjectIdentifier::new_unchecked(vec![1, 3, 6, 1, 2, 1, 11, *self as u32, 0].into())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SnmpStatistics {
+    counters: [AtomicU32; SNMP_GROUP_LEN],
+}
+
+impl SnmpStatistics {
+    /// Increments the counter and returns its new value
+    pub fn increment(&self, counter: SnmpCounter) -> u32 {
+        self.counters[counter as usize - 1].fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn value(&self, counter: SnmpCounter) -> u32 {
+        self.counters[counter as usize - 1].load(Ordering::Relaxed)
+    }
+
+    /// The value of the counter instance registered under the snmp group, e.g. snmpInPkts.0,
+    /// the counters not maintained by the device are zero
+    pub fn instance_value(&self, name: &ObjectIdentifier) -> Option<u32> {
+        match name.strip_prefix(&SNMP_GROUP_OID[..]) {
+            Some([sub_id, 0])
+                if (1..=SNMP_GROUP_LEN as u32).contains(sub_id) && *sub_id != SNMP_ENABLE_AUTHEN_TRAPS =>
+            {
+                Some(self.counters[*sub_id as usize - 1].load(Ordering::Relaxed))
+            }
+            _ => None,
+        }
+    }
+
+    /// The names of the counter instances registered under the snmp group, e.g. snmpInPkts.0
+    pub fn instance_names() -> impl Iterator<Item = ObjectIdentifier> {
+        (1..=SNMP_GROUP_LEN as u32)
+            .filter(|sub_id| *sub_id != SNMP_ENABLE_AUTHEN_TRAPS)
+            .map(|sub_id| ObjectIdentifier::new_unchecked([&SNMP_GROUP_OID[..], &[sub_id, 0]].concat().into()))
+    }
+
+    /// The values of all the counters at this moment
+    pub fn snapshot(&self) -> SnmpStatisticsSnapshot {
+        SnmpStatisticsSnapshot {
+            counters: self
+                .counters
+                .iter()
+                .map(|counter| counter.load(Ordering::Relaxed))
+                .collect(),
+        }
+    }
+}
+
+/// The values of the snmp group counters of a running device
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SnmpStatisticsSnapshot {
+    counters: Vec<u32>,
+}
+
+impl SnmpStatisticsSnapshot {
+    pub fn value(&self, counter: SnmpCounter) -> u32 {
+        self.counters
+            .get(counter as usize - 1)
+            .copied()
+            .unwrap_or_default()
+    }
+}
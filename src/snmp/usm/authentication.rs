@@ -0,0 +1,198 @@
+use crate::domain::AuthenticationAlgorithm;
+use crate::snmp::codec::SnmpV3Datagram;
+
+use hmac::digest::core_api::BlockSizeUser;
+use hmac::digest::Digest;
+use hmac::{Mac, SimpleHmac};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use std::fmt;
+use std::ops::Range;
+
+// the password is repeated up to 1 Megabyte to produce the key (RFC 3414, A.2)
+const PASSWORD_EXPANSION_LEN: usize = 1_048_576;
+const PASSWORD_CHUNK_LEN: usize = 64;
+
+// HMAC-MD5-96 and HMAC-SHA-96 truncate the digest to the first 12 octets (RFC 3414, 6.3.1 and 7.3.1),
+// the SHA-2 based protocols truncate it to the half of the hash length rounded up to 8 octets (RFC 7860, 4.2.1)
+const HMAC_96_PARAMETERS_LEN: usize = 12;
+const HMAC_128_SHA_224_PARAMETERS_LEN: usize = 16;
+const HMAC_192_SHA_256_PARAMETERS_LEN: usize = 24;
+const HMAC_256_SHA_384_PARAMETERS_LEN: usize = 32;
+const HMAC_384_SHA_512_PARAMETERS_LEN: usize = 48;
+
+/// The authentication key of a USM user, localized to the snmpEngineID of the authoritative engine
+#[derive(Clone, PartialEq)]
+pub struct AuthenticationKey {
+    pub algorithm: AuthenticationAlgorithm,
+    pub key: Vec<u8>,
+}
+
+impl fmt::Debug for AuthenticationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never log the key material
+        write!(f, "algorithm={:?}", self.algorithm)
+    }
+}
+
+impl AuthenticationKey {
+    /// Converts the user's password to a key localized to the given snmpEngineID.
+    /// Returns None when the password is empty, the user does not support authentication then.
+    pub fn new(algorithm: AuthenticationAlgorithm, password: &str, engine_id: &[u8]) -> Option<Self> {
+        if password.is_empty() {
+            return None;
+        }
+
+        let key = localized_key(&algorithm, password.as_bytes(), engine_id);
+        Some(AuthenticationKey { algorithm, key })
+    }
+
+    /// Length of the msgAuthenticationParameters field used by the authentication protocol
+    pub fn authentication_parameters_len(&self) -> usize {
+        match self.algorithm {
+            AuthenticationAlgorithm::Md5 | AuthenticationAlgorithm::Sha => HMAC_96_PARAMETERS_LEN,
+            AuthenticationAlgorithm::Sha224 => HMAC_128_SHA_224_PARAMETERS_LEN,
+            AuthenticationAlgorithm::Sha256 => HMAC_192_SHA_256_PARAMETERS_LEN,
+            AuthenticationAlgorithm::Sha384 => HMAC_256_SHA_384_PARAMETERS_LEN,
+            AuthenticationAlgorithm::Sha512 => HMAC_384_SHA_512_PARAMETERS_LEN,
+        }
+    }
+
+    /// Computes the (truncated) message digest of the whole serialized message
+    pub fn digest(&self, message: &[u8]) -> Vec<u8> {
+        let mut digest = match self.algorithm {
+            AuthenticationAlgorithm::Md5 => hmac_digest::<Md5>(&self.key, message),
+            AuthenticationAlgorithm::Sha => hmac_digest::<Sha1>(&self.key, message),
+            AuthenticationAlgorithm::Sha224 => hmac_digest::<Sha224>(&self.key, message),
+            AuthenticationAlgorithm::Sha256 => hmac_digest::<Sha256>(&self.key, message),
+            AuthenticationAlgorithm::Sha384 => hmac_digest::<Sha384>(&self.key, message),
+            AuthenticationAlgorithm::Sha512 => hmac_digest::<Sha512>(&self.key, message),
+        };
+        digest.truncate(self.authentication_parameters_len());
+        digest
+    }
+}
+
+/// Signs the outgoing message, the digest is computed over the whole message serialized with
+/// msgAuthenticationParameters set to zeros and then placed into msgAuthenticationParameters (RFC 3414, 3.1)
+pub fn authenticate_message(
+    message: &mut rasn_snmp::v3::Message,
+    key: &AuthenticationKey,
+) -> Result<(), rasn::ber::enc::Error> {
+    message.security_parameters.authentication_parameters = vec![0u8; key.authentication_parameters_len()].into();
+    let digest = key.digest(&rasn::ber::encode(message)?);
+    message.security_parameters.authentication_parameters = digest.into();
+    Ok(())
+}
+
+/// Verifies the digest of the incoming message, the digest is computed over the wholeMsg as received with
+/// msgAuthenticationParameters set to zeros (RFC 3414, 3.2 step 6 and 6.3.2)
+pub fn verify_message(message: &SnmpV3Datagram, key: &AuthenticationKey) -> bool {
+    let whole_msg = match &message.whole_msg {
+        Some(whole_msg) => whole_msg,
+        None => return false,
+    };
+    let parameters = match authentication_parameters_range(whole_msg) {
+        Some(parameters) if parameters.len() == key.authentication_parameters_len() => parameters,
+        _ => return false,
+    };
+
+    let mut whole_msg = whole_msg.to_vec();
+    let received_digest = whole_msg[parameters.clone()].to_vec();
+    whole_msg[parameters].fill(0);
+    key.digest(&whole_msg) == received_digest
+}
+
+// the position of the msgAuthenticationParameters octets in the serialized message, the parameters are the fifth field
+// of the UsmSecurityParameters SEQUENCE nested in the msgSecurityParameters OCTET STRING (RFC 3412, 6 and RFC 3414, 2.4)
+fn authentication_parameters_range(whole_msg: &[u8]) -> Option<Range<usize>> {
+    // msgVersion and msgGlobalData precede msgSecurityParameters in the message SEQUENCE
+    let message = ber_contents(whole_msg, 0)?;
+    let version = ber_contents(whole_msg, message.start)?;
+    let global_data = ber_contents(whole_msg, version.end)?;
+    let security_parameters = ber_contents(whole_msg, global_data.end)?;
+    let usm_parameters = ber_contents(whole_msg, security_parameters.start)?;
+
+    // msgAuthoritativeEngineID, msgAuthoritativeEngineBoots, msgAuthoritativeEngineTime and msgUserName are skipped
+    let mut field = ber_contents(whole_msg, usm_parameters.start)?;
+    for _ in 0..4 {
+        field = ber_contents(whole_msg, field.end)?;
+    }
+    (field.end <= usm_parameters.end).then_some(field)
+}
+
+// the contents octets of the BER encoded value starting at the offset, the definite length form is expected
+fn ber_contents(data: &[u8], offset: usize) -> Option<Range<usize>> {
+    let first_length_octet = *data.get(offset + 1)? as usize;
+    let (start, length) = if first_length_octet < 0x80 {
+        (offset + 2, first_length_octet)
+    } else {
+        let length_octets = first_length_octet & 0x7F;
+        if length_octets == 0 || length_octets > std::mem::size_of::<u32>() {
+            return None;
+        }
+        let octets = data.get(offset + 2..offset + 2 + length_octets)?;
+        let length = octets
+            .iter()
+            .fold(0usize, |length, octet| (length << 8) | *octet as usize);
+        (offset + 2 + length_octets, length)
+    };
+    (start + length <= data.len()).then_some(start..start + length)
+}
+
+/// Password to key algorithm followed by the key localization (RFC 3414, A.2),
+/// the SHA-2 based protocols use the same algorithm with their hash function (RFC 7860, 9.3)
+pub fn localized_key(algorithm: &AuthenticationAlgorithm, password: &[u8], engine_id: &[u8]) -> Vec<u8> {
+    match algorithm {
+        AuthenticationAlgorithm::Md5 => localize_key::<Md5>(&password_to_key::<Md5>(password), engine_id),
+        AuthenticationAlgorithm::Sha => localize_key::<Sha1>(&password_to_key::<Sha1>(password), engine_id),
+        AuthenticationAlgorithm::Sha224 => localize_key::<Sha224>(&password_to_key::<Sha224>(password), engine_id),
+        AuthenticationAlgorithm::Sha256 => localize_key::<Sha256>(&password_to_key::<Sha256>(password), engine_id),
+        AuthenticationAlgorithm::Sha384 => localize_key::<Sha384>(&password_to_key::<Sha384>(password), engine_id),
+        AuthenticationAlgorithm::Sha512 => localize_key::<Sha512>(&password_to_key::<Sha512>(password), engine_id),
+    }
+}
+
+/// The hash function of the authentication protocol applied to the data
+pub fn hash(algorithm: &AuthenticationAlgorithm, data: &[u8]) -> Vec<u8> {
+    match algorithm {
+        AuthenticationAlgorithm::Md5 => Md5::digest(data).to_vec(),
+        AuthenticationAlgorithm::Sha => Sha1::digest(data).to_vec(),
+        AuthenticationAlgorithm::Sha224 => Sha224::digest(data).to_vec(),
+        AuthenticationAlgorithm::Sha256 => Sha256::digest(data).to_vec(),
+        AuthenticationAlgorithm::Sha384 => Sha384::digest(data).to_vec(),
+        AuthenticationAlgorithm::Sha512 => Sha512::digest(data).to_vec(),
+    }
+}
+
+fn password_to_key<D: Digest>(password: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    if !password.is_empty() {
+        let mut chunk = [0u8; PASSWORD_CHUNK_LEN];
+        let mut password_index = 0;
+        for _ in 0..PASSWORD_EXPANSION_LEN / PASSWORD_CHUNK_LEN {
+            for byte in chunk.iter_mut() {
+                *byte = password[password_index % password.len()];
+                password_index += 1;
+            }
+            hasher.update(chunk);
+        }
+    }
+    hasher.finalize().to_vec()
+}
+
+fn localize_key<D: Digest>(key: &[u8], engine_id: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    hasher.update(key);
+    hasher.update(engine_id);
+    hasher.update(key);
+    hasher.finalize().to_vec()
+}
+
+fn hmac_digest<D: Digest + BlockSizeUser>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length, so the key initialization cannot fail
+    let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(key).unwrap();
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
@@ -0,0 +1,7 @@
+mod authentication;
+mod privacy;
+mod usm_statistics;
+
+pub use self::authentication::*;
+pub use self::privacy::*;
+pub use self::usm_statistics::*;
@@ -0,0 +1,253 @@
+use crate::domain::{AuthenticationAlgorithm, EncryptionAlgorithm};
+use crate::snmp::usm::{hash, localized_key};
+
+use cbc::cipher::block_padding::NoPadding;
+use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use cfb_mode::cipher::AsyncStreamCipher;
+use lazy_static::lazy_static;
+use num_traits::ToPrimitive;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+type DesCbcEncryptor = cbc::Encryptor<des::Des>;
+type DesCbcDecryptor = cbc::Decryptor<des::Des>;
+type Aes128CfbEncryptor = cfb_mode::Encryptor<aes::Aes128>;
+type Aes128CfbDecryptor = cfb_mode::Decryptor<aes::Aes128>;
+type Aes192CfbEncryptor = cfb_mode::Encryptor<aes::Aes192>;
+type Aes192CfbDecryptor = cfb_mode::Decryptor<aes::Aes192>;
+type Aes256CfbEncryptor = cfb_mode::Encryptor<aes::Aes256>;
+type Aes256CfbDecryptor = cfb_mode::Decryptor<aes::Aes256>;
+
+// CBC-DES and all CFB128-AES variants use 8 octets of msgPrivacyParameters
+// (RFC 3414, 8.1.1.1, RFC 3826, 3.1.2.1 and draft-blumenthal-aes-usm-04, 3.1.2.1)
+const PRIVACY_PARAMETERS_LEN: usize = 8;
+const DES_BLOCK_LEN: usize = 8;
+const DES_KEY_LEN: usize = 8;
+const AES128_KEY_LEN: usize = 16;
+const AES192_KEY_LEN: usize = 24;
+const AES256_KEY_LEN: usize = 32;
+
+lazy_static! {
+    // the salt integers are initialized with a pseudo random value and incremented for each encrypted message
+    static ref DES_SALT: AtomicU32 = AtomicU32::new(initial_salt() as u32);
+    static ref AES_SALT: AtomicU64 = AtomicU64::new(initial_salt());
+}
+
+/// The privacy key of a USM user, localized to the snmpEngineID of the authoritative engine
+#[derive(Clone, PartialEq)]
+pub struct PrivacyKey {
+    pub algorithm: EncryptionAlgorithm,
+    pub key: Vec<u8>,
+}
+
+impl fmt::Debug for PrivacyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never log the key material
+        write!(f, "algorithm={:?}", self.algorithm)
+    }
+}
+
+impl PrivacyKey {
+    /// Converts the user's privacy password to a key localized to the given snmpEngineID,
+    /// the hash function of the user's authentication protocol is used (RFC 3414, 2.6).
+    /// The key is extended when it is shorter than the key the privacy protocol requires.
+    /// Returns None when the password is empty, the user does not support privacy then.
+    pub fn new(
+        algorithm: EncryptionAlgorithm,
+        authentication: &AuthenticationAlgorithm,
+        password: &str,
+        engine_id: &[u8],
+    ) -> Option<Self> {
+        if password.is_empty() {
+            return None;
+        }
+
+        let key = localized_key(authentication, password.as_bytes(), engine_id);
+        let key = extend_key(authentication, key, algorithm.key_len());
+        Some(PrivacyKey { algorithm, key })
+    }
+
+    /// Encrypts the serialized scopedPDU, returns the encrypted data and the msgPrivacyParameters
+    pub fn encrypt(&self, engine_boots: u32, engine_time: u32, plaintext: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        match self.algorithm {
+            EncryptionAlgorithm::Des => {
+                // the salt is the concatenation of snmpEngineBoots and the local integer (RFC 3414, 8.1.1.1)
+                let salt = [
+                    engine_boots.to_be_bytes(),
+                    DES_SALT.fetch_add(1, Ordering::Relaxed).to_be_bytes(),
+                ]
+                .concat();
+                let iv = self.des_iv(&salt);
+
+                // the data is padded to the multiple of the block size, the padding is ignored by the receiver
+                let mut data = plaintext.to_vec();
+                data.resize((plaintext.len() + DES_BLOCK_LEN - 1) / DES_BLOCK_LEN * DES_BLOCK_LEN, 0);
+                let len = data.len();
+                let _ = DesCbcEncryptor::new(self.key[..DES_KEY_LEN].into(), iv.as_slice().into())
+                    .encrypt_padded_mut::<NoPadding>(&mut data, len);
+                (data, salt)
+            }
+            EncryptionAlgorithm::Aes | EncryptionAlgorithm::Aes192 | EncryptionAlgorithm::Aes256 => {
+                let salt = AES_SALT
+                    .fetch_add(1, Ordering::Relaxed)
+                    .to_be_bytes()
+                    .to_vec();
+                let iv = aes_iv(engine_boots, engine_time, &salt);
+                let key = &self.key[..self.algorithm.key_len()];
+
+                let mut data = plaintext.to_vec();
+                match self.algorithm {
+                    EncryptionAlgorithm::Aes192 => {
+                        Aes192CfbEncryptor::new(key.into(), iv.as_slice().into()).encrypt(&mut data)
+                    }
+                    EncryptionAlgorithm::Aes256 => {
+                        Aes256CfbEncryptor::new(key.into(), iv.as_slice().into()).encrypt(&mut data)
+                    }
+                    _ => Aes128CfbEncryptor::new(key.into(), iv.as_slice().into()).encrypt(&mut data),
+                }
+                (data, salt)
+            }
+        }
+    }
+
+    /// Decrypts the encryptedPDU, returns None if the msgPrivacyParameters or the encrypted data are not valid
+    pub fn decrypt(
+        &self,
+        engine_boots: u32,
+        engine_time: u32,
+        privacy_parameters: &[u8],
+        ciphertext: &[u8],
+    ) -> Option<Vec<u8>> {
+        if privacy_parameters.len() != PRIVACY_PARAMETERS_LEN {
+            return None;
+        }
+
+        let mut data = ciphertext.to_vec();
+        match self.algorithm {
+            EncryptionAlgorithm::Des => {
+                if data.len() % DES_BLOCK_LEN != 0 {
+                    return None;
+                }
+                let iv = self.des_iv(privacy_parameters);
+                DesCbcDecryptor::new(self.key[..DES_KEY_LEN].into(), iv.as_slice().into())
+                    .decrypt_padded_mut::<NoPadding>(&mut data)
+                    .ok()?;
+            }
+            EncryptionAlgorithm::Aes | EncryptionAlgorithm::Aes192 | EncryptionAlgorithm::Aes256 => {
+                let iv = aes_iv(engine_boots, engine_time, privacy_parameters);
+                let key = &self.key[..self.algorithm.key_len()];
+                match self.algorithm {
+                    EncryptionAlgorithm::Aes192 => {
+                        Aes192CfbDecryptor::new(key.into(), iv.as_slice().into()).decrypt(&mut data)
+                    }
+                    EncryptionAlgorithm::Aes256 => {
+                        Aes256CfbDecryptor::new(key.into(), iv.as_slice().into()).decrypt(&mut data)
+                    }
+                    _ => Aes128CfbDecryptor::new(key.into(), iv.as_slice().into()).decrypt(&mut data),
+                }
+            }
+        }
+        Some(data)
+    }
+
+    // the pre-IV (the last 8 octets of the localized key) XOR-ed with the salt (RFC 3414, 8.1.1.1)
+    fn des_iv(&self, salt: &[u8]) -> Vec<u8> {
+        self.key[DES_KEY_LEN..DES_KEY_LEN + DES_BLOCK_LEN]
+            .iter()
+            .zip(salt)
+            .map(|(pre_iv, salt)| pre_iv ^ salt)
+            .collect()
+    }
+}
+
+impl EncryptionAlgorithm {
+    // the length of the localized key material used by the privacy protocol,
+    // DES uses the first 8 octets as the key and the following 8 octets as the pre-IV
+    fn key_len(&self) -> usize {
+        match self {
+            EncryptionAlgorithm::Des => DES_KEY_LEN + DES_BLOCK_LEN,
+            EncryptionAlgorithm::Aes => AES128_KEY_LEN,
+            EncryptionAlgorithm::Aes192 => AES192_KEY_LEN,
+            EncryptionAlgorithm::Aes256 => AES256_KEY_LEN,
+        }
+    }
+}
+
+/// Encrypts the scopedPDU of the outgoing message and sets the msgPrivacyParameters (RFC 3414, 3.1 step 4)
+pub fn encrypt_scoped_pdu(message: &mut rasn_snmp::v3::Message, key: &PrivacyKey) -> Result<(), rasn::ber::enc::Error> {
+    if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &message.scoped_data {
+        let (encrypted_pdu, privacy_parameters) = key.encrypt(
+            message_engine_boots(message),
+            message_engine_time(message),
+            &rasn::ber::encode(scoped_pdu)?,
+        );
+        message.scoped_data = rasn_snmp::v3::ScopedPduData::EncryptedPdu(encrypted_pdu.into());
+        message.security_parameters.privacy_parameters = privacy_parameters.into();
+    }
+    Ok(())
+}
+
+/// Decrypts the scopedPDU of the incoming message (RFC 3414, 3.2 step 8),
+/// returns false if the message could not be decrypted
+pub fn decrypt_scoped_pdu(message: &mut rasn_snmp::v3::Message, key: &PrivacyKey) -> bool {
+    let scoped_pdu = match &message.scoped_data {
+        rasn_snmp::v3::ScopedPduData::EncryptedPdu(encrypted_pdu) => key
+            .decrypt(
+                message_engine_boots(message),
+                message_engine_time(message),
+                &message.security_parameters.privacy_parameters,
+                encrypted_pdu,
+            )
+            // the DES padding follows the scopedPDU, the decoder ignores the trailing data
+            .and_then(|data| rasn::ber::decode::<rasn_snmp::v3::ScopedPdu>(&data).ok()),
+        rasn_snmp::v3::ScopedPduData::CleartextPdu(_) => None,
+    };
+
+    match scoped_pdu {
+        Some(scoped_pdu) => {
+            message.scoped_data = rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu);
+            true
+        }
+        None => false,
+    }
+}
+
+// the key extension required by AES-192 and AES-256 when the hash function of the authentication protocol
+// produces a shorter key: Kul = Kul || H(Kul), repeated until the key is long enough
+// (draft-blumenthal-aes-usm-04, 3.1.2.1)
+fn extend_key(authentication: &AuthenticationAlgorithm, mut key: Vec<u8>, key_len: usize) -> Vec<u8> {
+    while key.len() < key_len {
+        let extension = hash(authentication, &key);
+        key.extend(extension);
+    }
+    key
+}
+
+// the IV is the concatenation of snmpEngineBoots, snmpEngineTime and the salt (RFC 3826, 3.1.2.1)
+fn aes_iv(engine_boots: u32, engine_time: u32, salt: &[u8]) -> Vec<u8> {
+    [&engine_boots.to_be_bytes()[..], &engine_time.to_be_bytes()[..], salt].concat()
+}
+
+fn message_engine_boots(message: &rasn_snmp::v3::Message) -> u32 {
+    message
+        .security_parameters
+        .authoritative_engine_boots
+        .to_u32()
+        .unwrap_or_default()
+}
+
+fn message_engine_time(message: &rasn_snmp::v3::Message) -> u32 {
+    message
+        .security_parameters
+        .authoritative_engine_time
+        .to_u32()
+        .unwrap_or_default()
+}
+
+fn initial_salt() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,37 @@
+use rasn::prelude::ObjectIdentifier;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// The usmStats counters (RFC 3414, 5), each of them is reported back to the sender
+/// of an incoming message that failed the USM processing
+#[derive(strum_macros::Display, Debug, Clone, Copy, PartialEq)]
+pub enum UsmReport {
+    UnsupportedSecLevels = 1,
+    NotInTimeWindows = 2,
+    UnknownUserNames = 3,
+    UnknownEngineIds = 4,
+    WrongDigests = 5,
+    DecryptionErrors = 6,
+}
+
+impl UsmReport {
+    /// The OID of the usmStats counter instance, e.g. usmStatsUnknownEngineIDs.0 = .1.3.6.1.6.3.15.1.1.4.0
+    pub fn oid(&self) -> ObjectIdentifier {
+        ObjectIdentifier::new_unchecked(vec![1, 3, 6, 1, 6, 3, 15, 1, 1, *self as u32, 0].into())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UsmStatistics {
+    counters: [AtomicU32; 6],
+}
+
+impl UsmStatistics {
+    /// Increments the counter of the report and returns its new value
+    pub fn increment(&self, report: UsmReport) -> u32 {
+        self.counters[report as usize - 1].fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub fn value(&self, report: UsmReport) -> u32 {
+        self.counters[report as usize - 1].load(Ordering::Relaxed)
+    }
+}
@@ -3,8 +3,8 @@ use crate::snmp::codec::snmp_codec::SnmpCodec;
 use crate::udp_client::ClientError;
 use futures::stream::StreamExt;
 use futures::SinkExt;
-use std::net::SocketAddr;
 use std::net::UdpSocket as StdUdpSocket;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::{net::ToSocketAddrs, time::Duration};
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tokio_util::udp::UdpFramed;
@@ -12,7 +12,7 @@ use tokio_util::udp::UdpFramed;
 // Timeout in seconds.
 const TIMEOUT: u64 = 3;
 
-// Client to send and receive SNMP messages. Only supports IPv4.
+// Client to send and receive SNMP messages over IPv4 or IPv6, by the address family of the remote address.
 pub struct Client {
     udp_framed: UdpFramed<SnmpCodec>,
     remote_addr: SocketAddr,
@@ -22,7 +22,7 @@ impl Client {
     // Constructs a new `Client` and connect it to the remote address using UDP as a transport protocol.
     pub fn new(remote_addr: SocketAddr) -> Result<Client, ClientError> {
         let timeout = Some(Duration::from_secs(TIMEOUT));
-        let std_socket = StdUdpSocket::bind("0.0.0.0:0")?;
+        let std_socket = StdUdpSocket::bind(local_address(&remote_addr))?;
         std_socket.set_read_timeout(timeout)?;
         std_socket.set_write_timeout(timeout)?;
         std_socket.connect(remote_addr)?;
@@ -35,6 +35,19 @@ impl Client {
         })
     }
 
+    // Constructs a new `Client` on a non-blocking socket, so it doesn't block the runtime shared with other tasks.
+    // The timeouts of the responses are up to the caller.
+    pub async fn connect(remote_addr: SocketAddr) -> Result<Client, ClientError> {
+        let socket = TokioUdpSocket::bind(local_address(&remote_addr)).await?;
+        socket.connect(remote_addr).await?;
+        let udp_framed = UdpFramed::new(socket, SnmpCodec::default());
+
+        Ok(Self {
+            udp_framed,
+            remote_addr,
+        })
+    }
+
     // Sends a request and returns the response on success.
     pub async fn send_request(&mut self, msg: GenericSnmpMessage) -> Result<GenericSnmpMessage, ClientError> {
         self.send_message(msg).await?;
@@ -57,3 +70,12 @@ impl Client {
         }
     }
 }
+
+// the unspecified address and an ephemeral port of the same address family as the remote address
+fn local_address(remote_addr: &SocketAddr) -> SocketAddr {
+    let ip = match remote_addr {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    SocketAddr::new(ip, 0)
+}
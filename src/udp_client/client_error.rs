@@ -7,6 +7,18 @@ pub enum ClientError {
     #[error("Stream closed by peer")]
     StreamClosed,
 
+    #[error("No response received from {0}")]
+    Timeout(String),
+
+    #[error("The request is reported by the agent, {0}")]
+    Report(String),
+
+    #[error("The agent responded with the error-status {status} at the error-index {index}")]
+    ErrorStatus { status: u32, index: u32 },
+
+    #[error("The agent returned the OID {0} which is not increasing")]
+    OidNotIncreasing(String),
+
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
@@ -2,3 +2,4 @@ pub mod udp_server_delegate;
 pub mod udp_server_error;
 pub mod udp_server_provider;
 pub mod udp_stream_handler;
+pub mod udp_trap_receiver;
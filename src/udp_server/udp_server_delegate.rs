@@ -1,7 +1,11 @@
-use crate::domain::ManagedDevice;
+use crate::domain::{Inform, ManagedDevice, Notification};
+use crate::snmp::snmp_statistics::SnmpStatisticsSnapshot;
 use crate::udp_server::udp_server_error::UdpServerError;
-use crate::udp_server::udp_server_provider::{StartSnmpDevice, StopSnmpDevice, UdpServerProvider};
+use crate::udp_server::udp_server_provider::{
+    GetSnmpStatistics, SendSnmpInform, SendSnmpNotification, StartSnmpDevice, StopSnmpDevice, UdpServerProvider,
+};
 use actix_async::address::Addr;
+use uuid_dev::Uuid;
 
 #[derive(Clone)]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
@@ -25,6 +29,25 @@ impl UdpServerDelegate {
     pub async fn stop_snmp_device(&self, device: ManagedDevice) -> Result<(), UdpServerError> {
         stop_snmp_device(self.service_config_provider.clone(), device).await
     }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "UdpServerDelegate::send_notification",
+        skip(self, notification)
+    )]
+    pub async fn send_notification(&self, device_id: Uuid, notification: Notification) -> Result<(), UdpServerError> {
+        send_notification(self.service_config_provider.clone(), device_id, notification).await
+    }
+
+    #[tracing::instrument(level = "info", name = "UdpServerDelegate::send_inform", skip(self, inform))]
+    pub async fn send_inform(&self, device_id: Uuid, inform: Inform) -> Result<(), UdpServerError> {
+        send_inform(self.service_config_provider.clone(), device_id, inform).await
+    }
+
+    #[tracing::instrument(level = "info", name = "UdpServerDelegate::get_snmp_statistics", skip(self))]
+    pub async fn get_snmp_statistics(&self, device_id: Uuid) -> Result<SnmpStatisticsSnapshot, UdpServerError> {
+        get_snmp_statistics(self.service_config_provider.clone(), device_id).await
+    }
 }
 
 #[tracing::instrument(level = "info", name = "start_snmp_device", skip(service_config_provider, device))]
@@ -48,3 +71,45 @@ async fn stop_snmp_device(
         .await
         .map_err(|error| UdpServerError::StartFailed(error.to_string()))?
 }
+
+#[tracing::instrument(
+    level = "info",
+    name = "send_notification",
+    skip(service_config_provider, notification)
+)]
+async fn send_notification(
+    service_config_provider: Addr<UdpServerProvider>,
+    device_id: Uuid,
+    notification: Notification,
+) -> Result<(), UdpServerError> {
+    service_config_provider
+        .send(SendSnmpNotification {
+            device_id,
+            notification,
+        })
+        .await
+        .map_err(|error| UdpServerError::MailboxError(error.to_string()))?
+}
+
+#[tracing::instrument(level = "info", name = "send_inform", skip(service_config_provider, inform))]
+async fn send_inform(
+    service_config_provider: Addr<UdpServerProvider>,
+    device_id: Uuid,
+    inform: Inform,
+) -> Result<(), UdpServerError> {
+    service_config_provider
+        .send(SendSnmpInform { device_id, inform })
+        .await
+        .map_err(|error| UdpServerError::MailboxError(error.to_string()))?
+}
+
+#[tracing::instrument(level = "info", name = "get_snmp_statistics", skip(service_config_provider))]
+async fn get_snmp_statistics(
+    service_config_provider: Addr<UdpServerProvider>,
+    device_id: Uuid,
+) -> Result<SnmpStatisticsSnapshot, UdpServerError> {
+    service_config_provider
+        .send(GetSnmpStatistics { device_id })
+        .await
+        .map_err(|error| UdpServerError::MailboxError(error.to_string()))?
+}
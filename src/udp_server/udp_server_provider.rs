@@ -1,6 +1,11 @@
 use crate::domain::ManagedDevice;
+use crate::domain::{remove_informs, remove_upstream_agent, stop_agentx_master, Inform, Notification};
 use crate::snmp::handlers::snmp_generic_handler::generic_snmp_message_handler;
+use crate::snmp::snmp_statistics::SnmpStatisticsSnapshot;
 use crate::udp_server::udp_server_error::UdpServerError;
+use crate::udp_server::udp_stream_handler::GetStatistics;
+use crate::udp_server::udp_stream_handler::SendInform;
+use crate::udp_server::udp_stream_handler::SendNotification;
 use crate::udp_server::udp_stream_handler::StopActor;
 use crate::udp_server::udp_stream_handler::UdpStreamHandler;
 use actix_async::address::Addr;
@@ -69,9 +74,96 @@ impl Handler<StopSnmpDevice> for UdpServerProvider {
             if let Err(error) = addr.send(StopActor {}).await {
                 tracing::error!("{error}");
             }
+            // the delivery status of the device's InformRequests is kept only while the device is running
+            remove_informs(msg.device_id);
+            stop_agentx_master(msg.device_id);
+            remove_upstream_agent(msg.device_id);
             Ok(())
         } else {
             return Err(UdpServerError::DeviceNotRunning);
         }
     }
 }
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct SendSnmpNotification {
+    pub device_id: Uuid,
+    pub notification: Notification,
+}
+message!(SendSnmpNotification, Result<(), UdpServerError>);
+
+#[actix_async::handler]
+impl Handler<SendSnmpNotification> for UdpServerProvider {
+    #[tracing::instrument(level = "info", name = "UdpServerProvider::SendSnmpNotification", skip(self, _ctx))]
+    async fn handle(&self, msg: SendSnmpNotification, _ctx: Context<'_, Self>) -> Result<(), UdpServerError> {
+        let addr = UDP_SERVER_HANDLERS
+            .read()
+            .await
+            .get(&msg.device_id)
+            .cloned()
+            .ok_or(UdpServerError::DeviceNotRunning)?;
+
+        // the notification is sent from the device's socket
+        addr.send(SendNotification {
+            notification: msg.notification,
+        })
+        .await
+        .map_err(|error| UdpServerError::MailboxError(error.to_string()))
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct SendSnmpInform {
+    pub device_id: Uuid,
+    pub inform: Inform,
+}
+message!(SendSnmpInform, Result<(), UdpServerError>);
+
+#[actix_async::handler]
+impl Handler<SendSnmpInform> for UdpServerProvider {
+    #[tracing::instrument(level = "info", name = "UdpServerProvider::SendSnmpInform", skip(self, _ctx))]
+    async fn handle(&self, msg: SendSnmpInform, _ctx: Context<'_, Self>) -> Result<(), UdpServerError> {
+        let addr = UDP_SERVER_HANDLERS
+            .read()
+            .await
+            .get(&msg.device_id)
+            .cloned()
+            .ok_or(UdpServerError::DeviceNotRunning)?;
+
+        // the InformRequest is sent from the device's socket, where the manager's Response is received
+        addr.send(SendInform { inform: msg.inform })
+            .await
+            .map_err(|error| UdpServerError::MailboxError(error.to_string()))
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct GetSnmpStatistics {
+    pub device_id: Uuid,
+}
+message!(GetSnmpStatistics, Result<SnmpStatisticsSnapshot, UdpServerError>);
+
+#[actix_async::handler]
+impl Handler<GetSnmpStatistics> for UdpServerProvider {
+    #[tracing::instrument(level = "info", name = "UdpServerProvider::GetSnmpStatistics", skip(self, _ctx))]
+    async fn handle(
+        &self,
+        msg: GetSnmpStatistics,
+        _ctx: Context<'_, Self>,
+    ) -> Result<SnmpStatisticsSnapshot, UdpServerError> {
+        let addr = UDP_SERVER_HANDLERS
+            .read()
+            .await
+            .get(&msg.device_id)
+            .cloned()
+            .ok_or(UdpServerError::DeviceNotRunning)?;
+
+        // the counters are maintained by the device's SNMP engine, while the device is running
+        addr.send(GetStatistics)
+            .await
+            .map_err(|error| UdpServerError::MailboxError(error.to_string()))
+    }
+}
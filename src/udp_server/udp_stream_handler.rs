@@ -1,27 +1,44 @@
+use crate::domain::apply_device_system_group;
+use crate::domain::apply_pre_loaded_modifiers;
 use crate::domain::AgentContext;
 use crate::domain::ManagedDevice;
+use crate::domain::SnmpDataFormat;
+use crate::domain::SnmpEngine;
+use crate::domain::SnmpTransport;
+use crate::domain::{send_inform, send_notification, start_agentx_master, Inform, Notification};
 use crate::snmp::codec::generic_snmp_message::GenericSnmpMessage;
 use crate::snmp::codec::snmp_codec::SnmpCodec;
+use crate::snmp::codec::snmp_codec_error::CodecError;
+use crate::snmp::codec::snmp_stream_codec::SnmpStreamCodec;
+use crate::snmp::snmp_statistics::{SnmpCounter, SnmpStatisticsSnapshot};
 use crate::udp_server::udp_server_error::UdpServerError;
 
 use actix_async::address::Addr;
 use actix_async::prelude::*;
-use futures::future::Future;
-use futures::stream::{SplitSink, StreamExt};
+use futures::channel::mpsc;
+use futures::future::{self, Future};
+use futures::stream::{self, SplitSink, StreamExt};
 use futures::SinkExt;
 use snmp_data_parser::parser::snmp_data::component::SnmpData;
 use snmp_data_parser::parser::snmp_data::VeraxModifierExtractor;
-use snmp_data_parser::SnmpDataParser;
-use std::cell::RefCell;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{self, BufReader};
 use std::net::SocketAddr;
 use std::pin::Pin;
-use std::sync::Arc;
-use tokio::net::UdpSocket as TokioUdpSocket;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream, UdpSocket as TokioUdpSocket};
+use tokio_util::codec::Framed;
 use tokio_util::udp::UdpFramed;
 
+// the delay of the accepting after its first failure, it's doubled up to the maximum while the accepting keeps failing
+const ACCEPT_ERROR_MIN_DELAY: Duration = Duration::from_millis(10);
+const ACCEPT_ERROR_MAX_DELAY: Duration = Duration::from_secs(1);
+
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) type UdpSinkItem = (GenericSnmpMessage, SocketAddr);
 
@@ -34,7 +51,8 @@ pub(crate) type UdpServerHandler = fn(
     ManagedDevice,
     SocketAddr,
     Addr<UdpStreamHandler>,
-    Arc<SnmpData>,
+    Arc<RwLock<SnmpData>>,
+    Arc<SnmpEngine>,
 ) -> Pin<Box<dyn Future<Output = ()> + Send>>;
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
@@ -42,7 +60,12 @@ pub(crate) struct UdpStreamHandler {
     request_handler: UdpServerHandler,
     device: ManagedDevice,
     sink: RefCell<Option<UdpSplitSink>>,
-    snmp_data: Arc<SnmpData>,
+    // the outgoing messages of the TCP connections by the peer address, together with the id of the connection
+    connections: RefCell<HashMap<SocketAddr, (u64, mpsc::UnboundedSender<GenericSnmpMessage>)>>,
+    // the id of the next TCP connection, the connection replaced by a new one with the same peer is told apart by it
+    next_connection_id: Cell<u64>,
+    snmp_data: Arc<RwLock<SnmpData>>,
+    snmp_engine: Arc<SnmpEngine>,
 }
 actor!(UdpStreamHandler);
 
@@ -53,72 +76,196 @@ impl fmt::Debug for UdpStreamHandler {
 }
 
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
-pub(crate) struct UdpMessage(pub Result<(GenericSnmpMessage, SocketAddr), String>);
+pub(crate) struct UdpMessage(pub Result<(GenericSnmpMessage, SocketAddr), CodecError>);
 message!(UdpMessage, ());
 
+// the socket the device receives the SNMP requests on
+enum Listener {
+    Udp(TokioUdpSocket),
+    Tcp(TcpListener),
+}
+
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct TcpAccept(pub std::io::Result<(TcpStream, SocketAddr)>);
+message!(TcpAccept, ());
+
+// the item read from a TCP connection, None when the connection is closed or can't be framed anymore
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct TcpMessage {
+    pub peer: SocketAddr,
+    pub connection_id: u64,
+    pub item: Option<Result<GenericSnmpMessage, CodecError>>,
+}
+message!(TcpMessage, ());
+
 impl UdpStreamHandler {
     pub async fn new(request_handler: UdpServerHandler, device: ManagedDevice) -> Result<Addr<Self>, UdpServerError> {
-        let binding_address = format!("{}:{}", &device.snmp_host, &device.snmp_port);
-        tracing::debug!("Bind a UDP listener to address: {}", binding_address);
-        // the tokio UDP socket is created non-blocking by default
-        let socket = TokioUdpSocket::bind(binding_address)
+        let binding_address = resolve_binding_address(&device)
             .await
             .map_err(|error| UdpServerError::StartFailed(error.to_string()))?;
+        let listener = match device.transport {
+            SnmpTransport::Udp => {
+                tracing::debug!("Bind a UDP listener to address: {}", binding_address);
+                bind_udp_socket(binding_address).map(Listener::Udp)
+            }
+            SnmpTransport::Tcp => {
+                tracing::debug!("Bind a TCP listener to address: {}", binding_address);
+                bind_tcp_listener(binding_address).map(Listener::Tcp)
+            }
+        }
+        .map_err(|error| UdpServerError::StartFailed(error.to_string()))?;
 
-        let snmp_data = Arc::new(read_device_snmp_data(&device).await?);
+        let mut snmp_data = read_device_snmp_data(&device).await?;
+        apply_device_system_group(&mut snmp_data, &device);
+        let snmp_data = Arc::new(RwLock::new(snmp_data));
+        let snmp_engine = Arc::new(SnmpEngine::new(&device));
+        start_agentx_master(&device, snmp_engine.clone())
+            .await
+            .map_err(|error| UdpServerError::StartFailed(error.to_string()))?;
 
         let actor = UdpStreamHandler {
             request_handler,
             device,
             sink: RefCell::new(None),
+            connections: RefCell::new(HashMap::new()),
+            next_connection_id: Cell::new(0),
             snmp_data,
+            snmp_engine,
         };
 
         // create async actor context
         Ok(UdpStreamHandler::create_async(|ctx| {
-            actor.bind_stream(socket, ctx);
+            match listener {
+                Listener::Udp(socket) => actor.bind_stream(socket, ctx),
+                Listener::Tcp(listener) => actor.bind_listener(listener, ctx),
+            }
             async move { actor }
         }))
     }
 
+    fn codec(&self) -> SnmpCodec {
+        match self.device.snmp_protocol_attributes.max_message_size() {
+            Some(max_message_size) => SnmpCodec::with_max_message_size(max_message_size),
+            None => SnmpCodec::default(),
+        }
+    }
+
     #[tracing::instrument(level = "info", name = "UdpStreamHandler::bind_stream", skip(self, ctx))]
     fn bind_stream(&self, socket: TokioUdpSocket, ctx: Context<'_, Self>) {
         // create a UdpFramed object using the SnmpCodec to work with the encoded/decoded frames directly, instead of raw UDP data
-        let (sink, stream) = UdpFramed::new(socket, SnmpCodec::default()).split();
+        let (sink, stream) = UdpFramed::new(socket, self.codec()).split();
         let mut self_sink_mut = self.sink.borrow_mut();
         *self_sink_mut = Some(sink);
 
         // Add the stream to the actor's context.
         // Stream item will be treated as a concurrent message and the actor's handle will be called.
-        ctx.add_stream(stream.map(|a| UdpMessage(a.map_err(|e| e.to_string()))));
+        ctx.add_stream(stream.map(UdpMessage));
     }
-}
 
-async fn read_device_snmp_data(device: &ManagedDevice) -> Result<SnmpData, UdpServerError> {
-    let file_name = match &device.agent {
-        crate::domain::ManagedDeviceAgent::Agent(agent) => agent.snmp_data_url.as_str(),
-        crate::domain::ManagedDeviceAgent::Id(_agent_id) => todo!("retrieve agent by id from database"),
-    };
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::bind_listener", skip(self, ctx))]
+    fn bind_listener(&self, listener: TcpListener, ctx: Context<'_, Self>) {
+        // the accepted connections are delivered to the actor, the listener is closed together with the actor's context
+        // the accepting is delayed while it keeps failing, f.e. when the process runs out of the file descriptors
+        let connections = stream::unfold((listener, None), |(listener, delay)| async move {
+            let connection = listener.accept().await;
+            let delay = match (&connection, delay) {
+                (Ok(_), _) => None,
+                (Err(_), None) => Some(ACCEPT_ERROR_MIN_DELAY),
+                (Err(_), Some(delay)) => Some(ACCEPT_ERROR_MAX_DELAY.min(delay * 2)),
+            };
+            if let Some(delay) = delay {
+                tokio::time::sleep(delay).await;
+            }
+            Some((TcpAccept(connection), (listener, delay)))
+        });
+        ctx.add_stream(connections);
+    }
 
-    read_snmp_data_file(file_name).await
-}
+    // The messages of the TCP connection are handled by the same pipeline as the UDP datagrams, the responses are
+    // written to the connection the request was received from
+    #[tracing::instrument(
+        level = "info",
+        name = "UdpStreamHandler::bind_connection",
+        skip(self, connection, ctx)
+    )]
+    fn bind_connection(&self, connection: TcpStream, peer: SocketAddr, ctx: &Context<'_, Self>) {
+        let (sink, stream) = Framed::new(connection, SnmpStreamCodec::new(self.codec())).split();
 
-async fn read_snmp_data_file(file_name: &str) -> Result<SnmpData, UdpServerError> {
-    let input = BufReader::new(File::open(file_name).map_err(|error| UdpServerError::StartFailed(error.to_string()))?);
-    let mut reader = SnmpDataParser::new(input, VeraxModifierExtractor {});
+        let (sender, receiver) = mpsc::unbounded();
+        actix::spawn(async move {
+            if let Err(error) = receiver.map(Ok).forward(sink).await {
+                tracing::error!("Failed to write to the TCP connection {peer}: {error}");
+            }
+        });
+        // the connection replaced by a new one with the same peer is closed once its pending messages are written
+        let connection_id = self.next_connection_id.get();
+        self.next_connection_id.set(connection_id + 1);
+        self.connections
+            .borrow_mut()
+            .insert(peer, (connection_id, sender));
 
-    match reader.next() {
-        Some(data) => data.map_err(|error| UdpServerError::StartFailed(error.to_string())),
-        _ => Ok(SnmpData::new()),
+        // the connection which can't be framed anymore is closed, the undecodable messages are skipped only
+        let stream = stream
+            .map(move |item| match item {
+                Ok(message) => Some(message),
+                Err(error) => {
+                    tracing::error!("Failed to read from the TCP connection {peer}: {error}");
+                    None
+                }
+            })
+            .take_while(|item| future::ready(item.is_some()))
+            .chain(stream::once(async { None }))
+            .map(move |item| TcpMessage {
+                peer,
+                connection_id,
+                item,
+            });
+        ctx.add_stream(stream);
     }
-}
 
-#[actix_async::handler]
-impl Handler<UdpMessage> for UdpStreamHandler {
-    #[tracing::instrument(level = "info", name = "UdpStreamHandler::UdpMessage", skip(self, data, ctx))]
-    async fn handle(&self, data: UdpMessage, ctx: Context<'_, Self>) {
-        match data.0 {
+    // The message is written to the TCP connection of the peer. The notifications are sent to the targets over a new
+    // connection opened by the device, the responses of the targets are received from it as well.
+    async fn send_stream_data(&self, data: SendData, ctx: &Context<'_, Self>) {
+        if !self.connections.borrow().contains_key(&data.peer) {
+            match TcpStream::connect(data.peer).await {
+                Ok(connection) => self.bind_connection(connection, data.peer, ctx),
+                Err(error) => {
+                    tracing::error!("Failed to connect to {}: {error}", data.peer);
+                    return;
+                }
+            }
+        }
+
+        let sent = self
+            .connections
+            .borrow()
+            .get(&data.peer)
+            .map(|(_, sender)| sender.unbounded_send(data.message).is_ok());
+        match sent {
+            Some(true) => {
+                self.snmp_engine
+                    .snmp_statistics
+                    .increment(SnmpCounter::OutPkts);
+            }
+            _ => tracing::error!(
+                "Failed to send the message: the TCP connection of {} is closed",
+                data.peer
+            ),
+        }
+    }
+
+    // the received message or the error of the undecodable one
+    async fn handle_message(
+        &self,
+        message: Result<(GenericSnmpMessage, SocketAddr), CodecError>,
+        ctx: &Context<'_, Self>,
+    ) {
+        match message {
             Ok((message, peer)) => {
+                self.snmp_engine
+                    .snmp_statistics
+                    .increment(SnmpCounter::InPkts);
+
                 // handle the SNMP request by calling the generic snmp message handler
                 (self.request_handler)(
                     message,
@@ -126,11 +273,25 @@ impl Handler<UdpMessage> for UdpStreamHandler {
                     peer,
                     ctx.address().unwrap(),
                     self.snmp_data.clone(),
+                    self.snmp_engine.clone(),
                 )
                 .await;
             }
 
             Err(error) => {
+                // the messages which are not decodable are delivered by the transport service as well (RFC 3418, 2)
+                let counter = match error {
+                    CodecError::InvalidVersion(_) => Some(SnmpCounter::InBadVersions),
+                    CodecError::Decoder(_) => Some(SnmpCounter::InASNParseErrs),
+                    _ => None,
+                };
+                if let Some(counter) = counter {
+                    self.snmp_engine
+                        .snmp_statistics
+                        .increment(SnmpCounter::InPkts);
+                    self.snmp_engine.snmp_statistics.increment(counter);
+                }
+
                 // we cannot do more than just log the error
                 tracing::error!("Failed to read the message: {error}");
             }
@@ -138,6 +299,111 @@ impl Handler<UdpMessage> for UdpStreamHandler {
     }
 }
 
+// the host of the device is either an IPv4/IPv6 literal or a host name resolved to its first address
+async fn resolve_binding_address(device: &ManagedDevice) -> io::Result<SocketAddr> {
+    let binding_address = device.binding_address();
+    let mut addresses = tokio::net::lookup_host(&binding_address).await?;
+    addresses.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            format!("{binding_address} is not resolved"),
+        )
+    })
+}
+
+// the socket bound to an IPv6 address accepts the IPv4 peers too (as IPv4-mapped addresses), so the device bound to
+// the unspecified address "::" is reachable on both the IPv4 and IPv6 addresses of the host
+fn new_socket(address: SocketAddr, socket_type: Type, protocol: Protocol) -> io::Result<Socket> {
+    let socket = Socket::new(Domain::for_address(address), socket_type, Some(protocol))?;
+    if address.is_ipv6() {
+        socket.set_only_v6(false)?;
+    }
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+fn bind_udp_socket(address: SocketAddr) -> io::Result<TokioUdpSocket> {
+    let socket = new_socket(address, Type::DGRAM, Protocol::UDP)?;
+    socket.bind(&address.into())?;
+    TokioUdpSocket::from_std(socket.into())
+}
+
+fn bind_tcp_listener(address: SocketAddr) -> io::Result<TcpListener> {
+    let socket = new_socket(address, Type::STREAM, Protocol::TCP)?;
+    // the same way as tokio's TcpListener::bind, the restarted device rebinds while its connections are in TIME_WAIT
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.bind(&address.into())?;
+    socket.listen(1024)?;
+    TcpListener::from_std(socket.into())
+}
+
+async fn read_device_snmp_data(device: &ManagedDevice) -> Result<SnmpData, UdpServerError> {
+    let agent = match &device.agent {
+        crate::domain::ManagedDeviceAgent::Agent(agent) => agent,
+        crate::domain::ManagedDeviceAgent::Id(_agent_id) => todo!("retrieve agent by id from database"),
+    };
+
+    let mut snmp_data = read_snmp_data_file(&agent.snmp_data_url, agent.snmp_data_format()).await?;
+    apply_pre_loaded_modifiers(&mut snmp_data, &VeraxModifierExtractor {}, device);
+    Ok(snmp_data)
+}
+
+async fn read_snmp_data_file(file_name: &str, data_format: SnmpDataFormat) -> Result<SnmpData, UdpServerError> {
+    let input = BufReader::new(File::open(file_name).map_err(|error| UdpServerError::StartFailed(error.to_string()))?);
+
+    data_format
+        .parse(input)
+        .map_err(|error| UdpServerError::StartFailed(error.to_string()))
+}
+
+#[actix_async::handler]
+impl Handler<UdpMessage> for UdpStreamHandler {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::UdpMessage", skip(self, data, ctx))]
+    async fn handle(&self, data: UdpMessage, ctx: Context<'_, Self>) {
+        self.handle_message(data.0, &ctx).await
+    }
+}
+
+#[actix_async::handler]
+impl Handler<TcpAccept> for UdpStreamHandler {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::TcpAccept", skip(self, data, ctx))]
+    async fn handle(&self, data: TcpAccept, ctx: Context<'_, Self>) {
+        match data.0 {
+            Ok((connection, peer)) => {
+                tracing::debug!("Accepted a TCP connection from {peer}");
+                self.bind_connection(connection, peer, &ctx);
+            }
+            Err(error) => tracing::error!("Failed to accept a TCP connection: {error}"),
+        }
+    }
+}
+
+#[actix_async::handler]
+impl Handler<TcpMessage> for UdpStreamHandler {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::TcpMessage", skip(self, data, ctx))]
+    async fn handle(&self, data: TcpMessage, ctx: Context<'_, Self>) {
+        match data.item {
+            Some(message) => {
+                self.handle_message(message.map(|message| (message, data.peer)), &ctx)
+                    .await
+            }
+            None => {
+                tracing::debug!("The TCP connection of {} is closed", data.peer);
+                // the connection could have been replaced by a new one with the same peer already
+                let mut connections = self.connections.borrow_mut();
+                if connections
+                    .get(&data.peer)
+                    .map(|(connection_id, _)| *connection_id)
+                    == Some(data.connection_id)
+                {
+                    connections.remove(&data.peer);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "integration-tests", visibility::make(pub))]
 pub(crate) struct StopActor;
@@ -161,11 +427,70 @@ message!(SendData, ());
 
 #[actix_async::handler]
 impl Handler<SendData> for UdpStreamHandler {
-    #[tracing::instrument(level = "info", name = "UdpStreamHandler::handle::SendData", skip(self, _ctx))]
-    async fn handle(&self, data: SendData, _ctx: Context<'_, Self>) {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::handle::SendData", skip(self, ctx))]
+    async fn handle(&self, data: SendData, ctx: Context<'_, Self>) {
+        if self.device.transport == SnmpTransport::Tcp {
+            return self.send_stream_data(data, &ctx).await;
+        }
+
         let mut sink = self.sink.borrow_mut();
         // TODO, OPTIMIZE: change send to feed and wake up and flush it in 50ms
-        let _ = sink.as_mut().unwrap().send((data.message, data.peer)).await;
+        match sink.as_mut().unwrap().send((data.message, data.peer)).await {
+            Ok(_) => {
+                self.snmp_engine
+                    .snmp_statistics
+                    .increment(SnmpCounter::OutPkts);
+            }
+            Err(error) => tracing::error!("Failed to send the message: {error}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct GetStatistics;
+message!(GetStatistics, SnmpStatisticsSnapshot);
+
+#[actix_async::handler]
+impl Handler<GetStatistics> for UdpStreamHandler {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::handle::GetStatistics", skip(self, _ctx))]
+    async fn handle(&self, _: GetStatistics, _ctx: Context<'_, Self>) -> SnmpStatisticsSnapshot {
+        self.snmp_engine.snmp_statistics.snapshot()
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct SendNotification {
+    pub notification: Notification,
+}
+message!(SendNotification, ());
+
+#[actix_async::handler]
+impl Handler<SendNotification> for UdpStreamHandler {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::handle::SendNotification", skip(self, ctx))]
+    async fn handle(&self, data: SendNotification, ctx: Context<'_, Self>) {
+        send_notification(
+            data.notification,
+            &self.device,
+            &self.snmp_engine,
+            &ctx.address().unwrap(),
+        );
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "integration-tests", visibility::make(pub))]
+pub(crate) struct SendInform {
+    pub inform: Inform,
+}
+message!(SendInform, ());
+
+#[actix_async::handler]
+impl Handler<SendInform> for UdpStreamHandler {
+    #[tracing::instrument(level = "info", name = "UdpStreamHandler::handle::SendInform", skip(self, ctx))]
+    async fn handle(&self, data: SendInform, ctx: Context<'_, Self>) {
+        send_inform(data.inform, &self.device, &self.snmp_engine, &ctx.address().unwrap());
     }
 }
 
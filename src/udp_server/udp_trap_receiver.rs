@@ -0,0 +1,47 @@
+use crate::domain::{create_notification, host_port, NotificationReceiver};
+use crate::settings::TrapReceiverSettings;
+use crate::snmp::codec::snmp_codec::SnmpCodec;
+use anyhow::Context;
+use futures::{SinkExt, StreamExt};
+use sea_orm::DatabaseConnection;
+use tokio::net::UdpSocket;
+use tokio_util::udp::UdpFramed;
+
+/// Starts the built-in trap receiver listening on the configured address, the received
+/// notifications are recorded in the notification log
+#[tracing::instrument(level = "info", name = "start_trap_receiver", skip(settings, db_conn))]
+pub async fn start_trap_receiver(settings: &TrapReceiverSettings, db_conn: DatabaseConnection) -> anyhow::Result<()> {
+    let binding_address = host_port(&settings.host, settings.port);
+    let socket = UdpSocket::bind(&binding_address)
+        .await
+        .context(format!("Failed to bind the trap receiver to {binding_address}"))?;
+    let receiver = NotificationReceiver::new(settings.communities.clone(), settings.users.clone());
+    tracing::debug!("Trap receiver binding address: {}, {:?}", binding_address, receiver);
+
+    let (mut sink, mut stream) = UdpFramed::new(socket, SnmpCodec::default()).split();
+    actix::spawn(async move {
+        while let Some(received) = stream.next().await {
+            let (message, peer) = match received {
+                Ok(received) => received,
+                Err(error) => {
+                    tracing::debug!("Failed to decode the received message: {error}");
+                    continue;
+                }
+            };
+
+            let (notification, response) = receiver.receive_message(message, peer);
+            if let Some(response) = response {
+                if let Err(error) = sink.send((response, peer)).await {
+                    tracing::error!("Failed to send the message to {peer}: {error}");
+                }
+            }
+            if let Some(notification) = notification {
+                if let Err(error) = create_notification(&db_conn, notification).await {
+                    tracing::error!("Failed to record the notification received from {peer}: {error:?}");
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
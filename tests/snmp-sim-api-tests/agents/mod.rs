@@ -2,4 +2,5 @@ mod delete_agents_id;
 mod get_agents;
 mod get_agents_id;
 mod post_agents;
+mod post_agents_record;
 mod put_agents_id;
@@ -107,5 +107,140 @@ demonstrate! {
                 }
             }
         }
+
+        context "snmp_data_file" {
+            before {
+                let name = Uuid::new_v4().to_string();
+                let snmp_data_url = std::env::temp_dir()
+                    .join(format!("{}.txt", Uuid::new_v4()))
+                    .into_os_string()
+                    .into_string()
+                    .unwrap();
+            }
+
+            context "valid_values" {
+                before {
+                    std::fs::write(
+                        &snmp_data_url,
+                        ".1.3.6.1.2.1.1.3.0 = Timeticks: (328918409) 38 days, 1:39:44.09\n\
+                         .1.3.6.1.2.1.2.1.0 = INTEGER: 2\n",
+                    )
+                    .unwrap();
+                    let response = client
+                        .post(format!("{}/agents", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_201" {
+                    assert_eq!(response.status(), StatusCode::CREATED);
+                }
+            }
+
+            context "invalid_values" {
+                before {
+                    std::fs::write(
+                        &snmp_data_url,
+                        ".1.3.6.1.2.1.1.3.0 = Timeticks: (abc) 38 days\n\
+                         .1.3.6.1.2.1.2.1.0 = INTEGER: 2\n\
+                         .1.3.6.1.2.1.2.2.1.5.1 = Gauge32: 10000000000\n",
+                    )
+                    .unwrap();
+                    let response = client
+                        .post(format!("{}/agents", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_400_with_the_invalid_lines" {
+                    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    let body = response.text().await.unwrap();
+                    assert!(body.contains("Line 1:"), "{}", body);
+                    assert!(body.contains("Line 3:"), "{}", body);
+                    assert!(!body.contains("Line 2:"), "{}", body);
+                }
+
+                async it "does_not_create_the_object_in_database" {
+                    let db_obj = Entity::find()
+                        .filter(Column::Name.eq(name))
+                        .one(app.db_conn.as_ref().unwrap())
+                        .await
+                        .expect("Failed to find the object");
+
+                    assert!(db_obj.is_none());
+                }
+            }
+
+            context "snmprec_data_format" {
+                before {
+                    std::fs::write(
+                        &snmp_data_url,
+                        "1.3.6.1.2.1.1.3.0|67|328918409\n\
+                         1.3.6.1.2.1.1.5.0|4x|6c6f63616c686f7374\n",
+                    )
+                    .unwrap();
+                    let response = client
+                        .post(format!("{}/agents", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url,
+                            "data_format": "snmprec"
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_the_data_format" {
+                    assert_eq!(response.status(), StatusCode::CREATED);
+                    let agent: response::Agent = response.json().await.unwrap();
+                    assert_eq!(agent.data_format, Some(snmp_sim::routes::SnmpDataFormat::Snmprec));
+                }
+            }
+
+            context "invalid_snmprec_records" {
+                before {
+                    std::fs::write(
+                        &snmp_data_url,
+                        "1.3.6.1.2.1.1.3.0|67|abc\n\
+                         1.3.6.1.2.1.1.5.0|4|localhost\n\
+                         1.3.6.1.2.1.1.6.0|99|unknown\n",
+                    )
+                    .unwrap();
+                    let response = client
+                        .post(format!("{}/agents", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url,
+                            "data_format": "snmprec"
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_400_with_the_invalid_lines" {
+                    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    let body = response.text().await.unwrap();
+                    assert!(body.contains("Line 1:"), "{}", body);
+                    assert!(body.contains("Line 3:"), "{}", body);
+                    assert!(!body.contains("Line 2:"), "{}", body);
+                }
+            }
+        }
     }
 }
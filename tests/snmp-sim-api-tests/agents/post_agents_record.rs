@@ -0,0 +1,231 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "post_agents_record" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, route_snmp_v1_attributes, route_snmp_v2c_attributes, domain_snmp_v2c_attributes_json};
+        use crate::snmp::DEVICE_PORT;
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::entity::agents::{Entity, Column};
+        use snmp_sim::data_access::helpers::create_agent;
+        use snmp_sim::routes::agents::response;
+        use sea_orm::EntityTrait;
+        use sea_orm::entity::prelude::*;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+
+        before {
+            let app = spawn_app().await;
+            let client = reqwest::Client::new();
+            let db_conn = app.db_conn.as_ref().unwrap();
+            let name = Uuid::new_v4().to_string();
+            let snmp_data_url = std::env::temp_dir()
+                .join(format!("{}.txt", Uuid::new_v4()))
+                .into_os_string()
+                .into_string()
+                .unwrap();
+        }
+
+        context "invalid_target" {
+            before {
+                let response = client
+                    .post(format!("{}/agents/record", app.address))
+                    .json(&serde_json::json!({
+                        "name": name,
+                        "snmp_data_url": snmp_data_url,
+                        "target": "localhost",
+                        "snmp_protocol_attributes": route_snmp_v2c_attributes("public"),
+                    }))
+                    .send()
+                    .await
+                    .expect("Failed to execute request");
+            }
+
+            async it "returns_400" {
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            }
+        }
+
+        context "get_bulk_over_snmp_v1" {
+            before {
+                let response = client
+                    .post(format!("{}/agents/record", app.address))
+                    .json(&serde_json::json!({
+                        "name": name,
+                        "snmp_data_url": snmp_data_url,
+                        "target": "127.0.0.1:161",
+                        "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                        "method": "GETBULK",
+                    }))
+                    .send()
+                    .await
+                    .expect("Failed to execute request");
+            }
+
+            async it "returns_400" {
+                assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            }
+        }
+
+        context "not_responding_target" {
+            before {
+                let silent_agent = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+                let response = client
+                    .post(format!("{}/agents/record", app.address))
+                    .json(&serde_json::json!({
+                        "name": name,
+                        "snmp_data_url": snmp_data_url,
+                        "target": silent_agent.local_addr().unwrap().to_string(),
+                        "snmp_protocol_attributes": route_snmp_v2c_attributes("public"),
+                        "timeout": 100,
+                        "retries": 0,
+                    }))
+                    .send()
+                    .await
+                    .expect("Failed to execute request");
+            }
+
+            async it "returns_502" {
+                assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+            }
+
+            async it "does_not_write_the_file" {
+                assert!(!std::path::Path::new(&snmp_data_url).exists());
+            }
+        }
+
+        context "running_device" {
+            before {
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let protocol = domain_snmp_v2c_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                client
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+                let target = format!("{host_ipaddr}:{device_port}");
+            }
+
+            context "record_system_subtree" {
+                before {
+                    let response = client
+                        .post(format!("{}/agents/record", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url,
+                            "target": target,
+                            "snmp_protocol_attributes": route_snmp_v2c_attributes("public"),
+                            "root_oid": ".1.3.6.1.2.1.1",
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let status = response.status();
+                    let agent: Option<response::Agent> = response.json().await.ok();
+                    let snmp_data = std::fs::read_to_string(&snmp_data_url).unwrap_or_default();
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_201" {
+                    assert_eq!(status, StatusCode::CREATED);
+                }
+
+                async it "writes_the_walked_subtree" {
+                    assert!(snmp_data.lines().count() > 0);
+                    assert!(snmp_data.lines().all(|line| line.starts_with(".1.3.6.1.2.1.1.")));
+                    assert!(snmp_data.contains(".1.3.6.1.2.1.1.1.0 = STRING: "));
+                }
+
+                async it "creates_the_object_in_database" {
+                    let agent = agent.unwrap();
+                    assert_eq!(agent.snmp_data_url, snmp_data_url);
+                    let db_obj = Entity::find()
+                        .filter(Column::Name.eq(agent.name))
+                        .one(db_conn)
+                        .await
+                        .expect("Failed to find the inserted object")
+                        .expect("No object is inserted in the database");
+
+                    assert_eq!(Uuid::from_str(&db_obj.id).unwrap(), agent.id);
+                }
+            }
+
+            // the datagram which is not an SNMP message is dropped and the response of the agent is awaited further
+            context "undecodable_datagram" {
+                before {
+                    let relay = tokio::net::UdpSocket::bind(format!("{host_ipaddr}:0")).await.unwrap();
+                    let relay_target = relay.local_addr().unwrap().to_string();
+                    let device_addr: std::net::SocketAddr = target.parse().unwrap();
+                    actix_rt::spawn(async move {
+                        let upstream = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+                        let mut buffer = vec![0u8; 65535];
+                        loop {
+                            let (len, peer) = relay.recv_from(&mut buffer).await.unwrap();
+                            relay.send_to(b"not an SNMP message", peer).await.unwrap();
+                            upstream.send_to(&buffer[..len], device_addr).await.unwrap();
+                            let (len, _) = upstream.recv_from(&mut buffer).await.unwrap();
+                            relay.send_to(&buffer[..len], peer).await.unwrap();
+                        }
+                    });
+                    let response = client
+                        .post(format!("{}/agents/record", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url,
+                            "target": relay_target,
+                            "snmp_protocol_attributes": route_snmp_v2c_attributes("public"),
+                            "root_oid": ".1.3.6.1.2.1.1",
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let snmp_data = std::fs::read_to_string(&snmp_data_url).unwrap_or_default();
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_201" {
+                    assert_eq!(response.status(), StatusCode::CREATED);
+                }
+
+                async it "writes_the_walked_subtree" {
+                    assert!(snmp_data.contains(".1.3.6.1.2.1.1.1.0 = STRING: "));
+                }
+            }
+
+            context "existing_file" {
+                before {
+                    std::fs::write(&snmp_data_url, "").unwrap();
+                    let response = client
+                        .post(format!("{}/agents/record", app.address))
+                        .json(&serde_json::json!({
+                            "name": name,
+                            "snmp_data_url": snmp_data_url,
+                            "target": target,
+                            "snmp_protocol_attributes": route_snmp_v2c_attributes("public"),
+                        }))
+                        .send()
+                        .await
+                        .expect("Failed to execute request");
+                    let snmp_data = std::fs::read_to_string(&snmp_data_url).unwrap();
+                    let _ = std::fs::remove_file(&snmp_data_url);
+                }
+
+                async it "returns_409" {
+                    assert_eq!(response.status(), StatusCode::CONFLICT);
+                }
+
+                async it "does_not_overwrite_the_file" {
+                    assert!(snmp_data.is_empty());
+                }
+            }
+        }
+    }
+}
@@ -71,7 +71,7 @@ demonstrate! {
 
             context "update_existing" {
                 before {
-                    let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string())
+                    let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string(), &None)
                         .await
                         .unwrap()
                         .unwrap_created();
@@ -60,7 +60,7 @@ demonstrate! {
         context "seeded_database" {
             before {
                 let db_conn = app.db_conn.as_ref().unwrap();
-                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string())
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string(), &None)
                     .await
                     .unwrap()
                     .unwrap_created();
@@ -0,0 +1,184 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "get_device_statistics" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, domain_snmp_v2c_attributes_json};
+        use crate::snmp::{DEVICE_PORT, get_request_v2};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use snmp_sim::routes::managed_devices::response::Statistics;
+        use snmp_sim::snmp::codec::GenericSnmpMessage;
+        use snmp_sim::udp_client::Client as UdpClient;
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+        use rasn_snmp::v2::*;
+        use rasn_smi::v2::*;
+        use reqwest::Client;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+
+        before {
+            let app = spawn_app().await;
+            let db_conn = app.db_conn.as_ref().unwrap();
+            let host_ipaddr = "127.0.0.1";
+
+            let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                .await
+                .unwrap()
+                .unwrap_created();
+            #[allow(unused)]
+            let agent_id = Uuid::from_str(&agent.id).unwrap();
+        }
+
+        context "not_existing_device" {
+            before {
+                let response = Client::new()
+                    .get(format!("{}/devices/{}/statistics", app.address, Uuid::new_v4()))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "returns_404" {
+                assert_eq!(StatusCode::NOT_FOUND, response.status());
+            }
+        }
+
+        context "snmp_v2c_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v2c_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                #[allow(unused)]
+                let device_id = *device_ids.first().unwrap();
+            }
+
+            context "not_running" {
+                before {
+                    let response = Client::new()
+                        .get(format!("{}/devices/{}/statistics", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_409" {
+                    assert_eq!(StatusCode::CONFLICT, response.status());
+                }
+            }
+
+            context "running" {
+                before {
+                    Client::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let in_pkts_oid = string_to_oid(".1.3.6.1.2.1.11.1.0");
+                    let snmp_response = UdpClient::new(remote_addr.parse().unwrap()).unwrap()
+                        .send_request(get_request_v2(1, "public", vec![in_pkts_oid.clone()]))
+                        .await;
+
+                    let response = Client::new()
+                        .get(format!("{}/devices/{}/statistics", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let statistics = response.json::<Statistics>().await.unwrap();
+                }
+
+                async it "serves_counters_by_mib" {
+                    if let Ok(GenericSnmpMessage::V2Message(msg)) = &snmp_response {
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(1, resp.0.variable_bindings.len());
+                            assert_eq!(in_pkts_oid, resp.0.variable_bindings[0].name);
+                            assert_eq!(
+                                VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(rasn_smi::v1::Counter(1)))),
+                                resp.0.variable_bindings[0].value);
+                        }
+                        else {
+                            println!("{:?}", snmp_response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", snmp_response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+
+                async it "returns_200" {
+                    assert_eq!(StatusCode::OK, status);
+                    assert_eq!(1, statistics.in_pkts);
+                    assert_eq!(1, statistics.out_pkts);
+                    assert_eq!(1, statistics.in_get_requests);
+                    assert_eq!(1, statistics.out_get_responses);
+                    assert_eq!(0, statistics.in_bad_community_names);
+                }
+            }
+        }
+
+        context "data_file_without_snmp_group" {
+            before {
+                let snmp_data_url = std::env::temp_dir()
+                    .join(format!("{}.txt", Uuid::new_v4()))
+                    .into_os_string()
+                    .into_string()
+                    .unwrap();
+                std::fs::write(&snmp_data_url, ".1.3.6.1.2.1.1.1.0 = STRING: Linux\n").unwrap();
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &snmp_data_url, &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v2c_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let remote_addr = format!("{host_ipaddr}:{device_port}");
+                let oids = vec![
+                    string_to_oid(".1.3.6.1.2.1.11.1.0"),
+                    string_to_oid(".1.3.6.1.2.1.11.31.0"),
+                ];
+                let snmp_response = UdpClient::new(remote_addr.parse().unwrap()).unwrap()
+                    .send_request(get_request_v2(1, "public", oids.clone()))
+                    .await;
+                let _ = std::fs::remove_file(&snmp_data_url);
+            }
+
+            async it "serves_counters_by_mib" {
+                if let Ok(GenericSnmpMessage::V2Message(msg)) = &snmp_response {
+                    if let Pdus::Response(resp) = &msg.data {
+                        assert_eq!(2, resp.0.variable_bindings.len());
+                        assert_eq!(oids[0], resp.0.variable_bindings[0].name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(rasn_smi::v1::Counter(1)))),
+                            resp.0.variable_bindings[0].value);
+                        assert_eq!(oids[1], resp.0.variable_bindings[1].name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(rasn_smi::v1::Counter(0)))),
+                            resp.0.variable_bindings[1].value);
+                    }
+                    else {
+                        println!("{:?}", snmp_response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+                else {
+                    println!("{:?}", snmp_response);
+                    debug_assert!(false, "Not a valid response");
+                }
+            }
+        }
+    }
+}
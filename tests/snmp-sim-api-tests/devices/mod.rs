@@ -1,7 +1,10 @@
 mod delete_device_id;
 mod get_device;
 mod get_device_id;
+mod get_device_statistics;
 mod post_device;
+mod post_device_inform;
+mod post_device_trap;
 mod put_device_id;
 mod start_device_id;
 mod stop_device_id;
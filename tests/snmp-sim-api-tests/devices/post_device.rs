@@ -7,6 +7,7 @@ demonstrate! {
         use actix_web::http::StatusCode;
         use snmp_sim::data_access::entity::managed_devices::{Entity, Column};
         use snmp_sim::routes::managed_devices::response;
+        use snmp_sim::routes::managed_devices::SnmpTransport;
         use sea_orm::EntityTrait;
         use uuid_dev::Uuid;
         use sea_orm::entity::prelude::*;
@@ -17,7 +18,7 @@ demonstrate! {
             let app = spawn_app().await;
             let client = reqwest::Client::new();
             let db_conn = app.db_conn.as_ref().unwrap();
-            let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string())
+            let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string(), &None)
                 .await
                 .unwrap()
                 .unwrap_created();
@@ -67,6 +68,7 @@ demonstrate! {
                         assert_eq!(device.snmp_protocol_attributes, route_snmp_v1_attributes("public"));
                         assert_eq!(device.snmp_host, snmp_host);
                         assert_eq!(device.snmp_port, snmp_port);
+                        assert_eq!(device.transport, SnmpTransport::Udp);
                     }
 
                     async it "creates_the_object_in_database" {
@@ -82,6 +84,327 @@ demonstrate! {
                         assert_eq!(Uuid::from_str(&db_obj.id).unwrap(), device.id);
                     }
                 }
+
+                context "create_proxy_device" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "proxy": &serde_json::json!({
+                                    "target": "127.0.0.1:161",
+                                    "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                    "subtrees": [".1.3.6.1.2.1.2"],
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_201" {
+                        assert_eq!(response.status(), StatusCode::CREATED);
+                    }
+
+                    async it "returns_the_proxy_with_defaults" {
+                        let device: response::Device = response.json().await.unwrap();
+                        let proxy = device.proxy.unwrap();
+                        assert_eq!(proxy.target, "127.0.0.1:161");
+                        assert_eq!(proxy.snmp_protocol_attributes, route_snmp_v1_attributes("public"));
+                        assert_eq!(proxy.subtrees, vec![".1.3.6.1.2.1.2".to_string()]);
+                        assert_eq!(proxy.cache_ttl, None);
+                        assert_eq!(proxy.timeout, Some(1000));
+                        assert_eq!(proxy.retries, Some(1));
+                    }
+                }
+
+                context "create_device_with_system_group" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "sys_name": "router-1",
+                                "sys_location": "Rack 4",
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_201" {
+                        assert_eq!(response.status(), StatusCode::CREATED);
+                    }
+
+                    async it "returns_the_system_group" {
+                        let device: response::Device = response.json().await.unwrap();
+                        assert_eq!(device.sys_name, Some("router-1".to_string()));
+                        assert_eq!(device.sys_location, Some("Rack 4".to_string()));
+                    }
+                }
+
+                context "create_tcp_device" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "transport": "tcp",
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_201" {
+                        assert_eq!(response.status(), StatusCode::CREATED);
+                    }
+
+                    async it "returns_the_transport" {
+                        let device: response::Device = response.json().await.unwrap();
+                        assert_eq!(device.transport, SnmpTransport::Tcp);
+                    }
+                }
+
+                context "create_device_with_unknown_transport" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "transport": "sctp",
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
+
+                context "create_ipv6_device" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": "[::1]",
+                                "snmp_port": snmp_port,
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_201" {
+                        assert_eq!(response.status(), StatusCode::CREATED);
+                    }
+
+                    async it "returns_the_host_without_brackets" {
+                        let device: response::Device = response.json().await.unwrap();
+                        assert_eq!(device.snmp_host, "::1");
+                    }
+                }
+
+                context "create_device_with_invalid_host" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": "127.0.0.1:161",
+                                "snmp_port": snmp_port,
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
+
+                context "create_device_proxying_itself" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": "127.0.0.1",
+                                "snmp_port": snmp_port,
+                                "proxy": &serde_json::json!({
+                                    "target": format!("127.0.0.1:{snmp_port}"),
+                                    "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
+
+                context "create_proxy_device_with_invalid_subtree" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "proxy": &serde_json::json!({
+                                    "target": "127.0.0.1:161",
+                                    "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                    "subtrees": ["interfaces"],
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
+
+                context "create_agentx_device" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "agentx": &serde_json::json!({
+                                    "address": "127.0.0.1:1705",
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_201" {
+                        assert_eq!(response.status(), StatusCode::CREATED);
+                    }
+
+                    async it "returns_the_agentx_with_defaults" {
+                        let device: response::Device = response.json().await.unwrap();
+                        let agentx = device.agentx.unwrap();
+                        assert_eq!(agentx.address, "127.0.0.1:1705");
+                        assert_eq!(agentx.timeout, Some(1000));
+                    }
+                }
+
+                context "create_agentx_device_with_invalid_address" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "agentx": &serde_json::json!({
+                                    "address": "localhost",
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
+
+                context "create_agentx_device_with_zero_timeout" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "agentx": &serde_json::json!({
+                                    "address": "127.0.0.1:1705",
+                                    "timeout": 0,
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
+
+                context "create_agentx_device_with_proxy" {
+                    before {
+                        let response = client
+                            .post(format!("{}/devices", app.address))
+                            .json(&serde_json::json!({
+                                "name": name,
+                                "agent": &serde_json::json!({"id": agent_id}),
+                                "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                "snmp_host": snmp_host,
+                                "snmp_port": snmp_port,
+                                "proxy": &serde_json::json!({
+                                    "target": "127.0.0.1:161",
+                                    "snmp_protocol_attributes": route_snmp_v1_attributes("public"),
+                                }),
+                                "agentx": &serde_json::json!({
+                                    "address": "127.0.0.1:1705",
+                                }),
+                            }))
+                            .send()
+                            .await
+                            .expect("Failed to execute request");
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+                    }
+                }
             }
 
             context "empty_database" {
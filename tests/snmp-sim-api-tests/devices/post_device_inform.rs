@@ -0,0 +1,267 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "post_device_inform" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, domain_snmp_v1_attributes_json, domain_snmp_v2c_attributes_json, domain_snmp_v3_attributes_json};
+        use crate::snmp::{snmp_v3_parameters, DEVICE_PORT};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use snmp_sim::snmp::codec::{GenericSnmpMessage, SnmpCodec};
+        use snmp_sim::routes::managed_devices::response::{Inform, InformStatus};
+        use snmp_sim::snmp::usm::{decrypt_scoped_pdu, verify_message, AuthenticationKey, PrivacyKey};
+        use snmp_sim::domain::{AuthenticationAlgorithm, EncryptionAlgorithm, Report, Variable};
+        use snmp_data_parser::parser::snmp_data::component::{string_to_oid, DataType};
+        use num_traits::ToPrimitive;
+        use rasn_snmp::v2::Pdus;
+        use reqwest::Client;
+        use tokio_util::udp::UdpFramed;
+        use futures::{SinkExt, StreamExt};
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+
+        before {
+            let app = spawn_app().await;
+            let db_conn = app.db_conn.as_ref().unwrap();
+            let host_ipaddr = "127.0.0.1";
+            let manager = tokio::net::UdpSocket::bind(format!("{host_ipaddr}:0")).await.unwrap();
+            let target = manager.local_addr().unwrap().to_string();
+            #[allow(unused)]
+            let mut manager = UdpFramed::new(manager, SnmpCodec::default());
+
+            let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                .await
+                .unwrap()
+                .unwrap_created();
+            #[allow(unused)]
+            let agent_id = Uuid::from_str(&agent.id).unwrap();
+        }
+
+        context "not_existing_device" {
+            before {
+                let response = Client::new()
+                    .post(format!("{}/devices/{}/informs", app.address, Uuid::new_v4()))
+                    .json(&serde_json::json!({"target": target, "trap_oid": ".1.3.6.1.6.3.1.1.5.1"}))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "returns_404" {
+                assert_eq!(StatusCode::NOT_FOUND, response.status());
+            }
+        }
+
+        context "snmp_v1_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v1_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                let response = Client::new()
+                    .post(format!("{}/devices/{}/informs", app.address, device_id))
+                    .json(&serde_json::json!({"target": target, "trap_oid": ".1.3.6.1.6.3.1.1.5.1"}))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "returns_400" {
+                assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            }
+        }
+
+        context "running_snmp_v2c_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v2c_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                #[allow(unused)]
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            context "unknown_inform" {
+                before {
+                    let response = Client::new()
+                        .get(format!("{}/devices/{}/informs/{}", app.address, device_id, i32::MAX))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_404" {
+                    assert_eq!(StatusCode::NOT_FOUND, response.status());
+                }
+            }
+
+            context "acknowledged_inform" {
+                before {
+                    let response = Client::new()
+                        .post(format!("{}/devices/{}/informs", app.address, device_id))
+                        .json(&serde_json::json!({"target": target, "trap_oid": ".1.3.6.1.6.3.1.1.5.3", "timeout": 500, "retries": 2}))
+                        .send()
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let inform = response.json::<Inform>().await.unwrap();
+
+                    // the manager acknowledges the InformRequest by the Response-PDU with the same request-id
+                    let request = tokio::time::timeout(std::time::Duration::from_secs(3), manager.next()).await;
+                    if let Ok(Some(Ok((GenericSnmpMessage::V2Message(message), peer)))) = request {
+                        if let Pdus::InformRequest(inform_request) = message.data {
+                            let acknowledgement = rasn_snmp::v2c::Message {
+                                version: message.version,
+                                community: message.community,
+                                data: Pdus::Response(rasn_snmp::v2::Response(inform_request.0)),
+                            };
+                            manager.send((GenericSnmpMessage::V2Message(acknowledgement), peer)).await.unwrap();
+                        }
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+                    let delivery = Client::new()
+                        .get(format!("{}/devices/{}/informs/{}", app.address, device_id, inform.request_id))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Inform>()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_201_pending" {
+                    assert_eq!(StatusCode::CREATED, status);
+                    assert_eq!(InformStatus::Pending, inform.status);
+                    assert_eq!(target, inform.target);
+                }
+
+                async it "is_acked" {
+                    assert_eq!(InformStatus::Acked, delivery.status);
+                    assert_eq!(1, delivery.attempts);
+                }
+            }
+
+            context "unacknowledged_inform" {
+                before {
+                    let inform = Client::new()
+                        .post(format!("{}/devices/{}/informs", app.address, device_id))
+                        .json(&serde_json::json!({"target": target, "trap_oid": ".1.3.6.1.6.3.1.1.5.3", "timeout": 100, "retries": 1}))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Inform>()
+                        .await
+                        .unwrap();
+                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+                    let delivery = Client::new()
+                        .get(format!("{}/devices/{}/informs/{}", app.address, device_id, inform.request_id))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Inform>()
+                        .await
+                        .unwrap();
+                }
+
+                async it "is_failed_after_retries" {
+                    assert_eq!(InformStatus::Failed, delivery.status);
+                    assert_eq!(2, delivery.attempts);
+                }
+            }
+        }
+
+        context "running_snmp_v3_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Sha, "simulator-password", EncryptionAlgorithm::Aes, "simulator-privacy");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                // the manager is the authoritative engine of the InformRequest
+                let engine_id = vec![0x80, 0x00, 0x1F, 0x88, 0x04, 0x6D, 0x61, 0x6E, 0x61, 0x67, 0x65, 0x72];
+                let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Sha, "simulator-password", &engine_id).unwrap();
+                let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Aes, &AuthenticationAlgorithm::Sha, "simulator-privacy", &engine_id).unwrap();
+
+                let inform = Client::new()
+                    .post(format!("{}/devices/{}/informs", app.address, device_id))
+                    .json(&serde_json::json!({"target": target, "trap_oid": ".1.3.6.1.6.3.1.1.5.3", "timeout": 500, "retries": 2}))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Inform>()
+                    .await
+                    .unwrap();
+
+                // the engine discovery is answered by the usmStatsUnknownEngineIDs Report
+                let discovery = tokio::time::timeout(std::time::Duration::from_secs(3), manager.next()).await;
+                let mut discovery_engine_id = None;
+                if let Ok(Some(Ok((GenericSnmpMessage::V3Message(message), peer)))) = discovery {
+                    discovery_engine_id = Some(message.security_parameters.authoritative_engine_id.to_vec());
+                    let mut parameters = snmp_v3_parameters(&engine_id, 1, 100, "", None, None);
+                    parameters.message_id = message.global_data.message_id.to_i32().unwrap();
+                    let report = Report {
+                        request_id: inform.request_id,
+                        variables: vec![Variable {
+                            name: string_to_oid(".1.3.6.1.6.3.15.1.1.4.0"),
+                            data_type: DataType::Counter32,
+                            value: "1".to_string(),
+                        }],
+                    };
+                    manager.send((GenericSnmpMessage::V3Message(Box::new(parameters.to_message(report.into()).into())), peer)).await.unwrap();
+                }
+
+                // the InformRequest is authenticated and encrypted by the keys localized to the manager
+                let request = tokio::time::timeout(std::time::Duration::from_secs(3), manager.next()).await;
+                let mut inform_request = None;
+                if let Ok(Some(Ok((GenericSnmpMessage::V3Message(mut message), peer)))) = request {
+                    let message_id = message.global_data.message_id.to_i32().unwrap();
+                    if verify_message(&message, &authentication_key) && decrypt_scoped_pdu(&mut message, &privacy_key) {
+                        if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = message.message.scoped_data {
+                            if let Pdus::InformRequest(request) = scoped_pdu.data {
+                                let mut parameters = snmp_v3_parameters(&engine_id, 1, 100, "simulator", Some(authentication_key.clone()), Some(privacy_key.clone()));
+                                parameters.message_id = message_id;
+                                let acknowledgement = parameters.to_message(Pdus::Response(rasn_snmp::v2::Response(request.0.clone())));
+                                manager.send((GenericSnmpMessage::V3Message(Box::new(acknowledgement.into())), peer)).await.unwrap();
+                                inform_request = Some(request);
+                            }
+                        }
+                    }
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+                let delivery = Client::new()
+                    .get(format!("{}/devices/{}/informs/{}", app.address, device_id, inform.request_id))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Inform>()
+                    .await
+                    .unwrap();
+            }
+
+            async it "discovers_manager_engine" {
+                assert_eq!(Some(vec![]), discovery_engine_id);
+            }
+
+            async it "sends_authenticated_inform" {
+                assert_eq!(Some(inform.request_id), inform_request.map(|request| request.0.request_id));
+            }
+
+            async it "is_acked" {
+                assert_eq!(InformStatus::Acked, delivery.status);
+                assert_eq!(1, delivery.attempts);
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "post_device_trap" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, domain_snmp_v1_attributes_json, domain_snmp_v2c_attributes_json, domain_snmp_v3_attributes_json};
+        use crate::snmp::DEVICE_PORT;
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use snmp_sim::snmp::codec::{GenericSnmpMessage, SnmpCodec};
+        use snmp_sim::snmp::usm::{decrypt_scoped_pdu, verify_message, AuthenticationKey, PrivacyKey};
+        use snmp_sim::domain::{AuthenticationAlgorithm, EncryptionAlgorithm};
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+        use rasn_snmp::v2::Pdus;
+        use reqwest::Client;
+        use tokio_util::udp::UdpFramed;
+        use futures::StreamExt;
+        use num_traits::ToPrimitive;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+
+        before {
+            let app = spawn_app().await;
+            let db_conn = app.db_conn.as_ref().unwrap();
+            let host_ipaddr = "127.0.0.1";
+            let trap_receiver = tokio::net::UdpSocket::bind(format!("{host_ipaddr}:0")).await.unwrap();
+            let trap_target = trap_receiver.local_addr().unwrap().to_string();
+            #[allow(unused)]
+            let mut trap_receiver = UdpFramed::new(trap_receiver, SnmpCodec::default());
+
+            let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                .await
+                .unwrap()
+                .unwrap_created();
+            #[allow(unused)]
+            let agent_id = Uuid::from_str(&agent.id).unwrap();
+        }
+
+        context "not_existing_device" {
+            before {
+                let response = Client::new()
+                    .post(format!("{}/devices/{}/traps", app.address, Uuid::new_v4()))
+                    .json(&serde_json::json!({"targets": [trap_target], "trap_oid": ".1.3.6.1.6.3.1.1.5.1"}))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "returns_404" {
+                assert_eq!(StatusCode::NOT_FOUND, response.status());
+            }
+        }
+
+        context "snmp_v2c_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v2c_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                #[allow(unused)]
+                let device_id = *device_ids.first().unwrap();
+            }
+
+            context "not_running" {
+                before {
+                    let response = Client::new()
+                        .post(format!("{}/devices/{}/traps", app.address, device_id))
+                        .json(&serde_json::json!({"targets": [trap_target], "trap_oid": ".1.3.6.1.6.3.1.1.5.1"}))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_409" {
+                    assert_eq!(StatusCode::CONFLICT, response.status());
+                }
+            }
+
+            context "running" {
+                before {
+                    Client::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+
+                context "with_invalid_variable" {
+                    before {
+                        let response = Client::new()
+                            .post(format!("{}/devices/{}/traps", app.address, device_id))
+                            .json(&serde_json::json!({
+                                "targets": [trap_target],
+                                "trap_oid": ".1.3.6.1.6.3.1.1.5.3",
+                                "variables": [{"oid": ".1.3.6.1.2.1.2.2.1.1.1", "data_type": "INTEGER", "value": "one"}]
+                            }))
+                            .send()
+                            .await
+                            .unwrap();
+                    }
+
+                    async it "returns_400" {
+                        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+                    }
+                }
+
+                context "with_trap_oid" {
+                    before {
+                        let response = Client::new()
+                            .post(format!("{}/devices/{}/traps", app.address, device_id))
+                            .json(&serde_json::json!({
+                                "targets": [trap_target],
+                                "trap_oid": ".1.3.6.1.6.3.1.1.5.3",
+                                "variables": [{"oid": ".1.3.6.1.2.1.2.2.1.1.1", "data_type": "INTEGER", "value": "1"}]
+                            }))
+                            .send()
+                            .await
+                            .unwrap();
+                        let status = response.status();
+                        let body = response.json::<snmp_sim::routes::managed_devices::response::Trap>().await.unwrap();
+                        let trap = tokio::time::timeout(std::time::Duration::from_secs(3), trap_receiver.next()).await;
+                    }
+
+                    async it "returns_201" {
+                        assert_eq!(StatusCode::CREATED, status);
+                        assert_eq!(".1.3.6.1.6.3.1.1.5.3", body.trap_oid);
+                        assert_eq!(vec![trap_target.clone()], body.targets);
+                    }
+
+                    async it "sends_trap" {
+                        if let Ok(Some(Ok((GenericSnmpMessage::V2Message(msg), _)))) = &trap {
+                            if let Pdus::Trap(trap_pdu) = &msg.data {
+                                assert_eq!(body.request_id, trap_pdu.0.request_id);
+                                assert_eq!(3, trap_pdu.0.variable_bindings.len());
+                                assert_eq!(string_to_oid(".1.3.6.1.6.3.1.1.4.1.0"), trap_pdu.0.variable_bindings[1].name);
+                                assert_eq!(string_to_oid(".1.3.6.1.2.1.2.2.1.1.1"), trap_pdu.0.variable_bindings[2].name);
+                            }
+                            else {
+                                println!("{:?}", trap);
+                                debug_assert!(false, "Not a valid trap");
+                            }
+                        }
+                        else {
+                            println!("{:?}", trap);
+                            debug_assert!(false, "Not a valid trap");
+                        }
+                    }
+                }
+            }
+        }
+
+        context "snmp_v1_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v1_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            context "with_enterprise_specific_trap" {
+                before {
+                    let response = Client::new()
+                        .post(format!("{}/devices/{}/traps", app.address, device_id))
+                        .json(&serde_json::json!({
+                            "targets": [trap_target],
+                            "enterprise": ".1.3.6.1.4.1.8072",
+                            "generic_trap": 6,
+                            "specific_trap": 5
+                        }))
+                        .send()
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let trap = tokio::time::timeout(std::time::Duration::from_secs(3), trap_receiver.next()).await;
+                }
+
+                async it "sends_trap" {
+                    assert_eq!(StatusCode::CREATED, status);
+                    if let Ok(Some(Ok((GenericSnmpMessage::V1Message(msg), _)))) = &trap {
+                        if let rasn_snmp::v1::Pdus::Trap(trap_pdu) = &msg.data {
+                            assert_eq!(string_to_oid(".1.3.6.1.4.1.8072"), trap_pdu.enterprise);
+                            assert_eq!(Some(6), trap_pdu.generic_trap.to_i32());
+                            assert_eq!(Some(5), trap_pdu.specific_trap.to_i32());
+                        }
+                        else {
+                            println!("{:?}", trap);
+                            debug_assert!(false, "Not a valid trap");
+                        }
+                    }
+                    else {
+                        println!("{:?}", trap);
+                        debug_assert!(false, "Not a valid trap");
+                    }
+                }
+            }
+
+            context "with_enterprise_specific_trap_without_enterprise" {
+                before {
+                    let response = Client::new()
+                        .post(format!("{}/devices/{}/traps", app.address, device_id))
+                        .json(&serde_json::json!({"targets": [trap_target], "generic_trap": 6, "specific_trap": 5}))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_400" {
+                    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+                }
+            }
+        }
+
+        context "snmp_v3_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Sha, "simulator-password", EncryptionAlgorithm::Aes, "simulator-privacy");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let response = Client::new()
+                    .post(format!("{}/devices/{}/traps", app.address, device_id))
+                    .json(&serde_json::json!({"targets": [trap_target], "trap_oid": ".1.3.6.1.6.3.1.1.5.3"}))
+                    .send()
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let trap = tokio::time::timeout(std::time::Duration::from_secs(3), trap_receiver.next()).await;
+            }
+
+            // the device is the authoritative engine, the keys are localized to its snmpEngineID
+            async it "sends_authenticated_and_encrypted_trap" {
+                assert_eq!(StatusCode::CREATED, status);
+                if let Ok(Some(Ok((GenericSnmpMessage::V3Message(mut msg), _)))) = trap {
+                    let engine_id = msg.security_parameters.authoritative_engine_id.to_vec();
+                    let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Sha, "simulator-password", &engine_id).unwrap();
+                    let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Aes, &AuthenticationAlgorithm::Sha, "simulator-privacy", &engine_id).unwrap();
+                    assert_eq!(b"simulator".to_vec(), msg.security_parameters.user_name.to_vec());
+                    assert!(verify_message(&msg, &authentication_key));
+                    assert!(decrypt_scoped_pdu(&mut msg, &privacy_key));
+                    if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                        assert_eq!(engine_id, scoped_pdu.engine_id.to_vec());
+                        assert!(matches!(scoped_pdu.data, Pdus::Trap(_)));
+                    }
+                    else {
+                        debug_assert!(false, "Not a decrypted trap");
+                    }
+                }
+                else {
+                    println!("{:?}", trap);
+                    debug_assert!(false, "Not a valid trap");
+                }
+            }
+        }
+    }
+}
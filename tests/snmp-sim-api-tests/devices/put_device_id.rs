@@ -21,7 +21,7 @@ demonstrate! {
             before {
                 let db_conn = app.db_conn.as_ref().unwrap();
 
-                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string())
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &Uuid::new_v4().to_string(), &None)
                     .await
                     .unwrap()
                     .unwrap_created();
@@ -95,7 +95,12 @@ demonstrate! {
                         &agent_id,
                         &domain_snmp_v1_attributes_json("public"),
                         "0.0.0.0",
-                        30161)
+                        30161,
+                        &None,
+                        &None,
+                        &None,
+                        &None,
+                        "udp")
                         .await
                         .unwrap()
                         .unwrap_created();
@@ -19,7 +19,7 @@ demonstrate! {
             before {
                 let db_conn = app.db_conn.as_ref().unwrap();
 
-                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt")
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
                     .await
                     .unwrap()
                     .unwrap_created();
@@ -132,6 +132,7 @@ pub async fn seed_agents(conn: &DatabaseConnection, agents_count: usize) {
             &Uuid::new_v4().to_string(),
             &Some(Uuid::new_v4().to_string()),
             &Uuid::new_v4().to_string(),
+            &None,
         )
         .await;
     }
@@ -144,6 +145,19 @@ pub async fn seed_devices(
     devices_count: usize,
     snmp_host: &str,
     snmp_initial_port: u16,
+) -> std::vec::Vec<Uuid> {
+    let protocol = domain_snmp_v1_attributes_json("public");
+    seed_devices_with_protocol(conn, agent_id, devices_count, snmp_host, snmp_initial_port, &protocol).await
+}
+
+// Creates required number device instances using the SNMP protocol attributes in json format
+pub async fn seed_devices_with_protocol(
+    conn: &DatabaseConnection,
+    agent_id: &Uuid,
+    devices_count: usize,
+    snmp_host: &str,
+    snmp_initial_port: u16,
+    protocol: &str,
 ) -> std::vec::Vec<Uuid> {
     use snmp_sim::data_access::helpers::*;
 
@@ -151,7 +165,6 @@ pub async fn seed_devices(
         let device_id = Uuid::new_v4();
         let device_name = Uuid::new_v4().to_string();
         let device_description = Some(Uuid::new_v4().to_string());
-        let protocol = domain_snmp_v1_attributes_json("public");
         let snmp_port = snmp_initial_port + idx as u16;
         create_managed_device(
             conn,
@@ -159,9 +172,14 @@ pub async fn seed_devices(
             &device_name,
             &device_description,
             agent_id,
-            &protocol,
+            protocol,
             snmp_host,
             snmp_port,
+            &None,
+            &None,
+            &None,
+            &None,
+            "udp",
         )
         .await
     }))
@@ -182,6 +200,9 @@ pub fn route_snmp_v1_attributes(community: &str) -> snmp_sim::routes::SnmpProtoc
     snmp_sim::routes::SnmpProtocolAttributes {
         snmp_v1: Some(snmp_sim::routes::SnmpV1Attributes {
             community: Some(community.to_string()),
+            read_write_community: Some("private".to_string()),
+            authentication_failure_trap: None,
+            max_message_size: None,
         }),
         snmp_v2c: None,
         snmp_v3: None,
@@ -191,6 +212,9 @@ pub fn route_snmp_v1_attributes(community: &str) -> snmp_sim::routes::SnmpProtoc
 pub fn domain_snmp_v1_attributes(community: &str) -> snmp_sim::domain::SnmpProtocolAttributes {
     snmp_sim::domain::SnmpProtocolAttributes::SnmpV1(snmp_sim::domain::SnmpV1Attributes {
         community: community.to_string(),
+        read_write_community: Some("private".to_string()),
+        authentication_failure_trap: None,
+        max_message_size: None,
     })
 }
 
@@ -204,6 +228,9 @@ pub fn route_snmp_v2c_attributes(community: &str) -> snmp_sim::routes::SnmpProto
         snmp_v1: None,
         snmp_v2c: Some(snmp_sim::routes::SnmpV2cAttributes {
             community: Some(community.to_string()),
+            read_write_community: Some("private".to_string()),
+            authentication_failure_trap: None,
+            max_message_size: None,
         }),
         snmp_v3: None,
     }
@@ -212,6 +239,9 @@ pub fn route_snmp_v2c_attributes(community: &str) -> snmp_sim::routes::SnmpProto
 pub fn domain_snmp_v2c_attributes(community: &str) -> snmp_sim::domain::SnmpProtocolAttributes {
     snmp_sim::domain::SnmpProtocolAttributes::SnmpV2c(snmp_sim::domain::SnmpV2cAttributes {
         community: community.to_string(),
+        read_write_community: Some("private".to_string()),
+        authentication_failure_trap: None,
+        max_message_size: None,
     })
 }
 
@@ -219,6 +249,22 @@ pub fn domain_snmp_v2c_attributes_json(community: &str) -> String {
     serde_json::to_string(&domain_snmp_v2c_attributes(community)).unwrap()
 }
 
+pub fn domain_snmp_v2c_attributes_with_trap_json(community: &str, trap_target: &str) -> String {
+    let mut attributes = domain_snmp_v2c_attributes(community);
+    if let snmp_sim::domain::SnmpProtocolAttributes::SnmpV2c(attr) = &mut attributes {
+        attr.authentication_failure_trap = Some(trap_target.to_string());
+    }
+    serde_json::to_string(&attributes).unwrap()
+}
+
+pub fn domain_snmp_v2c_attributes_with_max_message_size_json(community: &str, max_message_size: u32) -> String {
+    let mut attributes = domain_snmp_v2c_attributes(community);
+    if let snmp_sim::domain::SnmpProtocolAttributes::SnmpV2c(attr) = &mut attributes {
+        attr.max_message_size = Some(max_message_size);
+    }
+    serde_json::to_string(&attributes).unwrap()
+}
+
 //----- SNMP V3 protocol attributes for route and domain layers, raw and json format
 pub fn _snmp_v3_attributes(
     user: &str,
@@ -239,3 +285,29 @@ pub fn _snmp_v3_attributes(
         }),
     }
 }
+
+pub fn domain_snmp_v3_attributes(
+    user: &str,
+    auth_alg: snmp_sim::domain::AuthenticationAlgorithm,
+    auth_key: &str,
+    enc_alg: snmp_sim::domain::EncryptionAlgorithm,
+    enc_key: &str,
+) -> snmp_sim::domain::SnmpProtocolAttributes {
+    snmp_sim::domain::SnmpProtocolAttributes::SnmpV3(snmp_sim::domain::SnmpV3Attributes {
+        user: user.to_string(),
+        authentication: auth_alg,
+        authentication_password: auth_key.to_string(),
+        encryption: enc_alg,
+        encryption_key: enc_key.to_string(),
+    })
+}
+
+pub fn domain_snmp_v3_attributes_json(
+    user: &str,
+    auth_alg: snmp_sim::domain::AuthenticationAlgorithm,
+    auth_key: &str,
+    enc_alg: snmp_sim::domain::EncryptionAlgorithm,
+    enc_key: &str,
+) -> String {
+    serde_json::to_string(&domain_snmp_v3_attributes(user, auth_alg, auth_key, enc_alg, enc_key)).unwrap()
+}
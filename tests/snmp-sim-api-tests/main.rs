@@ -1,6 +1,7 @@
 mod agents;
 mod devices;
 mod helpers;
+mod notifications;
 mod service_scope;
 mod snmp;
 mod test_app;
@@ -0,0 +1,230 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "get_notifications" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, domain_snmp_v2c_attributes_json, domain_snmp_v3_attributes_json};
+        use crate::snmp::DEVICE_PORT;
+        use actix_web::http::StatusCode;
+        use snmp_sim::configuration::get_configuration;
+        use snmp_sim::data_access::helpers::*;
+        use snmp_sim::domain::{AuthenticationAlgorithm, EncryptionAlgorithm};
+        use snmp_sim::routes::managed_devices::response::{Inform, InformStatus};
+        use snmp_sim::routes::notifications::response::Notifications;
+        use reqwest::Client;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+
+        before {
+            let app = spawn_app().await;
+            let db_conn = app.db_conn.as_ref().unwrap();
+            let host_ipaddr = "127.0.0.1";
+            let trap_receiver = get_configuration(None).unwrap().trap_receiver.unwrap();
+            #[allow(unused)]
+            let trap_receiver = format!("{}:{}", trap_receiver.host, trap_receiver.port);
+
+            let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                .await
+                .unwrap()
+                .unwrap_created();
+            #[allow(unused)]
+            let agent_id = Uuid::from_str(&agent.id).unwrap();
+        }
+
+        context "with_invalid_source" {
+            before {
+                let response = Client::new()
+                    .get(format!("{}/notifications?source=localhost", app.address))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "returns_400" {
+                assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            }
+        }
+
+        context "with_invalid_time_range" {
+            before {
+                let response = Client::new()
+                    .get(format!("{}/notifications?from=yesterday", app.address))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "returns_400" {
+                assert_eq!(StatusCode::BAD_REQUEST, response.status());
+            }
+        }
+
+        context "snmp_v2c_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v2c_attributes_json("public");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let started_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+                Client::new()
+                    .post(format!("{}/devices/{}/traps", app.address, device_id))
+                    .json(&serde_json::json!({
+                        "targets": [trap_receiver],
+                        "trap_oid": ".1.3.6.1.6.3.1.1.5.3",
+                        "variables": [{"oid": ".1.3.6.1.2.1.2.2.1.1.1", "data_type": "INTEGER", "value": "1"}]
+                    }))
+                    .send()
+                    .await
+                    .unwrap();
+                let inform = Client::new()
+                    .post(format!("{}/devices/{}/informs", app.address, device_id))
+                    .json(&serde_json::json!({"target": trap_receiver, "trap_oid": ".1.3.6.1.6.3.1.1.5.4", "timeout": 500, "retries": 2}))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Inform>()
+                    .await
+                    .unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+                let delivery = Client::new()
+                    .get(format!("{}/devices/{}/informs/{}", app.address, device_id, inform.request_id))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Inform>()
+                    .await
+                    .unwrap();
+                let source = format!("{host_ipaddr}:{device_port}");
+            }
+
+            async it "acknowledges_inform" {
+                assert_eq!(InformStatus::Acked, delivery.status);
+            }
+
+            context "filtered_by_source" {
+                before {
+                    let response = Client::new()
+                        .get(format!("{}/notifications?source={}&from={}", app.address, source, started_at))
+                        .send()
+                        .await
+                        .unwrap();
+                    let status = response.status();
+                    let notifications = response.json::<Notifications>().await.unwrap();
+                }
+
+                async it "returns_both_notifications" {
+                    assert_eq!(StatusCode::OK, status);
+                    assert_eq!(2, notifications.count);
+                    // the latest notifications come first
+                    assert_eq!("InformRequest", notifications.items[0].pdu_type);
+                    assert_eq!(inform.request_id, notifications.items[0].request_id);
+                    assert_eq!("Trap", notifications.items[1].pdu_type);
+                    assert!(notifications.items.iter().all(|notification| notification.source == source));
+                    assert!(notifications.items.iter().all(|notification| notification.snmp_version == "SNMPv2c"));
+                    assert!(notifications.items.iter().all(|notification| notification.security_name == "public"));
+                }
+            }
+
+            context "filtered_by_trap_oid" {
+                before {
+                    let notifications = Client::new()
+                        .get(format!("{}/notifications?source={}&trap_oid=.1.3.6.1.6.3.1.1.5.3", app.address, source))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Notifications>()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_trap" {
+                    assert_eq!(1, notifications.count);
+                    let trap = &notifications.items[0];
+                    assert_eq!(".1.3.6.1.6.3.1.1.5.3", trap.trap_oid);
+                    assert_eq!(1, trap.variables.len());
+                    assert_eq!(".1.3.6.1.2.1.2.2.1.1.1", trap.variables[0].oid);
+                    assert_eq!("INTEGER", trap.variables[0].data_type);
+                    assert_eq!("1", trap.variables[0].value);
+                }
+            }
+
+            context "filtered_by_time_range" {
+                before {
+                    let notifications = Client::new()
+                        .get(format!("{}/notifications?source={}&to={}", app.address, source, "2000-01-01T00:00:00Z"))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Notifications>()
+                        .await
+                        .unwrap();
+                }
+
+                async it "returns_no_notification" {
+                    assert_eq!(0, notifications.count);
+                }
+            }
+        }
+
+        context "snmp_v3_device" {
+            before {
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Sha, "simulator-password", EncryptionAlgorithm::Aes, "simulator-privacy");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                Client::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                // the trap receiver is the authoritative engine of the InformRequest, it's discovered by the device
+                let inform = Client::new()
+                    .post(format!("{}/devices/{}/informs", app.address, device_id))
+                    .json(&serde_json::json!({"target": trap_receiver, "trap_oid": ".1.3.6.1.6.3.1.1.5.4", "timeout": 500, "retries": 2}))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Inform>()
+                    .await
+                    .unwrap();
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+                let delivery = Client::new()
+                    .get(format!("{}/devices/{}/informs/{}", app.address, device_id, inform.request_id))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Inform>()
+                    .await
+                    .unwrap();
+                let notifications = Client::new()
+                    .get(format!("{}/notifications?source={}:{}", app.address, host_ipaddr, device_port))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json::<Notifications>()
+                    .await
+                    .unwrap();
+            }
+
+            async it "acknowledges_inform" {
+                assert_eq!(InformStatus::Acked, delivery.status);
+            }
+
+            async it "records_inform" {
+                assert_eq!(1, notifications.count);
+                assert_eq!("SNMPv3", notifications.items[0].snmp_version);
+                assert_eq!("simulator", notifications.items[0].security_name);
+                assert_eq!(inform.request_id, notifications.items[0].request_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "agentx" {
+        use crate::helpers::{spawn_app, domain_snmp_v2c_attributes_json};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use reqwest::Client as reqwestClient;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+        use snmp_sim::snmp::codec::GenericSnmpMessage;
+        use crate::snmp::DEVICE_PORT;
+        use num_traits::ToPrimitive;
+        use rasn_snmp::v2::*;
+        use rasn_smi::v2::*;
+        use crate::snmp::{get_request_v2, get_next_request_v2};
+        use snmp_sim::domain::{AgentxAddress, AgentxAttributes};
+        use snmp_sim::snmp::agentx::*;
+        use snmp_sim::udp_client::Client;
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+        use futures::{SinkExt, StreamExt};
+        use tokio_util::codec::Framed;
+
+        before {
+            let app = spawn_app().await;
+        }
+
+        describe "device_with_subagent" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let master_port = DEVICE_PORT.write().get_next_value();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+
+                let agentx = AgentxAttributes {
+                    address: AgentxAddress::Tcp(format!("{host_ipaddr}:{master_port}").parse().unwrap()),
+                    timeout: 1000,
+                };
+                let (device, _agent) = create_managed_device(
+                    db_conn,
+                    &Uuid::new_v4(),
+                    &Uuid::new_v4().to_string(),
+                    &None,
+                    &agent_id,
+                    &domain_snmp_v2c_attributes_json("public"),
+                    host_ipaddr,
+                    device_port,
+                    &None,
+                    &None,
+                    &None,
+                    &Some(serde_json::to_string(&agentx).unwrap()),
+                    "udp",
+                )
+                .await
+                .unwrap()
+                .unwrap_created();
+                let device_id = Uuid::from_str(&device.id).unwrap();
+
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                // the subagent opens a session and registers the sysName subtree
+                let header = |session_id, packet_id| AgentxHeader {
+                    flags: NETWORK_BYTE_ORDER,
+                    session_id,
+                    transaction_id: 0,
+                    packet_id,
+                };
+                let stream = tokio::net::TcpStream::connect(format!("{host_ipaddr}:{master_port}")).await.unwrap();
+                let mut subagent = Framed::new(stream, AgentxCodec::new());
+                subagent.send(AgentxPdu {
+                    header: header(0, 1),
+                    context: None,
+                    payload: AgentxPayload::Open {
+                        timeout: 0,
+                        id: string_to_oid(".1.3.6.1.4.1.8072"),
+                        description: b"subagent".to_vec(),
+                    },
+                }).await.unwrap();
+                let open_response = subagent.next().await.unwrap().unwrap();
+                let session_id = open_response.header.session_id;
+
+                let name_oid = string_to_oid(".1.3.6.1.2.1.1.5.0");
+                subagent.send(AgentxPdu {
+                    header: header(session_id, 2),
+                    context: None,
+                    payload: AgentxPayload::Register {
+                        timeout: 0,
+                        region: SubtreeRegion {
+                            priority: 127,
+                            range_subid: 0,
+                            subtree: string_to_oid(".1.3.6.1.2.1.1.5"),
+                            upper_bound: None,
+                        },
+                    },
+                }).await.unwrap();
+                #[allow(unused_variables)]
+                let register_response = subagent.next().await.unwrap().unwrap();
+
+                // the subagent serves the sysName.0 variable only
+                let served_oid = name_oid.clone();
+                actix::spawn(async move {
+                    while let Some(Ok(pdu)) = subagent.next().await {
+                        let var_binds = match &pdu.payload {
+                            AgentxPayload::Get { ranges } => ranges
+                                .iter()
+                                .map(|range| AgentxVarBind {
+                                    name: range.start.clone(),
+                                    value: if range.start == served_oid {
+                                        AgentxValue::OctetString(b"subagent".to_vec())
+                                    } else {
+                                        AgentxValue::NoSuchInstance
+                                    },
+                                })
+                                .collect(),
+                            AgentxPayload::GetNext { ranges } => ranges
+                                .iter()
+                                .map(|range| {
+                                    if range.start < served_oid || (range.include && range.start == served_oid) {
+                                        AgentxVarBind {
+                                            name: served_oid.clone(),
+                                            value: AgentxValue::OctetString(b"subagent".to_vec()),
+                                        }
+                                    } else {
+                                        AgentxVarBind {
+                                            name: range.start.clone(),
+                                            value: AgentxValue::EndOfMibView,
+                                        }
+                                    }
+                                })
+                                .collect(),
+                            AgentxPayload::Close { .. } => break,
+                            _ => vec![],
+                        };
+                        subagent.send(pdu.response(0, 0, 0, var_binds)).await.unwrap();
+                    }
+                });
+            }
+
+            async it "started" {
+                assert_eq!(StatusCode::OK, response.status());
+            }
+
+            async it "opens_session_and_registers_subtree" {
+                assert_ne!(0, session_id);
+                assert_eq!(session_id, register_response.header.session_id);
+                for response in [open_response, register_response] {
+                    if let AgentxPayload::Response { error, .. } = &response.payload {
+                        assert_eq!(AgentxErrorStatus::NoAgentxError as u16, *error);
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+
+            describe "get_request" {
+                before {
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let descr_oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                        .send_request(get_request_v2(42, "public", vec![descr_oid.clone(), name_oid.clone()]))
+                        .await;
+                }
+
+                async it "returns_simulated_and_subagent_values" {
+                    if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(42, resp.0.request_id);
+                            assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                            assert_eq!(2, resp.0.variable_bindings.len());
+                            assert_eq!(descr_oid, resp.0.variable_bindings[0].name);
+                            assert_eq!(
+                                VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                                    "Linux nmsworker-devel 2.6.18-164.el5 #1 SMP Thu Sep 3 03:28:30 EDT 2009 x86_64".into()))),
+                                resp.0.variable_bindings[0].value);
+                            assert_eq!(name_oid, resp.0.variable_bindings[1].name);
+                            assert_eq!(
+                                VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("subagent".into()))),
+                                resp.0.variable_bindings[1].value);
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+
+            describe "get_next_request" {
+                before {
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let contact_oid = string_to_oid(".1.3.6.1.2.1.1.4.0");
+                    let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                        .send_request(get_next_request_v2(43, "public", vec![contact_oid]))
+                        .await;
+                }
+
+                async it "returns_subagent_successor" {
+                    if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(43, resp.0.request_id);
+                            assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                            assert_eq!(1, resp.0.variable_bindings.len());
+                            let var_bind = resp.0.variable_bindings.first().unwrap();
+                            assert_eq!(name_oid, var_bind.name);
+                            assert_eq!(
+                                VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("subagent".into()))),
+                                var_bind.value);
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+        }
+    }
+}
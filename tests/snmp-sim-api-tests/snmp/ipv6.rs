@@ -0,0 +1,236 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "ipv6" {
+        use crate::helpers::{spawn_app, domain_snmp_v2c_attributes_json};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use reqwest::Client as reqwestClient;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+        use snmp_sim::snmp::codec::GenericSnmpMessage;
+        use crate::snmp::DEVICE_PORT;
+        use num_traits::ToPrimitive;
+        use rasn_snmp::v2::*;
+        use rasn_smi::v2::*;
+        use crate::snmp::get_request_v2;
+        use snmp_sim::udp_client::Client;
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+        use snmp_sim::snmp::codec::SnmpCodec;
+        use snmp_sim::routes::managed_devices::response::{Inform, InformStatus};
+        use tokio_util::udp::UdpFramed;
+        use futures::{SinkExt, StreamExt};
+
+        before {
+            let app = spawn_app().await;
+        }
+
+        describe "device_on_host" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let device_port = DEVICE_PORT.write().get_next_value();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+            }
+
+            describe "loopback" {
+                before {
+                    let (device, _agent) = create_managed_device(
+                        db_conn,
+                        &Uuid::new_v4(),
+                        &Uuid::new_v4().to_string(),
+                        &None,
+                        &agent_id,
+                        &domain_snmp_v2c_attributes_json("public"),
+                        "::1",
+                        device_port,
+                        &None,
+                        &None,
+                        &None,
+                        &None,
+                        "udp",
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                    let device_id = Uuid::from_str(&device.id).unwrap();
+
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let descr_oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    let snmp_response = Client::new(format!("[::1]:{device_port}").parse().unwrap()).unwrap()
+                        .send_request(get_request_v2(42, "public", vec![descr_oid.clone()]))
+                        .await;
+                }
+
+                async it "returns_the_variable" {
+                    assert_eq!(StatusCode::OK, response.status());
+                    if let Ok(GenericSnmpMessage::V2Message(msg)) = &snmp_response {
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(42, resp.0.request_id);
+                            assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                            assert_eq!(1, resp.0.variable_bindings.len());
+                            assert_eq!(descr_oid, resp.0.variable_bindings[0].name);
+                            assert_eq!(
+                                VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                                    "Linux nmsworker-devel 2.6.18-164.el5 #1 SMP Thu Sep 3 03:28:30 EDT 2009 x86_64".into()))),
+                                resp.0.variable_bindings[0].value);
+                        }
+                        else {
+                            println!("{:?}", snmp_response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", snmp_response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+
+            describe "dual_stack" {
+                before {
+                    let (device, _agent) = create_managed_device(
+                        db_conn,
+                        &Uuid::new_v4(),
+                        &Uuid::new_v4().to_string(),
+                        &None,
+                        &agent_id,
+                        &domain_snmp_v2c_attributes_json("public"),
+                        "::",
+                        device_port,
+                        &None,
+                        &None,
+                        &None,
+                        &None,
+                        "udp",
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                    let device_id = Uuid::from_str(&device.id).unwrap();
+
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    // the same device is polled over both IPv4 and IPv6
+                    let name_oid = string_to_oid(".1.3.6.1.2.1.1.5.0");
+                    let mut snmp_responses = vec![];
+                    for remote_addr in [format!("127.0.0.1:{device_port}"), format!("[::1]:{device_port}")] {
+                        snmp_responses.push(
+                            Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(43, "public", vec![name_oid.clone()]))
+                                .await
+                        );
+                    }
+                }
+
+                async it "responds_on_both_address_families" {
+                    assert_eq!(StatusCode::OK, response.status());
+                    assert_eq!(2, snmp_responses.len());
+                    for snmp_response in &snmp_responses {
+                        if let Ok(GenericSnmpMessage::V2Message(msg)) = snmp_response {
+                            if let Pdus::Response(resp) = &msg.data {
+                                assert_eq!(43, resp.0.request_id);
+                                assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                assert_eq!(name_oid, resp.0.variable_bindings[0].name);
+                            }
+                            else {
+                                println!("{:?}", snmp_response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", snmp_response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                }
+            }
+
+            // the acknowledgement of the IPv4 target is received by the dual-stack device from the IPv4-mapped address
+            describe "inform_to_ipv4_target" {
+                before {
+                    let (device, _agent) = create_managed_device(
+                        db_conn,
+                        &Uuid::new_v4(),
+                        &Uuid::new_v4().to_string(),
+                        &None,
+                        &agent_id,
+                        &domain_snmp_v2c_attributes_json("public"),
+                        "::",
+                        device_port,
+                        &None,
+                        &None,
+                        &None,
+                        &None,
+                        "udp",
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                    let device_id = Uuid::from_str(&device.id).unwrap();
+
+                    reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let manager = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+                    let target = manager.local_addr().unwrap().to_string();
+                    let mut manager = UdpFramed::new(manager, SnmpCodec::default());
+
+                    let inform = reqwestClient::new()
+                        .post(format!("{}/devices/{}/informs", app.address, device_id))
+                        .json(&serde_json::json!({"target": target, "trap_oid": ".1.3.6.1.6.3.1.1.5.3", "timeout": 500, "retries": 2}))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Inform>()
+                        .await
+                        .unwrap();
+
+                    let request = tokio::time::timeout(std::time::Duration::from_secs(3), manager.next()).await;
+                    if let Ok(Some(Ok((GenericSnmpMessage::V2Message(message), peer)))) = request {
+                        if let Pdus::InformRequest(inform_request) = message.data {
+                            let acknowledgement = rasn_snmp::v2c::Message {
+                                version: message.version,
+                                community: message.community,
+                                data: Pdus::Response(rasn_snmp::v2::Response(inform_request.0)),
+                            };
+                            manager.send((GenericSnmpMessage::V2Message(acknowledgement), peer)).await.unwrap();
+                        }
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+
+                    let delivery = reqwestClient::new()
+                        .get(format!("{}/devices/{}/informs/{}", app.address, device_id, inform.request_id))
+                        .send()
+                        .await
+                        .unwrap()
+                        .json::<Inform>()
+                        .await
+                        .unwrap();
+                }
+
+                async it "is_acked" {
+                    assert_eq!(InformStatus::Acked, delivery.status);
+                    assert_eq!(1, delivery.attempts);
+                }
+            }
+        }
+    }
+}
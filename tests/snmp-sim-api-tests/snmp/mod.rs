@@ -1,10 +1,17 @@
+mod agentx;
+mod ipv6;
+mod proxy;
 mod snmp_v1;
 mod snmp_v2;
+mod snmp_v3;
+mod tcp;
 
 use rasn::prelude::ObjectIdentifier;
-use snmp_sim::domain::entity::{GetNextRequest, GetRequest};
+use snmp_sim::domain::entity::{GetBulkRequest, GetNextRequest, GetRequest, SetRequest, SnmpV3Parameters, Variable};
 use snmp_sim::domain::SnmpProtocolVersion;
 use snmp_sim::snmp::codec::GenericSnmpMessage;
+use snmp_sim::snmp::codec::SnmpCodec;
+use snmp_sim::snmp::usm::{AuthenticationKey, PrivacyKey};
 use static_init::dynamic;
 
 pub fn get_request_v1(request_id: i32, community: &str, objects: Vec<ObjectIdentifier>) -> GenericSnmpMessage {
@@ -19,6 +26,40 @@ pub fn get_request_v2(request_id: i32, community: &str, objects: Vec<ObjectIdent
     )
 }
 
+#[allow(dead_code)]
+pub fn get_request_v3(
+    request_id: i32,
+    parameters: SnmpV3Parameters,
+    objects: Vec<ObjectIdentifier>,
+) -> GenericSnmpMessage {
+    get_request(request_id, &SnmpProtocolVersion::SNMPV3(parameters), objects)
+}
+
+// SNMPv3 parameters of the request sent to the authoritative engine, the engine is discovered
+// by a request with empty engine identification
+#[allow(dead_code)]
+pub fn snmp_v3_parameters(
+    engine_id: &[u8],
+    engine_boots: u32,
+    engine_time: u32,
+    user_name: &str,
+    authentication_key: Option<AuthenticationKey>,
+    privacy_key: Option<PrivacyKey>,
+) -> SnmpV3Parameters {
+    SnmpV3Parameters {
+        message_id: 1,
+        max_size: 65507,
+        engine_id: engine_id.to_vec(),
+        engine_boots,
+        engine_time,
+        user_name: user_name.as_bytes().to_vec(),
+        context_engine_id: engine_id.to_vec(),
+        context_name: vec![],
+        authentication_key,
+        privacy_key,
+    }
+}
+
 fn get_request(request_id: i32, protocol: &SnmpProtocolVersion, objects: Vec<ObjectIdentifier>) -> GenericSnmpMessage {
     let response = GetRequest { request_id, objects };
     (protocol, response).into()
@@ -48,6 +89,51 @@ pub fn get_next_request(
     (protocol, response).into()
 }
 
+#[allow(dead_code)]
+pub fn get_bulk_request_v2(
+    request_id: i32,
+    community: &str,
+    non_repeaters: u32,
+    max_repetitions: u32,
+    objects: Vec<ObjectIdentifier>,
+) -> GenericSnmpMessage {
+    let request = GetBulkRequest {
+        request_id,
+        non_repeaters,
+        max_repetitions,
+        objects,
+    };
+    GenericSnmpMessage::V2Message(rasn_snmp::v2c::Message {
+        version: SnmpCodec::SNMP_VERSION2.into(),
+        community: community.to_string().into(),
+        data: request.into(),
+    })
+}
+
+#[allow(dead_code)]
+pub fn set_request_v1(request_id: i32, community: &str, variables: Vec<Variable>) -> GenericSnmpMessage {
+    set_request(
+        request_id,
+        &SnmpProtocolVersion::SNMPV1(community.to_string()),
+        variables,
+    )
+}
+
+#[allow(dead_code)]
+pub fn set_request_v2(request_id: i32, community: &str, variables: Vec<Variable>) -> GenericSnmpMessage {
+    set_request(
+        request_id,
+        &SnmpProtocolVersion::SNMPV2C(community.to_string()),
+        variables,
+    )
+}
+
+#[allow(dead_code)]
+pub fn set_request(request_id: i32, protocol: &SnmpProtocolVersion, variables: Vec<Variable>) -> GenericSnmpMessage {
+    let request = SetRequest { request_id, variables };
+    (protocol, request).into()
+}
+
 #[dynamic]
 pub static mut DEVICE_PORT: DevicePort = DevicePort::new(30160);
 
@@ -0,0 +1,177 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "proxy" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, domain_snmp_v2c_attributes, domain_snmp_v2c_attributes_json};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use reqwest::Client as reqwestClient;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+        use snmp_sim::snmp::codec::GenericSnmpMessage;
+        use crate::snmp::DEVICE_PORT;
+        use num_traits::ToPrimitive;
+        use rasn_snmp::v2::*;
+        use rasn_smi::v2::*;
+        use crate::snmp::{get_request_v2, get_next_request_v2, set_request_v2};
+        use snmp_sim::domain::entity::Variable;
+        use snmp_sim::domain::ProxyAttributes;
+        use snmp_data_parser::parser::snmp_data::component::DataType;
+        use snmp_sim::udp_client::Client;
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+
+        before {
+            let app = spawn_app().await;
+        }
+
+        describe "upstream_and_proxy_device" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let upstream_port = DEVICE_PORT.write().get_next_value();
+                let device_port = DEVICE_PORT.write().get_next_value();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let upstream_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, upstream_port, &domain_snmp_v2c_attributes_json("public")).await;
+                let upstream_id = *upstream_ids.first().unwrap();
+
+                // the sysLocation subtree is served by the upstream device
+                let proxy = ProxyAttributes {
+                    target: format!("{host_ipaddr}:{upstream_port}").parse().unwrap(),
+                    snmp_protocol_attributes: domain_snmp_v2c_attributes("public"),
+                    subtrees: vec![".1.3.6.1.2.1.1.6".to_string()],
+                    cache_ttl: None,
+                    timeout: 1000,
+                    retries: 1,
+                };
+                let (device, _agent) = create_managed_device(
+                    db_conn,
+                    &Uuid::new_v4(),
+                    &Uuid::new_v4().to_string(),
+                    &None,
+                    &agent_id,
+                    &domain_snmp_v2c_attributes_json("public"),
+                    host_ipaddr,
+                    device_port,
+                    &Some(serde_json::to_string(&proxy).unwrap()),
+                    &None,
+                    &None,
+                    &None,
+                    "udp",
+                )
+                .await
+                .unwrap()
+                .unwrap_created();
+                let device_id = Uuid::from_str(&device.id).unwrap();
+            }
+
+            describe "with_devices" {
+                before {
+                    let upstream_response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, upstream_id))
+                        .send()
+                        .await
+                        .unwrap();
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    // the value of the upstream device differs from the simulated one
+                    let upstream_addr = format!("{host_ipaddr}:{upstream_port}");
+                    let location_oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                    let variable = Variable {
+                        name: location_oid.clone(),
+                        data_type: DataType::String,
+                        value: "Upstream Room".to_string(),
+                    };
+                    #[allow(unused)]
+                    let set_response = Client::new(upstream_addr.parse().unwrap()).unwrap()
+                        .send_request(set_request_v2(1, "private", vec![variable]))
+                        .await;
+                }
+
+                async it "started" {
+                    assert_eq!(StatusCode::OK, upstream_response.status());
+                    assert_eq!(StatusCode::OK, response.status());
+                }
+
+                describe "get_request" {
+                    before {
+                        let remote_addr = format!("{host_ipaddr}:{device_port}");
+                        let descr_oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                        let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                            .send_request(get_request_v2(42, "public", vec![descr_oid.clone(), location_oid.clone()]))
+                            .await;
+                    }
+
+                    async it "returns_simulated_and_forwarded_values" {
+                        if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                            if let Pdus::Response(resp) = &msg.data {
+                                assert_eq!(42, resp.0.request_id);
+                                assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                assert_eq!(2, resp.0.variable_bindings.len());
+                                assert_eq!(descr_oid, resp.0.variable_bindings[0].name);
+                                assert_eq!(
+                                    VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                                        "Linux nmsworker-devel 2.6.18-164.el5 #1 SMP Thu Sep 3 03:28:30 EDT 2009 x86_64".into()))),
+                                    resp.0.variable_bindings[0].value);
+                                assert_eq!(location_oid, resp.0.variable_bindings[1].name);
+                                assert_eq!(
+                                    VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("Upstream Room".into()))),
+                                    resp.0.variable_bindings[1].value);
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                }
+
+                describe "get_next_request" {
+                    before {
+                        let remote_addr = format!("{host_ipaddr}:{device_port}");
+                        let name_oid = string_to_oid(".1.3.6.1.2.1.1.5.0");
+                        let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                            .send_request(get_next_request_v2(43, "public", vec![name_oid]))
+                            .await;
+                    }
+
+                    async it "returns_forwarded_successor" {
+                        if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                            if let Pdus::Response(resp) = &msg.data {
+                                assert_eq!(43, resp.0.request_id);
+                                assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                assert_eq!(1, resp.0.variable_bindings.len());
+                                let var_bind = resp.0.variable_bindings.first().unwrap();
+                                assert_eq!(location_oid, var_bind.name);
+                                assert_eq!(
+                                    VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("Upstream Room".into()))),
+                                    var_bind.value);
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
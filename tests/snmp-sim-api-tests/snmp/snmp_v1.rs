@@ -16,7 +16,9 @@ demonstrate! {
         use rasn_snmp::v1::*;
         use snmp_sim::udp_client::Client;
         use snmp_data_parser::parser::snmp_data::component::string_to_oid;
-        use crate::snmp::{get_request_v1, get_next_request_v1};
+        use crate::snmp::{get_request_v1, get_next_request_v1, set_request_v1};
+        use snmp_sim::domain::entity::Variable;
+        use snmp_data_parser::parser::snmp_data::component::DataType;
 
         before {
             let app = spawn_app().await;
@@ -28,7 +30,7 @@ demonstrate! {
                 let host_ipaddr = "127.0.0.1";
                 let device_port = DEVICE_PORT.write().get_next_value();
 
-                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt")
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
                     .await
                     .unwrap()
                     .unwrap_created();
@@ -350,6 +352,40 @@ demonstrate! {
                             }
                         }
                     }
+
+                    describe "with_oid_returning_hex_string" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.3.1.1.2.2.1.192.168.104.1");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v1(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_valid_octets" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    let expected_value = ObjectSyntax::Simple(
+                                        SimpleSyntax::String(vec![0x00, 0x1B, 0x53, 0xB8, 0x09, 0xD9].into()));
+                                    assert_eq!(expected_value, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
                 }
 
                 describe "get_next_request" {
@@ -388,6 +424,237 @@ demonstrate! {
                             }
                         }
                     }
+
+                    describe "at_end_of_mib_view" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.92.1.2.2.0");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_next_request_v1(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NO_SUCH_NAME, resp.0.error_status.to_u64().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+                }
+
+                describe "set_request" {
+                    describe "with_writable_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::String,
+                                value: "Server Room".to_string(),
+                            };
+                            let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                            let response = client
+                                .send_request(set_request_v1(1, "private", vec![variable]))
+                                .await;
+                            let get_response = client
+                                .send_request(get_request_v1(2, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_set_value" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(ObjectSyntax::Simple(SimpleSyntax::String("Server Room".into())), var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+
+                        async it "updates_value" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &get_response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(ObjectSyntax::Simple(SimpleSyntax::String("Server Room".into())), var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", get_response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", get_response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_read_only_community" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::String,
+                                value: "Server Room".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v1(1, "public", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NO_SUCH_NAME, resp.0.error_status.to_u64().unwrap());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_unknown_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.99.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::String,
+                                value: "unknown".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v1(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NO_SUCH_NAME, resp.0.error_status.to_u64().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_counter_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.2.2.1.10.1");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::Counter32,
+                                value: "0".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v1(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_READ_ONLY, resp.0.error_status.to_u64().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_wrong_type" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::Integer,
+                                value: "1".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v1(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V1Message(msg)) = &response {
+                                if let Pdus::GetResponse(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_BAD_VALUE, resp.0.error_status.to_u64().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
                 }
             }
         }
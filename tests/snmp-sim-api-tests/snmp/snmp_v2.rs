@@ -3,7 +3,7 @@ use demonstrate::demonstrate;
 demonstrate! {
     #[actix_rt::test]
     describe "snmp_v2" {
-        use crate::helpers::{spawn_app, seed_devices};
+        use crate::helpers::{spawn_app, seed_devices, seed_devices_with_protocol, domain_snmp_v2c_attributes_with_trap_json, domain_snmp_v2c_attributes_with_max_message_size_json};
         use actix_web::http::StatusCode;
         use snmp_sim::data_access::helpers::*;
         use reqwest::Client as reqwestClient;
@@ -14,8 +14,13 @@ demonstrate! {
         use num_traits::ToPrimitive;
         use rasn_snmp::v2::*;
         use rasn_smi::v2::*;
-        use crate::snmp::{get_request_v2, get_next_request_v2};
+        use crate::snmp::{get_request_v2, get_next_request_v2, get_bulk_request_v2, set_request_v2};
+        use snmp_sim::domain::entity::Variable;
+        use snmp_data_parser::parser::snmp_data::component::DataType;
         use snmp_sim::udp_client::Client;
+        use snmp_sim::snmp::codec::SnmpCodec;
+        use tokio_util::udp::UdpFramed;
+        use futures::StreamExt;
         use snmp_data_parser::parser::snmp_data::component::string_to_oid;
 
         before {
@@ -28,7 +33,7 @@ demonstrate! {
                 let host_ipaddr = "127.0.0.1";
                 let device_port = DEVICE_PORT.write().get_next_value();
 
-                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt")
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
                     .await
                     .unwrap()
                     .unwrap_created();
@@ -58,6 +63,44 @@ demonstrate! {
                 }
 
                 describe "get_request" {
+                    describe "with_sys_up_time" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.3.0");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_time_since_device_start" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    // the walk file records 38 days of uptime, the device has just been started
+                                    if let VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(ticks))) = &var_bind.value {
+                                        assert!(ticks.0 < 6000);
+                                    }
+                                    else {
+                                        println!("{:?}", var_bind);
+                                        debug_assert!(false, "Not a Timeticks value");
+                                    }
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
                     describe "with_known_oid" {
 
                         before {
@@ -104,13 +147,47 @@ demonstrate! {
                                 .await;
                         }
 
-                        async it "returns_error" {
+                        async it "returns_no_such_instance" {
                             if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
                                 if let Pdus::Response(resp) = &msg.data {
-                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
-                                    assert_eq!(2, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
                                     assert_eq!(1, resp.0.variable_bindings.len());
-                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(VarBindValue::NoSuchInstance, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_unknown_object" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.4.1.99999.1.0");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_no_such_object" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(VarBindValue::NoSuchObject, var_bind.value);
                                 }
                                 else {
                                     println!("{:?}", response);
@@ -124,6 +201,21 @@ demonstrate! {
                         }
                     }
 
+                    describe "with_unknown_community" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(1, "unknown", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "drops_request" {
+                            assert!(response.is_err());
+                        }
+                    }
+
                     describe "with_known_oids_array" {
 
                         before {
@@ -193,16 +285,16 @@ demonstrate! {
                                 .await;
                         }
 
-                        async it "returns_error" {
+                        async it "returns_exception" {
                             if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
                                 if let Pdus::Response(resp) = &msg.data {
-                                    assert_eq!(3, resp.0.error_index.to_u32().unwrap());
-                                    assert_eq!(Pdu::ERROR_STATUS_NO_SUCH_NAME, resp.0.error_status.to_u32().unwrap());
-                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(3, resp.0.variable_bindings.len());
 
-                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    let var_bind = resp.0.variable_bindings.last().unwrap();
                                     assert_eq!(var_bind.name, string_to_oid(".1.3.6.1.2.1.1.6.1"));
-                                    assert_eq!(var_bind.value, VarBindValue::Unspecified);
+                                    assert_eq!(var_bind.value, VarBindValue::NoSuchInstance);
                                 }
                                 else {
                                     println!("{:?}", response);
@@ -284,6 +376,50 @@ demonstrate! {
                         }
                     }
 
+                    describe "with_oid_of_post_loaded_modifier" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            // hrProcessorLoad modified by int.rnd(0,0,10,0,10,1,100)
+                            let oid = string_to_oid(".1.3.6.1.2.1.25.3.3.1.2.768");
+                            let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                            let first_response = client.send_request(get_request_v2(1, "public", vec![oid.clone()])).await;
+                            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                            let second_response = client.send_request(get_request_v2(2, "public", vec![oid.clone()])).await;
+                        }
+
+                        async it "returns_loaded_value_then_modified_values" {
+                            let mut values = vec![];
+                            for response in [&first_response, &second_response] {
+                                if let Ok(GenericSnmpMessage::V2Message(msg)) = response {
+                                    if let Pdus::Response(resp) = &msg.data {
+                                        assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                        let var_bind = resp.0.variable_bindings.first().unwrap();
+                                        assert_eq!(oid, var_bind.name);
+                                        if let VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(value))) = &var_bind.value {
+                                            values.push(value.clone());
+                                        }
+                                        else {
+                                            println!("{:?}", response);
+                                            debug_assert!(false, "Not an integer value");
+                                        }
+                                    }
+                                    else {
+                                        println!("{:?}", response);
+                                        debug_assert!(false, "Not a valid response");
+                                    }
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            // the first request is served the loaded value, the modified one stays within the range
+                            assert_eq!(Some(&12.into()), values.first());
+                            assert!(values.iter().all(|value| *value >= 1.into() && *value <= 100.into()));
+                        }
+                    }
+
                     describe "with_oid_returning_gauge" {
 
                         before {
@@ -353,6 +489,109 @@ demonstrate! {
                             }
                         }
                     }
+
+                    describe "with_oid_returning_hex_string" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.3.1.1.2.2.1.192.168.104.1");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_valid_octets" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    let expected_value = VarBindValue::Value(ObjectSyntax::Simple(
+                                        SimpleSyntax::String(vec![0x00, 0x1B, 0x53, 0xB8, 0x09, 0xD9].into())));
+                                    assert_eq!(expected_value, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_oid_returning_network_address" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.3.1.1.3.2.1.192.168.104.1");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_valid_address" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    let expected_value = VarBindValue::Value(ObjectSyntax::ApplicationWide(
+                                        ApplicationSyntax::Address(
+                                                rasn_smi::v1::IpAddress(bytes::Bytes::from(vec![192, 168, 104, 1])))));
+                                    assert_eq!(expected_value, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_oid_returning_bits" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.88.1.4.2.1.3.6.95.115.110.109.112.100.95.108.105.110.107.68.111.119.110");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_request_v2(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_valid_octets" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    let expected_value = VarBindValue::Value(ObjectSyntax::Simple(
+                                        SimpleSyntax::String(vec![0x80].into())));
+                                    assert_eq!(expected_value, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
                 }
 
                 describe "get_next_request" {
@@ -392,6 +631,715 @@ demonstrate! {
                             }
                         }
                     }
+
+                    describe "at_end_of_mib_view" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.92.1.2.2.0");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_next_request_v2(1, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_end_of_mib_view" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(VarBindValue::EndOfMibView, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+                }
+
+                describe "get_bulk_request" {
+                    describe "with_non_repeaters_and_repetitions" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oids = vec![
+                                string_to_oid(".1.3.6.1.2.1.1.1.0"),
+                                string_to_oid(".1.3.6.1.2.1.2.2.1.2")
+                                ];
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_bulk_request_v2(1, "public", 1, 2, oids))
+                                .await;
+                        }
+
+                        async it "returns_valid_objects" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(3, resp.0.variable_bindings.len());
+
+                                    let expected_oids = vec![
+                                        string_to_oid(".1.3.6.1.2.1.1.2.0"),
+                                        string_to_oid(".1.3.6.1.2.1.2.2.1.2.1"),
+                                        string_to_oid(".1.3.6.1.2.1.2.2.1.2.2")
+                                        ];
+                                    let expected_values = vec![
+                                            VarBindValue::Value(
+                                                ObjectSyntax::Simple(
+                                                    SimpleSyntax::ObjectId(
+                                                        string_to_oid(".1.3.6.1.4.1.8072.3.2.10")))),
+                                            VarBindValue::Value(
+                                                ObjectSyntax::Simple(
+                                                    SimpleSyntax::String("lo".into()))),
+                                            VarBindValue::Value(
+                                                ObjectSyntax::Simple(
+                                                    SimpleSyntax::String("eth0".into())))];
+
+                                    resp.0.variable_bindings
+                                        .iter()
+                                        .enumerate()
+                                        .for_each(|(idx, var_bind)|{
+                                            assert_eq!(expected_oids[idx], var_bind.name);
+                                            assert_eq!(expected_values[idx], var_bind.value);
+                                    });
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "at_end_of_mib_view" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.92.1.2.1.0");
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(get_bulk_request_v2(1, "public", 0, 5, vec![oid]))
+                                .await;
+                        }
+
+                        async it "returns_end_of_mib_view" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(2, resp.0.variable_bindings.len());
+
+                                    let last_oid = string_to_oid(".1.3.6.1.2.1.92.1.2.2.0");
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(last_oid, var_bind.name);
+                                    let var_bind = resp.0.variable_bindings.last().unwrap();
+                                    assert_eq!(last_oid, var_bind.name);
+                                    assert_eq!(VarBindValue::EndOfMibView, var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+                }
+
+                describe "set_request" {
+                    describe "with_writable_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::String,
+                                value: "Server Room".to_string(),
+                            };
+                            let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                            let response = client
+                                .send_request(set_request_v2(1, "private", vec![variable]))
+                                .await;
+                            let get_response = client
+                                .send_request(get_request_v2(2, "public", vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_set_value" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("Server Room".into()))), var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+
+                        async it "updates_value" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &get_response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    let var_bind = resp.0.variable_bindings.first().unwrap();
+                                    assert_eq!(oid, var_bind.name);
+                                    assert_eq!(VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("Server Room".into()))), var_bind.value);
+                                }
+                                else {
+                                    println!("{:?}", get_response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", get_response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_read_only_community" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::String,
+                                value: "Server Room".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v2(1, "public", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NO_ACCESS, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_unknown_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.99.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::String,
+                                value: "unknown".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v2(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NO_CREATION, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_counter_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.2.2.1.10.1");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::Counter32,
+                                value: "0".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v2(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NOT_WRITABLE, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_sys_up_time_oid" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.3.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::Timeticks,
+                                value: "0".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v2(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_NOT_WRITABLE, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "with_wrong_type" {
+
+                        before {
+                            let remote_addr = format!("{host_ipaddr}:{device_port}");
+                            let oid = string_to_oid(".1.3.6.1.2.1.1.6.0");
+                            let variable = Variable {
+                                name: oid.clone(),
+                                data_type: DataType::Integer,
+                                value: "1".to_string(),
+                            };
+                            let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                                .send_request(set_request_v2(1, "private", vec![variable]))
+                                .await;
+                        }
+
+                        async it "returns_error" {
+                            if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                                if let Pdus::Response(resp) = &msg.data {
+                                    assert_eq!(1, resp.0.error_index.to_u32().unwrap());
+                                    assert_eq!(Pdu::ERROR_STATUS_WRONG_TYPE, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        describe "agent_with_authentication_failure_trap" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let trap_receiver = tokio::net::UdpSocket::bind(format!("{host_ipaddr}:0")).await.unwrap();
+                let trap_target = trap_receiver.local_addr().unwrap().to_string();
+                let mut trap_receiver = UdpFramed::new(trap_receiver, SnmpCodec::default());
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let protocol = domain_snmp_v2c_attributes_with_trap_json("public", &trap_target);
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                #[allow(unused)]
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let remote_addr = format!("{host_ipaddr}:{device_port}");
+                let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                client
+                    .send_message(get_request_v2(1, "unknown", vec![oid]))
+                    .await
+                    .unwrap();
+                let trap = tokio::time::timeout(std::time::Duration::from_secs(3), trap_receiver.next()).await;
+            }
+
+            async it "sends_authentication_failure_trap" {
+                if let Ok(Some(Ok((GenericSnmpMessage::V2Message(msg), _)))) = &trap {
+                    assert_eq!(b"public".as_slice(), msg.community.as_ref());
+                    if let Pdus::Trap(trap_pdu) = &msg.data {
+                        assert_eq!(2, trap_pdu.0.variable_bindings.len());
+                        let trap_oid = &trap_pdu.0.variable_bindings[1];
+                        assert_eq!(string_to_oid(".1.3.6.1.6.3.1.1.4.1.0"), trap_oid.name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::ObjectId(string_to_oid(".1.3.6.1.6.3.1.1.5.5")))),
+                            trap_oid.value);
+                    }
+                    else {
+                        println!("{:?}", trap);
+                        debug_assert!(false, "Not a valid trap");
+                    }
+                }
+                else {
+                    println!("{:?}", trap);
+                    debug_assert!(false, "Not a valid trap");
+                }
+            }
+        }
+
+        describe "agent_with_max_message_size" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let max_message_size = 484;
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let protocol = domain_snmp_v2c_attributes_with_max_message_size_json("public", max_message_size);
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                let device_id = *device_ids.first().unwrap();
+                #[allow(unused)]
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+                let remote_addr = format!("{host_ipaddr}:{device_port}");
+            }
+
+            describe "get_request_exceeding_limit" {
+                before {
+                    let oids = vec![string_to_oid(".1.3.6.1.2.1.1.1.0"); 6];
+                    let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                        .send_request(get_request_v2(1, "public", oids))
+                        .await;
+                }
+
+                async it "returns_too_big" {
+                    if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(0, resp.0.error_index.to_u32().unwrap());
+                            assert_eq!(Pdu::ERROR_STATUS_TOO_BIG, resp.0.error_status.to_u32().unwrap());
+                            assert!(resp.0.variable_bindings.is_empty());
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+
+            describe "get_bulk_request_exceeding_limit" {
+                before {
+                    let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                        .send_request(get_bulk_request_v2(1, "public", 0, 100, vec![string_to_oid(".1.3")]))
+                        .await;
+                }
+
+                async it "returns_truncated_response" {
+                    if let Ok(message @ GenericSnmpMessage::V2Message(msg)) = &response {
+                        assert!(message.encoded_len().unwrap() <= max_message_size as usize);
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                            assert!(!resp.0.variable_bindings.is_empty());
+                            assert!(resp.0.variable_bindings.len() < 100);
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+        }
+
+        describe "agent_without_time_variables" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let snmp_data_url = std::env::temp_dir()
+                    .join(format!("{}.txt", Uuid::new_v4()))
+                    .into_os_string()
+                    .into_string()
+                    .unwrap();
+                std::fs::write(&snmp_data_url, ".1.3.6.1.2.1.1.1.0 = STRING: Linux\n").unwrap();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &snmp_data_url, &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let device_ids = seed_devices(db_conn, &agent_id, 1, host_ipaddr, device_port).await;
+                let device_id = *device_ids.first().unwrap();
+                #[allow(unused)]
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let remote_addr = format!("{host_ipaddr}:{device_port}");
+                let oids = vec![
+                    string_to_oid(".1.3.6.1.2.1.1.3.0"),
+                    string_to_oid(".1.3.6.1.6.3.10.2.1.3.0"),
+                ];
+                let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                    .send_request(get_request_v2(1, "public", oids.clone()))
+                    .await;
+                let _ = std::fs::remove_file(&snmp_data_url);
+            }
+
+            async it "returns_time_since_device_start" {
+                if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                    if let Pdus::Response(resp) = &msg.data {
+                        assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                        assert_eq!(2, resp.0.variable_bindings.len());
+                        assert_eq!(oids[0], resp.0.variable_bindings[0].name);
+                        if let VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Ticks(ticks))) = &resp.0.variable_bindings[0].value {
+                            assert!(ticks.0 < 6000);
+                        }
+                        else {
+                            debug_assert!(false, "Not a Timeticks value");
+                        }
+                        assert_eq!(oids[1], resp.0.variable_bindings[1].name);
+                        if let VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(seconds))) = &resp.0.variable_bindings[1].value {
+                            assert!(seconds.to_u32().unwrap() < 60);
+                        }
+                        else {
+                            debug_assert!(false, "Not an Integer value");
+                        }
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+                else {
+                    println!("{:?}", response);
+                    debug_assert!(false, "Not a valid response");
+                }
+            }
+        }
+
+        describe "agent_with_modifiers_exceeding_data_type" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let snmp_data_url = std::env::temp_dir()
+                    .join(format!("{}.txt", Uuid::new_v4()))
+                    .into_os_string()
+                    .into_string()
+                    .unwrap();
+                std::fs::write(
+                    &snmp_data_url,
+                    concat!(
+                        ".1.3.6.1.2.1.1.1.0 = Gauge32: 5//$g32.tme(0,1,1,1,1,-100,100)\n",
+                        ".1.3.6.1.2.1.1.2.0 = INTEGER: 2147483647//$int.tme(1,1,1,1,1,0,4294967296)\n",
+                    ),
+                )
+                .unwrap();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &snmp_data_url, &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let device_ids = seed_devices(db_conn, &agent_id, 1, host_ipaddr, device_port).await;
+                let device_id = *device_ids.first().unwrap();
+                #[allow(unused)]
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let remote_addr = format!("{host_ipaddr}:{device_port}");
+                let oids = vec![
+                    string_to_oid(".1.3.6.1.2.1.1.1.0"),
+                    string_to_oid(".1.3.6.1.2.1.1.2.0"),
+                ];
+                let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                let _ = client.send_request(get_request_v2(1, "public", oids.clone())).await;
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                let response = client.send_request(get_request_v2(2, "public", oids.clone())).await;
+                let _ = std::fs::remove_file(&snmp_data_url);
+            }
+
+            async it "returns_values_bounded_by_data_type" {
+                if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                    if let Pdus::Response(resp) = &msg.data {
+                        assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                        assert_eq!(2, resp.0.variable_bindings.len());
+                        assert_eq!(oids[0], resp.0.variable_bindings[0].name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Unsigned(rasn_smi::v1::Gauge(0)))),
+                            resp.0.variable_bindings[0].value);
+                        assert_eq!(oids[1], resp.0.variable_bindings[1].name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::Integer(i32::MAX.into()))),
+                            resp.0.variable_bindings[1].value);
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+                else {
+                    println!("{:?}", response);
+                    debug_assert!(false, "Not a valid response");
+                }
+            }
+        }
+
+        describe "agent_with_snmprec_data" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+                let snmp_data_url = std::env::temp_dir()
+                    .join(format!("{}.snmprec", Uuid::new_v4()))
+                    .into_os_string()
+                    .into_string()
+                    .unwrap();
+                std::fs::write(
+                    &snmp_data_url,
+                    "1.3.6.1.2.1.1.3.0|67|328918409\n\
+                     1.3.6.1.2.1.1.5.0|4x|6c6f63616c686f7374\n\
+                     1.3.6.1.2.1.2.2.1.10.1|65:numeric|scale=10,initial=100\n",
+                )
+                .unwrap();
+
+                // the parser is picked by the extension of the file
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), &snmp_data_url, &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let device_ids = seed_devices(db_conn, &agent_id, 1, host_ipaddr, device_port).await;
+                let device_id = *device_ids.first().unwrap();
+                #[allow(unused)]
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+
+                let remote_addr = format!("{host_ipaddr}:{device_port}");
+                let oids = vec![
+                    string_to_oid(".1.3.6.1.2.1.1.5.0"),
+                    string_to_oid(".1.3.6.1.2.1.2.2.1.10.1"),
+                ];
+                let response = Client::new(remote_addr.parse().unwrap()).unwrap()
+                    .send_request(get_request_v2(1, "public", oids.clone()))
+                    .await;
+                let _ = std::fs::remove_file(&snmp_data_url);
+            }
+
+            async it "returns_recorded_values" {
+                if let Ok(GenericSnmpMessage::V2Message(msg)) = &response {
+                    if let Pdus::Response(resp) = &msg.data {
+                        assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                        assert_eq!(2, resp.0.variable_bindings.len());
+                        assert_eq!(oids[0], resp.0.variable_bindings[0].name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String("localhost".into()))),
+                            resp.0.variable_bindings[0].value);
+                        assert_eq!(oids[1], resp.0.variable_bindings[1].name);
+                        assert_eq!(
+                            VarBindValue::Value(ObjectSyntax::ApplicationWide(ApplicationSyntax::Counter(rasn_smi::v1::Counter(100)))),
+                            resp.0.variable_bindings[1].value);
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+                else {
+                    println!("{:?}", response);
+                    debug_assert!(false, "Not a valid response");
                 }
             }
         }
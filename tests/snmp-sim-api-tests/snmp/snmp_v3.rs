@@ -0,0 +1,695 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "snmp_v3" {
+        use crate::helpers::{spawn_app, seed_devices_with_protocol, domain_snmp_v3_attributes_json};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use reqwest::Client as reqwestClient;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+        use snmp_sim::snmp::codec::GenericSnmpMessage;
+        use crate::snmp::DEVICE_PORT;
+        use num_traits::ToPrimitive;
+        use rasn_snmp::v2::*;
+        use rasn_smi::v2::*;
+        use crate::snmp::{get_request_v3, snmp_v3_parameters};
+        use snmp_sim::domain::{AuthenticationAlgorithm, EncryptionAlgorithm};
+        use snmp_sim::snmp::usm::{decrypt_scoped_pdu, localized_key, verify_message, AuthenticationKey, PrivacyKey};
+        use snmp_sim::udp_client::Client;
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+        use snmp_sim::data_access::entity::managed_devices::{Entity, Column};
+        use sea_orm::entity::prelude::*;
+        use sea_orm::sea_query::Expr;
+
+        before {
+            let app = spawn_app().await;
+        }
+
+        describe "usm" {
+            // RFC 3414, A.3.1 and A.3.2
+            async it "localizes_md5_key" {
+                let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+                assert_eq!(
+                    vec![0x52, 0x6f, 0x5e, 0xed, 0x9f, 0xcc, 0xe2, 0x6f, 0x89, 0x64, 0xc2, 0x93, 0x07, 0x87, 0xd8, 0x2b],
+                    localized_key(&AuthenticationAlgorithm::Md5, b"maplesyrup", &engine_id));
+            }
+
+            async it "localizes_sha_key" {
+                let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+                assert_eq!(
+                    vec![0x66, 0x95, 0xfe, 0xbc, 0x92, 0x88, 0xe3, 0x62, 0x82, 0x23,
+                         0x5f, 0xc7, 0x15, 0x1f, 0x12, 0x84, 0x97, 0xb3, 0x8f, 0x3f],
+                    localized_key(&AuthenticationAlgorithm::Sha, b"maplesyrup", &engine_id));
+            }
+
+            async it "localizes_sha256_key" {
+                let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+                assert_eq!(
+                    vec![0x89, 0x82, 0xe0, 0xe5, 0x49, 0xe8, 0x66, 0xdb, 0x36, 0x1a, 0x6b, 0x62, 0x5d, 0x84, 0xcc, 0xcc,
+                         0x11, 0x16, 0x2d, 0x45, 0x3e, 0xe8, 0xce, 0x3a, 0x64, 0x45, 0xc2, 0xd6, 0x77, 0x6f, 0x0f, 0x8b],
+                    localized_key(&AuthenticationAlgorithm::Sha256, b"maplesyrup", &engine_id));
+            }
+
+            async it "truncates_sha2_digests" {
+                let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+                for (algorithm, len) in [
+                    (AuthenticationAlgorithm::Sha224, 16),
+                    (AuthenticationAlgorithm::Sha256, 24),
+                    (AuthenticationAlgorithm::Sha384, 32),
+                    (AuthenticationAlgorithm::Sha512, 48),
+                ] {
+                    let key = AuthenticationKey::new(algorithm, "maplesyrup", &engine_id).unwrap();
+                    assert_eq!(len, key.authentication_parameters_len());
+                    assert_eq!(len, key.digest(b"message").len());
+                }
+            }
+
+            // the MD5 localized key is extended by its MD5 hash (draft-blumenthal-aes-usm-04, 3.1.2.1)
+            async it "extends_md5_key_for_aes256" {
+                let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+                assert_eq!(
+                    vec![0x52, 0x6f, 0x5e, 0xed, 0x9f, 0xcc, 0xe2, 0x6f, 0x89, 0x64, 0xc2, 0x93, 0x07, 0x87, 0xd8, 0x2b,
+                         0xfa, 0x24, 0xa9, 0x24, 0x67, 0x42, 0x6c, 0x2f, 0x4b, 0x09, 0x19, 0x2b, 0xe1, 0x0d, 0xfa, 0xec],
+                    PrivacyKey::new(EncryptionAlgorithm::Aes256, &AuthenticationAlgorithm::Md5, "maplesyrup", &engine_id).unwrap().key);
+            }
+
+            // the digest covers the octets as received, a message encoded by the long form of its length is authentic
+            async it "verifies_digest_of_received_octets" {
+                use bytes::BytesMut;
+                use snmp_sim::snmp::codec::SnmpCodec;
+                use tokio_util::codec::{Decoder, Encoder};
+
+                let engine_id = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2];
+                let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Md5, "maplesyrup", &engine_id).unwrap();
+                let parameters = snmp_v3_parameters(&engine_id, 1, 100, "simulator", Some(authentication_key.clone()), None);
+                let mut codec = SnmpCodec::new();
+                let mut data = BytesMut::new();
+                codec.encode(get_request_v3(1, parameters, vec![string_to_oid("1.3.6.1.2.1.1.1.0")]), &mut data).unwrap();
+                let digest = match codec.decode(&mut data.clone()) {
+                    Ok(Some(GenericSnmpMessage::V3Message(msg))) => msg.security_parameters.authentication_parameters.to_vec(),
+                    _ => panic!("Not a valid SNMPv3 message"),
+                };
+
+                // the length of the message SEQUENCE is re-encoded by 4 octets and the message is signed again
+                let length_octets = if data[1] < 0x80 { 1 } else { 1 + (data[1] & 0x7F) as usize };
+                let contents = data[1 + length_octets..].to_vec();
+                let mut received = vec![0x30, 0x84];
+                received.extend_from_slice(&(contents.len() as u32).to_be_bytes());
+                received.extend_from_slice(&contents);
+                let position = received.windows(digest.len()).position(|window| window == digest.as_slice()).unwrap();
+                received[position..position + digest.len()].fill(0);
+                let digest = authentication_key.digest(&received);
+                received[position..position + digest.len()].copy_from_slice(&digest);
+
+                match codec.decode(&mut BytesMut::from(received.as_slice())) {
+                    Ok(Some(GenericSnmpMessage::V3Message(msg))) => assert!(verify_message(&msg, &authentication_key)),
+                    _ => panic!("Not a valid SNMPv3 message"),
+                }
+
+                // the last octet of the requested OID is modified
+                *received.last_mut().unwrap() ^= 0x01;
+                match codec.decode(&mut BytesMut::from(received.as_slice())) {
+                    Ok(Some(GenericSnmpMessage::V3Message(msg))) => assert!(!verify_message(&msg, &authentication_key)),
+                    _ => panic!("Not a valid SNMPv3 message"),
+                }
+            }
+        }
+
+        describe "agent_and_device" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+                let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Sha, "simulator-password", EncryptionAlgorithm::Aes, "");
+                let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                #[allow(unused)]
+                let device_id = *device_ids.first().unwrap();
+            }
+
+            async it "created" {
+                assert_eq!(1, device_ids.len());
+            }
+
+            describe "with_device" {
+
+                before {
+                    #[allow(unused)]
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+                }
+
+                async it "started" {
+                    assert_eq!(StatusCode::OK, response.status());
+                }
+
+                describe "engine_discovery" {
+
+                    before {
+                        let remote_addr = format!("{host_ipaddr}:{device_port}");
+                        let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                        let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                        let discovery = client
+                            .send_request(get_request_v3(1, snmp_v3_parameters(&[], 0, 0, "", None, None), vec![oid.clone()]))
+                            .await;
+                        #[allow(unused)]
+                        let (engine_id, engine_boots, engine_time) = match &discovery {
+                            Ok(GenericSnmpMessage::V3Message(msg)) => (
+                                msg.security_parameters.authoritative_engine_id.to_vec(),
+                                msg.security_parameters.authoritative_engine_boots.to_u32().unwrap(),
+                                msg.security_parameters.authoritative_engine_time.to_u32().unwrap(),
+                            ),
+                            _ => (vec![], 0, 0),
+                        };
+                    }
+
+                    async it "returns_unknown_engine_ids_report" {
+                        if let Ok(GenericSnmpMessage::V3Message(msg)) = &discovery {
+                            assert!(!engine_id.is_empty());
+                            assert_eq!(1, engine_boots);
+                            if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                if let Pdus::Report(report) = &scoped_pdu.data {
+                                    assert_eq!(1, report.0.request_id);
+                                    assert_eq!(1, report.0.variable_bindings.len());
+                                    assert_eq!(string_to_oid(".1.3.6.1.6.3.15.1.1.4.0"), report.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", discovery);
+                                    debug_assert!(false, "Not a valid report");
+                                }
+                            }
+                            else {
+                                println!("{:?}", discovery);
+                                debug_assert!(false, "Not a valid report");
+                            }
+                        }
+                        else {
+                            println!("{:?}", discovery);
+                            debug_assert!(false, "Not a valid report");
+                        }
+                    }
+
+                    describe "get_request_with_authentication" {
+
+                        before {
+                            let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Sha, "simulator-password", &engine_id);
+                            let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key.clone(), None);
+                            let response = client
+                                .send_request(get_request_v3(2, parameters, vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_authenticated_response" {
+                            if let Ok(GenericSnmpMessage::V3Message(msg)) = &response {
+                                assert!(verify_message(msg, authentication_key.as_ref().unwrap()));
+                                if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                    if let Pdus::Response(resp) = &scoped_pdu.data {
+                                        assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                        assert_eq!(1, resp.0.variable_bindings.len());
+                                        let var_bind = resp.0.variable_bindings.first().unwrap();
+                                        assert_eq!(oid, var_bind.name);
+                                        let expected_value = VarBindValue::Value(
+                                            ObjectSyntax::Simple(
+                                                SimpleSyntax::String(
+                                                    "Linux nmsworker-devel 2.6.18-164.el5 #1 SMP Thu Sep 3 03:28:30 EDT 2009 x86_64".into())));
+                                        assert_eq!(expected_value, var_bind.value);
+                                    }
+                                    else {
+                                        println!("{:?}", response);
+                                        debug_assert!(false, "Not a valid response");
+                                    }
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                    }
+
+                    describe "get_request_with_wrong_password" {
+
+                        before {
+                            let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Sha, "wrong-password", &engine_id);
+                            let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key, None);
+                            let response = client
+                                .send_request(get_request_v3(3, parameters, vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_wrong_digests_report" {
+                            if let Ok(GenericSnmpMessage::V3Message(msg)) = &response {
+                                if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                    if let Pdus::Report(report) = &scoped_pdu.data {
+                                        assert_eq!(3, report.0.request_id);
+                                        assert_eq!(string_to_oid(".1.3.6.1.6.3.15.1.1.5.0"), report.0.variable_bindings.first().unwrap().name);
+                                    }
+                                    else {
+                                        println!("{:?}", response);
+                                        debug_assert!(false, "Not a valid report");
+                                    }
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid report");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid report");
+                            }
+                        }
+                    }
+
+                    describe "get_request_with_unknown_user" {
+
+                        before {
+                            let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "unknown", None, None);
+                            let response = client
+                                .send_request(get_request_v3(4, parameters, vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_unknown_user_names_report" {
+                            if let Ok(GenericSnmpMessage::V3Message(msg)) = &response {
+                                if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                    if let Pdus::Report(report) = &scoped_pdu.data {
+                                        assert_eq!(4, report.0.request_id);
+                                        assert_eq!(string_to_oid(".1.3.6.1.6.3.15.1.1.3.0"), report.0.variable_bindings.first().unwrap().name);
+                                    }
+                                    else {
+                                        println!("{:?}", response);
+                                        debug_assert!(false, "Not a valid report");
+                                    }
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid report");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid report");
+                            }
+                        }
+                    }
+
+                    describe "get_request_out_of_time_window" {
+
+                        before {
+                            let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Sha, "simulator-password", &engine_id);
+                            let parameters = snmp_v3_parameters(&engine_id, engine_boots + 1, engine_time, "simulator", authentication_key.clone(), None);
+                            let response = client
+                                .send_request(get_request_v3(5, parameters, vec![oid.clone()]))
+                                .await;
+                        }
+
+                        async it "returns_authenticated_not_in_time_windows_report" {
+                            if let Ok(GenericSnmpMessage::V3Message(msg)) = &response {
+                                assert!(verify_message(msg, authentication_key.as_ref().unwrap()));
+                                assert_eq!(engine_boots, msg.security_parameters.authoritative_engine_boots.to_u32().unwrap());
+                                if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                    if let Pdus::Report(report) = &scoped_pdu.data {
+                                        assert_eq!(5, report.0.request_id);
+                                        assert_eq!(string_to_oid(".1.3.6.1.6.3.15.1.1.2.0"), report.0.variable_bindings.first().unwrap().name);
+                                    }
+                                    else {
+                                        println!("{:?}", response);
+                                        debug_assert!(false, "Not a valid report");
+                                    }
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid report");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid report");
+                            }
+                        }
+                    }
+                }
+            }
+
+            describe "with_device_started_before" {
+
+                before {
+                    // the device has been started 41 times before, e.g. by the previous runs of the service
+                    Entity::update_many()
+                        .col_expr(Column::EngineBoots, Expr::value(41))
+                        .filter(Column::Id.eq(device_id.to_string()))
+                        .exec(db_conn)
+                        .await
+                        .unwrap();
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                    let discovery = client
+                        .send_request(get_request_v3(1, snmp_v3_parameters(&[], 0, 0, "", None, None), vec![oid.clone()]))
+                        .await;
+                }
+
+                async it "reports_persisted_engine_boots" {
+                    assert_eq!(StatusCode::OK, response.status());
+                    if let Ok(GenericSnmpMessage::V3Message(msg)) = &discovery {
+                        assert_eq!(42, msg.security_parameters.authoritative_engine_boots.to_u32().unwrap());
+                    }
+                    else {
+                        println!("{:?}", discovery);
+                        debug_assert!(false, "Not a valid report");
+                    }
+                    let device = Entity::find_by_id(device_id.to_string()).one(db_conn).await.unwrap().unwrap();
+                    assert_eq!(42, device.engine_boots);
+                }
+            }
+        }
+
+        describe "agent_with_privacy" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+            }
+
+            describe "with_aes_device" {
+
+                before {
+                    let device_port = DEVICE_PORT.write().get_next_value();
+                    let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Md5, "simulator-password", EncryptionAlgorithm::Aes, "simulator-privacy");
+                    let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                    let device_id = *device_ids.first().unwrap();
+                    #[allow(unused)]
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                    let discovery = client
+                        .send_request(get_request_v3(1, snmp_v3_parameters(&[], 0, 0, "", None, None), vec![oid.clone()]))
+                        .await;
+                    let (engine_id, engine_boots, engine_time) = match &discovery {
+                        Ok(GenericSnmpMessage::V3Message(msg)) => (
+                            msg.security_parameters.authoritative_engine_id.to_vec(),
+                            msg.security_parameters.authoritative_engine_boots.to_u32().unwrap(),
+                            msg.security_parameters.authoritative_engine_time.to_u32().unwrap(),
+                        ),
+                        _ => (vec![], 0, 0),
+                    };
+                    let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Md5, "simulator-password", &engine_id);
+                }
+
+                async it "started" {
+                    assert_eq!(StatusCode::OK, response.status());
+                }
+
+                describe "get_request_with_privacy" {
+
+                    before {
+                        let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Aes, &AuthenticationAlgorithm::Md5, "simulator-privacy", &engine_id);
+                        let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key.clone(), privacy_key.clone());
+                        let response = client
+                            .send_request(get_request_v3(2, parameters, vec![oid.clone()]))
+                            .await;
+                    }
+
+                    async it "returns_encrypted_response" {
+                        if let Ok(GenericSnmpMessage::V3Message(msg)) = response {
+                            let mut msg = *msg;
+                            assert!(verify_message(&msg, authentication_key.as_ref().unwrap()));
+                            assert!(matches!(msg.scoped_data, rasn_snmp::v3::ScopedPduData::EncryptedPdu(_)));
+                            assert!(decrypt_scoped_pdu(&mut msg, privacy_key.as_ref().unwrap()));
+                            if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                if let Pdus::Response(resp) = &scoped_pdu.data {
+                                    assert_eq!(2, resp.0.request_id);
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", msg);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", msg);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                }
+
+                describe "get_request_with_wrong_privacy_key" {
+
+                    before {
+                        let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Aes, &AuthenticationAlgorithm::Md5, "wrong-privacy", &engine_id);
+                        let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key.clone(), privacy_key);
+                        let response = client
+                            .send_request(get_request_v3(3, parameters, vec![oid.clone()]))
+                            .await;
+                    }
+
+                    async it "returns_decryption_errors_report" {
+                        if let Ok(GenericSnmpMessage::V3Message(msg)) = &response {
+                            if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                if let Pdus::Report(report) = &scoped_pdu.data {
+                                    assert_eq!(string_to_oid(".1.3.6.1.6.3.15.1.1.6.0"), report.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid report");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid report");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid report");
+                        }
+                    }
+                }
+            }
+
+            describe "with_sha256_aes256_device" {
+
+                before {
+                    let device_port = DEVICE_PORT.write().get_next_value();
+                    let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Sha256, "simulator-password", EncryptionAlgorithm::Aes256, "simulator-privacy");
+                    let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                    let device_id = *device_ids.first().unwrap();
+                    #[allow(unused)]
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                    let discovery = client
+                        .send_request(get_request_v3(1, snmp_v3_parameters(&[], 0, 0, "", None, None), vec![oid.clone()]))
+                        .await;
+                    let (engine_id, engine_boots, engine_time) = match &discovery {
+                        Ok(GenericSnmpMessage::V3Message(msg)) => (
+                            msg.security_parameters.authoritative_engine_id.to_vec(),
+                            msg.security_parameters.authoritative_engine_boots.to_u32().unwrap(),
+                            msg.security_parameters.authoritative_engine_time.to_u32().unwrap(),
+                        ),
+                        _ => (vec![], 0, 0),
+                    };
+                    let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Sha256, "simulator-password", &engine_id);
+                }
+
+                async it "started" {
+                    assert_eq!(StatusCode::OK, response.status());
+                }
+
+                describe "get_request_with_privacy" {
+
+                    before {
+                        let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Aes256, &AuthenticationAlgorithm::Sha256, "simulator-privacy", &engine_id);
+                        let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key.clone(), privacy_key.clone());
+                        let response = client
+                            .send_request(get_request_v3(2, parameters, vec![oid.clone()]))
+                            .await;
+                    }
+
+                    async it "returns_encrypted_response" {
+                        if let Ok(GenericSnmpMessage::V3Message(msg)) = response {
+                            let mut msg = *msg;
+                            assert!(verify_message(&msg, authentication_key.as_ref().unwrap()));
+                            assert!(matches!(msg.scoped_data, rasn_snmp::v3::ScopedPduData::EncryptedPdu(_)));
+                            assert!(decrypt_scoped_pdu(&mut msg, privacy_key.as_ref().unwrap()));
+                            if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                if let Pdus::Response(resp) = &scoped_pdu.data {
+                                    assert_eq!(2, resp.0.request_id);
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", msg);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", msg);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                }
+            }
+
+            describe "with_des_device" {
+
+                before {
+                    let device_port = DEVICE_PORT.write().get_next_value();
+                    let protocol = domain_snmp_v3_attributes_json("simulator", AuthenticationAlgorithm::Md5, "simulator-password", EncryptionAlgorithm::Des, "simulator-privacy");
+                    let device_ids = seed_devices_with_protocol(db_conn, &agent_id, 1, host_ipaddr, device_port, &protocol).await;
+                    let device_id = *device_ids.first().unwrap();
+                    #[allow(unused)]
+                    let response = reqwestClient::new()
+                        .put(format!("{}/devices/{}/start", app.address, device_id))
+                        .send()
+                        .await
+                        .unwrap();
+
+                    let remote_addr = format!("{host_ipaddr}:{device_port}");
+                    let oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    let mut client = Client::new(remote_addr.parse().unwrap()).unwrap();
+                    let discovery = client
+                        .send_request(get_request_v3(1, snmp_v3_parameters(&[], 0, 0, "", None, None), vec![oid.clone()]))
+                        .await;
+                    let (engine_id, engine_boots, engine_time) = match &discovery {
+                        Ok(GenericSnmpMessage::V3Message(msg)) => (
+                            msg.security_parameters.authoritative_engine_id.to_vec(),
+                            msg.security_parameters.authoritative_engine_boots.to_u32().unwrap(),
+                            msg.security_parameters.authoritative_engine_time.to_u32().unwrap(),
+                        ),
+                        _ => (vec![], 0, 0),
+                    };
+                    let authentication_key = AuthenticationKey::new(AuthenticationAlgorithm::Md5, "simulator-password", &engine_id);
+                }
+
+                async it "started" {
+                    assert_eq!(StatusCode::OK, response.status());
+                }
+
+                describe "get_request_with_privacy" {
+
+                    before {
+                        let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Des, &AuthenticationAlgorithm::Md5, "simulator-privacy", &engine_id);
+                        let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key.clone(), privacy_key.clone());
+                        let response = client
+                            .send_request(get_request_v3(2, parameters, vec![oid.clone()]))
+                            .await;
+                    }
+
+                    async it "returns_encrypted_response" {
+                        if let Ok(GenericSnmpMessage::V3Message(msg)) = response {
+                            let mut msg = *msg;
+                            assert!(verify_message(&msg, authentication_key.as_ref().unwrap()));
+                            assert!(matches!(msg.scoped_data, rasn_snmp::v3::ScopedPduData::EncryptedPdu(_)));
+                            assert!(decrypt_scoped_pdu(&mut msg, privacy_key.as_ref().unwrap()));
+                            if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                if let Pdus::Response(resp) = &scoped_pdu.data {
+                                    assert_eq!(2, resp.0.request_id);
+                                    assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                    assert_eq!(1, resp.0.variable_bindings.len());
+                                    assert_eq!(oid, resp.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", msg);
+                                    debug_assert!(false, "Not a valid response");
+                                }
+                            }
+                            else {
+                                println!("{:?}", msg);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                }
+
+                describe "get_request_with_wrong_privacy_key" {
+
+                    before {
+                        let privacy_key = PrivacyKey::new(EncryptionAlgorithm::Des, &AuthenticationAlgorithm::Md5, "wrong-privacy", &engine_id);
+                        let parameters = snmp_v3_parameters(&engine_id, engine_boots, engine_time, "simulator", authentication_key.clone(), privacy_key);
+                        let response = client
+                            .send_request(get_request_v3(3, parameters, vec![oid.clone()]))
+                            .await;
+                    }
+
+                    async it "returns_decryption_errors_report" {
+                        if let Ok(GenericSnmpMessage::V3Message(msg)) = &response {
+                            if let rasn_snmp::v3::ScopedPduData::CleartextPdu(scoped_pdu) = &msg.scoped_data {
+                                if let Pdus::Report(report) = &scoped_pdu.data {
+                                    assert_eq!(string_to_oid(".1.3.6.1.6.3.15.1.1.6.0"), report.0.variable_bindings.first().unwrap().name);
+                                }
+                                else {
+                                    println!("{:?}", response);
+                                    debug_assert!(false, "Not a valid report");
+                                }
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid report");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid report");
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
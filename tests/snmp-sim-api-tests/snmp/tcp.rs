@@ -0,0 +1,140 @@
+use demonstrate::demonstrate;
+
+demonstrate! {
+    #[actix_rt::test]
+    describe "tcp" {
+        use crate::helpers::{spawn_app, domain_snmp_v2c_attributes_json};
+        use actix_web::http::StatusCode;
+        use snmp_sim::data_access::helpers::*;
+        use reqwest::Client as reqwestClient;
+        use uuid_dev::Uuid;
+        use std::str::FromStr;
+        use snmp_sim::snmp::codec::{GenericSnmpMessage, SnmpCodec, SnmpStreamCodec};
+        use crate::snmp::DEVICE_PORT;
+        use num_traits::ToPrimitive;
+        use rasn_snmp::v2::*;
+        use rasn_smi::v2::*;
+        use crate::snmp::{get_request_v2, get_bulk_request_v2};
+        use snmp_data_parser::parser::snmp_data::component::string_to_oid;
+        use futures::{SinkExt, StreamExt};
+        use tokio_util::codec::Framed;
+
+        before {
+            let app = spawn_app().await;
+        }
+
+        describe "tcp_device" {
+            before {
+                let db_conn = app.db_conn.as_ref().unwrap();
+                let host_ipaddr = "127.0.0.1";
+                let device_port = DEVICE_PORT.write().get_next_value();
+
+                let agent = create_agent(db_conn, &Uuid::new_v4(), &Uuid::new_v4().to_string(), &Some(Uuid::new_v4().to_string()), "os-linux-std.txt", &None)
+                    .await
+                    .unwrap()
+                    .unwrap_created();
+                let agent_id = Uuid::from_str(&agent.id).unwrap();
+
+                let (device, _agent) = create_managed_device(
+                    db_conn,
+                    &Uuid::new_v4(),
+                    &Uuid::new_v4().to_string(),
+                    &None,
+                    &agent_id,
+                    &domain_snmp_v2c_attributes_json("public"),
+                    host_ipaddr,
+                    device_port,
+                    &None,
+                    &None,
+                    &None,
+                    &None,
+                    "tcp",
+                )
+                .await
+                .unwrap()
+                .unwrap_created();
+                let device_id = Uuid::from_str(&device.id).unwrap();
+
+                let response = reqwestClient::new()
+                    .put(format!("{}/devices/{}/start", app.address, device_id))
+                    .send()
+                    .await
+                    .unwrap();
+            }
+
+            async it "started" {
+                assert_eq!(StatusCode::OK, response.status());
+            }
+
+            describe "get_requests" {
+                before {
+                    let stream = tokio::net::TcpStream::connect(format!("{host_ipaddr}:{device_port}")).await.unwrap();
+                    let mut connection = Framed::new(stream, SnmpStreamCodec::new(SnmpCodec::default()));
+
+                    // both requests are sent before reading the responses from the same connection
+                    let descr_oid = string_to_oid(".1.3.6.1.2.1.1.1.0");
+                    connection.send(get_request_v2(42, "public", vec![descr_oid.clone()])).await.unwrap();
+                    connection.send(get_request_v2(43, "public", vec![descr_oid.clone()])).await.unwrap();
+                    let first_response = connection.next().await;
+                    let second_response = connection.next().await;
+                }
+
+                async it "returns_both_responses" {
+                    let mut request_ids = vec![];
+                    for response in [first_response, second_response] {
+                        if let Some(Ok(Ok(GenericSnmpMessage::V2Message(msg)))) = &response {
+                            if let Pdus::Response(resp) = &msg.data {
+                                request_ids.push(resp.0.request_id);
+                                assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                                assert_eq!(1, resp.0.variable_bindings.len());
+                                assert_eq!(descr_oid, resp.0.variable_bindings[0].name);
+                                assert_eq!(
+                                    VarBindValue::Value(ObjectSyntax::Simple(SimpleSyntax::String(
+                                        "Linux nmsworker-devel 2.6.18-164.el5 #1 SMP Thu Sep 3 03:28:30 EDT 2009 x86_64".into()))),
+                                    resp.0.variable_bindings[0].value);
+                            }
+                            else {
+                                println!("{:?}", response);
+                                debug_assert!(false, "Not a valid response");
+                            }
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    // the requests are handled concurrently, the responses may be written in any order
+                    request_ids.sort();
+                    assert_eq!(vec![42, 43], request_ids);
+                }
+            }
+
+            describe "get_bulk_request" {
+                before {
+                    let stream = tokio::net::TcpStream::connect(format!("{host_ipaddr}:{device_port}")).await.unwrap();
+                    let mut connection = Framed::new(stream, SnmpStreamCodec::new(SnmpCodec::default()));
+                    connection.send(get_bulk_request_v2(44, "public", 0, 50, vec![string_to_oid(".1.3.6.1.2.1")])).await.unwrap();
+                    let response = connection.next().await;
+                }
+
+                async it "returns_all_repetitions" {
+                    if let Some(Ok(Ok(GenericSnmpMessage::V2Message(msg)))) = &response {
+                        if let Pdus::Response(resp) = &msg.data {
+                            assert_eq!(44, resp.0.request_id);
+                            assert_eq!(0, resp.0.error_status.to_u32().unwrap());
+                            assert_eq!(50, resp.0.variable_bindings.len());
+                        }
+                        else {
+                            println!("{:?}", response);
+                            debug_assert!(false, "Not a valid response");
+                        }
+                    }
+                    else {
+                        println!("{:?}", response);
+                        debug_assert!(false, "Not a valid response");
+                    }
+                }
+            }
+        }
+    }
+}